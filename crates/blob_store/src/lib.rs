@@ -3,7 +3,8 @@
 //! Overview
 //! - Content-addressable identity: SHA-256 computed over plaintext bytes.
 //! - Determinism: fixed zstd level; AES-256-GCM with nonce = SHA-256(key || digest)[..12].
-//! - Atomicity & durability: write to a temporary file, `fsync`, atomic rename, then directory `fsync`.
+//! - Atomicity & durability: write to a temporary file, `fsync`, atomically hard-link it into place
+//!   (never replacing an existing blob), then directory `fsync`.
 //! - Fail-closed: any I/O, crypto, or integrity error aborts the operation.
 //!
 //! Security Model
@@ -23,8 +24,10 @@
 //! - Header (9 bytes): magic "BS2\0" (4), version = 1 (1), chunk_size (u32 BE) (4)
 //! - Body: repeated [len_be (u32)][ciphertext bytes] where each ciphertext is AES-256-GCM of up to
 //!   `chunk_size` bytes of zstd-compressed data. Each chunk carries its own auth tag.
-//! - Nonce scheme: deterministic 96-bit nonce constructed as `(prefix || counter_be32)`, where
-//!   `prefix = SHA256(key || digest)[..12]` and `counter_be32` increments from 0 per chunk.
+//! - Nonce scheme: deterministic 96-bit nonce constructed as `(prefix[..8] || counter_be32)`, where
+//!   `prefix = SHA256(key || digest)[..12]` and `counter_be32` increments from 0 per chunk. The body
+//!   always holds at least one chunk; an empty compressed stream is a single tag-only chunk at
+//!   counter 0, so the layout (and version) is the same as for any other blob.
 //!   This yields stable ciphertext per (key, digest) and supports idempotent writes/dedup.
 //! - Determinism: plaintext digest is SHA-256 over uncompressed bytes; compression uses a fixed level
 //!   (default 3). With the same key and input, digests and ciphertext are stable.
@@ -32,9 +35,28 @@
 //!   no large, unbounded allocations on the control path. Temp files are used for compressed payloads.
 //! - Legacy compatibility: blobs without the BS2 header are treated as legacy single-shot (nonce-prefix only)
 //!   ciphertext of a full compressed stream. Reads remain supported; new writes use BS2.
+//! - Dictionary compression: with [`Config::zstd_dictionary`] set, blobs are written as version 2,
+//!   whose header appends the dictionary id (SHA-256 of the dictionary, 32 bytes) after
+//!   `chunk_size`. Reads use the configured dictionary only when its id matches, and fail with
+//!   [`Error::DictionaryMismatch`] otherwise. Version 1 blobs (no dictionary) stay readable by any
+//!   store, and stores without a dictionary keep writing version 1.
 //! - Fail-closed: header/version mismatch, auth tag failures, or digest mismatches return typed errors.
 //!
 
+//! Namespace binding (associated data)
+//! - A store may be bound to a namespace (e.g. a tenant id) via [`BlobStore::with_namespace`].
+//!   The namespace bytes are passed as AEAD associated data on every encrypt/decrypt, so a blob
+//!   written under one namespace fails authentication when read under another.
+//! - The default namespace is empty, which is byte-for-byte identical to stores written before
+//!   namespaces existed.
+//! - Namespaces sharing one root are stored apart: the empty namespace keeps `root/sha256/...`, and
+//!   any other namespace lives under `root/ns/<hex SHA-256(namespace)>/sha256/...`, so identical
+//!   content put under two namespaces yields two blobs.
+//! - Migration: existing blobs only decrypt with the empty namespace. To move them into a namespace,
+//!   `get` each blob from a store opened without a namespace and `put` it into a store (same root or
+//!   another) bound to the new namespace.
+//!
+
 //! Determinism Guarantees
 //! - `Digest` identity is computed on plaintext only.
 //! - Compression uses a fixed zstd level (default 3) for stable output.
@@ -65,9 +87,15 @@ use std::{
     sync::OnceLock,
 };
 
-use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
 use sha2::digest::{FixedOutput as ShaFixedOutputTrait, Update as ShaUpdateTrait};
 
+/// WAL v2 attachment entry returned by [`BlobStore::put_reader_meta`].
+pub use event_log::v2::{Attachment, Compression};
+
 /// 32-byte SHA-256 digest type
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Digest(pub [u8; 32]);
@@ -77,6 +105,22 @@ impl Digest {
     pub fn to_hex(&self) -> String {
         hex::encode(self.0)
     }
+
+    /// Parse a lowercase or uppercase 64-char hex string; `None` if malformed
+    pub fn from_hex(s: &str) -> Option<Self> {
+        let bytes = hex::decode(s).ok()?;
+        let arr: [u8; 32] = bytes.try_into().ok()?;
+        Some(Digest(arr))
+    }
+}
+
+/// Result of a put: the content digest and whether this call stored a new blob.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PutOutcome {
+    /// Content digest (SHA-256 over plaintext)
+    pub digest: Digest,
+    /// `true` when this call wrote the blob; `false` on a dedup hit (content already present)
+    pub created: bool,
 }
 
 /// Error type for blob store operations
@@ -94,12 +138,18 @@ pub enum Error {
     /// Blob not found
     #[error("not found")]
     NotFound,
+    /// Digest string is not 64 hex characters
+    #[error("invalid digest: expected 64 hex characters")]
+    InvalidDigest,
     /// Detected partial/incomplete write artifact
     #[error("partial write detected")]
     PartialWriteDetected,
     /// Wrong key used for decrypting
     #[error("wrong key or decryption failed")]
     WrongKey,
+    /// Blob was compressed with a zstd dictionary this store is not configured with
+    #[error("blob needs zstd dictionary {0}, which is not configured")]
+    DictionaryMismatch(String),
 }
 
 /// Key provider trait for encryption-at-rest
@@ -126,6 +176,29 @@ impl KeyProvider for DevKeyProvider {
     }
 }
 
+/// Deterministic key provider for tests and examples: the key is SHA-256 of a readable seed,
+/// so `SeededKeyProvider::from_seed("tenant-a")` yields the same key in every run.
+///
+/// Not for production keys; use [`DevKeyProvider`] when a test needs an explicit key.
+pub struct SeededKeyProvider {
+    key: [u8; 32],
+}
+
+impl SeededKeyProvider {
+    /// Derive the key as SHA-256 over the UTF-8 bytes of `seed`
+    pub fn from_seed(seed: &str) -> Self {
+        let mut h = sha2::Sha256::default();
+        ShaUpdateTrait::update(&mut h, seed.as_bytes());
+        Self { key: ShaFixedOutputTrait::finalize_fixed(h).into() }
+    }
+}
+
+impl KeyProvider for SeededKeyProvider {
+    fn key_bytes(&self) -> [u8; 32] {
+        self.key
+    }
+}
+
 /// Optional observability hooks (low-cardinality counters and spans).
 /// By default these are no-ops. Integrations may register a global observer
 /// to emit metrics/traces via OpenTelemetry or other backends.
@@ -188,10 +261,13 @@ fn observer() -> &'static dyn BlobStoreObserver {
 ///
 /// Header layout:
 /// - magic:    4 bytes, ASCII "BS2\0"
-/// - version:  1 byte, currently 1
+/// - version:  1 byte, 1 (no dictionary) or 2 (dictionary)
 /// - chunk_sz: 4 bytes, big-endian u32 (default 65536)
+/// - dict_id:  version 2 only, 32 bytes, SHA-256 of the zstd dictionary
 const FILE_MAGIC: [u8; 4] = *b"BS2\0";
 const FILE_VERSION: u8 = 1;
+/// Header version for blobs compressed with a zstd dictionary.
+const FILE_VERSION_DICT: u8 = 2;
 /// Default plaintext/compressed chunk size (bounds memory on read/write)
 const CHUNK_SIZE: usize = 64 * 1024; // 64 KiB
 /// AEAD tag size for AES-256-GCM (bytes)
@@ -213,6 +289,29 @@ fn derive_nonce_prefix(key_bytes: [u8; 32], digest: &Digest) -> [u8; 12] {
     out
 }
 
+/// Create a new file at the first `make(i)` (i = 0, 1, ...) that does not exist yet.
+fn create_unique(make: impl Fn(u64) -> PathBuf) -> Result<(PathBuf, fs::File), Error> {
+    let mut i = 0u64;
+    loop {
+        let candidate = make(i);
+        match fs::OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(f) => return Ok((candidate, f)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => i = i.wrapping_add(1),
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+}
+
+/// Nonce for chunk `counter`: `prefix[..8] || counter_be32`. Shared by the writer and
+/// [`DecryptedCompressedReader`] so every chunk, including the lone chunk of an empty
+/// compressed stream, uses the same derivation.
+fn chunk_nonce(prefix: &[u8; 12], counter: u32) -> [u8; 12] {
+    let mut nonce_bytes = [0u8; 12];
+    nonce_bytes[..8].copy_from_slice(&prefix[..8]);
+    nonce_bytes[8..].copy_from_slice(&counter.to_be_bytes());
+    nonce_bytes
+}
+
 /// Writer adapter that forwards bytes while computing a SHA-256 over the
 /// plaintext stream and counting total bytes written. Used to verify integrity
 /// against the expected `Digest` without buffering.
@@ -254,6 +353,7 @@ impl<W: Write> Write for HashingWriter<W> {
 struct DecryptedCompressedReader {
     file: fs::File,
     cipher: Aes256Gcm,
+    aad: Vec<u8>,
     nonce_prefix: [u8; 12],
     counter: u32,
     buf: Vec<u8>,
@@ -280,14 +380,12 @@ impl DecryptedCompressedReader {
         }
         self.buf.resize(clen, 0);
         self.file.read_exact(&mut self.buf)?;
-        let mut nonce_bytes = [0u8; 12];
-        nonce_bytes[..8].copy_from_slice(&self.nonce_prefix[..8]);
-        nonce_bytes[8..].copy_from_slice(&self.counter.to_be_bytes());
+        let nonce_bytes = chunk_nonce(&self.nonce_prefix, self.counter);
         #[allow(deprecated)]
         let nonce = Nonce::from_slice(&nonce_bytes);
         let pt = self
             .cipher
-            .decrypt(nonce, self.buf.as_ref())
+            .decrypt(nonce, Payload { msg: self.buf.as_ref(), aad: &self.aad })
             .map_err(|_| Error::Crypto("decrypt".into()))?;
         self.buf = pt;
         self.pos = 0;
@@ -322,35 +420,112 @@ pub struct Config {
     pub root: PathBuf,
     /// Fixed zstd compression level (deterministic)
     pub zstd_level: i32,
+    /// Optional zstd dictionary (e.g. trained with `zstd::dict::from_samples` on typical
+    /// attachments) used for both compression and decompression; it pays off for many small,
+    /// similarly structured blobs. Blobs record its id, so a store reading them must be
+    /// configured with the same dictionary.
+    pub zstd_dictionary: Option<Vec<u8>>,
 }
 
 impl Config {
-    /// Default config with level 3
+    /// Default config with level 3 and no dictionary
     pub fn with_root(root: PathBuf) -> Self {
-        Self { root, zstd_level: 3 }
+        Self { root, zstd_level: 3, zstd_dictionary: None }
+    }
+
+    /// Id recorded in version 2 headers: SHA-256 of the dictionary bytes.
+    fn dictionary_id(&self) -> Option<[u8; 32]> {
+        self.zstd_dictionary.as_deref().map(|dict| {
+            let mut h = sha2::Sha256::default();
+            ShaUpdateTrait::update(&mut h, dict);
+            ShaFixedOutputTrait::finalize_fixed(h).into()
+        })
     }
 }
 
+/// Decompress `reader` (optionally with `dict`) into `writer`, returning the plaintext digest
+/// and byte count.
+fn decompress_hashed<R: Read, W: Write>(
+    reader: R,
+    dict: Option<&[u8]>,
+    writer: W,
+) -> Result<(Digest, usize), Error> {
+    let mut hw = HashingWriter::new(writer);
+    let count = match dict {
+        None => {
+            let mut dec = zstd::stream::read::Decoder::new(reader).map_err(|_| Error::Integrity)?;
+            io::copy(&mut dec, &mut hw)
+        }
+        Some(dict) => {
+            let mut dec =
+                zstd::stream::read::Decoder::with_dictionary(io::BufReader::new(reader), dict)
+                    .map_err(|_| Error::Integrity)?;
+            io::copy(&mut dec, &mut hw)
+        }
+    }
+    .map_err(|_| Error::Integrity)? as usize;
+    let (_w, d_bytes, _c) = hw.finalize();
+    Ok((Digest(d_bytes), count))
+}
+
 /// Blob Store API
 pub struct BlobStore<K: KeyProvider> {
     cfg: Config,
 
     key: K,
+    /// AEAD associated data binding blobs to a namespace (empty by default)
+    aad: Vec<u8>,
 }
 
 impl<K: KeyProvider> BlobStore<K> {
     /// Create a new store with config and key provider
     pub fn new(cfg: Config, key: K) -> Result<Self, Error> {
-        let s = Self { cfg, key };
+        let s = Self { cfg, key, aad: Vec::new() };
         // ensure root exists
         std::fs::create_dir_all(&s.cfg.root)?;
         Ok(s)
     }
 
-    /// Compute deterministic blob path from digest (sharded aa/bb/<digest>)
-    pub fn path_for(&self, digest_hex: &str) -> PathBuf {
-        let (a, b) = (&digest_hex[0..2], &digest_hex[2..4]);
-        self.cfg.root.join("sha256").join(a).join(b).join(digest_hex)
+    /// Bind this store to a namespace (e.g. a tenant id) used as AEAD associated data.
+    ///
+    /// Blobs written under one namespace fail to decrypt under any other. The default is the
+    /// empty namespace, which matches blobs written before namespaces were introduced.
+    pub fn with_namespace(mut self, namespace: impl AsRef<[u8]>) -> Self {
+        self.aad = namespace.as_ref().to_vec();
+        self
+    }
+
+    /// Namespace bytes used as AEAD associated data (empty by default)
+    pub fn namespace(&self) -> &[u8] {
+        &self.aad
+    }
+
+    /// Compute deterministic blob path from a hex digest (sharded aa/bb/<digest>) under this
+    /// store's namespace directory.
+    ///
+    /// Only a 64-char hex digest is accepted (either case; the path uses lowercase), so a
+    /// short or path-like input yields [`Error::InvalidDigest`] instead of a panic or a path
+    /// outside the store root.
+    pub fn path_for(&self, digest_hex: &str) -> Result<PathBuf, Error> {
+        Digest::from_hex(digest_hex).map(|d| self.path_for_digest(&d)).ok_or(Error::InvalidDigest)
+    }
+
+    /// Deterministic blob path for `digest` (sharded aa/bb/<digest>) under this store's
+    /// namespace directory
+    pub fn path_for_digest(&self, digest: &Digest) -> PathBuf {
+        let hex = digest.to_hex();
+        let (a, b) = (&hex[0..2], &hex[2..4]);
+        self.blobs_dir().join(a).join(b).join(&hex)
+    }
+
+    /// `root/sha256` for the empty namespace, `root/ns/<hex sha256(ns)>/sha256` otherwise
+    fn blobs_dir(&self) -> PathBuf {
+        if self.aad.is_empty() {
+            self.cfg.root.join("sha256")
+        } else {
+            let ns = Self::digest_of(&self.aad).to_hex();
+            self.cfg.root.join("ns").join(ns).join("sha256")
+        }
     }
 
     /// Compute the SHA-256 digest for the given bytes (plaintext)
@@ -370,18 +545,59 @@ impl<K: KeyProvider> BlobStore<K> {
         self.put_reader(Cursor::new(bytes))
     }
 
+    /// Store bytes and report whether a new blob was created or the content already existed.
+    ///
+    /// Useful for write-once-verify workflows and dedup accounting; `put` is a thin wrapper
+    /// that discards `created`.
+    pub fn put_new(&self, bytes: &[u8]) -> Result<PutOutcome, Error> {
+        self.put_reader_new(Cursor::new(bytes))
+    }
+
     /// Streaming put from any reader.
     ///
+    /// See [`BlobStore::put_reader_new`] for the pipeline; this variant returns only the digest.
+    pub fn put_reader<R: Read>(&self, reader: R) -> Result<Digest, Error> {
+        self.put_reader_new(reader).map(|o| o.digest)
+    }
+
+    /// Streaming put from any reader, reporting whether a new blob was created.
+    ///
     /// Pipeline:
     /// 1) Hash plaintext while zstd-compressing to a temporary file (no large buffers)
     /// 2) Encrypt compressed stream in chunks with BS2 header and deterministic nonces
-    /// 3) Atomic rename to final path; record logical plaintext bytes in observer
+    /// 3) Publish to the final path with a no-replace hard link (the temp name is then
+    ///    removed); record logical plaintext bytes in observer
     ///
     /// Determinism & bounds:
     /// - Digest computed on plaintext; fixed zstd level
     /// - Nonce prefix = SHA256(key||digest)[..12], counter_be32 per chunk
     /// - Working set bounded by `CHUNK_SIZE`
-    pub fn put_reader<R: Read>(&self, mut reader: R) -> Result<Digest, Error> {
+    /// - `created == false` when the blob already existed (including a concurrent writer
+    ///   publishing first); exactly one of several racing writers sees `created == true`.
+    ///   The root's filesystem must support hard links.
+    pub fn put_reader_new<R: Read>(&self, reader: R) -> Result<PutOutcome, Error> {
+        self.put_stream(reader).map(|(outcome, _)| outcome)
+    }
+
+    /// Streaming put returning the WAL v2 attachment entry for the stored body.
+    ///
+    /// `size_bytes` is the plaintext length counted while streaming and `compression` is
+    /// always `zstd`, so callers can record the attachment without re-reading or stat-ing
+    /// the blob. `mime` is passed through unchecked; WAL v2 validates it on append.
+    pub fn put_reader_meta<R: Read>(&self, reader: R, mime: &str) -> Result<Attachment, Error> {
+        let (outcome, size_bytes) = self.put_stream(reader)?;
+        Ok(Attachment {
+            digest_sha256: outcome.digest.to_hex(),
+            size_bytes,
+            mime: mime.to_string(),
+            encoding: None,
+            compression: Compression::Zstd,
+        })
+    }
+
+    /// Shared put pipeline behind [`BlobStore::put_reader_new`]; also returns the plaintext
+    /// byte count.
+    fn put_stream<R: Read>(&self, mut reader: R) -> Result<(PutOutcome, u64), Error> {
         let _span = observer().span("blob.put");
 
         // First pass: hash plaintext and zstd-compress to a temporary compressed file on disk.
@@ -392,23 +608,14 @@ impl<K: KeyProvider> BlobStore<K> {
         // We don't know digest yet; write compressed to a temp path under root/tmp
         let tmp_dir = self.cfg.root.join(".tmp");
         fs::create_dir_all(&tmp_dir)?;
-        // Create a unique temp file without adding extra dependencies
-        let compressed_tmp = {
-            let mut i = 0u64;
-            loop {
-                let candidate = tmp_dir.join(format!("compressed-{}.tmp", i));
-                match fs::OpenOptions::new().write(true).create_new(true).open(&candidate) {
-                    Ok(f) => break (candidate, f),
-                    Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
-                        i = i.wrapping_add(1);
-                        continue;
-                    }
-                    Err(e) => return Err(Error::Io(e)),
-                }
+        let (compressed_tmp, comp_file) =
+            create_unique(|i| tmp_dir.join(format!("compressed-{}.tmp", i)))?;
+        let mut encoder = match &self.cfg.zstd_dictionary {
+            Some(dict) => {
+                zstd::stream::write::Encoder::with_dictionary(comp_file, self.cfg.zstd_level, dict)?
             }
+            None => zstd::stream::write::Encoder::new(comp_file, self.cfg.zstd_level)?,
         };
-        let (compressed_tmp, comp_file) = compressed_tmp;
-        let mut encoder = zstd::stream::write::Encoder::new(comp_file, self.cfg.zstd_level)?;
 
         let mut buf = vec![0u8; CHUNK_SIZE];
         let mut total_plain: usize = 0;
@@ -429,86 +636,80 @@ impl<K: KeyProvider> BlobStore<K> {
         let mut d = [0u8; 32];
         d.copy_from_slice(&d_bytes);
         let digest = Digest(d);
-        let hex = digest.to_hex();
-        let final_path = self.path_for(&hex);
+        let final_path = self.path_for_digest(&digest);
 
         // Idempotency: if exists, record logical bytes and return
         if final_path.exists() {
+            let _ = fs::remove_file(&compressed_tmp);
             observer().put_bytes(total_plain as u64);
-            return Ok(digest);
+            return Ok((PutOutcome { digest, created: false }, total_plain as u64));
         }
 
         if let Some(parent) = final_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        // Encrypt the compressed temp stream into the final .incomplete file with header, then atomic rename.
+        // Encrypt the compressed temp stream into a unique .incomplete file with header, then
+        // publish it with a no-replace hard link; if the digest path already exists, the
+        // stored blob is kept and this copy is discarded.
         let key_bytes = self.key.key_bytes();
         #[allow(deprecated)]
         let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
         let cipher = Aes256Gcm::new(key);
         let nonce_prefix = derive_nonce_prefix(key_bytes, &digest);
-        let tmp_path = final_path.with_extension("incomplete");
+        // Unique per writer: concurrent puts of the same content must not share a temp file.
+        let (tmp_path, mut out) =
+            create_unique(|i| final_path.with_extension(format!("{}.incomplete", i)))?;
         {
-            let mut out = fs::File::create(&tmp_path)?;
-            // Header: magic + version + chunk_size (u32 BE)
+            // Header: magic + version + chunk_size (u32 BE) [+ dictionary id]
+            let dict_id = self.cfg.dictionary_id();
             out.write_all(&FILE_MAGIC)?;
-            out.write_all(&[FILE_VERSION])?;
+            out.write_all(&[if dict_id.is_some() { FILE_VERSION_DICT } else { FILE_VERSION }])?;
             out.write_all(&(CHUNK_SIZE as u32).to_be_bytes())?;
+            if let Some(id) = dict_id {
+                out.write_all(&id)?;
+            }
 
-            // Chunked AEAD encrypt: for each plaintext chunk, derive nonce(prefix||counter_be)
+            // Chunked AEAD encrypt: for each plaintext chunk, derive nonce(prefix||counter_be).
+            // At least one chunk is always written; an empty compressed stream becomes a single
+            // tag-only chunk with counter 0, like the first chunk of any other stream.
             let mut comp_in = fs::File::open(&compressed_tmp)?;
             let mut ring = vec![0u8; CHUNK_SIZE];
             let mut next = vec![0u8; CHUNK_SIZE];
             let mut n = comp_in.read(&mut ring)?;
             let mut counter: u32 = 0;
-            if n == 0 {
-                // Write one empty chunk to carry an auth tag
-                let nonce_bytes = nonce_prefix;
-                // last 4 bytes are counter
-                out.write_all(&(16u32).to_be_bytes())?; // AES-GCM tag size for empty plaintext
+            loop {
+                let nonce_bytes = chunk_nonce(&nonce_prefix, counter);
                 #[allow(deprecated)]
                 let nonce = Nonce::from_slice(&nonce_bytes);
                 let ct = cipher
-                    .encrypt(nonce, &[][..])
-                    .map_err(|_| Error::Crypto("encrypt(empty)".into()))?;
+                    .encrypt(nonce, Payload { msg: &ring[..n], aad: &self.aad })
+                    .map_err(|_| Error::Crypto("encrypt".into()))?;
+                out.write_all(&(ct.len() as u32).to_be_bytes())?;
                 out.write_all(&ct)?;
-            } else {
-                loop {
-                    let mut nonce_bytes = [0u8; 12];
-                    nonce_bytes[..8].copy_from_slice(&nonce_prefix[..8]);
-                    nonce_bytes[8..].copy_from_slice(&counter.to_be_bytes());
-                    #[allow(deprecated)]
-                    let nonce = Nonce::from_slice(&nonce_bytes);
-                    let ct = cipher
-                        .encrypt(nonce, &ring[..n])
-                        .map_err(|_| Error::Crypto("encrypt".into()))?;
-                    out.write_all(&(ct.len() as u32).to_be_bytes())?;
-                    out.write_all(&ct)?;
-                    counter = counter.wrapping_add(1);
-
-                    let m = comp_in.read(&mut next)?;
-                    if m == 0 {
-                        break;
-                    }
-                    std::mem::swap(&mut ring, &mut next);
-                    n = m;
+                counter = counter.wrapping_add(1);
+
+                let m = comp_in.read(&mut next)?;
+                if m == 0 {
+                    break;
                 }
+                std::mem::swap(&mut ring, &mut next);
+                n = m;
             }
             out.sync_all()?;
         }
-        // Atomic rename with AlreadyExists race handling
-        match fs::rename(&tmp_path, &final_path) {
-            Ok(_) => {}
-            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
-                if final_path.exists() {
-                    let _ = fs::remove_file(&tmp_path);
-                } else {
-                    return Err(Error::Io(e));
-                }
+        // Publish without replacing: `rename` silently overwrites an existing target on
+        // Unix, so two racing writers would both report `created`. A hard link fails with
+        // AlreadyExists instead, and exactly one writer wins.
+        let created = match fs::hard_link(&tmp_path, &final_path) {
+            Ok(()) => true,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => false,
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_path);
+                return Err(Error::Io(e));
             }
-            Err(e) => return Err(Error::Io(e)),
-        }
+        };
+        fs::remove_file(&tmp_path)?;
         if let Some(parent) = final_path.parent() {
             if let Ok(dirf) = fs::File::open(parent) {
                 let _ = dirf.sync_all();
@@ -520,7 +721,7 @@ impl<K: KeyProvider> BlobStore<K> {
 
         // Record logical plaintext bytes written
         observer().put_bytes(total_plain as u64);
-        Ok(digest)
+        Ok((PutOutcome { digest, created }, total_plain as u64))
     }
 
     /// Retrieve plaintext bytes by digest
@@ -543,7 +744,7 @@ impl<K: KeyProvider> BlobStore<K> {
     pub fn get_to_writer<W: Write>(&self, digest: &Digest, mut writer: W) -> Result<usize, Error> {
         let _span = observer().span("blob.get");
 
-        let path = self.path_for(&digest.to_hex());
+        let path = self.path_for_digest(digest);
         let mut f = match fs::File::open(&path) {
             Ok(f) => f,
             Err(e) => {
@@ -574,26 +775,30 @@ impl<K: KeyProvider> BlobStore<K> {
             #[allow(deprecated)]
             let nonce = Nonce::from_slice(&nonce_prefix);
             let compressed = cipher
-                .decrypt(nonce, enc.as_ref())
+                .decrypt(nonce, Payload { msg: enc.as_ref(), aad: &self.aad })
                 .map_err(|_| Error::Crypto("decrypt(legacy)".into()))?;
 
             // Decompress and stream to hashing writer via read::Decoder
-            let mut dec = zstd::stream::read::Decoder::new(Cursor::new(compressed))
-                .map_err(|_| Error::Integrity)?;
-            let mut hw = HashingWriter::new(&mut writer);
-            let count = io::copy(&mut dec, &mut hw).map_err(|_| Error::Integrity)? as usize;
-            let (_w, d_bytes, _c) = hw.finalize();
-            if Digest(d_bytes) != *digest {
+            let (got, count) = decompress_hashed(Cursor::new(compressed), None, &mut writer)?;
+            if got != *digest {
                 return Err(Error::Integrity);
             }
             observer().get_bytes(count as u64);
             return Ok(count);
         }
 
-        let version = header[4];
-        if version != FILE_VERSION {
-            return Err(Error::Integrity);
-        }
+        let dict = match header[4] {
+            FILE_VERSION => None,
+            FILE_VERSION_DICT => {
+                let mut id = [0u8; 32];
+                f.read_exact(&mut id).map_err(|_| Error::Integrity)?;
+                if self.cfg.dictionary_id() != Some(id) {
+                    return Err(Error::DictionaryMismatch(hex::encode(id)));
+                }
+                self.cfg.zstd_dictionary.as_deref()
+            }
+            _ => return Err(Error::Integrity),
+        };
         let mut sz = [0u8; 4];
         sz.copy_from_slice(&header[5..9]);
         let hdr_chunk_size = u32::from_be_bytes(sz) as usize;
@@ -610,17 +815,15 @@ impl<K: KeyProvider> BlobStore<K> {
         let reader = DecryptedCompressedReader {
             file: f,
             cipher,
+            aad: self.aad.clone(),
             nonce_prefix,
             counter: 0,
             buf: Vec::new(),
             pos: 0,
             chunk_size: hdr_chunk_size,
         };
-        let mut dec = zstd::stream::read::Decoder::new(reader).map_err(|_| Error::Integrity)?;
-        let mut hw = HashingWriter::new(&mut writer);
-        let count = io::copy(&mut dec, &mut hw).map_err(|_| Error::Integrity)? as usize;
-        let (_w, d_bytes, _c) = hw.finalize();
-        if Digest(d_bytes) != *digest {
+        let (got, count) = decompress_hashed(reader, dict, &mut writer)?;
+        if got != *digest {
             return Err(Error::Integrity);
         }
         observer().get_bytes(count as u64);
@@ -629,10 +832,40 @@ impl<K: KeyProvider> BlobStore<K> {
 
     /// Return true if a blob with this digest is present
     pub fn exists(&self, digest: &Digest) -> bool {
-        self.path_for(&digest.to_hex()).exists()
+        self.path_for_digest(digest).exists()
+    }
+
+    /// Decrypt, decompress and hash a blob without retaining its plaintext.
+    /// Returns `Ok(())` only when the stored bytes authenticate and match `digest`.
+    pub fn verify(&self, digest: &Digest) -> Result<(), Error> {
+        self.get_to_writer(digest, io::sink()).map(|_| ())
+    }
+
+    /// List digests of all committed blobs in this namespace (sorted; `.incomplete` artifacts are skipped)
+    pub fn iter_digests(&self) -> Result<Vec<Digest>, Error> {
+        fn walk(dir: &Path, out: &mut Vec<Digest>) -> io::Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    walk(&path, out)?;
+                } else if let Some(d) =
+                    path.file_name().and_then(|n| n.to_str()).and_then(Digest::from_hex)
+                {
+                    out.push(d);
+                }
+            }
+            Ok(())
+        }
+        let mut out = Vec::new();
+        let root = self.blobs_dir();
+        if root.exists() {
+            walk(&root, &mut out)?;
+        }
+        out.sort_unstable_by_key(|d| d.0);
+        Ok(out)
     }
 
-    /// Remove any .incomplete artifacts under root; return count removed
+    /// Remove any .incomplete artifacts under this namespace's directory; return count removed
     pub fn cleanup_incomplete(&self) -> Result<usize, Error> {
         let _span = observer().span("blob.cleanup");
 
@@ -650,7 +883,7 @@ impl<K: KeyProvider> BlobStore<K> {
             Ok(())
         }
         let mut removed = 0usize;
-        let root = self.cfg.root.join("sha256");
+        let root = self.blobs_dir();
         if root.exists() {
             let _ = walk(&root, &mut removed);
         }
@@ -660,6 +893,66 @@ impl<K: KeyProvider> BlobStore<K> {
     }
 }
 
+/// Read-only view of a blob store for consumers that must never write (replay tooling, verifiers).
+///
+/// Write operations are absent from this type, so accidental writes fail at compile time.
+/// Unlike [`BlobStore::new`], opening does not create the root directory.
+pub struct ReadOnlyBlobStore<K: KeyProvider> {
+    inner: BlobStore<K>,
+}
+
+impl<K: KeyProvider> ReadOnlyBlobStore<K> {
+    /// Open an existing store root for reading; `Error::NotFound` if the root is missing
+    pub fn open(cfg: Config, key: K) -> Result<Self, Error> {
+        if !cfg.root.is_dir() {
+            return Err(Error::NotFound);
+        }
+        Ok(Self { inner: BlobStore { cfg, key, aad: Vec::new() } })
+    }
+
+    /// Bind to a namespace (see [`BlobStore::with_namespace`])
+    pub fn with_namespace(self, namespace: impl AsRef<[u8]>) -> Self {
+        Self { inner: self.inner.with_namespace(namespace) }
+    }
+
+    /// Namespace bytes used as AEAD associated data (empty by default)
+    pub fn namespace(&self) -> &[u8] {
+        self.inner.namespace()
+    }
+
+    /// Retrieve plaintext bytes by digest
+    pub fn get(&self, digest: &Digest) -> Result<Vec<u8>, Error> {
+        self.inner.get(digest)
+    }
+
+    /// Streaming read into `writer` (see [`BlobStore::get_to_writer`])
+    pub fn get_to_writer<W: Write>(&self, digest: &Digest, writer: W) -> Result<usize, Error> {
+        self.inner.get_to_writer(digest, writer)
+    }
+
+    /// Return true if a blob with this digest is present
+    pub fn exists(&self, digest: &Digest) -> bool {
+        self.inner.exists(digest)
+    }
+
+    /// Verify a blob authenticates and matches its digest (see [`BlobStore::verify`])
+    pub fn verify(&self, digest: &Digest) -> Result<(), Error> {
+        self.inner.verify(digest)
+    }
+
+    /// List digests of all committed blobs (sorted)
+    pub fn iter_digests(&self) -> Result<Vec<Digest>, Error> {
+        self.inner.iter_digests()
+    }
+}
+
+impl<K: KeyProvider> From<BlobStore<K>> for ReadOnlyBlobStore<K> {
+    /// Downgrade a writer handle to a read-only one
+    fn from(inner: BlobStore<K>) -> Self {
+        Self { inner }
+    }
+}
+
 /// Helper to build a deterministic test buffer of given size
 pub fn deterministic_bytes(len: usize) -> Vec<u8> {
     let mut v = Vec::with_capacity(len);
@@ -0,0 +1,70 @@
+// Namespace-bound stores authenticate the namespace as AEAD associated data.
+
+use blob_store::{BlobStore, Config, DevKeyProvider, Error};
+use std::path::PathBuf;
+
+const KEY: [u8; 32] = [9u8; 32];
+
+fn store_at(dir: &tempfile::TempDir) -> BlobStore<DevKeyProvider> {
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
+    BlobStore::new(cfg, DevKeyProvider::new(KEY)).unwrap()
+}
+
+#[test]
+fn same_namespace_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store_at(&dir).with_namespace("tenant-a");
+    assert_eq!(store.namespace(), b"tenant-a");
+    let d = store.put(b"scoped payload").unwrap();
+    assert_eq!(store.get(&d).unwrap(), b"scoped payload");
+}
+
+#[test]
+fn mismatched_namespace_fails_closed() {
+    let dir = tempfile::tempdir().unwrap();
+    let writer = store_at(&dir).with_namespace("tenant-a");
+    let d = writer.put(b"scoped payload").unwrap();
+
+    for reader in [store_at(&dir).with_namespace("tenant-b"), store_at(&dir)] {
+        assert!(matches!(reader.get(&d), Err(Error::NotFound)));
+        // Even a blob file copied across namespaces fails AEAD authentication.
+        let target = reader.path_for_digest(&d);
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        std::fs::copy(writer.path_for_digest(&d), &target).unwrap();
+        let err = reader.get(&d).unwrap_err();
+        assert!(matches!(err, Error::Crypto(_) | Error::Integrity), "got {err:?}");
+    }
+}
+
+#[test]
+fn namespaces_sharing_a_root_do_not_collide() {
+    let dir = tempfile::tempdir().unwrap();
+    let (a, b) =
+        (store_at(&dir).with_namespace("tenant-a"), store_at(&dir).with_namespace("tenant-b"));
+    let da = a.put_new(b"same content").unwrap();
+    let db = b.put_new(b"same content").unwrap();
+    assert_eq!(da.digest, db.digest);
+    assert!(da.created && db.created, "each namespace stores its own copy");
+    assert_ne!(a.path_for_digest(&da.digest), b.path_for_digest(&db.digest));
+    assert_eq!(a.get(&da.digest).unwrap(), b"same content");
+    assert_eq!(b.get(&db.digest).unwrap(), b"same content");
+    assert_eq!(a.iter_digests().unwrap(), vec![da.digest]);
+    assert!(store_at(&dir).iter_digests().unwrap().is_empty());
+}
+
+#[test]
+fn empty_blob_is_bound_to_namespace() {
+    let dir = tempfile::tempdir().unwrap();
+    let d = store_at(&dir).with_namespace("tenant-a").put(b"").unwrap();
+    assert!(store_at(&dir).with_namespace("tenant-b").get(&d).is_err());
+    assert!(store_at(&dir).get(&d).is_err());
+    assert_eq!(store_at(&dir).with_namespace("tenant-a").get(&d).unwrap(), b"");
+}
+
+#[test]
+fn default_namespace_is_empty_and_compatible() {
+    let dir = tempfile::tempdir().unwrap();
+    let d = store_at(&dir).put(b"legacy").unwrap();
+    assert!(store_at(&dir).namespace().is_empty());
+    assert_eq!(store_at(&dir).with_namespace("").get(&d).unwrap(), b"legacy");
+}
@@ -64,7 +64,7 @@ fn tamper_detection() -> Result<()> {
     let digest = store.put(&data)?;
 
     // Mutate one byte on disk
-    let path = store.path_for(&digest.to_hex());
+    let path = store.path_for_digest(&digest);
     let mut bytes = fs::read(&path)?;
     let mid = bytes.len() / 2;
     bytes[mid] ^= 0xAA;
@@ -84,7 +84,7 @@ fn partial_write_detection_and_cleanup() -> Result<()> {
     let digest = store.put(&data)?;
 
     // Create an incomplete artifact next to the final blob
-    let path = store.path_for(&digest.to_hex());
+    let path = store.path_for_digest(&digest);
     let tmp = path.with_extension("incomplete");
     fs::create_dir_all(tmp.parent().unwrap())?;
     fs::write(&tmp, b"partial")?;
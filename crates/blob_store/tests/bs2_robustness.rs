@@ -9,7 +9,7 @@ use std::path::PathBuf;
 
 fn make_store() -> (tempfile::TempDir, BlobStore<DevKeyProvider>) {
     let dir = tempfile::tempdir().unwrap();
-    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3 };
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
     let store: BlobStore<DevKeyProvider> =
         BlobStore::new(cfg, DevKeyProvider::new([7u8; 32])).unwrap();
     (dir, store)
@@ -28,7 +28,7 @@ fn write_at_path(path: &std::path::Path, bytes: &[u8]) {
 fn rejects_header_chunk_size_zero() {
     let (_dir, store) = make_store();
     let digest = blob_store::BlobStore::<DevKeyProvider>::digest_of(b"dummy");
-    let path = store.path_for(&digest.to_hex());
+    let path = store.path_for_digest(&digest);
 
     // Header: magic + version + chunk_size=0
     let mut file = Vec::new();
@@ -46,7 +46,7 @@ fn rejects_header_chunk_size_zero() {
 fn rejects_chunk_len_over_bound() {
     let (_dir, store) = make_store();
     let digest = blob_store::BlobStore::<DevKeyProvider>::digest_of(b"dummy2");
-    let path = store.path_for(&digest.to_hex());
+    let path = store.path_for_digest(&digest);
 
     let chunk_size = 4096u32; // small for test
     let clen = chunk_size as usize + 16 + 1; // > chunk_size + AEAD_TAG_SIZE
@@ -97,7 +97,7 @@ fn streaming_get_memory_bound_manual() {
 
     let (_dir, store) = make_store();
     let digest = blob_store::BlobStore::<DevKeyProvider>::digest_of(b"dummy3");
-    let path = store.path_for(&digest.to_hex());
+    let path = store.path_for_digest(&digest);
 
     // Craft a file declaring a huge clen without providing the bytes to force old
     // implementations to allocate before failing read_exact. New code should reject
@@ -0,0 +1,79 @@
+// Empty-blob regression: the lone chunk of an empty blob uses the same nonce derivation as
+// every other chunk (`SHA256(key || digest)[..8] || counter_be32`, counter starting at 0).
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use blob_store::{BlobStore, Config, DevKeyProvider, Digest};
+use sha2::{Digest as _, Sha256};
+use std::path::PathBuf;
+
+const KEY: [u8; 32] = [0x42; 32];
+
+fn make_store() -> (tempfile::TempDir, BlobStore<DevKeyProvider>) {
+    let dir = tempfile::tempdir().unwrap();
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
+    let store = BlobStore::new(cfg, DevKeyProvider::new(KEY)).unwrap();
+    (dir, store)
+}
+
+fn chunk_nonce(digest: &Digest, counter: u32) -> [u8; 12] {
+    let prefix = Sha256::new().chain_update(KEY).chain_update(digest.0).finalize();
+    let mut nonce = [0u8; 12];
+    nonce[..8].copy_from_slice(&prefix[..8]);
+    nonce[8..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+fn cipher() -> Aes256Gcm {
+    Aes256Gcm::new_from_slice(&KEY).unwrap()
+}
+
+#[allow(deprecated)]
+fn seal(nonce: &[u8], msg: &[u8]) -> Vec<u8> {
+    cipher().encrypt(Nonce::from_slice(nonce), Payload { msg, aad: &[] }).unwrap()
+}
+
+#[allow(deprecated)]
+fn open(nonce: &[u8], ct: &[u8]) -> Vec<u8> {
+    cipher().decrypt(Nonce::from_slice(nonce), Payload { msg: ct, aad: &[] }).unwrap()
+}
+
+#[test]
+fn empty_blob_round_trips_with_counter_zero_nonce() {
+    let (_dir, store) = make_store();
+    let digest = store.put(b"").unwrap();
+    assert_eq!(digest, BlobStore::<DevKeyProvider>::digest_of(b""));
+    assert_eq!(store.get(&digest).unwrap(), Vec::<u8>::new());
+
+    // Layout: header, then exactly one [len][ct] chunk sealed with counter 0.
+    let file = std::fs::read(store.path_for_digest(&digest)).unwrap();
+    assert_eq!(&file[..5], b"BS2\x00\x01");
+    let clen = u32::from_be_bytes(file[9..13].try_into().unwrap()) as usize;
+    assert_eq!(file.len(), 13 + clen, "a single chunk");
+    let compressed = open(&chunk_nonce(&digest, 0), &file[13..]);
+    assert_eq!(zstd::decode_all(compressed.as_slice()).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn reader_expects_counter_zero_for_the_only_chunk() {
+    let (_dir, store) = make_store();
+    let digest = BlobStore::<DevKeyProvider>::digest_of(b"");
+    let compressed = zstd::encode_all(&b""[..], 3).unwrap();
+    let path = store.path_for_digest(&digest);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let write_sealed = |ct: Vec<u8>| {
+        let mut file = b"BS2\x00\x01".to_vec();
+        file.extend_from_slice(&(64u32 * 1024).to_be_bytes());
+        file.extend_from_slice(&(ct.len() as u32).to_be_bytes());
+        file.extend_from_slice(&ct);
+        std::fs::write(&path, &file).unwrap();
+    };
+
+    write_sealed(seal(&chunk_nonce(&digest, 0), &compressed));
+    assert!(store.verify(&digest).is_ok());
+
+    // The old empty-input branch sealed with the raw 12-byte prefix; the reader rejects it.
+    let prefix = Sha256::new().chain_update(KEY).chain_update(digest.0).finalize();
+    write_sealed(seal(&prefix[..12], &compressed));
+    assert!(store.verify(&digest).is_err());
+}
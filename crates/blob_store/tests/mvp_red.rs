@@ -9,7 +9,7 @@ use std::path::PathBuf;
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 fn store_at(path: &std::path::Path, key: [u8; 32]) -> BlobStore<DevKeyProvider> {
-    let cfg = Config { root: PathBuf::from(path), zstd_level: 3 };
+    let cfg = Config { root: PathBuf::from(path), zstd_level: 3, zstd_dictionary: None };
     let kp = DevKeyProvider::new(key);
     BlobStore::new(cfg, kp).unwrap()
 }
@@ -35,7 +35,7 @@ fn zstd_round_trip_and_ratio_on_compressible_payload() -> Result<()> {
     // Highly compressible payload
     let payload = vec![b'A'; 2 * 1024 * 1024]; // 2 MiB of 'A'
     let digest = store.put(&payload)?;
-    let shard_path = store.path_for(&digest.to_hex());
+    let shard_path = store.path_for_digest(&digest);
 
     // On-disk file should be significantly smaller than plaintext (zstd + AEAD overhead tolerated)
     let disk_len = fs::metadata(&shard_path)?.len() as usize;
@@ -59,7 +59,7 @@ fn encryption_at_rest_ciphertext_properties() -> Result<()> {
     let d = store1.put(&data)?;
 
     // Header should be BS2 and not contain plaintext
-    let path = store1.path_for(&d.to_hex());
+    let path = store1.path_for_digest(&d);
     let bytes = fs::read(&path)?;
     assert!(bytes.starts_with(b"BS2\0"), "missing BS2 header");
     assert!(
@@ -70,7 +70,7 @@ fn encryption_at_rest_ciphertext_properties() -> Result<()> {
     // Stable ciphertext for same input/key across puts
     let d2 = store2.put(&data)?;
     assert_eq!(d, d2);
-    let bytes2 = fs::read(store2.path_for(&d2.to_hex()))?;
+    let bytes2 = fs::read(store2.path_for_digest(&d2))?;
     assert_eq!(bytes, bytes2, "ciphertext must be deterministic for same (key, digest)");
 
     // Different key must fail to decrypt
@@ -94,7 +94,7 @@ fn error_cases_missing_and_corrupted() -> Result<()> {
 
     // Corrupted (flip a byte)
     let d = store.put(&deterministic_bytes(16 * 1024))?;
-    let path = store.path_for(&d.to_hex());
+    let path = store.path_for_digest(&d);
     let mut c = fs::read(&path)?;
     let mid = c.len() / 2;
     c[mid] ^= 0x5A;
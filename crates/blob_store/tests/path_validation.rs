@@ -0,0 +1,36 @@
+// path_for accepts only 64-hex digests and never resolves outside the store root.
+
+use blob_store::{BlobStore, Config, DevKeyProvider, Digest, Error};
+
+#[test]
+fn malformed_digests_are_rejected_without_panicking() {
+    let dir = tempfile::tempdir().unwrap();
+    let store =
+        BlobStore::new(Config::with_root(dir.path().into()), DevKeyProvider::new([1; 32])).unwrap();
+    let traversal = format!("../../{}", "a".repeat(58));
+    for bad in ["", "a", "abc", "zz".repeat(32).as_str(), "../etc/passwd", traversal.as_str()] {
+        assert!(matches!(store.path_for(bad), Err(Error::InvalidDigest)), "{bad:?}");
+    }
+    assert!(matches!(store.path_for(&"a".repeat(63)), Err(Error::InvalidDigest)));
+    assert!(matches!(store.path_for(&"a".repeat(65)), Err(Error::InvalidDigest)));
+}
+
+#[test]
+fn valid_digests_stay_within_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = BlobStore::new(Config::with_root(dir.path().into()), DevKeyProvider::new([1; 32]))
+        .unwrap()
+        .with_namespace("tenant-a");
+    let digest = store.put(b"payload").unwrap();
+    let path = store.path_for(&digest.to_hex()).unwrap();
+    assert_eq!(path, store.path_for_digest(&digest));
+    assert!(path.starts_with(dir.path()));
+    assert!(path.components().all(|c| c != std::path::Component::ParentDir));
+    assert!(path.exists());
+
+    // Uppercase input maps to the same (lowercase) path.
+    let upper = store.path_for(&digest.to_hex().to_ascii_uppercase()).unwrap();
+    assert_eq!(upper, path);
+    assert_eq!(path.file_name().unwrap().to_str().unwrap(), digest.to_hex());
+    assert_eq!(Digest::from_hex(&digest.to_hex()), Some(digest));
+}
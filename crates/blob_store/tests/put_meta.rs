@@ -0,0 +1,25 @@
+// put_reader_meta returns the WAL v2 attachment entry for the stored body.
+
+use blob_store::{BlobStore, Compression, Config, DevKeyProvider};
+use std::io::Cursor;
+
+#[test]
+fn meta_reports_plaintext_size_digest_and_mime() {
+    let dir = tempfile::tempdir().unwrap();
+    let store =
+        BlobStore::new(Config::with_root(dir.path().to_path_buf()), DevKeyProvider::new([4u8; 32]))
+            .unwrap();
+    // Spans several 64 KiB read chunks and compresses well, so on-disk size differs.
+    let body: Vec<u8> = b"captured tool output\n".iter().copied().cycle().take(200_000).collect();
+
+    let att = store.put_reader_meta(Cursor::new(body.clone()), "text/plain").unwrap();
+    assert_eq!(att.size_bytes, body.len() as u64);
+    assert_eq!(att.digest_sha256, BlobStore::<DevKeyProvider>::digest_of(&body).to_hex());
+    assert_eq!(att.mime, "text/plain");
+    assert_eq!(att.compression, Compression::Zstd);
+    assert_eq!(att.encoding, None);
+
+    // A dedup hit reports the same entry; the empty body is size 0.
+    assert_eq!(store.put_reader_meta(Cursor::new(body), "text/plain").unwrap(), att);
+    assert_eq!(store.put_reader_meta(Cursor::new(Vec::new()), "text/plain").unwrap().size_bytes, 0);
+}
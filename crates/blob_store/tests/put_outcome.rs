@@ -0,0 +1,58 @@
+// put_new reports whether a put created a new blob or hit existing content.
+
+use blob_store::{BlobStore, Config, DevKeyProvider};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+fn make_store() -> (tempfile::TempDir, BlobStore<DevKeyProvider>) {
+    let dir = tempfile::tempdir().unwrap();
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
+    let store = BlobStore::new(cfg, DevKeyProvider::new([3u8; 32])).unwrap();
+    (dir, store)
+}
+
+#[test]
+fn first_put_creates_and_second_is_dedup_hit() {
+    let (_dir, store) = make_store();
+    let first = store.put_new(b"write once").unwrap();
+    assert!(first.created);
+    assert_eq!(first.digest, BlobStore::<DevKeyProvider>::digest_of(b"write once"));
+
+    let second = store.put_new(b"write once").unwrap();
+    assert!(!second.created, "identical content must be reported as existing");
+    assert_eq!(second.digest, first.digest);
+}
+
+#[test]
+fn put_and_put_new_agree_on_digest() {
+    let (_dir, store) = make_store();
+    let d = store.put(b"shared").unwrap();
+    let o = store.put_reader_new(Cursor::new(b"shared".to_vec())).unwrap();
+    assert_eq!(o.digest, d);
+    assert!(!o.created, "put already stored the blob");
+}
+
+#[test]
+fn dedup_hit_leaves_no_temp_artifacts() {
+    let (dir, store) = make_store();
+    store.put_new(b"dup").unwrap();
+    store.put_new(b"dup").unwrap();
+    let leftovers = std::fs::read_dir(dir.path().join(".tmp")).unwrap().count();
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn racing_writers_see_exactly_one_creation() {
+    let (_dir, store) = make_store();
+    let store = std::sync::Arc::new(store);
+    let created: usize = (0..8)
+        .map(|_| {
+            let store = store.clone();
+            std::thread::spawn(move || store.put_new(b"contended content").unwrap().created)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|h| usize::from(h.join().unwrap()))
+        .sum();
+    assert_eq!(created, 1);
+}
@@ -0,0 +1,62 @@
+// ReadOnlyBlobStore exposes only read paths over an existing store root.
+
+use blob_store::{BlobStore, Config, DevKeyProvider, Digest, Error, ReadOnlyBlobStore};
+use std::path::PathBuf;
+
+const KEY: [u8; 32] = [5u8; 32];
+
+fn cfg(dir: &tempfile::TempDir) -> Config {
+    Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None }
+}
+
+#[test]
+fn reads_what_a_writer_stored() {
+    let dir = tempfile::tempdir().unwrap();
+    let writer = BlobStore::new(cfg(&dir), DevKeyProvider::new(KEY)).unwrap();
+    let a = writer.put(b"alpha").unwrap();
+    let b = writer.put(b"beta").unwrap();
+
+    let ro = ReadOnlyBlobStore::open(cfg(&dir), DevKeyProvider::new(KEY)).unwrap();
+    assert_eq!(ro.get(&a).unwrap(), b"alpha");
+    assert!(ro.exists(&b));
+    ro.verify(&a).unwrap();
+    let mut expected = vec![a, b];
+    expected.sort_unstable_by_key(|d| d.0);
+    assert_eq!(ro.iter_digests().unwrap(), expected);
+}
+
+#[test]
+fn open_does_not_create_missing_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("absent");
+    let cfg = Config::with_root(missing.clone());
+    let err = ReadOnlyBlobStore::open(cfg, DevKeyProvider::new(KEY)).err().unwrap();
+    assert!(matches!(err, Error::NotFound));
+    assert!(!missing.exists());
+}
+
+#[test]
+fn verify_detects_tamper_and_wrong_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let writer = BlobStore::new(cfg(&dir), DevKeyProvider::new(KEY)).unwrap();
+    let d = writer.put(&blob_store::deterministic_bytes(4096)).unwrap();
+
+    let wrong = ReadOnlyBlobStore::open(cfg(&dir), DevKeyProvider::new([6u8; 32])).unwrap();
+    assert!(wrong.verify(&d).is_err());
+
+    let path = writer.path_for_digest(&d);
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    std::fs::write(&path, bytes).unwrap();
+    let ro = ReadOnlyBlobStore::from(writer);
+    assert!(ro.verify(&d).is_err());
+}
+
+#[test]
+fn digest_hex_round_trips() {
+    let d = BlobStore::<DevKeyProvider>::digest_of(b"x");
+    assert_eq!(Digest::from_hex(&d.to_hex()), Some(d));
+    assert_eq!(Digest::from_hex("abc"), None);
+    assert_eq!(Digest::from_hex(&"zz".repeat(32)), None);
+}
@@ -65,7 +65,7 @@ fn streaming_put_memory_bound_manual() {
         std::env::var("RSS_LIMIT_KB").ok().and_then(|v| v.parse().ok()).unwrap_or(32 * 1024);
 
     let dir = tempfile::tempdir().unwrap();
-    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3 };
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
     let store: BlobStore<DevKeyProvider> =
         BlobStore::new(cfg, DevKeyProvider::new([5u8; 32])).unwrap();
 
@@ -7,7 +7,7 @@ use std::path::PathBuf;
 
 fn new_store() -> (tempfile::TempDir, BlobStore<DevKeyProvider>) {
     let dir = tempfile::tempdir().unwrap();
-    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3 };
+    let cfg = Config { root: PathBuf::from(dir.path()), zstd_level: 3, zstd_dictionary: None };
     let store: BlobStore<DevKeyProvider> =
         BlobStore::new(cfg, DevKeyProvider::new([7u8; 32])).unwrap();
     (dir, store)
@@ -42,7 +42,7 @@ fn cleanup_incomplete_still_works() {
     let (dir, store) = new_store();
     // Create a fake incomplete artifact
     let digest = store.put(&deterministic_bytes(16)).unwrap();
-    let shard = store.path_for(&digest.to_hex());
+    let shard = store.path_for_digest(&digest);
     let tmp = shard.with_extension("incomplete");
     std::fs::create_dir_all(tmp.parent().unwrap()).unwrap();
     std::fs::write(&tmp, b"junk").unwrap();
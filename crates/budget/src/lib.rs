@@ -10,9 +10,18 @@ use std::sync::Arc;
 pub struct BudgetConfig {
     pub max_tokens: Option<u64>,
     pub max_cost_micros: Option<u64>,
+    /// Warning-only ceiling on tokens: past it tasks are still allowed (up to `max_tokens`) but
+    /// flagged for review.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_max_tokens: Option<u64>,
+    /// Warning-only ceiling on cost, as `soft_max_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_max_cost_micros: Option<u64>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Budget pressure, ordered from least to most severe (`Within < Warning80 < Warning90 <
+/// Exceeded`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum BudgetState {
     Within,
     Warning80,
@@ -48,6 +57,10 @@ impl Manager {
     pub fn new(cfg: BudgetConfig) -> Self {
         Self { cfg, counters: Counters::default() }
     }
+    /// Limits this manager enforces.
+    pub fn config(&self) -> BudgetConfig {
+        self.cfg.clone()
+    }
     pub fn counters(&self) -> Counters {
         self.counters.clone()
     }
@@ -57,6 +70,13 @@ impl Manager {
             && self.cfg.max_cost_micros.map(|m| c <= m).unwrap_or(true)
     }
 
+    /// True once usage is past either soft limit; always false when neither is set.
+    pub fn soft_exceeded(&self) -> bool {
+        let (t, c) = self.counters.snapshot();
+        self.cfg.soft_max_tokens.is_some_and(|m| t > m)
+            || self.cfg.soft_max_cost_micros.is_some_and(|m| c > m)
+    }
+
     pub fn add_usage(&self, tokens: u64, cost_micros: u64) {
         if tokens > 0 {
             self.counters.add_tokens(tokens);
@@ -66,6 +86,16 @@ impl Manager {
         }
     }
 
+    /// Remaining (tokens, cost_micros) before each limit; `None` when that limit is unset.
+    /// Saturates at zero once a limit is reached or exceeded.
+    pub fn remaining(&self) -> (Option<u64>, Option<u64>) {
+        let (t, c) = self.counters.snapshot();
+        (
+            self.cfg.max_tokens.map(|m| m.saturating_sub(t)),
+            self.cfg.max_cost_micros.map(|m| m.saturating_sub(c)),
+        )
+    }
+
     pub fn status(&self) -> BudgetState {
         let (t, c) = self.counters.snapshot();
         let token_ratio = self
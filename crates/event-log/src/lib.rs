@@ -2,10 +2,13 @@
 
 #![deny(unsafe_code)]
 
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 use thiserror::Error;
 
 /// Placeholder type for an event identifier.
@@ -20,6 +23,24 @@ pub enum EventLogError {
     Serde(#[from] serde_json::Error),
     #[error("invalid: {0}")]
     Invalid(String),
+    /// The WAL and its hash-chain sidecar disagree at (or just before) record `id`.
+    #[error("hash chain mismatch at id {id}")]
+    ChainMismatch { id: EventId },
+    /// The chain verifies but is shorter than a previously recorded [`ChainHead`].
+    #[error("hash chain truncated: {found} records, head recorded at {expected}")]
+    ChainTruncated { expected: u64, found: u64 },
+    /// A [`ChainCheckpoint`] signature does not verify under the checkpoint key.
+    #[error("checkpoint signature invalid at {records} records")]
+    CheckpointInvalid { records: u64 },
+    /// A checksummed line (see [`JsonlEventLog::with_checksums`]) is incomplete or its
+    /// frame is malformed, typically a torn write. `id` is the record id when still
+    /// readable from the line, `offset` the line's byte offset within its file.
+    #[error("corrupt WAL line at offset {offset} (record id {id:?})")]
+    Corrupt { id: Option<EventId>, offset: u64 },
+    /// A checksummed line holds a complete record whose bytes no longer match its CRC:
+    /// bit rot or an edit rather than a torn write. Fields as for [`Self::Corrupt`].
+    #[error("WAL checksum mismatch at offset {offset} (record id {id:?})")]
+    Checksum { id: Option<EventId>, offset: u64 },
 }
 
 /// Minimal event record persisted to the log.
@@ -33,20 +54,739 @@ pub struct EventRecord<T> {
     pub payload: T,
 }
 
+impl<T> EventRecord<T> {
+    /// Resume filter shared by streaming and replay.
+    ///
+    /// With `since_ts_ms == 0` this is a plain id filter (`id >= start_id`). Otherwise
+    /// records are ordered by `(ts_ms, id)` and kept when at or after
+    /// `(since_ts_ms, start_id)`, so a reader that last saw `(ts, id)` resumes exactly once
+    /// with `(ts, id + 1)` even when many records share that millisecond or ids restarted
+    /// in a later millisecond.
+    pub fn at_or_after(&self, since_ts_ms: u64, start_id: EventId) -> bool {
+        if since_ts_ms == 0 {
+            self.id >= start_id
+        } else {
+            (self.ts_ms, self.id) >= (since_ts_ms, start_id)
+        }
+    }
+}
+
+/// `payload["event"]` kinds emitted by ORCA producers. Replay consumers use this allowlist in
+/// strict mode to surface schema drift (e.g. a typo'd kind) instead of silently ignoring it.
+pub const KNOWN_EVENT_KINDS: &[&str] = &[
+    "start_run",
+    "task_enqueued",
+    "task_updated",
+    "task_dispatched",
+    "usage_update",
+    "task_failed",
+    "run_summary",
+    "run_cancelled",
+    "budget_warning",
+    "budget_exceeded",
+    "budget_soft_exceeded",
+    "budget_state_changed",
+    "policy_audit",
+    "external_io_started",
+    "external_io_finished",
+    "plugin_invoke",
+    "plugin_verified",
+    "plugin_verify_failed",
+];
+
+/// Whether `kind` is in [`KNOWN_EVENT_KINDS`].
+pub fn is_known_event_kind(kind: &str) -> bool {
+    KNOWN_EVENT_KINDS.contains(&kind)
+}
+
+/// One entry of the hash-chain sidecar (`<wal>.chain`), written in WAL order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChainEntry {
+    id: EventId,
+    /// Lowercase hex SHA-256 (or HMAC-SHA256 when keyed) over `prev_hash || wal_line_bytes`
+    /// (newline excluded).
+    hash: String,
+}
+
+/// Hash of the "previous record" for the first entry in a chain.
+const CHAIN_GENESIS: [u8; 32] = [0u8; 32];
+
+fn chain_link(key: Option<&[u8; 32]>, prev: &[u8; 32], line: &[u8]) -> [u8; 32] {
+    match key {
+        Some(key) => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+            mac.update(prev);
+            mac.update(line);
+            mac.finalize().into_bytes().into()
+        }
+        None => {
+            let mut h = Sha256::new();
+            h.update(prev);
+            h.update(line);
+            h.finalize().into()
+        }
+    }
+}
+
+/// Position of a hash chain: how many records it covers and the last link.
+///
+/// Record it out of band (e.g. in a checkpoint or an external audit store) and pass it to
+/// [`JsonlEventLog::verify_chain_from`] to detect records truncated from both the WAL and
+/// its sidecar, which the chain alone cannot see.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+    /// Records covered by the chain in the current file.
+    pub records: u64,
+    /// Lowercase hex link of the last covered record (all zeros when `records == 0`).
+    pub hash: String,
+}
+
+/// Signed [`ChainHead`]: proof, under a key the log writer holds, that the chain once
+/// covered `records` records ending in `hash`. Written to the `<wal>.checkpoints` sidecar
+/// (one JSON line each) by [`JsonlEventLog::write_checkpoint`] and on rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainCheckpoint {
+    /// Records covered by the chain when the checkpoint was taken.
+    pub records: u64,
+    /// Lowercase hex chain link at that position.
+    pub hash: String,
+    /// Lowercase hex HMAC-SHA256 over a domain tag, `records` (big-endian) and `hash`.
+    pub signature: String,
+}
+
+const CHECKPOINT_DOMAIN: &[u8] = b"orca.wal.checkpoint.v1";
+
+fn checkpoint_mac(key: &[u8; 32], records: u64, hash: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(CHECKPOINT_DOMAIN);
+    mac.update(&records.to_be_bytes());
+    mac.update(hash.as_bytes());
+    mac
+}
+
+impl ChainCheckpoint {
+    /// Sign `head` with `key`.
+    pub fn sign(head: &ChainHead, key: &[u8; 32]) -> Self {
+        let sig = checkpoint_mac(key, head.records, &head.hash).finalize().into_bytes();
+        Self { records: head.records, hash: head.hash.clone(), signature: hex::encode(sig) }
+    }
+
+    /// Whether the signature verifies under `key` (constant-time comparison).
+    pub fn verify_signature(&self, key: &[u8; 32]) -> bool {
+        hex::decode(&self.signature).is_ok_and(|sig| {
+            checkpoint_mac(key, self.records, &self.hash).verify_slice(&sig).is_ok()
+        })
+    }
+
+    /// The chain position this checkpoint vouches for.
+    pub fn head(&self) -> ChainHead {
+        ChainHead { records: self.records, hash: self.hash.clone() }
+    }
+}
+
+/// Outcome of [`verify_checkpoints`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointVerification {
+    /// Records whose chain links verified.
+    pub records: u64,
+    /// Checkpoints whose signatures verified and which the chain passes through.
+    pub checkpoints: usize,
+}
+
+/// In-memory chain state shared by every clone of a chained log.
+#[derive(Debug)]
+struct ChainState {
+    head: [u8; 32],
+    records: u64,
+    /// HMAC key for the links; `None` => plain SHA-256.
+    key: Option<[u8; 32]>,
+    /// Sidecar lines whose WAL records are written but which have not reached the sidecar
+    /// yet. The sidecar is only ever written after the WAL, so it may lag but never lead.
+    pending: Vec<u8>,
+}
+
+impl ChainState {
+    fn push(&mut self, id: EventId, line: &[u8]) -> Result<(), EventLogError> {
+        self.head = chain_link(self.key.as_ref(), &self.head, line);
+        self.records += 1;
+        let entry = ChainEntry { id, hash: hex::encode(self.head) };
+        serde_json::to_writer(&mut self.pending, &entry)?;
+        self.pending.push(b'\n');
+        Ok(())
+    }
+
+    /// Append pending entries to the sidecar; on failure they stay pending for the next try.
+    fn write_pending(&mut self, chain_path: &str) -> Result<(), EventLogError> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut side = OpenOptions::new().create(true).append(true).open(chain_path)?;
+        side.write_all(&self.pending)?;
+        side.flush()?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// Durability policy for [`JsonlEventLog::append`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncPolicy {
+    /// Open, write, and flush the file on every append (default).
+    #[default]
+    PerAppend,
+    /// Keep appends in an in-process buffer shared by all clones of the log. Records reach
+    /// the file on [`JsonlEventLog::flush`], before reads, on [`JsonlEventLog::close`]
+    /// (which also fsyncs), or best-effort when a handle is dropped. Call `close` before
+    /// exiting: a crash may lose buffered records.
+    Buffered,
+}
+
+/// Default cap on a single WAL line when reading (1 MiB, well above the ~10 KiB record target).
+pub const DEFAULT_MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Leading byte of a checksummed WAL line, format version 1: this byte, 8 lowercase hex
+/// digits of the CRC-32 (IEEE) of the record JSON, a space, then the JSON itself. Lines
+/// starting with `{` carry no checksum; other leading bytes are reserved for later versions.
+pub const CHECKSUM_V1: u8 = b'1';
+
+/// Bytes before the record JSON in a [`CHECKSUM_V1`] line.
+const CHECKSUM_V1_HEADER: usize = 10;
+
+/// `json` framed as a [`CHECKSUM_V1`] line (without the newline).
+fn frame_v1(json: &str) -> String {
+    format!("{}{:08x} {}", CHECKSUM_V1 as char, crc32fast::hash(json.as_bytes()), json)
+}
+
+/// Why a [`CHECKSUM_V1`] line did not verify.
+enum LineFault {
+    /// Malformed frame, or a CRC mismatch on JSON that does not parse: a partial record.
+    Torn,
+    /// CRC mismatch on a complete JSON record.
+    Mismatch,
+}
+
+/// The record JSON of a WAL line: the line itself when unframed, or the verified payload of
+/// a [`CHECKSUM_V1`] frame.
+fn record_json(line: &[u8]) -> Result<&[u8], LineFault> {
+    if line.first() != Some(&CHECKSUM_V1) {
+        return Ok(line);
+    }
+    let Some(header) = line.get(..CHECKSUM_V1_HEADER) else {
+        return Err(LineFault::Torn);
+    };
+    let crc = std::str::from_utf8(&header[1..9])
+        .ok()
+        .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+    let (Some(crc), b' ') = (crc, header[9]) else {
+        return Err(LineFault::Torn);
+    };
+    let json = &line[CHECKSUM_V1_HEADER..];
+    if crc32fast::hash(json) == crc {
+        Ok(json)
+    } else if serde_json::from_slice::<serde::de::IgnoredAny>(json).is_ok() {
+        Err(LineFault::Mismatch)
+    } else {
+        Err(LineFault::Torn)
+    }
+}
+
+/// Record id read textually from the `{"id":N` prefix every serialized record starts with,
+/// so a torn or corrupt line can still be named.
+fn record_id_hint(line: &[u8]) -> Option<EventId> {
+    let json = match line.first() {
+        Some(&CHECKSUM_V1) => line.get(CHECKSUM_V1_HEADER..)?,
+        _ => line,
+    };
+    let digits = json.strip_prefix(b"{\"id\":")?;
+    let end = digits.iter().position(|b| !b.is_ascii_digit()).unwrap_or(digits.len());
+    std::str::from_utf8(&digits[..end]).ok()?.parse().ok()
+}
+
+/// A WAL line that did not decode, as reported by [`JsonlEventLog::read_range_lossy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadLine {
+    /// Record id, when still readable from the line.
+    pub id: Option<EventId>,
+    /// Byte offset of the line within its file.
+    pub offset: u64,
+}
+
+/// Records recovered by [`JsonlEventLog::read_range_lossy`] (or
+/// [`JsonlEventLog::read_range_lenient`]) and the lines it skipped.
+#[derive(Debug, Clone)]
+pub struct LossyRange<T> {
+    /// Decodable records with id in the requested range, in file order.
+    pub records: Vec<EventRecord<T>>,
+    /// Corrupt or partial lines, in file order.
+    pub skipped: Vec<BadLine>,
+}
+
+/// Thresholds for automatic segment rolling (see [`JsonlEventLog::with_rotation`]).
+///
+/// `None` disables a threshold; the default disables both, which keeps the single-file log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RotationPolicy {
+    /// Seal the active file once it holds at least this many bytes.
+    pub max_bytes: Option<u64>,
+    /// Seal the active file once it holds this many records.
+    pub max_records: Option<u64>,
+}
+
+/// A sealed segment as recorded in the manifest (`<wal>.manifest`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SegmentInfo {
+    /// Sequence number (1-based), also encoded in the file name.
+    pub seq: u64,
+    /// File name, relative to the WAL's directory (`log.0001.jsonl` for `log.jsonl`).
+    pub file: String,
+    /// Smallest record id in the segment.
+    pub min_id: EventId,
+    /// Largest record id in the segment.
+    pub max_id: EventId,
+    /// Records in the segment.
+    pub records: u64,
+    /// Segment size in bytes.
+    pub bytes: u64,
+}
+
+impl SegmentInfo {
+    /// Whether the segment may hold ids in `[start, end)`.
+    fn overlaps(&self, start: EventId, end: EventId) -> bool {
+        self.min_id < end && self.max_id >= start
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Manifest {
+    segments: Vec<SegmentInfo>,
+}
+
+/// Records, bytes, and id range of one WAL file.
+#[derive(Debug, Clone, Copy, Default)]
+struct FileStats {
+    records: u64,
+    bytes: u64,
+    min_id: Option<EventId>,
+    max_id: Option<EventId>,
+}
+
+impl FileStats {
+    fn add(&mut self, min_id: EventId, max_id: EventId, records: u64, bytes: u64) {
+        self.records += records;
+        self.bytes += bytes;
+        self.min_id = Some(self.min_id.map_or(min_id, |m| m.min(min_id)));
+        self.max_id = Some(self.max_id.map_or(max_id, |m| m.max(max_id)));
+    }
+
+    /// Count every parsable line of the file at `path` (bytes are the file size).
+    fn scan(path: &Path, max_line_bytes: usize) -> Result<Self, EventLogError> {
+        let mut stats = FileStats::default();
+        for line in BoundedLines::new(File::open(path)?, max_line_bytes) {
+            let line = line?;
+            // A torn or corrupt line still takes up space but holds no record.
+            if let Ok(rec) = parse_wal_line::<serde::de::IgnoredAny>(&line) {
+                stats.add(rec.id, rec.id, 1, 0);
+            }
+        }
+        stats.bytes = std::fs::metadata(path)?.len();
+        Ok(stats)
+    }
+}
+
+/// Segment rolling state shared by every clone of a rotating log.
+#[derive(Debug)]
+struct RotationState {
+    policy: RotationPolicy,
+    manifest: Manifest,
+    next_seq: u64,
+    /// The active file at the WAL path.
+    active: FileStats,
+}
+
+impl RotationState {
+    /// Whether the active file has reached a threshold; an empty file never has.
+    fn is_full(&self) -> bool {
+        let a = &self.active;
+        a.records > 0
+            && (self.policy.max_records.is_some_and(|m| a.records >= m)
+                || self.policy.max_bytes.is_some_and(|m| a.bytes >= m))
+    }
+
+    fn push_segment(&mut self, path: &Path, stats: FileStats) {
+        let seq = self.next_seq;
+        self.manifest.segments.push(SegmentInfo {
+            seq,
+            file: file_name(&segment_path(path, seq)),
+            min_id: stats.min_id.unwrap_or(0),
+            max_id: stats.max_id.unwrap_or(0),
+            records: stats.records,
+            bytes: stats.bytes,
+        });
+        self.next_seq = seq + 1;
+    }
+}
+
+/// `log.0001.jsonl` for `log.jsonl` and `seq == 1`; `log.0001` when there is no extension.
+fn segment_path(path: &Path, seq: u64) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{:04}.{}", stem, seq, ext.to_string_lossy()),
+        None => format!("{}.{:04}", stem, seq),
+    };
+    path.with_file_name(name)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
 /// A simple JSONL-backed append-only event log.
+///
+/// By default everything goes to one file; [`JsonlEventLog::with_rotation`] rolls it into
+/// numbered segments instead.
+///
+/// Clones share one append gate, [`SyncPolicy::Buffered`] buffer, rotation state, and
+/// hash-chain state.
+/// Handles opened separately on the same path share none of it: their appends are not
+/// drained by [`JsonlEventLog::rotate_to`], may interleave with buffered writes, and break
+/// the hash chain. Open each WAL once per process and clone the handle.
 #[derive(Debug, Clone)]
 pub struct JsonlEventLog {
     path: String,
+    /// Reads fail with [`EventLogError::Invalid`] on any line longer than this.
+    max_line_bytes: usize,
+    /// Sidecar hash chain state when chaining is enabled (shared across clones).
+    chain: Option<Arc<Mutex<ChainState>>>,
+    /// Shared append buffer under [`SyncPolicy::Buffered`].
+    buffer: Option<Arc<Mutex<BufWriter<File>>>>,
+    /// Appends hold this shared; [`JsonlEventLog::rotate_to`] holds it exclusively to drain
+    /// them (shared across clones).
+    gate: Arc<RwLock<()>>,
+    /// Signs [`ChainCheckpoint`]s when set (see [`JsonlEventLog::with_checkpoint_key`]).
+    checkpoint_key: Option<[u8; 32]>,
+    /// Segment rolling state when a [`RotationPolicy`] is set (shared across clones).
+    rotation: Option<Arc<Mutex<RotationState>>>,
+    /// Frame appended lines with a [`CHECKSUM_V1`] checksum.
+    checksums: bool,
+    /// Opened with [`JsonlEventLog::open_read_only`]: writes fail instead of touching the file.
+    read_only: bool,
+    /// Set once the first append has ended any torn tail (shared across clones).
+    tail_checked: Arc<Mutex<bool>>,
 }
 
 impl JsonlEventLog {
     /// Create or open a log at `path`.
+    ///
+    /// Fails with [`EventLogError::Invalid`] when `path` is a directory, or the file cannot
+    /// be opened for append or accept a (zero-byte) probe write. Nothing else is created
+    /// next to the file, and existing contents are left as they are: an unterminated last
+    /// line (a crash mid-append) gets its newline only at the first append, so it reads back
+    /// as one bad line and later appends stay intact.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, EventLogError> {
         let p = path.as_ref();
-        if !p.exists() {
-            OpenOptions::new().create(true).write(true).truncate(true).open(p)?;
+        if p.is_dir() {
+            return Err(EventLogError::Invalid(format!("WAL path {} is a directory", p.display())));
+        }
+        let not_writable = |e: std::io::Error| {
+            EventLogError::Invalid(format!("WAL path {} is not writable: {}", p.display(), e))
+        };
+        let mut file =
+            OpenOptions::new().create(true).append(true).open(p).map_err(not_writable)?;
+        // Probe the handle itself: an empty write surfaces EBADF/EROFS-style errors without
+        // touching the file or its directory.
+        file.write(&[]).map_err(not_writable)?;
+        Ok(Self::at(p))
+    }
+
+    /// Open an existing log at `path` for reading only, as inspection and replay tools do.
+    ///
+    /// Nothing is created, probed, or repaired, so a log on read-only storage (or one a
+    /// running writer owns) can be read as-is. Fails with [`EventLogError::Invalid`] when
+    /// `path` is missing, a directory, or not readable. Appends, [`Self::rotate_to`], and
+    /// [`Self::truncate_to`] on the handle fail with [`EventLogError::Invalid`].
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self, EventLogError> {
+        let p = path.as_ref();
+        if p.is_dir() {
+            return Err(EventLogError::Invalid(format!("WAL path {} is a directory", p.display())));
+        }
+        File::open(p).map_err(|e| {
+            EventLogError::Invalid(format!("WAL path {} is not readable: {}", p.display(), e))
+        })?;
+        let mut log = Self::at(p);
+        log.read_only = true;
+        Ok(log)
+    }
+
+    /// Handle on `p` with default settings, without touching the file.
+    fn at(p: &Path) -> Self {
+        Self {
+            path: p.to_string_lossy().into_owned(),
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+            chain: None,
+            buffer: None,
+            gate: Arc::new(RwLock::new(())),
+            checkpoint_key: None,
+            rotation: None,
+            checksums: false,
+            read_only: false,
+            tail_checked: Arc::new(Mutex::new(false)),
         }
-        Ok(Self { path: p.to_string_lossy().into_owned() })
+    }
+
+    /// Cap the length of a single line accepted by reads (default [`DEFAULT_MAX_LINE_BYTES`]).
+    ///
+    /// Reads stop with `EventLogError::Invalid("line exceeds max bytes")` instead of
+    /// allocating an unbounded buffer for a corrupt or crafted WAL. Appends are not checked.
+    pub fn with_max_line_bytes(mut self, max: usize) -> Self {
+        self.max_line_bytes = max;
+        self
+    }
+
+    /// Prefix every appended line with a versioned CRC-32 of the record bytes (see
+    /// [`CHECKSUM_V1`]), so reads tell a torn or bit-rotted record
+    /// ([`EventLogError::Corrupt`]) from a malformed one.
+    ///
+    /// Reads always accept both framed and plain lines, so checksums can be enabled on an
+    /// existing log and any handle can read a checksummed one. Tools that parse WAL lines as
+    /// raw JSON must go through [`parse_wal_line`] instead.
+    pub fn with_checksums(mut self) -> Self {
+        self.checksums = true;
+        self
+    }
+
+    /// Serialize `rec` as one WAL line (no newline), framed when checksums are enabled.
+    fn encode_line<T: Serialize>(&self, rec: &EventRecord<T>) -> Result<String, EventLogError> {
+        let json = serde_json::to_string(rec)?;
+        Ok(if self.checksums { frame_v1(&json) } else { json })
+    }
+
+    /// Select the append durability policy (default [`SyncPolicy::PerAppend`]).
+    pub fn with_sync_policy(mut self, policy: SyncPolicy) -> Result<Self, EventLogError> {
+        self.flush()?;
+        self.buffer = match policy {
+            SyncPolicy::PerAppend => None,
+            SyncPolicy::Buffered => {
+                let file = OpenOptions::new().append(true).open(&self.path)?;
+                Some(Arc::new(Mutex::new(BufWriter::new(file))))
+            }
+        };
+        Ok(self)
+    }
+
+    /// Roll the log into numbered segments under `policy`.
+    ///
+    /// Before an append would land in an active file that has reached `max_records` records
+    /// or `max_bytes` bytes, the file is sealed as for [`Self::rotate_to`] (drained, fsynced,
+    /// renamed, with its chain sidecars) to `log.0001.jsonl`, `log.0002.jsonl`, ... next to
+    /// `log.jsonl`, and the manifest ([`Self::manifest_path`]) recording each segment's id
+    /// range is then replaced atomically. A batch is never split, so a segment may overshoot
+    /// by one batch. The active file keeps the WAL path, so readers of it alone see only the
+    /// newest records; [`Self::read_range`] and [`Self::iter_range`] cover every segment
+    /// overlapping the requested window.
+    ///
+    /// An existing manifest is loaded, and segments renamed by a roll that crashed before
+    /// its manifest update are recovered into it. Fails with [`EventLogError::Invalid`] on a
+    /// zero threshold.
+    pub fn with_rotation(mut self, policy: RotationPolicy) -> Result<Self, EventLogError> {
+        if policy.max_bytes == Some(0) || policy.max_records == Some(0) {
+            return Err(EventLogError::Invalid("rotation thresholds must be at least 1".into()));
+        }
+        self.flush()?;
+        let manifest = match std::fs::read(self.manifest_path()) {
+            Ok(bytes) => serde_json::from_slice::<Manifest>(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Manifest::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let next_seq = manifest.segments.last().map_or(1, |s| s.seq + 1);
+        let mut state = RotationState { policy, manifest, next_seq, active: FileStats::default() };
+        let path = Path::new(&self.path);
+        let mut recovered = false;
+        loop {
+            let orphan = segment_path(path, state.next_seq);
+            if !orphan.exists() {
+                break;
+            }
+            state.push_segment(path, FileStats::scan(&orphan, self.max_line_bytes)?);
+            recovered = true;
+        }
+        if recovered {
+            replace_atomically(&self.manifest_path(), &serde_json::to_vec(&state.manifest)?)?;
+        }
+        state.active = FileStats::scan(path, self.max_line_bytes)?;
+        self.rotation = Some(Arc::new(Mutex::new(state)));
+        Ok(self)
+    }
+
+    /// Path of the segment manifest for this log.
+    pub fn manifest_path(&self) -> String {
+        format!("{}.manifest", self.path)
+    }
+
+    /// Sealed segments in order, oldest first (empty without a [`RotationPolicy`]).
+    pub fn segments(&self) -> Result<Vec<SegmentInfo>, EventLogError> {
+        match &self.rotation {
+            Some(rot) => Ok(lock(rot)?.manifest.segments.clone()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Push any buffered appends to the file (no-op under [`SyncPolicy::PerAppend`]), then
+    /// their hash-chain entries to the sidecar.
+    pub fn flush(&self) -> Result<(), EventLogError> {
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        if let Some(buf) = &self.buffer {
+            lock(buf)?.flush()?;
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            state.write_pending(&self.chain_path())?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered appends and fsync the log (and its hash-chain sidecar, if enabled).
+    ///
+    /// Required for [`SyncPolicy::Buffered`] to guarantee durability; the best-effort
+    /// flush on drop cannot report errors and does not fsync.
+    pub fn close(self) -> Result<(), EventLogError> {
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        match &self.buffer {
+            Some(buf) => {
+                let mut w = lock(buf)?;
+                w.flush()?;
+                w.get_ref().sync_all()?;
+            }
+            None => OpenOptions::new().append(true).open(&self.path)?.sync_all()?,
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            state.write_pending(&self.chain_path())?;
+            if let Ok(side) = OpenOptions::new().append(true).open(self.chain_path()) {
+                side.sync_all()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable the integrity sidecar (`<wal>.chain`) for subsequent appends.
+    ///
+    /// Each appended record gets a sidecar entry whose hash covers the previous entry's
+    /// hash and the exact WAL line bytes, so insertions, deletions, reorderings, and edits
+    /// are detectable with [`JsonlEventLog::verify_chain`].
+    ///
+    /// The sidecar is written after the WAL (under [`SyncPolicy::Buffered`], when the WAL
+    /// buffer is flushed), so after a crash it may lag but never lead. On enable, the
+    /// existing sidecar is checked against the WAL (failing with
+    /// [`EventLogError::ChainMismatch`] if they disagree) and then extended over WAL records
+    /// it does not cover yet: a WAL written before chaining was enabled is adopted whole.
+    /// An unkeyed chain can be recomputed by anyone who can edit the files; see
+    /// [`Self::with_keyed_hash_chain`].
+    pub fn with_hash_chain(self) -> Result<Self, EventLogError> {
+        self.enable_chain(None)
+    }
+
+    /// Like [`Self::with_hash_chain`], with every link an HMAC-SHA256 under `key`, so the
+    /// chain cannot be recomputed after tampering without the key. Truncating the same
+    /// records from both files still leaves a valid (shorter) chain; compare against a
+    /// recorded [`ChainHead`] with [`Self::verify_chain_from`] to catch that.
+    pub fn with_keyed_hash_chain(self, key: [u8; 32]) -> Result<Self, EventLogError> {
+        self.enable_chain(Some(key))
+    }
+
+    fn enable_chain(mut self, key: Option<[u8; 32]>) -> Result<Self, EventLogError> {
+        self.flush()?;
+        let mut state = ChainState { head: CHAIN_GENESIS, records: 0, key, pending: Vec::new() };
+        let mut side = Vec::new();
+        match File::open(self.chain_path()) {
+            Ok(f) => {
+                for line in BufReader::new(f).lines() {
+                    let line = line?;
+                    if !line.is_empty() {
+                        side.push(serde_json::from_str::<ChainEntry>(&line)?);
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        let mut side = side.into_iter();
+        for line in BoundedLines::new(File::open(&self.path)?, self.max_line_bytes) {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let rec: EventRecord<serde_json::Value> = parse_wal_line(&line)?;
+            match side.next() {
+                Some(entry) => {
+                    let expected = chain_link(key.as_ref(), &state.head, &line);
+                    if entry.id != rec.id || decode_hash(&entry)? != expected {
+                        return Err(EventLogError::ChainMismatch { id: entry.id });
+                    }
+                    state.head = expected;
+                    state.records += 1;
+                }
+                // Not covered by the sidecar yet: adopt it.
+                None => state.push(rec.id, &line)?,
+            }
+        }
+        if let Some(extra) = side.next() {
+            return Err(EventLogError::ChainMismatch { id: extra.id });
+        }
+        state.write_pending(&self.chain_path())?;
+        self.chain = Some(Arc::new(Mutex::new(state)));
+        Ok(self)
+    }
+
+    /// Sign chain checkpoints with `key`: [`Self::write_checkpoint`] appends one on demand,
+    /// and [`Self::rotate_to`] seals each closed segment with a final one. Requires the
+    /// hash chain; the key is independent of a keyed chain's link key and must be kept
+    /// away from anyone able to edit the WAL.
+    pub fn with_checkpoint_key(mut self, key: [u8; 32]) -> Self {
+        self.checkpoint_key = Some(key);
+        self
+    }
+
+    /// Path of the signed-checkpoint sidecar for this log.
+    pub fn checkpoint_path(&self) -> String {
+        format!("{}.checkpoints", self.path)
+    }
+
+    /// Flush, then sign the current chain head and append it to [`Self::checkpoint_path`]
+    /// (fsynced). Fails with [`EventLogError::Invalid`] without a hash chain or a
+    /// checkpoint key.
+    pub fn write_checkpoint(&self) -> Result<ChainCheckpoint, EventLogError> {
+        let (Some(chain), Some(key)) = (&self.chain, &self.checkpoint_key) else {
+            return Err(EventLogError::Invalid(
+                "checkpoints need a hash chain and a checkpoint key".into(),
+            ));
+        };
+        let mut state = lock(chain)?;
+        if let Some(buf) = &self.buffer {
+            lock(buf)?.flush()?;
+        }
+        state.write_pending(&self.chain_path())?;
+        append_checkpoint(&self.checkpoint_path(), &state, key)
+    }
+
+    /// Current position of the hash chain, or `None` when chaining is not enabled.
+    pub fn chain_head(&self) -> Result<Option<ChainHead>, EventLogError> {
+        match &self.chain {
+            Some(c) => {
+                let state = lock(c)?;
+                Ok(Some(ChainHead { records: state.records, hash: hex::encode(state.head) }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Path of the hash-chain sidecar for this log.
+    pub fn chain_path(&self) -> String {
+        format!("{}.chain", self.path)
     }
 
     /// Append a payload; returns assigned EventId.
@@ -56,36 +796,999 @@ impl JsonlEventLog {
         ts_ms: u64,
         payload: &T,
     ) -> Result<EventId, EventLogError> {
-        let mut file = OpenOptions::new().append(true).open(&self.path)?;
         let rec = EventRecord { id, ts_ms, payload };
-        let line = serde_json::to_string(&rec)?;
-        file.write_all(line.as_bytes())?;
-        file.write_all(b"\n")?;
-        file.flush()?;
+        let mut line = self.encode_line(&rec)?;
+        line.push('\n');
+        let _gate = self.enter_append(id, id, 1, line.len() as u64)?;
+        self.terminate_torn_tail()?;
+        // Hold the chain state across both writes so WAL and sidecar order agree.
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        // One write per record (newline included), so concurrent O_APPEND writers never
+        // interleave inside a line.
+        if let Some(buf) = &self.buffer {
+            lock(buf)?.write_all(line.as_bytes())?;
+        } else {
+            let mut file = OpenOptions::new().append(true).open(&self.path)?;
+            file.write_all(line.as_bytes())?;
+            file.flush()?;
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            state.push(id, line.trim_end_matches('\n').as_bytes())?;
+            if self.buffer.is_none() {
+                state.write_pending(&self.chain_path())?;
+            }
+        }
         Ok(id)
     }
 
+    /// Append several records; returns the number appended.
+    ///
+    /// Every record is serialized before anything is written, so a serialization failure on
+    /// the Kth record leaves the log untouched. The write itself is not atomic: the lines
+    /// go out in one `write_all` (into the shared buffer under [`SyncPolicy::Buffered`],
+    /// which may reach the file across several flushes), and an I/O error or crash
+    /// mid-write can leave a prefix of the batch, with a torn last line. Hash-chain entries
+    /// reach the sidecar after the WAL lines, as for [`Self::append`].
+    pub fn append_batch<T: Serialize>(
+        &self,
+        records: &[EventRecord<T>],
+    ) -> Result<usize, EventLogError> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        let mut lines = Vec::with_capacity(records.len());
+        for rec in records {
+            lines.push(self.encode_line(rec)?);
+        }
+        let mut batch = String::with_capacity(lines.iter().map(|l| l.len() + 1).sum());
+        for line in &lines {
+            batch.push_str(line);
+            batch.push('\n');
+        }
+        let min_id = records.iter().map(|r| r.id).min().unwrap_or(0);
+        let max_id = records.iter().map(|r| r.id).max().unwrap_or(0);
+        let _gate = self.enter_append(min_id, max_id, records.len() as u64, batch.len() as u64)?;
+        self.terminate_torn_tail()?;
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        if let Some(buf) = &self.buffer {
+            lock(buf)?.write_all(batch.as_bytes())?;
+        } else {
+            let mut file = OpenOptions::new().append(true).open(&self.path)?;
+            file.write_all(batch.as_bytes())?;
+            file.flush()?;
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            for (rec, line) in records.iter().zip(&lines) {
+                state.push(rec.id, line.as_bytes())?;
+            }
+            if self.buffer.is_none() {
+                state.write_pending(&self.chain_path())?;
+            }
+        }
+        Ok(records.len())
+    }
+
+    /// Rotate the active file out to `segment` and continue appending to a fresh, empty
+    /// file at the original path.
+    ///
+    /// Appends from every clone are drained first: in-flight appends finish, new ones wait
+    /// until the handoff completes, buffered records are flushed and the file is fsynced
+    /// before the rename. Every record therefore lands whole in exactly one of the two
+    /// files. With the hash chain enabled the sidecar moves to `<segment>.chain` and the new
+    /// file starts a fresh chain; with a checkpoint key, a final signed checkpoint is
+    /// appended first and the checkpoints move to `<segment>.checkpoints`. Fails with
+    /// [`EventLogError::Invalid`] when `segment` already exists.
+    ///
+    /// Only clones of this handle are quiesced. Appends through a handle opened separately
+    /// on the same path (in this or another process) are not drained and may land in
+    /// either file or be lost with the rename; see [`JsonlEventLog`]. Under a
+    /// [`RotationPolicy`] it fails with [`EventLogError::Invalid`] instead: a segment outside
+    /// the manifest would drop out of ranged reads, so the policy does all rolling.
+    pub fn rotate_to<P: AsRef<Path>>(&self, segment: P) -> Result<(), EventLogError> {
+        self.check_writable()?;
+        if self.rotation.is_some() {
+            return Err(EventLogError::Invalid(
+                "rotate_to is not available under a rotation policy".into(),
+            ));
+        }
+        let segment = segment.as_ref();
+        let _drained = self
+            .gate
+            .write()
+            .map_err(|_| EventLogError::Invalid("event log lock poisoned".into()))?;
+        if segment.exists() {
+            return Err(EventLogError::Invalid(format!(
+                "rotation target {} already exists",
+                segment.display()
+            )));
+        }
+        self.seal_active(segment)
+    }
+
+    /// Fail with [`EventLogError::Invalid`] on a handle from [`Self::open_read_only`].
+    fn check_writable(&self) -> Result<(), EventLogError> {
+        if self.read_only {
+            return Err(EventLogError::Invalid(format!("WAL {} is open read-only", self.path)));
+        }
+        Ok(())
+    }
+
+    /// Before this handle family's first write, end an unterminated last line left by a
+    /// crash mid-append so the next record starts a line of its own instead of being merged
+    /// into the torn one. The caller holds the append gate.
+    fn terminate_torn_tail(&self) -> Result<(), EventLogError> {
+        let mut checked = lock(&self.tail_checked)?;
+        if *checked {
+            return Ok(());
+        }
+        let path = Path::new(&self.path);
+        if ends_unterminated(path)? {
+            OpenOptions::new().append(true).open(path)?.write_all(b"\n")?;
+            tracing::warn!(wal = %path.display(), "terminated torn WAL tail");
+        }
+        *checked = true;
+        Ok(())
+    }
+
+    /// Take the shared append gate for `records` records with ids in `[min_id, max_id]`
+    /// totalling `bytes`, first rolling a full active segment under a [`RotationPolicy`].
+    fn enter_append(
+        &self,
+        min_id: EventId,
+        max_id: EventId,
+        records: u64,
+        bytes: u64,
+    ) -> Result<std::sync::RwLockReadGuard<'_, ()>, EventLogError> {
+        self.check_writable()?;
+        let Some(rot) = &self.rotation else {
+            return read_gate(&self.gate);
+        };
+        loop {
+            let gate = read_gate(&self.gate)?;
+            {
+                let mut state = lock(rot)?;
+                if !state.is_full() {
+                    state.active.add(min_id, max_id, records, bytes);
+                    return Ok(gate);
+                }
+            }
+            drop(gate);
+            self.roll_segment()?;
+        }
+    }
+
+    /// Seal the full active file as the next numbered segment, then record it in the
+    /// manifest. A crash between the two leaves an unlisted segment that
+    /// [`Self::with_rotation`] recovers.
+    fn roll_segment(&self) -> Result<(), EventLogError> {
+        let Some(rot) = &self.rotation else {
+            return Ok(());
+        };
+        let _drained = self
+            .gate
+            .write()
+            .map_err(|_| EventLogError::Invalid("event log lock poisoned".into()))?;
+        let (seq, stats) = {
+            let state = lock(rot)?;
+            // Another appender may have rolled while this one waited for the gate.
+            if !state.is_full() {
+                return Ok(());
+            }
+            (state.next_seq, state.active)
+        };
+        let path = Path::new(&self.path);
+        let segment = segment_path(path, seq);
+        if segment.exists() {
+            return Err(EventLogError::Invalid(format!(
+                "segment {} already exists",
+                segment.display()
+            )));
+        }
+        self.seal_active(&segment)?;
+        let mut state = lock(rot)?;
+        state.active = FileStats::default();
+        state.push_segment(path, stats);
+        // Listed in memory even if this write fails; the next roll rewrites the manifest.
+        replace_atomically(&self.manifest_path(), &serde_json::to_vec(&state.manifest)?)
+    }
+
+    /// Flush and fsync the active file, move it (and its chain sidecars) to `segment`, and
+    /// start a fresh file at the WAL path. The caller holds the gate exclusively.
+    fn seal_active(&self, segment: &Path) -> Result<(), EventLogError> {
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        let mut buffer = match &self.buffer {
+            Some(buf) => Some(lock(buf)?),
+            None => None,
+        };
+        match buffer.as_deref_mut() {
+            Some(w) => {
+                w.flush()?;
+                w.get_ref().sync_all()?;
+            }
+            None => OpenOptions::new().append(true).open(&self.path)?.sync_all()?,
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            state.write_pending(&self.chain_path())?;
+            if let Some(key) = &self.checkpoint_key {
+                append_checkpoint(&self.checkpoint_path(), state, key)?;
+            }
+        }
+        std::fs::rename(&self.path, segment)?;
+        if let Some(state) = chain.as_deref_mut() {
+            for (from, suffix) in
+                [(self.chain_path(), ".chain"), (self.checkpoint_path(), ".checkpoints")]
+            {
+                let mut side = segment.as_os_str().to_owned();
+                side.push(suffix);
+                match std::fs::rename(from, &side) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            state.head = CHAIN_GENESIS;
+            state.records = 0;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if let Some(w) = buffer.as_deref_mut() {
+            *w = BufWriter::new(file);
+        }
+        Ok(())
+    }
+
+    /// Rewrite the log keeping only the records up to and including `last_good_id`: the
+    /// prefix ending before the first record whose id is greater. Once the record with id
+    /// `last_good_id` has been kept, the first line that does not parse also ends the prefix,
+    /// so a torn or corrupt tail after it is cut off; an unparsable line before it fails.
+    ///
+    /// Appends from every clone are drained as for [`Self::rotate_to`]. The kept prefix is
+    /// written to a temporary file, fsynced, and renamed over the log, so a crash leaves
+    /// either the old or the truncated file. With the hash chain enabled the sidecar is cut
+    /// to the same records the same way; signed checkpoints beyond the cut no longer verify.
+    /// Under a [`RotationPolicy`] only the active file is cut; sealed segments are untouched.
+    pub fn truncate_to(&self, last_good_id: EventId) -> Result<(), EventLogError> {
+        self.check_writable()?;
+        let _drained = self
+            .gate
+            .write()
+            .map_err(|_| EventLogError::Invalid("event log lock poisoned".into()))?;
+        let mut chain = match &self.chain {
+            Some(c) => Some(lock(c)?),
+            None => None,
+        };
+        let mut buffer = match &self.buffer {
+            Some(buf) => Some(lock(buf)?),
+            None => None,
+        };
+        if let Some(w) = buffer.as_deref_mut() {
+            w.flush()?;
+        }
+        let mut kept = Vec::new();
+        let mut records = 0usize;
+        let mut stats = FileStats::default();
+        let mut reached = false;
+        for line in BoundedLines::new(File::open(&self.path)?, self.max_line_bytes) {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let rec: EventRecord<serde::de::IgnoredAny> = match parse_wal_line(&line) {
+                Ok(rec) => rec,
+                Err(_) if reached => break,
+                Err(e) => return Err(e),
+            };
+            if rec.id > last_good_id {
+                break;
+            }
+            reached |= rec.id == last_good_id;
+            kept.extend_from_slice(&line);
+            kept.push(b'\n');
+            records += 1;
+            stats.add(rec.id, rec.id, 1, line.len() as u64 + 1);
+        }
+        replace_atomically(&self.path, &kept)?;
+        if let Some(rot) = &self.rotation {
+            lock(rot)?.active = stats;
+        }
+        if let Some(state) = chain.as_deref_mut() {
+            state.write_pending(&self.chain_path())?;
+            let mut side = Vec::new();
+            let mut head = CHAIN_GENESIS;
+            let entries = BufReader::new(File::open(self.chain_path())?).lines();
+            for line in entries.take(records) {
+                let line = line?;
+                head = decode_hash(&serde_json::from_str::<ChainEntry>(&line)?)?;
+                side.extend_from_slice(line.as_bytes());
+                side.push(b'\n');
+            }
+            replace_atomically(&self.chain_path(), &side)?;
+            state.head = head;
+            state.records = records as u64;
+        }
+        // The buffered writer still points at the replaced file.
+        if let Some(w) = buffer.as_deref_mut() {
+            *w = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        }
+        Ok(())
+    }
+
+    /// Walk the WAL and its hash-chain sidecar in lockstep and recompute every link (with
+    /// this handle's key, if the chain is keyed).
+    ///
+    /// Returns the number of verified records. Fails with
+    /// [`EventLogError::ChainMismatch`] naming the first offending id: the edited record,
+    /// the record expected where one was deleted or reordered, or the first record present
+    /// on only one side.
+    pub fn verify_chain(&self) -> Result<u64, EventLogError> {
+        self.verify_chain_from(None)
+    }
+
+    /// [`Self::verify_chain`], additionally requiring the chain to pass through `head`
+    /// (recorded earlier with [`Self::chain_head`]): fails with
+    /// [`EventLogError::ChainTruncated`] when fewer records remain, or
+    /// [`EventLogError::ChainMismatch`] when the link at that position differs.
+    pub fn verify_chain_from(&self, head: Option<&ChainHead>) -> Result<u64, EventLogError> {
+        self.flush()?;
+        let key = match &self.chain {
+            Some(c) => lock(c)?.key,
+            None => None,
+        };
+        let heads: Vec<ChainHead> = head.cloned().into_iter().collect();
+        verify_chain_files(
+            &self.path,
+            &self.chain_path(),
+            key.as_ref(),
+            self.max_line_bytes,
+            &heads,
+        )
+    }
+
     /// Read events with id in [start, end) (half-open range).
+    ///
+    /// Collects [`Self::iter_range`]; prefer the iterator for large logs.
     pub fn read_range<T: for<'de> Deserialize<'de>>(
         &self,
         start: EventId,
         end: EventId,
     ) -> Result<Vec<EventRecord<T>>, EventLogError> {
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-        let mut out = Vec::new();
-        for line in reader.lines() {
-            let line = line?;
+        self.iter_range(start, end)?.collect()
+    }
+
+    /// [`Self::read_range`] that skips lines which do not decode (failed checksums, torn or
+    /// unparsable lines) instead of failing, and reports where they were, so the good
+    /// records around a crash or damaged region can still be recovered.
+    ///
+    /// I/O errors and over-long lines still fail the read.
+    pub fn read_range_lossy<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<LossyRange<T>, EventLogError> {
+        let mut iter = self.range_iter(start, end)?;
+        let mut out = LossyRange { records: Vec::new(), skipped: Vec::new() };
+        while let Some(item) = iter.next_item() {
+            match item? {
+                LineItem::Record(rec) => out.records.push(rec),
+                LineItem::OutOfRange => {}
+                LineItem::Bad { line, .. } => out.skipped.push(line),
+            }
+        }
+        Ok(out)
+    }
+
+    /// [`Self::read_range`] that tolerates undecodable lines only at the very end of the log,
+    /// where a crash during append leaves them: those are skipped, logged as warnings, and
+    /// reported in [`LossyRange::skipped`], so every complete record is recovered. A bad
+    /// line followed by any decodable one is damage inside the log and fails the read with
+    /// its error, as in [`Self::read_range`].
+    pub fn read_range_lenient<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<LossyRange<T>, EventLogError> {
+        let mut iter = self.range_iter(start, end)?;
+        let mut out = LossyRange { records: Vec::new(), skipped: Vec::new() };
+        let mut first_error = None;
+        while let Some(item) = iter.next_item() {
+            let item = item?;
+            if !matches!(item, LineItem::Bad { .. }) {
+                if let Some(e) = first_error.take() {
+                    return Err(e);
+                }
+            }
+            match item {
+                LineItem::Record(rec) => out.records.push(rec),
+                LineItem::OutOfRange => {}
+                LineItem::Bad { error, line } => {
+                    first_error.get_or_insert(error);
+                    out.skipped.push(line);
+                }
+            }
+        }
+        for bad in &out.skipped {
+            tracing::warn!(
+                offset = bad.offset,
+                id = ?bad.id,
+                wal = %self.path,
+                "skipping bad WAL tail line"
+            );
+        }
+        Ok(out)
+    }
+
+    /// Lazily read events with id in [start, end), one line at a time, so memory stays
+    /// bounded by the longest line rather than the log size.
+    ///
+    /// Under a [`RotationPolicy`] only the sealed segments whose manifest id range overlaps
+    /// the window are opened, oldest first, followed by the active file. Buffered appends
+    /// are flushed first; records appended while iterating may or may not be seen. The
+    /// iterator ends after yielding the first error (an over-long or unparsable line, or an
+    /// I/O failure).
+    pub fn iter_range<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<impl Iterator<Item = Result<EventRecord<T>, EventLogError>>, EventLogError> {
+        self.range_iter(start, end)
+    }
+
+    fn range_iter<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<RangeIter<T>, EventLogError> {
+        self.flush()?;
+        // Under the gate so a concurrent roll cannot move records between the segment list
+        // and the active file.
+        let (segments, active) = {
+            let _gate = read_gate(&self.gate)?;
+            let segments: Vec<PathBuf> = match &self.rotation {
+                Some(rot) => lock(rot)?
+                    .manifest
+                    .segments
+                    .iter()
+                    .filter(|s| s.overlaps(start, end))
+                    .map(|s| Path::new(&self.path).with_file_name(&s.file))
+                    .collect(),
+                None => Vec::new(),
+            };
+            (segments, File::open(&self.path)?)
+        };
+        Ok(RangeIter {
+            segments: segments.into_iter(),
+            active: Some(active),
+            lines: None,
+            max_line_bytes: self.max_line_bytes,
+            done: false,
+            start,
+            end,
+            _record: std::marker::PhantomData,
+        })
+    }
+}
+
+/// Iterator behind [`JsonlEventLog::iter_range`]: the listed segments, then the active file.
+struct RangeIter<T> {
+    /// Sealed segments still to open; they never move once listed.
+    segments: std::vec::IntoIter<PathBuf>,
+    /// The active file, opened up front because a roll may rename it.
+    active: Option<File>,
+    lines: Option<BoundedLines<File>>,
+    max_line_bytes: usize,
+    done: bool,
+    start: EventId,
+    end: EventId,
+    _record: std::marker::PhantomData<fn() -> T>,
+}
+
+/// One line's outcome in [`RangeIter`].
+enum LineItem<T> {
+    Record(EventRecord<T>),
+    /// A decodable record outside the requested range.
+    OutOfRange,
+    /// A line that did not decode; `error` is what a strict read reports for it.
+    Bad {
+        error: EventLogError,
+        line: BadLine,
+    },
+}
+
+impl<T: for<'de> Deserialize<'de>> RangeIter<T> {
+    /// The outcome of the next non-empty line. `Err` (I/O, over-long line) ends the
+    /// iteration.
+    fn next_item(&mut self) -> Option<Result<LineItem<T>, EventLogError>> {
+        while !self.done {
+            let Some(lines) = self.lines.as_mut() else {
+                let file = match self.segments.next() {
+                    Some(path) => File::open(path),
+                    None => match self.active.take() {
+                        Some(file) => Ok(file),
+                        None => return None,
+                    },
+                };
+                match file {
+                    Ok(file) => self.lines = Some(BoundedLines::new(file, self.max_line_bytes)),
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(e.into()));
+                    }
+                }
+                continue;
+            };
+            let line = match lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.lines = None;
+                    continue;
+                }
+            };
             if line.is_empty() {
                 continue;
             }
-            let rec: EventRecord<T> = serde_json::from_str(&line)?;
-            if rec.id >= start && rec.id < end {
-                out.push(rec);
+            let offset = lines.line_start;
+            match parse_wal_line_at::<T>(&line, offset) {
+                Ok(rec) if rec.id >= self.start && rec.id < self.end => {
+                    return Some(Ok(LineItem::Record(rec)))
+                }
+                Ok(_) => return Some(Ok(LineItem::OutOfRange)),
+                Err(error) => {
+                    let line = BadLine { id: record_id_hint(&line), offset };
+                    return Some(Ok(LineItem::Bad { error, line }));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T: for<'de> Deserialize<'de>> Iterator for RangeIter<T> {
+    type Item = Result<EventRecord<T>, EventLogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            return match self.next_item()? {
+                Ok(LineItem::Record(rec)) => Some(Ok(rec)),
+                Ok(LineItem::OutOfRange) => continue,
+                Ok(LineItem::Bad { error, .. }) => {
+                    self.done = true;
+                    Some(Err(error))
+                }
+                Err(e) => Some(Err(e)),
+            };
+        }
+    }
+}
+
+impl Drop for JsonlEventLog {
+    fn drop(&mut self) {
+        // Best-effort: errors cannot be reported here; use `close` for a checked flush + fsync.
+        let _ = self.flush();
+    }
+}
+
+/// A [`JsonlEventLog`] that rolls over to a new segment by size and only accepts increasing
+/// ids.
+///
+/// Appends go through [`JsonlEventLog::with_rotation`] with `max_bytes` set to
+/// `max_segment_bytes`: once the active file reaches it, the file is sealed as
+/// `log.0001.jsonl`, `log.0002.jsonl`, ... and the next record starts a fresh one. Segment
+/// names keep the four-digit sequence of [`JsonlEventLog::with_rotation`] (rather than
+/// `log.00001.jsonl`) so both APIs read and recover the same segment sets. Each id
+/// must exceed every id already in the log, including sealed segments and earlier
+/// processes, so segments hold disjoint, increasing id ranges and [`Self::read_range`]
+/// returns records in id order. Appends are serialized to keep that check and the write
+/// together.
+///
+/// Code that takes a [`JsonlEventLog`] (such as the orchestrator) can use the rotating log
+/// through [`Self::log`] or `From`; appends made that way keep rolling but skip the id check.
+#[derive(Debug, Clone)]
+pub struct RotatingEventLog {
+    log: JsonlEventLog,
+    /// Highest id in the log; locked across each append.
+    last_id: Arc<Mutex<Option<EventId>>>,
+}
+
+impl RotatingEventLog {
+    /// Open (or create) the rotating log at `path`, rolling past `max_segment_bytes`.
+    ///
+    /// An unterminated last line in the active file, left by a crash mid-append, is cut off
+    /// (and logged) so the next record starts on a fresh line; every complete record is
+    /// kept. Fails with [`EventLogError::Invalid`] when `max_segment_bytes` is zero.
+    pub fn open<P: AsRef<Path>>(path: P, max_segment_bytes: u64) -> Result<Self, EventLogError> {
+        let path = path.as_ref();
+        // Before the first append, which would otherwise keep the torn line as a bad one.
+        if path.is_file() {
+            let trimmed = trim_torn_tail(path)?;
+            if trimmed > 0 {
+                tracing::warn!(wal = %path.display(), bytes = trimmed, "trimmed torn WAL tail");
             }
         }
+        let log = JsonlEventLog::open(path)?;
+        let policy = RotationPolicy { max_bytes: Some(max_segment_bytes), max_records: None };
+        let log = log.with_rotation(policy)?;
+        let last_id = match &log.rotation {
+            Some(rot) => {
+                let state = lock(rot)?;
+                let sealed = state.manifest.segments.iter().map(|s| s.max_id).max();
+                sealed.max(state.active.max_id)
+            }
+            None => None,
+        };
+        Ok(Self { log, last_id: Arc::new(Mutex::new(last_id)) })
+    }
+
+    /// Append a payload with an `id` above every id in the log; returns it.
+    pub fn append<T: Serialize>(
+        &self,
+        id: EventId,
+        ts_ms: u64,
+        payload: &T,
+    ) -> Result<EventId, EventLogError> {
+        let mut last = lock(&self.last_id)?;
+        check_id_above(*last, id)?;
+        self.log.append(id, ts_ms, payload)?;
+        *last = Some(id);
+        Ok(id)
+    }
+
+    /// Append records with strictly increasing ids, all above every id in the log; returns
+    /// the number appended. Nothing is written when an id is out of order.
+    pub fn append_batch<T: Serialize>(
+        &self,
+        records: &[EventRecord<T>],
+    ) -> Result<usize, EventLogError> {
+        let mut last = lock(&self.last_id)?;
+        let mut prev = *last;
+        for rec in records {
+            check_id_above(prev, rec.id)?;
+            prev = Some(rec.id);
+        }
+        let n = self.log.append_batch(records)?;
+        *last = prev;
+        Ok(n)
+    }
+
+    /// Read events with id in [start, end) across every overlapping segment, in id order.
+    pub fn read_range<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<Vec<EventRecord<T>>, EventLogError> {
+        self.log.read_range(start, end)
+    }
+
+    /// Lazy form of [`Self::read_range`]; see [`JsonlEventLog::iter_range`].
+    pub fn iter_range<T: for<'de> Deserialize<'de>>(
+        &self,
+        start: EventId,
+        end: EventId,
+    ) -> Result<impl Iterator<Item = Result<EventRecord<T>, EventLogError>>, EventLogError> {
+        self.log.iter_range(start, end)
+    }
+
+    /// Every file holding records, oldest first, with its inclusive id range: the sealed
+    /// segments, then the active file at the WAL path unless it is empty.
+    pub fn segments(
+        &self,
+    ) -> Result<Vec<(PathBuf, std::ops::RangeInclusive<EventId>)>, EventLogError> {
+        let Some(rot) = &self.log.rotation else {
+            return Ok(Vec::new());
+        };
+        let state = lock(rot)?;
+        let path = Path::new(&self.log.path);
+        let mut out: Vec<_> = state
+            .manifest
+            .segments
+            .iter()
+            .map(|s| (path.with_file_name(&s.file), s.min_id..=s.max_id))
+            .collect();
+        if let (Some(min), Some(max)) = (state.active.min_id, state.active.max_id) {
+            out.push((path.to_path_buf(), min..=max));
+        }
         Ok(out)
     }
+
+    /// The underlying log.
+    pub fn log(&self) -> &JsonlEventLog {
+        &self.log
+    }
+}
+
+impl From<RotatingEventLog> for JsonlEventLog {
+    fn from(log: RotatingEventLog) -> Self {
+        log.log
+    }
+}
+
+fn check_id_above(last: Option<EventId>, id: EventId) -> Result<(), EventLogError> {
+    match last {
+        Some(last) if id <= last => Err(EventLogError::Invalid(format!(
+            "event id {} is not above the last id {} in the log",
+            id, last
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the file at `path` is non-empty and does not end with a newline.
+fn ends_unterminated(path: &Path) -> Result<bool, EventLogError> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(false);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+/// Cut an unterminated final line off the file at `path` so the next append starts on a
+/// fresh line; returns the number of bytes removed.
+fn trim_torn_tail(path: &Path) -> Result<u64, EventLogError> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let len = file.metadata()?.len();
+    let mut end = len;
+    let mut chunk = vec![0u8; 64 * 1024];
+    while end > 0 {
+        let start = end.saturating_sub(chunk.len() as u64);
+        let buf = &mut chunk[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(buf)?;
+        if let Some(i) = buf.iter().rposition(|b| *b == b'\n') {
+            end = start + i as u64 + 1;
+            break;
+        }
+        end = start;
+    }
+    if end < len {
+        file.set_len(end)?;
+        file.sync_all()?;
+    }
+    Ok(len - end)
+}
+
+/// Replace `path` with `bytes` via a fsynced temporary file and a rename, then fsync the
+/// directory (best-effort) so the rename itself is durable.
+fn replace_atomically(path: &str, bytes: &[u8]) -> Result<(), EventLogError> {
+    let tmp = format!("{}.tmp", path);
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+        let _ = File::open(dir).and_then(|d| d.sync_all());
+    }
+    Ok(())
+}
+
+/// Sign the chain position in `state` and append it to the checkpoint sidecar (fsynced).
+fn append_checkpoint(
+    path: &str,
+    state: &ChainState,
+    key: &[u8; 32],
+) -> Result<ChainCheckpoint, EventLogError> {
+    let head = ChainHead { records: state.records, hash: hex::encode(state.head) };
+    let cp = ChainCheckpoint::sign(&head, key);
+    let mut line = serde_json::to_vec(&cp)?;
+    line.push(b'\n');
+    let mut side = OpenOptions::new().create(true).append(true).open(path)?;
+    side.write_all(&line)?;
+    side.sync_all()?;
+    Ok(cp)
+}
+
+/// Verify a (possibly rotated-out) WAL at `wal` against its `.chain` and `.checkpoints`
+/// sidecars without opening it for writing: every checkpoint signature must verify under
+/// `checkpoint_key`, every link must recompute (under `chain_key` for a keyed chain), and
+/// the chain must pass through every checkpointed head.
+///
+/// Fails with [`EventLogError::CheckpointInvalid`] on a bad signature,
+/// [`EventLogError::ChainMismatch`] on an inserted, deleted, or modified record, and
+/// [`EventLogError::ChainTruncated`] when records after a checkpoint were cut from both
+/// files.
+pub fn verify_checkpoints<P: AsRef<Path>>(
+    wal: P,
+    chain_key: Option<&[u8; 32]>,
+    checkpoint_key: &[u8; 32],
+) -> Result<CheckpointVerification, EventLogError> {
+    let wal = wal.as_ref().to_string_lossy().into_owned();
+    let mut heads = Vec::new();
+    for line in BufReader::new(File::open(format!("{}.checkpoints", wal))?).lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let cp: ChainCheckpoint = serde_json::from_str(&line)?;
+        if !cp.verify_signature(checkpoint_key) {
+            return Err(EventLogError::CheckpointInvalid { records: cp.records });
+        }
+        heads.push(cp.head());
+    }
+    let chain_path = format!("{}.chain", wal);
+    let records = verify_chain_files(&wal, &chain_path, chain_key, DEFAULT_MAX_LINE_BYTES, &heads)?;
+    Ok(CheckpointVerification { records, checkpoints: heads.len() })
+}
+
+/// Walk `path` and its chain sidecar in lockstep, recomputing every link and requiring the
+/// chain to pass through each of `heads`.
+fn verify_chain_files(
+    path: &str,
+    chain_path: &str,
+    key: Option<&[u8; 32]>,
+    max_line_bytes: usize,
+    heads: &[ChainHead],
+) -> Result<u64, EventLogError> {
+    let wal = BoundedLines::new(File::open(path)?, max_line_bytes);
+    let side = BufReader::new(File::open(chain_path)?).lines();
+    let mut wal = wal.filter(|l| !matches!(l, Ok(s) if s.is_empty()));
+    let mut side = side.filter(|l| !matches!(l, Ok(s) if s.is_empty()));
+    let longest = heads.iter().map(|h| h.records).max().unwrap_or(0);
+    let mut prev = CHAIN_GENESIS;
+    let mut verified = 0u64;
+    loop {
+        match (wal.next().transpose()?, side.next().transpose()?) {
+            (None, None) => {
+                if longest > verified {
+                    return Err(EventLogError::ChainTruncated {
+                        expected: longest,
+                        found: verified,
+                    });
+                }
+                return Ok(verified);
+            }
+            (Some(line), None) => {
+                let rec: EventRecord<serde_json::Value> = parse_wal_line(&line)?;
+                return Err(EventLogError::ChainMismatch { id: rec.id });
+            }
+            (None, Some(entry)) => {
+                let entry: ChainEntry = serde_json::from_str(&entry)?;
+                return Err(EventLogError::ChainMismatch { id: entry.id });
+            }
+            (Some(line), Some(entry)) => {
+                let entry: ChainEntry = serde_json::from_str(&entry)?;
+                let rec_id = parse_wal_line::<serde_json::Value>(&line).map(|r| r.id).ok();
+                let expected = chain_link(key, &prev, &line);
+                if rec_id != Some(entry.id) || decode_hash(&entry)? != expected {
+                    return Err(EventLogError::ChainMismatch { id: entry.id });
+                }
+                prev = expected;
+                verified += 1;
+                let hash = hex::encode(prev);
+                if heads.iter().any(|h| h.records == verified && h.hash != hash) {
+                    return Err(EventLogError::ChainMismatch { id: entry.id });
+                }
+            }
+        }
+    }
+}
+
+/// Split `reader` into WAL lines of at most `max_line_bytes` (newline and a trailing `\r`
+/// excluded), as every WAL read path does. A longer line ends iteration with
+/// [`EventLogError::Invalid`] after buffering at most `max_line_bytes + 1` bytes of it, so
+/// untrusted input cannot force an unbounded allocation.
+pub fn wal_lines<R: std::io::Read>(
+    reader: R,
+    max_line_bytes: usize,
+) -> impl Iterator<Item = Result<Vec<u8>, EventLogError>> {
+    BoundedLines::new(reader, max_line_bytes)
+}
+
+/// Decode one line yielded by [`wal_lines`] into a record; malformed JSON, a wrong shape, or
+/// excessive nesting is an [`EventLogError::Serde`]. A [`CHECKSUM_V1`] line is verified
+/// first and fails with [`EventLogError::Checksum`] or [`EventLogError::Corrupt`] (offset 0:
+/// the line stands alone here).
+pub fn parse_wal_line<T: for<'de> Deserialize<'de>>(
+    line: &[u8],
+) -> Result<EventRecord<T>, EventLogError> {
+    parse_wal_line_at(line, 0)
+}
+
+/// [`parse_wal_line`] for a line starting at byte `offset` of its file.
+fn parse_wal_line_at<T: for<'de> Deserialize<'de>>(
+    line: &[u8],
+    offset: u64,
+) -> Result<EventRecord<T>, EventLogError> {
+    match record_json(line) {
+        Ok(json) => Ok(serde_json::from_slice(json)?),
+        Err(LineFault::Torn) => Err(EventLogError::Corrupt { id: record_id_hint(line), offset }),
+        Err(LineFault::Mismatch) => {
+            Err(EventLogError::Checksum { id: record_id_hint(line), offset })
+        }
+    }
+}
+
+/// Line iterator that refuses to buffer more than `max` bytes per line (newline and a
+/// trailing `\r` excluded), unlike `BufRead::lines`.
+struct BoundedLines<R> {
+    reader: BufReader<R>,
+    max: usize,
+    done: bool,
+    /// Bytes consumed so far.
+    pos: u64,
+    /// Byte offset of the line last returned.
+    line_start: u64,
+}
+
+impl<R: std::io::Read> BoundedLines<R> {
+    fn new(inner: R, max: usize) -> Self {
+        Self { reader: BufReader::new(inner), max, done: false, pos: 0, line_start: 0 }
+    }
+
+    fn next_line(&mut self) -> Result<Option<Vec<u8>>, EventLogError> {
+        self.line_start = self.pos;
+        let mut line = Vec::new();
+        loop {
+            let avail = self.reader.fill_buf()?;
+            if avail.is_empty() {
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                break;
+            }
+            let newline = avail.iter().position(|b| *b == b'\n');
+            let chunk = &avail[..newline.unwrap_or(avail.len())];
+            if line.len() + chunk.len() > self.max.saturating_add(1) {
+                return Err(EventLogError::Invalid("line exceeds max bytes".into()));
+            }
+            line.extend_from_slice(chunk);
+            let used = newline.map(|i| i + 1).unwrap_or(avail.len());
+            self.reader.consume(used);
+            self.pos += used as u64;
+            if newline.is_some() {
+                break;
+            }
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        if line.len() > self.max {
+            return Err(EventLogError::Invalid("line exceeds max bytes".into()));
+        }
+        Ok(Some(line))
+    }
+}
+
+impl<R: std::io::Read> Iterator for BoundedLines<R> {
+    type Item = Result<Vec<u8>, EventLogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next_line().transpose();
+        if matches!(next, None | Some(Err(_))) {
+            self.done = true;
+        }
+        next
+    }
+}
+
+fn read_gate(g: &RwLock<()>) -> Result<std::sync::RwLockReadGuard<'_, ()>, EventLogError> {
+    g.read().map_err(|_| EventLogError::Invalid("event log lock poisoned".into()))
+}
+
+fn lock<T>(m: &Mutex<T>) -> Result<std::sync::MutexGuard<'_, T>, EventLogError> {
+    m.lock().map_err(|_| EventLogError::Invalid("event log lock poisoned".into()))
+}
+
+fn decode_hash(entry: &ChainEntry) -> Result<[u8; 32], EventLogError> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(&entry.hash, &mut out)
+        .map_err(|_| EventLogError::ChainMismatch { id: entry.id })?;
+    Ok(out)
 }
 
 /// Example usage (doc test):
@@ -143,14 +1846,22 @@ pub mod v2 {
         ExternalIoFinished,
     }
 
+    /// Attachment compression; serialized as `"zstd"` | `"none"`.
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Compression {
+        Zstd,
+        None,
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
     pub struct Attachment {
         pub digest_sha256: String,
         pub size_bytes: u64,
-        pub mime: String,
+        pub mime: String, // `type/subtype`, optionally followed by `;` parameters
         #[serde(skip_serializing_if = "Option::is_none")]
         pub encoding: Option<String>,
-        pub compression: String, // "zstd" | "none"
+        pub compression: Compression,
     }
 
     impl Ord for Attachment {
@@ -220,21 +1931,107 @@ pub mod v2 {
     const ATTACH_MAX_COUNT: usize = 8;
     const STR_MAX_LEN: usize = 128;
     const TOTAL_ATTACH_JSON_MAX: usize = 8 * 1024; // bytes
+    /// Default cap on the sum of `size_bytes` across one record's attachments (1 GiB).
+    /// Bounds how much blob storage a single record can pin.
+    pub const TOTAL_ATTACH_BYTES_MAX: u64 = 1024 * 1024 * 1024;
+
+    /// Attachment caps enforced by [`to_jsonl_line_with_limits`]. Defaults are the built-in
+    /// caps: 8 attachments, 128-byte string fields, 8 KiB of attachment JSON, and
+    /// [`TOTAL_ATTACH_BYTES_MAX`] of referenced blob bytes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct V2Limits {
+        /// Maximum attachments per record (1..=1024).
+        pub attach_max_count: usize,
+        /// Maximum length of `mime` and `encoding` (1..=4096).
+        pub str_max_len: usize,
+        /// Maximum serialized JSON length of a record's attachments, in bytes
+        /// (256..=[`super::DEFAULT_MAX_LINE_BYTES`], so the line stays readable).
+        pub total_attach_json_max: usize,
+        /// Maximum summed attachment `size_bytes`, inclusive (at least 1).
+        pub total_attach_bytes_max: u64,
+    }
+
+    impl Default for V2Limits {
+        fn default() -> Self {
+            Self {
+                attach_max_count: ATTACH_MAX_COUNT,
+                str_max_len: STR_MAX_LEN,
+                total_attach_json_max: TOTAL_ATTACH_JSON_MAX,
+                total_attach_bytes_max: TOTAL_ATTACH_BYTES_MAX,
+            }
+        }
+    }
+
+    impl V2Limits {
+        /// Reject limits outside the documented ranges.
+        pub fn validate(&self) -> Result<(), super::EventLogError> {
+            let check = |name: &str, v: u64, lo: u64, hi: u64| {
+                if (lo..=hi).contains(&v) {
+                    Ok(())
+                } else {
+                    Err(super::EventLogError::Invalid(format!(
+                        "v2 limits: {name} {v} outside {lo}..={hi}"
+                    )))
+                }
+            };
+            check("attach_max_count", self.attach_max_count as u64, 1, 1024)?;
+            check("str_max_len", self.str_max_len as u64, 1, 4096)?;
+            check(
+                "total_attach_json_max",
+                self.total_attach_json_max as u64,
+                256,
+                super::DEFAULT_MAX_LINE_BYTES as u64,
+            )?;
+            check("total_attach_bytes_max", self.total_attach_bytes_max, 1, u64::MAX)
+        }
+    }
 
     fn is_hex_sha256(s: &str) -> bool {
         s.len() == 64 && s.as_bytes().iter().all(|b| b.is_ascii_hexdigit())
     }
 
+    /// Basic `type/subtype` shape (RFC 6838 restricted names); parameters after `;` are
+    /// not inspected.
+    fn is_valid_mime(s: &str) -> bool {
+        let essence = s.split(';').next().unwrap_or("").trim();
+        let is_name = |n: &str| {
+            !n.is_empty()
+                && n.len() <= 127
+                && n.as_bytes()[0].is_ascii_alphanumeric()
+                && n.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&b))
+        };
+        matches!(essence.split_once('/'), Some((t, sub)) if is_name(t) && is_name(sub))
+    }
+
     /// Serialize a V2 record to a JSON line with stable field ordering and deterministic attachment ordering.
+    /// Attachments are checked against the default [`V2Limits`].
     pub fn to_jsonl_line<T: Serialize>(rec: &RecordV2<T>) -> Result<String, super::EventLogError> {
+        to_jsonl_line_with_limits(rec, &V2Limits::default())
+    }
+
+    /// [`to_jsonl_line`] with a caller-supplied cap on the summed attachment `size_bytes`
+    /// (inclusive: a total equal to `total_attach_bytes_max` is accepted).
+    pub fn to_jsonl_line_with_attach_bytes_max<T: Serialize>(
+        rec: &RecordV2<T>,
+        total_attach_bytes_max: u64,
+    ) -> Result<String, super::EventLogError> {
+        to_jsonl_line_with_limits(rec, &V2Limits { total_attach_bytes_max, ..V2Limits::default() })
+    }
+
+    /// [`to_jsonl_line`] with caller-supplied attachment caps; `limits` are validated first.
+    pub fn to_jsonl_line_with_limits<T: Serialize>(
+        rec: &RecordV2<T>,
+        limits: &V2Limits,
+    ) -> Result<String, super::EventLogError> {
+        limits.validate()?;
         // Validate + sort attachments deterministically by digest
         let mut sorted: Option<Vec<Attachment>> = None;
         if let Some(att) = &rec.attachments {
-            if att.len() > ATTACH_MAX_COUNT {
+            if att.len() > limits.attach_max_count {
                 return Err(super::EventLogError::Invalid(format!(
                     "attachments count {} exceeds max {}",
                     att.len(),
-                    ATTACH_MAX_COUNT
+                    limits.attach_max_count
                 )));
             }
             let mut a = att.clone();
@@ -242,19 +2039,34 @@ pub mod v2 {
                 if !is_hex_sha256(&x.digest_sha256) {
                     return Err(super::EventLogError::Invalid("invalid digest".into()));
                 }
-                if x.mime.len() > STR_MAX_LEN
-                    || x.encoding.as_deref().map(|e| e.len()).unwrap_or(0) > STR_MAX_LEN
-                    || x.compression.len() > STR_MAX_LEN
+                if x.mime.len() > limits.str_max_len
+                    || x.encoding.as_deref().map(|e| e.len()).unwrap_or(0) > limits.str_max_len
                 {
                     return Err(super::EventLogError::Invalid(
                         "oversized attachment string field".into(),
                     ));
                 }
+                if !is_valid_mime(&x.mime) {
+                    return Err(super::EventLogError::Invalid(format!(
+                        "invalid attachment mime '{}'",
+                        x.mime
+                    )));
+                }
+            }
+            let total_bytes = a
+                .iter()
+                .try_fold(0u64, |acc, x| acc.checked_add(x.size_bytes))
+                .filter(|t| *t <= limits.total_attach_bytes_max);
+            if total_bytes.is_none() {
+                return Err(super::EventLogError::Invalid(format!(
+                    "attachments total size_bytes exceeds max {}",
+                    limits.total_attach_bytes_max
+                )));
             }
             a.sort();
             // Rough size cap via JSON length of attachments only
             let approx = serde_json::to_string(&a).map_err(super::EventLogError::Serde)?.len();
-            if approx > TOTAL_ATTACH_JSON_MAX {
+            if approx > limits.total_attach_json_max {
                 return Err(super::EventLogError::Invalid("attachments too large".into()));
             }
             sorted = Some(a);
@@ -290,4 +2102,161 @@ pub mod v2 {
         let s = serde_json::to_string(&ser)?;
         Ok(s)
     }
+
+    /// A v1 WAL event converted to a typed v2 record (best-effort migration).
+    ///
+    /// Recognized v1 kinds: `start_run` (run id = `workflow_id`), `task_enqueued`,
+    /// `usage_update`, `external_io_started` and `external_io_finished`. Ids and timestamps
+    /// are carried over unchanged; `trace_id` comes from the embedded envelope when present.
+    #[derive(Debug, Clone)]
+    pub enum MigratedV2 {
+        StartRun(RecordV2<StartRunPayload>),
+        TaskEnqueued(RecordV2<TaskEnqueuedPayload>),
+        UsageUpdate(RecordV2<UsageUpdatePayload>),
+        ExternalIoStarted(RecordV2<ExternalIOStartedPayload>),
+        ExternalIoFinished(RecordV2<ExternalIOFinishedPayload>),
+    }
+
+    impl MigratedV2 {
+        /// Serialize with [`to_jsonl_line`].
+        pub fn to_jsonl_line(&self) -> Result<String, super::EventLogError> {
+            match self {
+                Self::StartRun(r) => to_jsonl_line(r),
+                Self::TaskEnqueued(r) => to_jsonl_line(r),
+                Self::UsageUpdate(r) => to_jsonl_line(r),
+                Self::ExternalIoStarted(r) => to_jsonl_line(r),
+                Self::ExternalIoFinished(r) => to_jsonl_line(r),
+            }
+        }
+    }
+
+    fn v1_str(p: &Value, kind: &str, key: &str) -> Result<String, super::EventLogError> {
+        p.get(key).and_then(Value::as_str).map(str::to_string).ok_or_else(|| {
+            super::EventLogError::Invalid(format!("{kind}: missing string field '{key}'"))
+        })
+    }
+
+    fn v1_u64(p: &Value, kind: &str, key: &str) -> Result<u64, super::EventLogError> {
+        p.get(key).and_then(Value::as_u64).ok_or_else(|| {
+            super::EventLogError::Invalid(format!("{kind}: missing integer field '{key}'"))
+        })
+    }
+
+    fn migrated<T>(
+        rec: &super::EventRecord<Value>,
+        event_type: EventTypeV2,
+        run_id: String,
+        trace_id: &str,
+        payload: T,
+    ) -> RecordV2<T> {
+        RecordV2 {
+            id: rec.id,
+            ts_ms: rec.ts_ms,
+            version: WAL_VERSION_V2,
+            event_type,
+            run_id,
+            trace_id: trace_id.to_string(),
+            payload,
+            attachments: None,
+            metadata: Value::Object(serde_json::Map::new()),
+        }
+    }
+
+    impl TryFrom<&super::EventRecord<Value>> for MigratedV2 {
+        type Error = super::EventLogError;
+
+        /// Fails with [`super::EventLogError::Invalid`] for unrecognized kinds and for
+        /// records missing a field the typed payload requires.
+        fn try_from(rec: &super::EventRecord<Value>) -> Result<Self, Self::Error> {
+            let p = &rec.payload;
+            let kind = p
+                .get("event")
+                .and_then(Value::as_str)
+                .ok_or_else(|| super::EventLogError::Invalid("missing 'event' kind".into()))?;
+            let trace_id =
+                p.pointer("/envelope/trace_id").and_then(Value::as_str).unwrap_or("").to_string();
+            Ok(match kind {
+                "start_run" => {
+                    let workflow_id = v1_str(p, kind, "workflow_id")?;
+                    let run_id = workflow_id.clone();
+                    let payload = StartRunPayload { workflow_id };
+                    Self::StartRun(migrated(rec, EventTypeV2::StartRun, run_id, &trace_id, payload))
+                }
+                "task_enqueued" => {
+                    let env = p.get("envelope").cloned().unwrap_or(Value::Null);
+                    let payload = TaskEnqueuedPayload {
+                        envelope_id: v1_str(&env, kind, "id")?,
+                        agent: env.get("agent").and_then(Value::as_str).unwrap_or("").into(),
+                    };
+                    let run_id = v1_str(p, kind, "run_id")?;
+                    let mut r =
+                        migrated(rec, EventTypeV2::TaskEnqueued, run_id, &trace_id, payload);
+                    if let Some(atts) = p.get("attachments") {
+                        r.attachments = Some(serde_json::from_value(atts.clone())?);
+                    }
+                    Self::TaskEnqueued(r)
+                }
+                "usage_update" => {
+                    let payload = UsageUpdatePayload {
+                        tokens: v1_u64(p, kind, "tokens")?,
+                        cost_micros: v1_u64(p, kind, "cost_micros")?,
+                    };
+                    let run_id = v1_str(p, kind, "run_id")?;
+                    Self::UsageUpdate(migrated(
+                        rec,
+                        EventTypeV2::UsageUpdate,
+                        run_id,
+                        &trace_id,
+                        payload,
+                    ))
+                }
+                "external_io_started" => {
+                    let port = u16::try_from(v1_u64(p, kind, "port")?).map_err(|_| {
+                        super::EventLogError::Invalid(format!("{kind}: port out of range"))
+                    })?;
+                    let payload = ExternalIOStartedPayload {
+                        system: v1_str(p, kind, "system")?,
+                        direction: v1_str(p, kind, "direction")?,
+                        scheme: v1_str(p, kind, "scheme")?,
+                        host: v1_str(p, kind, "host")?,
+                        port,
+                        method: v1_str(p, kind, "method")?,
+                        request_id: v1_str(p, kind, "request_id")?,
+                        headers: p
+                            .get("headers")
+                            .and_then(Value::as_object)
+                            .cloned()
+                            .unwrap_or_default(),
+                        body_digest_sha256: v1_str(p, kind, "body_digest_sha256")?,
+                    };
+                    Self::ExternalIoStarted(migrated(
+                        rec,
+                        EventTypeV2::ExternalIoStarted,
+                        String::new(),
+                        &trace_id,
+                        payload,
+                    ))
+                }
+                "external_io_finished" => {
+                    let payload = ExternalIOFinishedPayload {
+                        request_id: v1_str(p, kind, "request_id")?,
+                        status: v1_str(p, kind, "status")?,
+                        duration_ms: v1_u64(p, kind, "duration_ms")?,
+                    };
+                    Self::ExternalIoFinished(migrated(
+                        rec,
+                        EventTypeV2::ExternalIoFinished,
+                        String::new(),
+                        &trace_id,
+                        payload,
+                    ))
+                }
+                other => {
+                    return Err(super::EventLogError::Invalid(format!(
+                        "no v2 mapping for event kind '{other}'"
+                    )))
+                }
+            })
+        }
+    }
 }
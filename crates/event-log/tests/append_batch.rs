@@ -0,0 +1,55 @@
+use event_log::{EventRecord, JsonlEventLog};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Payload whose serialization can be made to fail on demand.
+struct Flaky {
+    v: u32,
+    fail: bool,
+}
+
+impl Serialize for Flaky {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if self.fail {
+            return Err(serde::ser::Error::custom("injected serialize failure"));
+        }
+        s.serialize_u32(self.v)
+    }
+}
+
+fn rec(id: u64, fail: bool) -> EventRecord<Flaky> {
+    EventRecord { id, ts_ms: id, payload: Flaky { v: id as u32, fail } }
+}
+
+#[test]
+fn serialize_failure_on_third_record_writes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("batch.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    log.append(1, 1, &0u32).unwrap();
+    let before = std::fs::read(&path).unwrap();
+    let chain_before = std::fs::read(log.chain_path()).unwrap();
+
+    let batch = vec![rec(2, false), rec(3, false), rec(4, true), rec(5, false)];
+    assert!(log.append_batch(&batch).is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), before);
+    assert_eq!(std::fs::read(log.chain_path()).unwrap(), chain_before);
+
+    // The log is still usable and the chain intact after the aborted batch.
+    let ok = vec![rec(2, false), rec(3, false), rec(4, false)];
+    assert_eq!(log.append_batch(&ok).unwrap(), 3);
+    log.append(5, 5, &5u32).unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 5);
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    let ids: Vec<u64> = recs.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn empty_batch_is_a_no_op() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    assert_eq!(log.append_batch::<u32>(&[]).unwrap(), 0);
+    assert!(std::fs::read(&path).unwrap().is_empty());
+}
@@ -0,0 +1,120 @@
+use event_log::{verify_checkpoints, ChainCheckpoint, EventLogError, JsonlEventLog};
+use serde_json::json;
+
+const CHECKPOINT_KEY: [u8; 32] = [0x5a; 32];
+
+fn checkpointed_log(dir: &std::path::Path, n: u64) -> (JsonlEventLog, std::path::PathBuf) {
+    let path = dir.join("wal.jsonl");
+    let log = JsonlEventLog::open(&path)
+        .unwrap()
+        .with_hash_chain()
+        .unwrap()
+        .with_checkpoint_key(CHECKPOINT_KEY);
+    for id in 1..=n {
+        log.append(id, 1000 + id, &json!({"event":"usage_update","tokens":id})).unwrap();
+    }
+    (log, path)
+}
+
+fn rewrite_lines(path: &std::path::Path, f: impl FnOnce(&mut Vec<String>)) {
+    let mut lines: Vec<String> =
+        std::fs::read_to_string(path).unwrap().lines().map(str::to_string).collect();
+    f(&mut lines);
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn checkpointed_wal_verifies() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = checkpointed_log(dir.path(), 3);
+    let cp = log.write_checkpoint().unwrap();
+    assert_eq!(cp.records, 3);
+    assert!(cp.verify_signature(&CHECKPOINT_KEY));
+    log.append(4, 2000, &json!({"event":"usage_update","tokens":4})).unwrap();
+    log.write_checkpoint().unwrap();
+
+    let v = verify_checkpoints(&path, None, &CHECKPOINT_KEY).unwrap();
+    assert_eq!((v.records, v.checkpoints), (4, 2));
+}
+
+#[test]
+fn checkpoint_needs_chain_and_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("wal.jsonl")).unwrap().with_hash_chain().unwrap();
+    assert!(matches!(log.write_checkpoint(), Err(EventLogError::Invalid(_))));
+}
+
+#[test]
+fn modified_record_is_detected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = checkpointed_log(dir.path(), 4);
+    log.write_checkpoint().unwrap();
+    rewrite_lines(&path, |l| {
+        l[1] = l[1].replace("\"tokens\":2", "\"tokens\":20");
+    });
+    match verify_checkpoints(&path, None, &CHECKPOINT_KEY) {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 2),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn rebuilt_chain_does_not_match_the_signed_head() {
+    // An attacker who edits a record and recomputes the unkeyed chain still cannot
+    // produce the head the checkpoint signed.
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = checkpointed_log(dir.path(), 3);
+    log.write_checkpoint().unwrap();
+    drop(log);
+    rewrite_lines(&path, |l| {
+        l[2] = l[2].replace("\"tokens\":3", "\"tokens\":30");
+    });
+    std::fs::remove_file(format!("{}.chain", path.display())).unwrap();
+    JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    match verify_checkpoints(&path, None, &CHECKPOINT_KEY) {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 3),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn truncation_behind_a_checkpoint_is_detected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = checkpointed_log(dir.path(), 4);
+    log.write_checkpoint().unwrap();
+    rewrite_lines(&path, |l| l.truncate(2));
+    rewrite_lines(std::path::Path::new(&format!("{}.chain", path.display())), |l| l.truncate(2));
+    match verify_checkpoints(&path, None, &CHECKPOINT_KEY) {
+        Err(EventLogError::ChainTruncated { expected, found }) => {
+            assert_eq!((expected, found), (4, 2))
+        }
+        other => panic!("expected ChainTruncated, got {other:?}"),
+    }
+}
+
+#[test]
+fn forged_checkpoint_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = checkpointed_log(dir.path(), 2);
+    let head = log.chain_head().unwrap().unwrap();
+    let forged = ChainCheckpoint::sign(&head, &[0x11; 32]);
+    std::fs::write(
+        format!("{}.checkpoints", path.display()),
+        serde_json::to_string(&forged).unwrap() + "\n",
+    )
+    .unwrap();
+    match verify_checkpoints(&path, None, &CHECKPOINT_KEY) {
+        Err(EventLogError::CheckpointInvalid { records }) => assert_eq!(records, 2),
+        other => panic!("expected CheckpointInvalid, got {other:?}"),
+    }
+}
+
+#[test]
+fn rotation_seals_the_segment_with_a_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, _) = checkpointed_log(dir.path(), 3);
+    let segment = dir.path().join("wal.1.jsonl");
+    log.rotate_to(&segment).unwrap();
+    let v = verify_checkpoints(&segment, None, &CHECKPOINT_KEY).unwrap();
+    assert_eq!((v.records, v.checkpoints), (3, 1));
+}
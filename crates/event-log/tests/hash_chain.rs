@@ -0,0 +1,175 @@
+use event_log::{EventLogError, JsonlEventLog, SyncPolicy};
+use serde_json::json;
+
+fn chained_log(dir: &std::path::Path, n: u64) -> (JsonlEventLog, std::path::PathBuf) {
+    let path = dir.join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    for id in 1..=n {
+        log.append(id, 1000 + id, &json!({"event":"usage_update","tokens":id})).unwrap();
+    }
+    (log, path)
+}
+
+fn rewrite_lines(path: &std::path::PathBuf, f: impl FnOnce(&mut Vec<String>)) {
+    let mut lines: Vec<String> =
+        std::fs::read_to_string(path).unwrap().lines().map(str::to_string).collect();
+    f(&mut lines);
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn untampered_wal_verifies() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, _) = chained_log(dir.path(), 5);
+    assert_eq!(log.verify_chain().unwrap(), 5);
+}
+
+#[test]
+fn chain_resumes_after_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let (_, path) = chained_log(dir.path(), 3);
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    log.append(4, 2000, &json!({"event":"usage_update","tokens":4})).unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 4);
+}
+
+#[test]
+fn deleted_middle_record_is_detected_with_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 5);
+    rewrite_lines(&path, |l| {
+        l.remove(2); // id 3
+    });
+    match log.verify_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 3),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn altered_record_is_detected_with_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 5);
+    rewrite_lines(&path, |l| {
+        l[3] = l[3].replace("\"tokens\":4", "\"tokens\":40");
+    });
+    match log.verify_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 4),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn reordered_records_are_detected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 5);
+    rewrite_lines(&path, |l| l.swap(1, 2));
+    match log.verify_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 2),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn truncated_tail_is_detected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 5);
+    rewrite_lines(&path, |l| {
+        l.pop();
+    });
+    match log.verify_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 5),
+        other => panic!("expected ChainMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn enabling_on_an_existing_wal_adopts_its_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let plain = JsonlEventLog::open(&path).unwrap();
+    for id in 1..=3 {
+        plain.append(id, 1000 + id, &json!({"event":"usage_update","tokens":id})).unwrap();
+    }
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    log.append(4, 2000, &json!({"event":"usage_update","tokens":4})).unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 4);
+}
+
+#[test]
+fn sidecar_lagging_after_a_crash_is_caught_up_on_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 3);
+    rewrite_lines(&log.chain_path().into(), |l| {
+        l.pop(); // WAL write of id 3 landed, its sidecar entry did not
+    });
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 3);
+}
+
+#[test]
+fn sidecar_that_disagrees_with_the_wal_is_refused_on_enable() {
+    let dir = tempfile::tempdir().unwrap();
+    let (_, path) = chained_log(dir.path(), 3);
+    rewrite_lines(&path, |l| {
+        l[1] = l[1].replace("\"tokens\":2", "\"tokens\":20");
+    });
+    match JsonlEventLog::open(&path).unwrap().with_hash_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 2),
+        other => panic!("expected ChainMismatch, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn buffered_sidecar_never_runs_ahead_of_the_wal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path)
+        .unwrap()
+        .with_sync_policy(SyncPolicy::Buffered)
+        .unwrap()
+        .with_hash_chain()
+        .unwrap();
+    log.append(1, 1000, &json!({"event":"usage_update","tokens":1})).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+    assert_eq!(std::fs::read_to_string(log.chain_path()).unwrap_or_default(), "");
+    log.flush().unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 1);
+}
+
+#[test]
+fn keyed_chain_only_verifies_with_its_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_keyed_hash_chain([7u8; 32]).unwrap();
+    for id in 1..=3 {
+        log.append(id, 1000 + id, &json!({"event":"usage_update","tokens":id})).unwrap();
+    }
+    assert_eq!(log.verify_chain().unwrap(), 3);
+    match JsonlEventLog::open(&path).unwrap().with_keyed_hash_chain([8u8; 32]) {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 1),
+        other => panic!("expected ChainMismatch, got {:?}", other.map(|_| ())),
+    }
+    match JsonlEventLog::open(&path).unwrap().with_hash_chain() {
+        Err(EventLogError::ChainMismatch { id }) => assert_eq!(id, 1),
+        other => panic!("expected ChainMismatch, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn truncating_both_files_is_caught_against_a_recorded_head() {
+    let dir = tempfile::tempdir().unwrap();
+    let (log, path) = chained_log(dir.path(), 5);
+    let head = log.chain_head().unwrap().expect("chain enabled");
+    assert_eq!(head.records, 5);
+    assert_eq!(log.verify_chain_from(Some(&head)).unwrap(), 5);
+    rewrite_lines(&path, |l| l.truncate(3));
+    rewrite_lines(&log.chain_path().into(), |l| l.truncate(3));
+    assert_eq!(log.verify_chain().unwrap(), 3, "a consistent prefix still chains");
+    match log.verify_chain_from(Some(&head)) {
+        Err(EventLogError::ChainTruncated { expected, found }) => {
+            assert_eq!((expected, found), (5, 3));
+        }
+        other => panic!("expected ChainTruncated, got {other:?}"),
+    }
+}
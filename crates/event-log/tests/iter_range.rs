@@ -0,0 +1,79 @@
+use event_log::{EventRecord, JsonlEventLog};
+use serde_json::{json, Value};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that tracks live bytes and their high-water mark.
+struct Tracking;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Tracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOC: Tracking = Tracking;
+
+const RECORDS: u64 = 100_000;
+
+// One test per binary: the allocation counters are process-wide.
+#[test]
+fn iterating_a_large_log_keeps_allocation_bounded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.jsonl");
+    {
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+        for id in 1..=RECORDS {
+            let run_id = format!("r{}", id % 7);
+            let rec = EventRecord {
+                id,
+                ts_ms: 1_000 + id,
+                payload: json!({"event":"usage_update","run_id":run_id,"tokens":id}),
+            };
+            serde_json::to_writer(&mut out, &rec).unwrap();
+            out.write_all(b"\n").unwrap();
+        }
+    }
+    let file_bytes = std::fs::metadata(&path).unwrap().len() as usize;
+    let log = JsonlEventLog::open(&path).unwrap();
+
+    let baseline = LIVE.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let (mut count, mut last_id, mut tokens) = (0u64, 0u64, 0u64);
+    for rec in log.iter_range::<Value>(0, u64::MAX).unwrap() {
+        let rec = rec.unwrap();
+        assert!(rec.id > last_id, "records arrive in file order");
+        last_id = rec.id;
+        tokens += rec.payload["tokens"].as_u64().unwrap();
+        count += 1;
+    }
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+
+    assert_eq!(count, RECORDS);
+    assert_eq!(tokens, RECORDS * (RECORDS + 1) / 2);
+    // Reader buffer plus one line and one record, independent of the ~8 MB log.
+    assert!(peak < 256 * 1024, "peak {} bytes while streaming {} bytes", peak, file_bytes);
+    assert!(file_bytes > 20 * peak);
+
+    // The range is half-open and read_range collects the same records.
+    let some: Vec<EventRecord<Value>> = log.read_range(10, 20).unwrap();
+    let streamed: Vec<u64> =
+        log.iter_range::<Value>(10, 20).unwrap().map(|r| r.unwrap().id).collect();
+    assert_eq!(some.iter().map(|r| r.id).collect::<Vec<_>>(), streamed);
+    assert_eq!(streamed, (10..20).collect::<Vec<_>>());
+}
@@ -0,0 +1,92 @@
+use event_log::{BadLine, EventLogError, EventRecord, JsonlEventLog};
+use serde_json::{json, Value};
+use std::path::Path;
+
+fn checksummed_log(path: &Path) -> JsonlEventLog {
+    let log = JsonlEventLog::open(path).unwrap().with_checksums();
+    for id in 1..=5 {
+        log.append(id, id, &json!({"event":"usage_update","tokens":id * 10})).unwrap();
+    }
+    log
+}
+
+/// Byte offset at which line `n` (0-based) starts.
+fn line_offset(bytes: &[u8], n: usize) -> u64 {
+    let mut starts = std::iter::once(0)
+        .chain(bytes.iter().enumerate().filter(|(_, b)| **b == b'\n').map(|(i, _)| i + 1));
+    starts.nth(n).unwrap() as u64
+}
+
+/// Flip a payload digit of line `n` (keeps the JSON valid) and return the line's offset.
+fn rot_line(path: &Path, n: usize) -> u64 {
+    let mut bytes = std::fs::read(path).unwrap();
+    let offset = line_offset(&bytes, n);
+    let body = offset as usize + 10;
+    let at = body + bytes[body..].windows(2).position(|w| w == b"0}").unwrap();
+    bytes[at] = b'7';
+    std::fs::write(path, &bytes).unwrap();
+    offset
+}
+
+fn ids(recs: &[EventRecord<Value>]) -> Vec<u64> {
+    recs.iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn corrupt_middle_line_fails_both_strict_and_lenient_reads() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mid.jsonl");
+    let log = checksummed_log(&path);
+    let offset = rot_line(&path, 2);
+
+    let strict = log.read_range::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(strict, EventLogError::Checksum { id: Some(3), offset: o } if o == offset));
+    // Valid records follow the damage, so it is not a torn tail.
+    let lenient = log.read_range_lenient::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(lenient, EventLogError::Checksum { id: Some(3), offset: o } if o == offset));
+    // Even when those records fall outside the requested window.
+    assert!(log.read_range_lenient::<Value>(0, 4).is_err());
+}
+
+#[test]
+fn corrupt_last_line_fails_strict_read_and_is_skipped_by_lenient_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tail.jsonl");
+    let log = checksummed_log(&path);
+    let offset = rot_line(&path, 4);
+
+    let strict = log.read_range::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(strict, EventLogError::Checksum { id: Some(5), offset: o } if o == offset));
+
+    let lenient = log.read_range_lenient::<Value>(0, u64::MAX).unwrap();
+    assert_eq!(ids(&lenient.records), [1, 2, 3, 4]);
+    assert_eq!(lenient.skipped, [BadLine { id: Some(5), offset }]);
+}
+
+#[test]
+fn torn_tail_is_corrupt_rather_than_a_checksum_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("torn.jsonl");
+    let log = checksummed_log(&path);
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - 5]).unwrap();
+    let offset = line_offset(&bytes, 4);
+
+    let strict = log.read_range::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(strict, EventLogError::Corrupt { id: Some(5), offset: o } if o == offset));
+    let lenient = log.read_range_lenient::<Value>(0, u64::MAX).unwrap();
+    assert_eq!(ids(&lenient.records), [1, 2, 3, 4]);
+    assert_eq!(lenient.skipped, [BadLine { id: Some(5), offset }]);
+
+    // Reopening leaves the file alone; the first append after it terminates the torn line,
+    // so the next record stays whole. The damage is now mid-file and lenient reads refuse it.
+    drop(log);
+    let torn = std::fs::read(&path).unwrap();
+    let log = JsonlEventLog::open(&path).unwrap().with_checksums();
+    assert_eq!(std::fs::read(&path).unwrap(), torn);
+    log.append(6, 6, &json!({"event":"usage_update"})).unwrap();
+    let err = log.read_range_lenient::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(err, EventLogError::Corrupt { id: Some(5), offset: o } if o == offset));
+    let lossy = log.read_range_lossy::<Value>(0, u64::MAX).unwrap();
+    assert_eq!(ids(&lossy.records), [1, 2, 3, 4, 6]);
+}
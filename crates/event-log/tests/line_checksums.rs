@@ -0,0 +1,94 @@
+use event_log::{parse_wal_line, BadLine, EventLogError, EventRecord, JsonlEventLog, CHECKSUM_V1};
+use serde_json::{json, Value};
+use std::path::Path;
+
+fn checksummed_log(path: &Path, ids: std::ops::RangeInclusive<u64>) -> JsonlEventLog {
+    let log = JsonlEventLog::open(path).unwrap().with_checksums();
+    for id in ids {
+        log.append(id, id, &json!({"event":"usage_update","tokens":id * 10})).unwrap();
+    }
+    log
+}
+
+/// Byte offset at which line `n` (0-based) starts.
+fn line_offset(bytes: &[u8], n: usize) -> u64 {
+    let mut starts = std::iter::once(0)
+        .chain(bytes.iter().enumerate().filter(|(_, b)| **b == b'\n').map(|(i, _)| i + 1));
+    starts.nth(n).unwrap() as u64
+}
+
+fn ids(recs: &[EventRecord<Value>]) -> Vec<u64> {
+    recs.iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn checksummed_lines_are_versioned_and_readable_by_any_handle() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("crc.jsonl");
+    // A plain record first: enabling checksums on an existing log keeps it readable.
+    JsonlEventLog::open(&path).unwrap().append(1, 1, &json!({"event":"start_run"})).unwrap();
+    let log = checksummed_log(&path, 2..=4).with_hash_chain().unwrap();
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with('{'));
+    for line in &lines[1..] {
+        assert_eq!(line.as_bytes()[0], CHECKSUM_V1);
+        let (crc, body) = line[1..].split_once(' ').unwrap();
+        assert_eq!(u32::from_str_radix(crc, 16).unwrap(), crc32fast::hash(body.as_bytes()));
+        let plain: EventRecord<Value> = serde_json::from_str(body).unwrap();
+        assert_eq!(parse_wal_line::<Value>(line.as_bytes()).unwrap().id, plain.id);
+    }
+
+    let plain_handle = JsonlEventLog::open(&path).unwrap();
+    assert_eq!(ids(&plain_handle.read_range(0, u64::MAX).unwrap()), [1, 2, 3, 4]);
+    assert_eq!(log.verify_chain().unwrap(), 4);
+}
+
+#[test]
+fn truncated_last_line_is_corrupt_and_lossy_read_recovers_the_prefix() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("torn.jsonl");
+    let log = checksummed_log(&path, 1..=5);
+    let bytes = std::fs::read(&path).unwrap();
+    // Crash mid-append: the last record lost its tail and newline.
+    std::fs::write(&path, &bytes[..bytes.len() - 12]).unwrap();
+    let offset = line_offset(&bytes, 4);
+
+    let err = log.read_range::<Value>(0, u64::MAX).unwrap_err();
+    assert!(
+        matches!(err, EventLogError::Corrupt { id: Some(5), offset: o } if o == offset),
+        "{:?}",
+        err
+    );
+
+    let lossy = log.read_range_lossy::<Value>(0, u64::MAX).unwrap();
+    assert_eq!(ids(&lossy.records), [1, 2, 3, 4]);
+    assert_eq!(lossy.skipped, [BadLine { id: Some(5), offset }]);
+}
+
+#[test]
+fn flipped_byte_mid_file_is_a_checksum_error_and_is_skipped_by_lossy_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rot.jsonl");
+    let log = checksummed_log(&path, 1..=5);
+    let mut bytes = std::fs::read(&path).unwrap();
+    let offset = line_offset(&bytes, 2);
+    // Bit rot inside record 3's payload: still valid JSON, only the checksum notices.
+    let body = offset as usize + 10;
+    let at = body + bytes[body..].windows(2).position(|w| w == b"30").unwrap();
+    bytes[at] = b'9';
+    std::fs::write(&path, &bytes).unwrap();
+
+    let err = log.read_range::<Value>(0, u64::MAX).unwrap_err();
+    assert!(matches!(err, EventLogError::Checksum { id: Some(3), offset: o } if o == offset));
+    // A window that never reaches the damaged line still reads strictly.
+    let early: Vec<EventRecord<Value>> =
+        log.iter_range(0, 3).unwrap().take(2).map(Result::unwrap).collect();
+    assert_eq!(ids(&early), [1, 2]);
+
+    let lossy = log.read_range_lossy::<Value>(0, u64::MAX).unwrap();
+    assert_eq!(ids(&lossy.records), [1, 2, 4, 5]);
+    assert_eq!(lossy.skipped, [BadLine { id: Some(3), offset }]);
+    assert_eq!(log.read_range_lossy::<Value>(4, 5).unwrap().records.len(), 1);
+}
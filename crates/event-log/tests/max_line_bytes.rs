@@ -0,0 +1,50 @@
+use event_log::{EventLogError, EventRecord, JsonlEventLog, DEFAULT_MAX_LINE_BYTES};
+use serde_json::Value;
+use std::io::Write;
+
+fn append_raw(path: &std::path::Path, bytes: &[u8]) {
+    let mut f = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+    f.write_all(bytes).unwrap();
+}
+
+#[test]
+fn oversized_line_fails_with_bounded_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    log.append(1, 1, &"ok").unwrap();
+    // A corrupt "line" with no newline, larger than the default cap.
+    append_raw(&path, &vec![b'x'; DEFAULT_MAX_LINE_BYTES + 1]);
+
+    match log.read_range::<Value>(0, u64::MAX).unwrap_err() {
+        EventLogError::Invalid(msg) => assert_eq!(msg, "line exceeds max bytes"),
+        other => panic!("expected Invalid, got {other:?}"),
+    }
+}
+
+#[test]
+fn custom_cap_accepts_boundary_and_rejects_one_over() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cap.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    log.append(1, 1, &"a").unwrap();
+    let line_len = std::fs::read(&path).unwrap().len() - 1;
+
+    let exact = log.clone().with_max_line_bytes(line_len);
+    let recs: Vec<EventRecord<String>> = exact.read_range(0, u64::MAX).unwrap();
+    assert_eq!(recs.len(), 1);
+
+    let tight = log.with_max_line_bytes(line_len - 1);
+    assert!(matches!(tight.read_range::<String>(0, u64::MAX), Err(EventLogError::Invalid(_))));
+}
+
+#[test]
+fn verify_chain_is_bounded_too() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chain.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_hash_chain().unwrap();
+    log.append(1, 1, &"a").unwrap();
+    append_raw(&path, &[b'y'; 64]);
+    let err = log.with_max_line_bytes(32).verify_chain().unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(ref m) if m == "line exceeds max bytes"));
+}
@@ -0,0 +1,65 @@
+use event_log::{EventLogError, JsonlEventLog};
+
+#[test]
+fn directory_path_is_rejected_with_clear_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = JsonlEventLog::open(dir.path()).unwrap_err();
+    match err {
+        EventLogError::Invalid(msg) => assert!(msg.contains("is a directory"), "{msg}"),
+        other => panic!("expected Invalid, got {other:?}"),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn read_only_path_is_rejected_with_clear_error() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ro.jsonl");
+    std::fs::write(&path, b"").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444)).unwrap();
+    // Privileged users (e.g. root in containers) bypass file modes; nothing to assert then.
+    if std::fs::OpenOptions::new().append(true).open(&path).is_ok() {
+        return;
+    }
+    let err = JsonlEventLog::open(&path).unwrap_err();
+    match err {
+        EventLogError::Invalid(msg) => assert!(msg.contains("is not writable"), "{msg}"),
+        other => panic!("expected Invalid, got {other:?}"),
+    }
+}
+
+#[test]
+fn open_leaves_no_probe_file_behind() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ok.jsonl");
+    JsonlEventLog::open(&path).unwrap();
+    let names: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(names, vec!["ok.jsonl".to_string()]);
+}
+
+#[test]
+fn read_only_open_reads_without_creating_or_writing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ro.jsonl");
+    let err = JsonlEventLog::open_read_only(&path).unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(ref m) if m.contains("not readable")), "{err}");
+    assert!(!path.exists());
+    let err = JsonlEventLog::open_read_only(dir.path()).unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(ref m) if m.contains("is a directory")), "{err}");
+
+    JsonlEventLog::open(&path).unwrap().append(1, 10, &serde_json::json!({"n": 1})).unwrap();
+    let before = std::fs::read(&path).unwrap();
+    let log = JsonlEventLog::open_read_only(&path).unwrap();
+    let recs = log.read_range::<serde_json::Value>(0, u64::MAX).unwrap();
+    assert_eq!(recs.len(), 1);
+    let err = log.append(2, 20, &serde_json::json!({"n": 2})).unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(ref m) if m.contains("read-only")), "{err}");
+    assert!(log.truncate_to(0).is_err());
+    assert!(log.rotate_to(dir.path().join("seg.jsonl")).is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), before);
+}
@@ -0,0 +1,108 @@
+use event_log::{EventLogError, EventRecord, RotatingEventLog};
+use serde_json::{json, Value};
+
+/// Ids 10..=99 serialize to lines of equal length.
+fn line_len() -> u64 {
+    serde_json::to_string(&EventRecord { id: 10, ts_ms: 10, payload: json!({"n":10}) })
+        .unwrap()
+        .len() as u64
+        + 1
+}
+
+fn append_ids(log: &RotatingEventLog, ids: std::ops::RangeInclusive<u64>) {
+    for id in ids {
+        log.append(id, id, &json!({"n":id})).unwrap();
+    }
+}
+
+fn ids(recs: &[EventRecord<Value>]) -> Vec<u64> {
+    recs.iter().map(|r| r.id).collect()
+}
+
+fn names(log: &RotatingEventLog) -> Vec<(String, u64, u64)> {
+    let segments = log.segments().unwrap();
+    segments
+        .into_iter()
+        .map(|(p, r)| (p.file_name().unwrap().to_string_lossy().into_owned(), *r.start(), *r.end()))
+        .collect()
+}
+
+#[test]
+fn rolls_over_once_the_active_file_reaches_max_segment_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.jsonl");
+    let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+
+    // Exactly at the threshold: the file stays active until the next append.
+    append_ids(&log, 10..=12);
+    assert_eq!(names(&log), [("log.jsonl".to_string(), 10, 12)]);
+    append_ids(&log, 13..=16);
+    assert_eq!(
+        names(&log),
+        [
+            ("log.0001.jsonl".to_string(), 10, 12),
+            ("log.0002.jsonl".to_string(), 13, 15),
+            ("log.jsonl".to_string(), 16, 16)
+        ]
+    );
+    for (file, _) in log.segments().unwrap() {
+        assert!(std::fs::metadata(file).unwrap().len() <= 3 * line_len());
+    }
+}
+
+#[test]
+fn range_spanning_two_segments_reads_in_id_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.jsonl");
+    let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+    append_ids(&log, 10..=17);
+
+    assert_eq!(ids(&log.read_range(11, 15).unwrap()), [11, 12, 13, 14]);
+    assert_eq!(ids(&log.read_range(0, u64::MAX).unwrap()), (10..=17).collect::<Vec<_>>());
+    let lazy: Vec<u64> = log.iter_range::<Value>(12, 14).unwrap().map(|r| r.unwrap().id).collect();
+    assert_eq!(lazy, [12, 13]);
+}
+
+#[test]
+fn ids_must_increase_across_rollovers_and_reopens() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.jsonl");
+    {
+        let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+        append_ids(&log, 10..=13);
+        assert!(matches!(log.append(12, 12, &json!({})), Err(EventLogError::Invalid(_))));
+        assert!(log.append(13, 13, &json!({})).is_err());
+
+        // An out-of-order batch writes nothing.
+        let batch: Vec<EventRecord<Value>> = [15, 14]
+            .into_iter()
+            .map(|id| EventRecord { id, ts_ms: id, payload: json!({"n":id}) })
+            .collect();
+        assert!(log.append_batch(&batch).is_err());
+        assert_eq!(ids(&log.read_range(0, u64::MAX).unwrap()), [10, 11, 12, 13]);
+    }
+
+    // The last id is recovered from the sealed segment and the active file.
+    let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+    assert!(log.append(11, 11, &json!({})).is_err());
+    append_ids(&log, 14..=14);
+    assert_eq!(ids(&log.read_range(0, u64::MAX).unwrap()), [10, 11, 12, 13, 14]);
+}
+
+#[test]
+fn reopen_after_crash_mid_segment_drops_only_the_torn_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.jsonl");
+    {
+        let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+        append_ids(&log, 10..=14);
+    }
+    // Crash mid-append of record 14: half its line reached the active file.
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - line_len() as usize / 2]).unwrap();
+
+    let log = RotatingEventLog::open(&path, 3 * line_len()).unwrap();
+    assert_eq!(names(&log).last().unwrap(), &("log.jsonl".to_string(), 13, 13));
+    append_ids(&log, 14..=16);
+    assert_eq!(ids(&log.read_range(0, u64::MAX).unwrap()), (10..=16).collect::<Vec<_>>());
+}
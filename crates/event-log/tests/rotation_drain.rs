@@ -0,0 +1,102 @@
+use event_log::{EventRecord, JsonlEventLog, SyncPolicy};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const WRITERS: u64 = 4;
+const PER_WRITER: u64 = 500;
+
+fn ids_in(path: &Path) -> Vec<u64> {
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(path).unwrap().read_range(0, u64::MAX).unwrap();
+    recs.into_iter().map(|r| r.id).collect()
+}
+
+fn rotate_under_load(log: JsonlEventLog, dir: &Path, active: &Path) -> Vec<PathBuf> {
+    let done = Arc::new(AtomicBool::new(false));
+    let writers: Vec<_> = (0..WRITERS)
+        .map(|w| {
+            let log = log.clone();
+            std::thread::spawn(move || {
+                for i in 0..PER_WRITER {
+                    let id = w * PER_WRITER + i + 1;
+                    log.append(id, id, &json!({"event":"usage_update","writer":w})).unwrap();
+                }
+            })
+        })
+        .collect();
+    let rotator = {
+        let (log, done, dir) = (log.clone(), done.clone(), dir.to_path_buf());
+        std::thread::spawn(move || {
+            let mut segments = Vec::new();
+            while !done.load(Ordering::SeqCst) {
+                let seg = dir.join(format!("wal.{:04}.jsonl", segments.len() + 1));
+                log.rotate_to(&seg).unwrap();
+                segments.push(seg);
+                std::thread::sleep(std::time::Duration::from_millis(2));
+            }
+            segments
+        })
+    };
+    for w in writers {
+        w.join().unwrap();
+    }
+    done.store(true, Ordering::SeqCst);
+    let mut files = rotator.join().unwrap();
+    log.close().unwrap();
+    files.push(active.to_path_buf());
+    files
+}
+
+fn assert_exactly_once(files: &[PathBuf]) {
+    let mut seen = BTreeMap::new();
+    for f in files {
+        for id in ids_in(f) {
+            *seen.entry(id).or_insert(0u32) += 1;
+        }
+    }
+    assert_eq!(seen.len() as u64, WRITERS * PER_WRITER);
+    assert!(seen.values().all(|n| *n == 1), "a record was duplicated");
+    assert!(files.len() > 1, "no rotation happened");
+}
+
+#[test]
+fn rotation_under_concurrent_appends_keeps_every_record_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let active = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&active).unwrap();
+    assert_exactly_once(&rotate_under_load(log, dir.path(), &active));
+}
+
+#[test]
+fn buffered_chained_rotation_flushes_and_splits_the_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let active = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&active)
+        .unwrap()
+        .with_sync_policy(SyncPolicy::Buffered)
+        .unwrap()
+        .with_hash_chain()
+        .unwrap();
+    let files = rotate_under_load(log, dir.path(), &active);
+    assert_exactly_once(&files);
+    // Each non-empty file carries its own chain (segments with no appends have no sidecar).
+    for f in files.iter().filter(|f| !ids_in(f).is_empty()) {
+        let n = JsonlEventLog::open(f).unwrap().verify_chain().unwrap();
+        assert_eq!(n as usize, ids_in(f).len());
+    }
+}
+
+#[test]
+fn rotating_onto_an_existing_file_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let active = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&active).unwrap();
+    log.append(1, 1, &json!({"event":"start_run"})).unwrap();
+    let taken = dir.path().join("taken.jsonl");
+    std::fs::write(&taken, "").unwrap();
+    assert!(log.rotate_to(&taken).is_err());
+    assert_eq!(ids_in(&active), vec![1]);
+}
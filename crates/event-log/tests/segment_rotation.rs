@@ -0,0 +1,168 @@
+use event_log::{EventRecord, JsonlEventLog, RotationPolicy, SegmentInfo};
+use serde_json::{json, Value};
+use std::path::Path;
+
+fn by_records(max: u64) -> RotationPolicy {
+    RotationPolicy { max_records: Some(max), ..Default::default() }
+}
+
+fn ids(log: &JsonlEventLog, start: u64, end: u64) -> Vec<u64> {
+    let recs: Vec<EventRecord<Value>> = log.read_range(start, end).unwrap();
+    recs.into_iter().map(|r| r.id).collect()
+}
+
+fn append_ids(log: &JsonlEventLog, ids: std::ops::RangeInclusive<u64>) {
+    for id in ids {
+        log.append(id, id, &json!({"event":"usage_update","n":id})).unwrap();
+    }
+}
+
+#[test]
+fn rolls_by_record_count_and_reads_only_overlapping_segments() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_rotation(by_records(10)).unwrap();
+    append_ids(&log, 1..=35);
+
+    let segments = log.segments().unwrap();
+    let ranges: Vec<(u64, &str, u64, u64, u64)> =
+        segments.iter().map(|s| (s.seq, s.file.as_str(), s.min_id, s.max_id, s.records)).collect();
+    assert_eq!(
+        ranges,
+        [
+            (1, "wal.0001.jsonl", 1, 10, 10),
+            (2, "wal.0002.jsonl", 11, 20, 10),
+            (3, "wal.0003.jsonl", 21, 30, 10)
+        ]
+    );
+    for s in &segments {
+        assert_eq!(std::fs::metadata(dir.path().join(&s.file)).unwrap().len(), s.bytes);
+    }
+    // The manifest on disk matches, and the active file keeps only the newest records.
+    let manifest: Value =
+        serde_json::from_slice(&std::fs::read(log.manifest_path()).unwrap()).unwrap();
+    let on_disk: Vec<SegmentInfo> = serde_json::from_value(manifest["segments"].clone()).unwrap();
+    assert_eq!(on_disk, segments);
+    assert_eq!(
+        ids(&JsonlEventLog::open(&path).unwrap(), 0, u64::MAX),
+        (31..=35).collect::<Vec<_>>()
+    );
+
+    assert_eq!(ids(&log, 0, u64::MAX), (1..=35).collect::<Vec<_>>());
+    assert_eq!(ids(&log, 9, 12), [9, 10, 11]);
+
+    // Segments outside the window are never opened: with the first one gone, windows that
+    // do not overlap it still read, and one that does fails.
+    std::fs::remove_file(dir.path().join("wal.0001.jsonl")).unwrap();
+    assert_eq!(ids(&log, 12, 33), (12..33).collect::<Vec<_>>());
+    assert!(log.read_range::<Value>(5, 15).is_err());
+}
+
+#[test]
+fn rolls_by_size_keeps_batches_whole_and_reopens_in_sequence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let policy = RotationPolicy { max_bytes: Some(300), ..Default::default() };
+    {
+        let log = JsonlEventLog::open(&path).unwrap().with_rotation(policy).unwrap();
+        let batch: Vec<EventRecord<Value>> = (1..=8)
+            .map(|id| EventRecord { id, ts_ms: id, payload: json!({"event":"usage_update"}) })
+            .collect();
+        assert_eq!(log.append_batch(&batch).unwrap(), 8);
+        // The batch overshoots the threshold but is not split; the next append rolls first.
+        assert!(log.segments().unwrap().is_empty());
+        append_ids(&log, 9..=9);
+        let segments = log.segments().unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!((segments[0].min_id, segments[0].max_id, segments[0].records), (1, 8, 8));
+        log.close().unwrap();
+    }
+
+    let log = JsonlEventLog::open(&path).unwrap().with_rotation(policy).unwrap();
+    append_ids(&log, 10..=40);
+    let segments = log.segments().unwrap();
+    assert!(segments.len() > 2);
+    for (i, s) in segments.iter().enumerate() {
+        assert_eq!(s.seq, i as u64 + 1);
+        assert!(s.bytes >= 300, "segment {} sealed at {} bytes", s.file, s.bytes);
+    }
+    assert_eq!(ids(&log, 0, u64::MAX), (1..=40).collect::<Vec<_>>());
+}
+
+#[test]
+fn reopen_recovers_a_segment_renamed_before_its_manifest_update() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    {
+        let log = JsonlEventLog::open(&path).unwrap().with_rotation(by_records(3)).unwrap();
+        append_ids(&log, 1..=7);
+        assert_eq!(log.segments().unwrap().len(), 2);
+    }
+    // Crash after sealing the active file but before the manifest was replaced.
+    std::fs::rename(&path, dir.path().join("wal.0003.jsonl")).unwrap();
+    std::fs::File::create(&path).unwrap();
+
+    let log = JsonlEventLog::open(&path).unwrap().with_rotation(by_records(3)).unwrap();
+    let segments = log.segments().unwrap();
+    assert_eq!(segments.len(), 3);
+    assert_eq!(
+        (segments[2].file.as_str(), segments[2].min_id, segments[2].max_id),
+        ("wal.0003.jsonl", 7, 7)
+    );
+    let manifest: Value =
+        serde_json::from_slice(&std::fs::read(log.manifest_path()).unwrap()).unwrap();
+    assert_eq!(manifest["segments"].as_array().unwrap().len(), 3);
+
+    append_ids(&log, 8..=11);
+    assert_eq!(log.segments().unwrap().last().unwrap().file, "wal.0004.jsonl");
+    assert_eq!(ids(&log, 0, u64::MAX), (1..=11).collect::<Vec<_>>());
+}
+
+#[test]
+fn concurrent_appenders_fill_segments_exactly() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_rotation(by_records(50)).unwrap();
+    std::thread::scope(|s| {
+        for w in 0..4u64 {
+            let log = log.clone();
+            s.spawn(move || append_ids(&log, w * 250 + 1..=w * 250 + 250));
+        }
+    });
+    let segments = log.segments().unwrap();
+    assert_eq!(segments.len(), 19, "the 20th segment is still active");
+    assert!(segments.iter().all(|s| s.records == 50));
+    let mut all = ids(&log, 0, u64::MAX);
+    all.sort_unstable();
+    assert_eq!(all, (1..=1000).collect::<Vec<_>>());
+}
+
+#[test]
+fn default_log_stays_a_single_file_and_zero_thresholds_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    append_ids(&log, 1..=100);
+    assert!(log.segments().unwrap().is_empty());
+    assert!(!Path::new(&log.manifest_path()).exists());
+    let names: Vec<_> =
+        std::fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(names, ["wal.jsonl"]);
+
+    assert!(log.clone().with_rotation(by_records(0)).is_err());
+    let by_bytes = RotationPolicy { max_bytes: Some(0), ..Default::default() };
+    assert!(log.clone().with_rotation(by_bytes).is_err());
+}
+
+#[test]
+fn manual_rotate_to_is_rejected_under_a_policy() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_rotation(by_records(10)).unwrap();
+    append_ids(&log, 1..=3);
+    let target = dir.path().join("manual.jsonl");
+    let err = log.rotate_to(&target).unwrap_err();
+    assert!(matches!(err, event_log::EventLogError::Invalid(_)), "{err}");
+    assert!(!target.exists());
+    assert_eq!(ids(&log, 0, u64::MAX), [1, 2, 3]);
+}
@@ -0,0 +1,58 @@
+use event_log::{EventRecord, JsonlEventLog, SyncPolicy};
+use serde_json::{json, Value};
+
+fn count_lines(path: &std::path::Path) -> usize {
+    std::fs::read_to_string(path).unwrap().lines().filter(|l| !l.is_empty()).count()
+}
+
+#[test]
+fn buffered_log_loses_nothing_after_close() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("buffered.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap().with_sync_policy(SyncPolicy::Buffered).unwrap();
+    for id in 1..=200u64 {
+        log.append(id, id, &json!({"event":"usage_update","tokens":id})).unwrap();
+    }
+    log.close().unwrap();
+
+    assert_eq!(count_lines(&path), 200);
+    let reopened = JsonlEventLog::open(&path).unwrap();
+    let recs: Vec<EventRecord<Value>> = reopened.read_range(0, u64::MAX).unwrap();
+    assert_eq!(recs.len(), 200);
+    assert_eq!(recs.last().map(|r| r.id), Some(200));
+}
+
+#[test]
+fn buffered_reads_see_own_appends() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("b.jsonl"))
+        .unwrap()
+        .with_sync_policy(SyncPolicy::Buffered)
+        .unwrap();
+    log.append(1, 1, &"a").unwrap();
+    let recs: Vec<EventRecord<String>> = log.read_range(0, u64::MAX).unwrap();
+    assert_eq!(recs.len(), 1);
+}
+
+#[test]
+fn drop_flushes_buffered_appends() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dropped.jsonl");
+    {
+        let log =
+            JsonlEventLog::open(&path).unwrap().with_sync_policy(SyncPolicy::Buffered).unwrap();
+        log.append(1, 1, &"a").unwrap();
+        log.append(2, 2, &"b").unwrap();
+    }
+    assert_eq!(count_lines(&path), 2);
+}
+
+#[test]
+fn close_on_per_append_log_is_ok() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plain.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    log.append(1, 1, &"a").unwrap();
+    log.close().unwrap();
+    assert_eq!(count_lines(&path), 1);
+}
@@ -0,0 +1,65 @@
+use event_log::{EventRecord, JsonlEventLog, SyncPolicy};
+use serde_json::{json, Value};
+
+fn ids(log: &JsonlEventLog) -> Vec<u64> {
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    recs.into_iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn truncate_to_keeps_only_records_up_to_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    for id in 1..=10 {
+        log.append(id, id * 10, &json!({"event":"usage_update","n":id})).unwrap();
+    }
+    log.truncate_to(5).unwrap();
+    assert_eq!(ids(&log), vec![1, 2, 3, 4, 5]);
+    assert!(!dir.path().join("wal.jsonl.tmp").exists());
+
+    // Appends continue after the kept prefix, also through a fresh handle.
+    log.append(6, 60, &json!({"event":"usage_update","n":6})).unwrap();
+    assert_eq!(ids(&JsonlEventLog::open(&path).unwrap()), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn truncate_to_cuts_off_a_corrupt_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("corrupt.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    for id in 1..=3 {
+        log.append(id, id, &json!({"event":"usage_update"})).unwrap();
+    }
+    let mut raw = std::fs::read(&path).unwrap();
+    raw.extend_from_slice(b"{\"id\":4,\"ts_ms\":4,\"payl");
+    std::fs::write(&path, raw).unwrap();
+    assert!(log.read_range::<Value>(0, u64::MAX).is_err());
+
+    // The torn line stands where record 4 would be, so a cut at 4 cannot be trusted.
+    assert!(log.truncate_to(4).is_err());
+    log.truncate_to(3).unwrap();
+    assert_eq!(ids(&log), vec![1, 2, 3]);
+}
+
+#[test]
+fn truncate_to_keeps_buffered_log_and_hash_chain_consistent() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chained.jsonl");
+    let log = JsonlEventLog::open(&path)
+        .unwrap()
+        .with_hash_chain()
+        .unwrap()
+        .with_sync_policy(SyncPolicy::Buffered)
+        .unwrap();
+    for id in 1..=10 {
+        log.append(id, id, &json!({"event":"usage_update"})).unwrap();
+    }
+    log.truncate_to(5).unwrap();
+    assert_eq!(log.verify_chain().unwrap(), 5);
+    assert_eq!(log.chain_head().unwrap().unwrap().records, 5);
+
+    log.append(11, 11, &json!({"event":"usage_update"})).unwrap();
+    assert_eq!(ids(&log), vec![1, 2, 3, 4, 5, 11]);
+    assert_eq!(log.verify_chain().unwrap(), 6);
+}
@@ -1,4 +1,4 @@
-use event_log::v2::{Attachment, EventTypeV2, RecordV2, WAL_VERSION_V2};
+use event_log::v2::{Attachment, Compression, EventTypeV2, RecordV2, WAL_VERSION_V2};
 use serde_json::json;
 
 #[test]
@@ -18,7 +18,7 @@ fn invalid_digest_rejected_red() {
             size_bytes: 1,
             mime: "application/octet-stream".into(),
             encoding: None,
-            compression: "none".into(),
+            compression: Compression::None,
         }]),
         metadata: json!({}),
     };
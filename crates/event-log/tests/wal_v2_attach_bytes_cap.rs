@@ -0,0 +1,52 @@
+use event_log::v2::{
+    to_jsonl_line, to_jsonl_line_with_attach_bytes_max, Attachment, Compression, EventTypeV2,
+    RecordV2, TOTAL_ATTACH_BYTES_MAX, WAL_VERSION_V2,
+};
+use event_log::EventLogError;
+use serde_json::{json, Value};
+
+fn record(sizes: &[u64]) -> RecordV2<Value> {
+    let attachments = sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &size_bytes)| Attachment {
+            digest_sha256: format!("{:064x}", i + 1),
+            size_bytes,
+            mime: "application/octet-stream".into(),
+            encoding: None,
+            compression: Compression::None,
+        })
+        .collect();
+    RecordV2 {
+        id: 1,
+        ts_ms: 1,
+        version: WAL_VERSION_V2,
+        event_type: EventTypeV2::TaskEnqueued,
+        run_id: "R1".into(),
+        trace_id: "T1".into(),
+        payload: json!({"envelope_id":"EV1","agent":"a1"}),
+        attachments: Some(attachments),
+        metadata: json!({}),
+    }
+}
+
+#[test]
+fn default_cap_accepts_boundary_and_rejects_one_over() {
+    let half = TOTAL_ATTACH_BYTES_MAX / 2;
+    let rest = TOTAL_ATTACH_BYTES_MAX - half;
+    assert!(to_jsonl_line(&record(&[half, rest])).is_ok());
+    let err = to_jsonl_line(&record(&[half, rest + 1])).unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(ref m) if m.contains("size_bytes")));
+}
+
+#[test]
+fn custom_cap_is_inclusive() {
+    assert!(to_jsonl_line_with_attach_bytes_max(&record(&[60, 40]), 100).is_ok());
+    assert!(to_jsonl_line_with_attach_bytes_max(&record(&[60, 41]), 100).is_err());
+}
+
+#[test]
+fn overflowing_sum_is_rejected() {
+    let err = to_jsonl_line_with_attach_bytes_max(&record(&[u64::MAX, 1]), u64::MAX).unwrap_err();
+    assert!(matches!(err, EventLogError::Invalid(_)));
+}
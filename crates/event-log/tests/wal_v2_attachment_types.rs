@@ -0,0 +1,70 @@
+use event_log::v2::{
+    to_jsonl_line, Attachment, Compression, EventTypeV2, RecordV2, WAL_VERSION_V2,
+};
+use event_log::EventLogError;
+use serde_json::{json, Value};
+
+fn attachment(mime: &str, compression: Compression) -> Attachment {
+    Attachment {
+        digest_sha256: format!("{:064x}", 7),
+        size_bytes: 3,
+        mime: mime.into(),
+        encoding: None,
+        compression,
+    }
+}
+
+fn record(att: Attachment) -> RecordV2<Value> {
+    RecordV2 {
+        id: 1,
+        ts_ms: 1,
+        version: WAL_VERSION_V2,
+        event_type: EventTypeV2::TaskEnqueued,
+        run_id: "R1".into(),
+        trace_id: "T1".into(),
+        payload: json!({"envelope_id":"EV1","agent":"a1"}),
+        attachments: Some(vec![att]),
+        metadata: json!({}),
+    }
+}
+
+#[test]
+fn compression_serializes_to_the_legacy_strings() {
+    assert_eq!(serde_json::to_string(&Compression::Zstd).unwrap(), r#""zstd""#);
+    assert_eq!(serde_json::to_string(&Compression::None).unwrap(), r#""none""#);
+    for (mime, c) in [
+        ("application/octet-stream", Compression::None),
+        ("text/plain; charset=utf-8", Compression::Zstd),
+        ("application/vnd.orca+json", Compression::Zstd),
+    ] {
+        let att = attachment(mime, c);
+        let s = serde_json::to_string(&att).unwrap();
+        assert_eq!(serde_json::from_str::<Attachment>(&s).unwrap(), att);
+        let line = to_jsonl_line(&record(att)).unwrap();
+        let v: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(v["attachments"][0]["mime"], mime);
+    }
+}
+
+#[test]
+fn unknown_compression_value_is_rejected() {
+    let s = format!(
+        r#"{{"digest_sha256":"{:064x}","size_bytes":3,"mime":"text/plain","compression":"gzip"}}"#,
+        7
+    );
+    assert!(serde_json::from_str::<Attachment>(&s).is_err());
+    // Case matters: the wire format is lowercase.
+    assert!(serde_json::from_str::<Attachment>(&s.replace("gzip", "Zstd")).is_err());
+}
+
+#[test]
+fn malformed_mime_is_rejected_at_serialization() {
+    for mime in ["", "text", "text/", "/plain", "text/plain/extra", "te xt/plain", "-text/plain"] {
+        match to_jsonl_line(&record(attachment(mime, Compression::None))) {
+            Err(EventLogError::Invalid(m)) => {
+                assert!(m.starts_with("invalid attachment mime"), "{m}")
+            }
+            other => panic!("mime {mime:?}: expected Invalid, got {other:?}"),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use event_log::v2::{
-    to_jsonl_line, Attachment, EventTypeV2, RecordV2, TaskEnqueuedPayload, WAL_VERSION_V2,
+    to_jsonl_line, Attachment, Compression, EventTypeV2, RecordV2, TaskEnqueuedPayload,
+    WAL_VERSION_V2,
 };
 use serde_json::json;
 
@@ -24,7 +25,7 @@ fn wal_v2_attachments_golden_red() {
                 size_bytes: 2048,
                 mime: "image/png".into(),
                 encoding: None,
-                compression: "zstd".into(),
+                compression: Compression::Zstd,
             },
             Attachment {
                 digest_sha256: "00e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0deadbeef"
@@ -32,7 +33,7 @@ fn wal_v2_attachments_golden_red() {
                 size_bytes: 1024,
                 mime: "text/plain".into(),
                 encoding: Some("utf-8".into()),
-                compression: "none".into(),
+                compression: Compression::None,
             },
         ]),
         metadata: json!({}),
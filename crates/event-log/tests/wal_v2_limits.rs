@@ -0,0 +1,89 @@
+use event_log::v2::{
+    to_jsonl_line, to_jsonl_line_with_limits, Attachment, Compression, EventTypeV2, RecordV2,
+    V2Limits, WAL_VERSION_V2,
+};
+use event_log::EventLogError;
+use serde_json::{json, Value};
+
+fn record(count: usize, mime: &str) -> RecordV2<Value> {
+    let attachments = (0..count)
+        .map(|i| Attachment {
+            digest_sha256: format!("{:064x}", i + 1),
+            size_bytes: 10,
+            mime: mime.into(),
+            encoding: None,
+            compression: Compression::None,
+        })
+        .collect();
+    RecordV2 {
+        id: 1,
+        ts_ms: 1,
+        version: WAL_VERSION_V2,
+        event_type: EventTypeV2::TaskEnqueued,
+        run_id: "R1".into(),
+        trace_id: "T1".into(),
+        payload: json!({"envelope_id":"EV1","agent":"a1"}),
+        attachments: Some(attachments),
+        metadata: json!({}),
+    }
+}
+
+fn invalid(r: Result<String, EventLogError>) -> String {
+    match r {
+        Err(EventLogError::Invalid(m)) => m,
+        other => panic!("expected Invalid, got {other:?}"),
+    }
+}
+
+#[test]
+fn defaults_match_builtin_caps() {
+    let d = V2Limits::default();
+    assert_eq!((d.attach_max_count, d.str_max_len, d.total_attach_json_max), (8, 128, 8 * 1024));
+    let rec = record(3, "text/plain");
+    assert_eq!(to_jsonl_line(&rec).unwrap(), to_jsonl_line_with_limits(&rec, &d).unwrap());
+}
+
+#[test]
+fn lower_custom_caps_reject() {
+    let limits = V2Limits { attach_max_count: 2, str_max_len: 8, ..V2Limits::default() };
+    let msg = invalid(to_jsonl_line_with_limits(&record(3, "text/plain"), &limits));
+    assert_eq!(msg, "attachments count 3 exceeds max 2");
+    let msg = invalid(to_jsonl_line_with_limits(&record(1, "text/plain+x"), &limits));
+    assert_eq!(msg, "oversized attachment string field");
+    let tight = V2Limits { total_attach_json_max: 256, ..V2Limits::default() };
+    assert_eq!(
+        invalid(to_jsonl_line_with_limits(&record(4, "text/plain"), &tight)),
+        "attachments too large"
+    );
+}
+
+#[test]
+fn higher_custom_caps_accept() {
+    let long_mime = format!("application/{}; profile={}", "x".repeat(100), "y".repeat(100));
+    assert!(to_jsonl_line(&record(12, &long_mime)).is_err());
+    let limits = V2Limits {
+        attach_max_count: 16,
+        str_max_len: 256,
+        total_attach_json_max: 64 * 1024,
+        ..V2Limits::default()
+    };
+    let line = to_jsonl_line_with_limits(&record(12, &long_mime), &limits).unwrap();
+    let v: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(v["attachments"].as_array().unwrap().len(), 12);
+}
+
+#[test]
+fn out_of_range_limits_are_rejected() {
+    let rec = record(1, "text/plain");
+    for limits in [
+        V2Limits { attach_max_count: 0, ..V2Limits::default() },
+        V2Limits { str_max_len: 5000, ..V2Limits::default() },
+        V2Limits { total_attach_json_max: 10, ..V2Limits::default() },
+        V2Limits { total_attach_json_max: 2 * 1024 * 1024, ..V2Limits::default() },
+        V2Limits { total_attach_bytes_max: 0, ..V2Limits::default() },
+    ] {
+        assert!(limits.validate().is_err(), "{limits:?}");
+        assert!(invalid(to_jsonl_line_with_limits(&rec, &limits)).starts_with("v2 limits: "));
+    }
+    assert!(V2Limits::default().validate().is_ok());
+}
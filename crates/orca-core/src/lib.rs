@@ -9,6 +9,7 @@ pub mod ids {
     //! ID utilities: monotonic event ids and trace ids.
 
     use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
     use std::time::{SystemTime, UNIX_EPOCH};
     use uuid::Uuid;
 
@@ -19,14 +20,89 @@ pub mod ids {
         NEXT_ID.fetch_add(1, Ordering::Relaxed)
     }
 
-    /// Milliseconds since UNIX epoch (for timestamps).
+    /// Reset the process-wide counter so the next [`next_monotonic_id`] returns `next`.
+    ///
+    /// For deterministic replay tests only: it affects every caller in the process, so
+    /// callers must serialize around it.
+    pub fn reseed_monotonic_id(next: u64) {
+        NEXT_ID.store(next, Ordering::Relaxed);
+    }
+
+    /// Ensure later [`next_monotonic_id`] calls return ids greater than `id`; never moves
+    /// the counter backwards. Used after replaying a WAL whose ids may have gaps (e.g.
+    /// after compaction), so allocation continues past the highest id rather than the
+    /// record count.
+    pub fn advance_monotonic_id_past(id: u64) {
+        NEXT_ID.fetch_max(id.saturating_add(1), Ordering::Relaxed);
+    }
+
+    /// Milliseconds since UNIX epoch (for timestamps). Under [`crate::repro`] mode this is
+    /// the seeded start time, fixed for the life of the process.
     pub fn now_ms() -> u64 {
+        if let Some(ms) = crate::repro::clock_ms() {
+            return ms;
+        }
         SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
     }
 
-    /// Opaque trace identifier (UUID v4 string).
+    /// Opaque trace identifier (UUID v4 string); drawn from the seeded generator under
+    /// [`crate::repro`] mode.
     pub fn new_trace_id() -> String {
-        Uuid::new_v4().to_string()
+        crate::repro::next_trace_id().unwrap_or_else(Uuid::new_v4).to_string()
+    }
+
+    /// Pluggable source of string identifiers (e.g. capture request ids).
+    pub trait IdGenerator: Send + Sync + std::fmt::Debug {
+        /// Produce the next identifier.
+        fn next_id(&self) -> String;
+    }
+
+    /// `{prefix}{n}` using the process-wide monotonic counter (resets on restart).
+    #[derive(Debug, Clone)]
+    pub struct PrefixedMonotonic {
+        prefix: String,
+    }
+
+    impl PrefixedMonotonic {
+        /// Generator emitting `{prefix}{next_monotonic_id()}`.
+        pub fn new(prefix: impl Into<String>) -> Self {
+            Self { prefix: prefix.into() }
+        }
+    }
+
+    impl IdGenerator for PrefixedMonotonic {
+        fn next_id(&self) -> String {
+            format!("{}{}", self.prefix, next_monotonic_id())
+        }
+    }
+
+    /// `{prefix}{ULID}`: globally unique and lexicographically sortable by creation time.
+    /// Ids from one generator are strictly increasing, even within the same millisecond.
+    pub struct UlidGenerator {
+        prefix: String,
+        inner: Mutex<ulid::Generator>,
+    }
+
+    impl UlidGenerator {
+        /// Generator emitting `{prefix}` followed by a 26-char Crockford base32 ULID.
+        pub fn new(prefix: impl Into<String>) -> Self {
+            Self { prefix: prefix.into(), inner: Mutex::new(ulid::Generator::new()) }
+        }
+    }
+
+    impl std::fmt::Debug for UlidGenerator {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("UlidGenerator").field("prefix", &self.prefix).finish_non_exhaustive()
+        }
+    }
+
+    impl IdGenerator for UlidGenerator {
+        fn next_id(&self) -> String {
+            let mut g = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            // Overflow only if 2^80 ids are drawn in one millisecond; fall back to a fresh ULID.
+            let id = g.generate().unwrap_or_else(|_| ulid::Ulid::new());
+            format!("{}{}", self.prefix, id)
+        }
     }
 
     #[cfg(test)]
@@ -40,6 +116,22 @@ pub mod ids {
             assert!(b > a);
         }
 
+        #[test]
+        fn prefixed_monotonic_keeps_prefix_and_increments() {
+            let g = PrefixedMonotonic::new("R");
+            let a: u64 = g.next_id().strip_prefix('R').unwrap().parse().unwrap();
+            let b: u64 = g.next_id().strip_prefix('R').unwrap().parse().unwrap();
+            assert!(b > a);
+        }
+
+        #[test]
+        fn ulid_ids_are_prefixed_unique_and_sorted() {
+            let g = UlidGenerator::new("req-");
+            let ids: Vec<String> = (0..1000).map(|_| g.next_id()).collect();
+            assert!(ids.iter().all(|i| i.starts_with("req-") && i.len() == 4 + 26));
+            assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        }
+
         #[test]
         fn trace_id_format() {
             let t = new_trace_id();
@@ -49,6 +141,114 @@ pub mod ids {
     }
 }
 
+pub mod repro {
+    //! Reproduction mode: one seed for every nondeterministic source.
+    //!
+    //! [`init`] (or `ORCA_REPRO_SEED` via [`init_from_env`]) makes trace ids come from a
+    //! seeded generator, restarts monotonic ids at 1, and fixes [`crate::ids::now_ms`] at a
+    //! seed-derived start time. Other crates hook in through [`seed`]: the orchestrator
+    //! installs a `VirtualClock` at [`start_ms`], and anything that needs jitter draws from
+    //! [`rng`] instead of an OS source. With the same seed and inputs, a run's WAL is
+    //! byte-identical; durations measured with `Instant` (e.g. `wall_ms`) are the
+    //! documented exception.
+
+    use std::sync::{Mutex, RwLock};
+
+    /// Environment variable holding the reproduction seed (decimal `u64`).
+    pub const ENV_VAR: &str = "ORCA_REPRO_SEED";
+
+    struct State {
+        seed: u64,
+        trace_ids: Mutex<SeededRng>,
+    }
+
+    static STATE: RwLock<Option<State>> = RwLock::new(None);
+
+    /// Deterministic SplitMix64 generator.
+    #[derive(Debug, Clone)]
+    pub struct SeededRng(u64);
+
+    impl SeededRng {
+        /// Generator starting from `seed`.
+        pub fn new(seed: u64) -> Self {
+            Self(seed)
+        }
+
+        /// Next pseudo-random `u64`.
+        pub fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        /// Next pseudo-random `f64` in `[0, 1)`.
+        pub fn next_f64(&mut self) -> f64 {
+            (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    /// Seed for the independent stream named `stream`, so sources do not share sequences.
+    pub fn derive(seed: u64, stream: &str) -> u64 {
+        let h = crate::hash::canonical_hash(&serde_json::json!([seed, stream]));
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&h[..8]);
+        u64::from_le_bytes(head)
+    }
+
+    /// Enter reproduction mode with `seed`. Calling it again re-seeds every source, so two
+    /// runs started after `init(seed)` with the same inputs produce the same output.
+    pub fn init(seed: u64) {
+        let state = State { seed, trace_ids: Mutex::new(SeededRng::new(derive(seed, "trace_id"))) };
+        *STATE.write().unwrap_or_else(|e| e.into_inner()) = Some(state);
+        crate::ids::reseed_monotonic_id(1);
+    }
+
+    /// Enter reproduction mode from `ORCA_REPRO_SEED` unless already active; returns the
+    /// active seed. An unparsable value is ignored.
+    pub fn init_from_env() -> Option<u64> {
+        if let Some(seed) = seed() {
+            return Some(seed);
+        }
+        let seed = std::env::var(ENV_VAR).ok()?.trim().parse::<u64>().ok()?;
+        init(seed);
+        Some(seed)
+    }
+
+    /// Active seed, if reproduction mode is on.
+    pub fn seed() -> Option<u64> {
+        STATE.read().unwrap_or_else(|e| e.into_inner()).as_ref().map(|s| s.seed)
+    }
+
+    /// Seeded generator for the named `stream` (e.g. `"retry_jitter"`), or `None` outside
+    /// reproduction mode.
+    pub fn rng(stream: &str) -> Option<SeededRng> {
+        seed().map(|s| SeededRng::new(derive(s, stream)))
+    }
+
+    /// Virtual clock start (ms since UNIX epoch) for `seed`: within a year after
+    /// 2024-01-01, so timestamps look realistic but are fixed per seed.
+    pub fn start_ms(seed: u64) -> u64 {
+        const BASE_MS: u64 = 1_704_067_200_000;
+        const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+        BASE_MS + derive(seed, "clock") % YEAR_MS
+    }
+
+    pub(crate) fn clock_ms() -> Option<u64> {
+        seed().map(start_ms)
+    }
+
+    pub(crate) fn next_trace_id() -> Option<uuid::Uuid> {
+        let guard = STATE.read().unwrap_or_else(|e| e.into_inner());
+        let mut rng = guard.as_ref()?.trace_ids.lock().unwrap_or_else(|e| e.into_inner());
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        Some(uuid::Builder::from_random_bytes(bytes).into_uuid())
+    }
+}
+
 pub mod envelope {
     //! Message envelope schema for tasks/results/errors.
 
@@ -222,3 +422,212 @@ pub mod metadata {
         }
     }
 }
+
+pub mod payload {
+    //! Typed envelope payloads, validated against a per-kind JSON schema (v1).
+    //!
+    //! Schemas live in `Docs/schemas/payload/<kind>.schema.json`; [`parse`] validates the raw
+    //! JSON for the envelope `kind` and then decodes it into the matching typed payload.
+    use jsonschema::{Draft, JSONSchema};
+    use once_cell::sync::Lazy;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Value};
+
+    fn compile(src: &str) -> JSONSchema {
+        let schema: Value = serde_json::from_str(src).expect("invalid payload schema json");
+        JSONSchema::options().with_draft(Draft::Draft7).compile(&schema).expect("compile schema")
+    }
+
+    static TASK: Lazy<JSONSchema> =
+        Lazy::new(|| compile(include_str!("../../../Docs/schemas/payload/agent_task.schema.json")));
+    static RESULT: Lazy<JSONSchema> = Lazy::new(|| {
+        compile(include_str!("../../../Docs/schemas/payload/agent_result.schema.json"))
+    });
+    static ERROR: Lazy<JSONSchema> = Lazy::new(|| {
+        compile(include_str!("../../../Docs/schemas/payload/agent_error.schema.json"))
+    });
+
+    /// Why a payload could not be parsed for its envelope kind.
+    #[derive(Debug, thiserror::Error)]
+    pub enum PayloadError {
+        #[error("unknown envelope kind '{0}'")]
+        UnknownKind(String),
+        #[error("{kind} payload is not valid JSON: {source}")]
+        Json { kind: &'static str, source: serde_json::Error },
+        #[error("{kind} payload violates schema: {}", violations.join("; "))]
+        Schema { kind: &'static str, violations: Vec<String> },
+    }
+
+    /// `agent_task` payload: any JSON object; fields are agent-specific.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TaskPayload {
+        #[serde(flatten)]
+        pub fields: Map<String, Value>,
+    }
+
+    /// Usage reported inside an `agent_result` payload.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct PayloadUsage {
+        #[serde(default)]
+        pub tokens: Option<u64>,
+        #[serde(default)]
+        pub cost_micros: Option<u64>,
+    }
+
+    /// `agent_result` payload: optional `output` and `usage`, plus agent-specific fields.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResultPayload {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub output: Option<Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub usage: Option<PayloadUsage>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    /// `agent_error` payload: a non-empty `message`, optional `code` and `retryable` flag.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ErrorPayload {
+        pub message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub code: Option<String>,
+        #[serde(default)]
+        pub retryable: bool,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    /// Payload decoded according to the envelope kind.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Payload {
+        Task(TaskPayload),
+        Result(ResultPayload),
+        Error(ErrorPayload),
+    }
+
+    /// Validate `payload_json` against the schema for `kind` and decode it.
+    pub fn parse(kind: &str, payload_json: &str) -> Result<Payload, PayloadError> {
+        let (kind, schema): (&'static str, &JSONSchema) = match kind {
+            "agent_task" => ("agent_task", &TASK),
+            "agent_result" => ("agent_result", &RESULT),
+            "agent_error" => ("agent_error", &ERROR),
+            other => return Err(PayloadError::UnknownKind(other.to_string())),
+        };
+        let value: Value = serde_json::from_str(payload_json)
+            .map_err(|source| PayloadError::Json { kind, source })?;
+        if let Err(errors) = schema.validate(&value) {
+            let violations = errors.map(|e| e.to_string()).collect();
+            return Err(PayloadError::Schema { kind, violations });
+        }
+        let decoded = match kind {
+            "agent_task" => serde_json::from_value(value).map(Payload::Task),
+            "agent_result" => serde_json::from_value(value).map(Payload::Result),
+            _ => serde_json::from_value(value).map(Payload::Error),
+        };
+        decoded.map_err(|source| PayloadError::Json { kind, source })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn task_payload_parses() {
+            let Payload::Task(t) = parse("agent_task", r#"{"text":"hi","n":2}"#).unwrap() else {
+                panic!("expected task payload");
+            };
+            assert_eq!(t.fields["text"], "hi");
+        }
+
+        #[test]
+        fn result_payload_with_bad_usage_is_rejected() {
+            let err =
+                parse("agent_result", r#"{"output":"ok","usage":{"tokens":-1}}"#).unwrap_err();
+            assert!(matches!(err, PayloadError::Schema { kind: "agent_result", .. }), "{err}");
+            assert!(matches!(parse("agent_result", "[1]"), Err(PayloadError::Schema { .. })));
+        }
+
+        #[test]
+        fn error_payload_requires_message() {
+            assert!(matches!(parse("agent_error", "{}"), Err(PayloadError::Schema { .. })));
+            let Payload::Error(e) = parse("agent_error", r#"{"message":"boom"}"#).unwrap() else {
+                panic!("expected error payload");
+            };
+            assert!(!e.retryable);
+            assert!(matches!(parse("agent_x", "{}"), Err(PayloadError::UnknownKind(_))));
+        }
+    }
+}
+
+pub mod hash {
+    //! Canonical JSON encoding and hashing for idempotency keys and caches.
+    use serde_json::Value;
+    use sha2::{Digest, Sha256};
+
+    /// Encode `v` as compact JSON with object keys sorted at every level, so logically equal
+    /// values always produce the same bytes regardless of insertion order.
+    pub fn canonical_json(v: &Value) -> String {
+        let mut out = String::new();
+        write_canonical(v, &mut out);
+        out
+    }
+
+    /// SHA-256 of [`canonical_json`]`(v)`.
+    pub fn canonical_hash(v: &Value) -> [u8; 32] {
+        Sha256::digest(canonical_json(v).as_bytes()).into()
+    }
+
+    fn write_canonical(v: &Value, out: &mut String) {
+        match v {
+            Value::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_canonical(item, out);
+                }
+                out.push(']');
+            }
+            Value::Object(map) => {
+                // Sort explicitly rather than relying on serde_json's map ordering, which
+                // changes if any crate in the build enables `preserve_order`.
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                out.push('{');
+                for (i, (k, item)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::String(k.clone()).to_string());
+                    out.push(':');
+                    write_canonical(item, out);
+                }
+                out.push('}');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_json::json;
+
+        #[test]
+        fn key_order_does_not_change_hash() {
+            let a: Value =
+                serde_json::from_str(r#"{"b":1,"a":{"y":[1,{"q":2,"p":3}],"x":null}}"#).unwrap();
+            let b: Value =
+                serde_json::from_str(r#"{"a":{"x":null,"y":[1,{"p":3,"q":2}]},"b":1}"#).unwrap();
+            assert_eq!(canonical_json(&a), r#"{"a":{"x":null,"y":[1,{"p":3,"q":2}]},"b":1}"#);
+            assert_eq!(canonical_hash(&a), canonical_hash(&b));
+        }
+
+        #[test]
+        fn different_values_hash_differently() {
+            assert_ne!(canonical_hash(&json!({"a": 1})), canonical_hash(&json!({"a": 2})));
+            assert_ne!(canonical_hash(&json!([1, 2])), canonical_hash(&json!([2, 1])));
+        }
+    }
+}
@@ -55,6 +55,8 @@ fn bench_capture_overhead(c: &mut Criterion) {
                             workflow_id: "wf".into(),
                             initial_task: None,
                             budget: None,
+                            client_id: String::new(),
+                            nonce: String::new(),
                         })
                         .await
                         .unwrap();
@@ -80,6 +82,8 @@ fn bench_capture_overhead(c: &mut Criterion) {
                             workflow_id: "wf".into(),
                             initial_task: None,
                             budget: None,
+                            client_id: String::new(),
+                            nonce: String::new(),
                         })
                         .await
                         .unwrap();
@@ -114,6 +118,8 @@ fn bench_client_capture_overhead(c: &mut Criterion) {
                             workflow_id: "wf".into(),
                             initial_task: None,
                             budget: None,
+                            client_id: String::new(),
+                            nonce: String::new(),
                         })
                         .await
                         .unwrap();
@@ -141,7 +147,7 @@ fn bench_client_capture_overhead(c: &mut Criterion) {
                             JsonlEventLog::open(tmp.path().join("client_bench.jsonl")).unwrap();
                         orchestrator::proxy::set_capture_log(log);
                         let svc = tower::ServiceBuilder::new()
-                            .layer(orchestrator::proxy::ProxyCaptureLayer)
+                            .layer(orchestrator::proxy::ProxyCaptureLayer::default())
                             .service(channel.clone());
                         let mut client = OrchestratorClient::new(svc);
                         let _ = client
@@ -149,6 +155,8 @@ fn bench_client_capture_overhead(c: &mut Criterion) {
                                 workflow_id: "wf".into(),
                                 initial_task: None,
                                 budget: None,
+                                client_id: String::new(),
+                                nonce: String::new(),
                             })
                             .await
                             .unwrap();
@@ -161,6 +169,8 @@ fn bench_client_capture_overhead(c: &mut Criterion) {
                                 workflow_id: "wf".into(),
                                 initial_task: None,
                                 budget: None,
+                                client_id: String::new(),
+                                nonce: String::new(),
                             })
                             .await
                             .unwrap();
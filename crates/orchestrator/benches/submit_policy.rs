@@ -0,0 +1,78 @@
+//! `submit_task` throughput through policy + budget + WAL, by policy shape.
+//!
+//! - `no_policy`: nothing loaded, every task takes the fail-closed deny path
+//! - `permissive`: `rules: []`
+//! - `rules_100`: 100 field-transform rules, evaluated on every task but never firing
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::orchestrator_server::Orchestrator;
+use orchestrator::{orca_v1::*, OrchestratorService};
+use serde_json::json;
+use std::fmt::Write as _;
+use tokio::runtime::Runtime;
+
+fn policy_with_rules(n: usize) -> String {
+    let mut yaml = String::from("rules:\n");
+    for i in 0..n {
+        let _ = write!(
+            yaml,
+            "  - name: drop-secret-{i}\n    when: always\n    action: modify\n    drop_fields: [\"payload.secret_{i}\"]\n"
+        );
+    }
+    yaml
+}
+
+fn service(dir: &tempfile::TempDir, policy: Option<&str>) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("orc.jsonl")).unwrap());
+    if let Some(yaml) = policy {
+        let path = dir.path().join("policy.yaml");
+        std::fs::write(&path, yaml).unwrap();
+        svc.load_policy_from_path(&path).unwrap();
+    }
+    svc
+}
+
+fn request(seq: u64) -> tonic::Request<SubmitTaskRequest> {
+    tonic::Request::new(SubmitTaskRequest {
+        run_id: "wf".into(),
+        task: Some(Envelope {
+            id: format!("m{seq}"), // unique, so the dedup fast path never short-circuits
+            parent_id: "".into(),
+            trace_id: "t".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: json!({"prompt": "summarize the report", "n": seq}).to_string(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 1,
+            usage: None,
+        }),
+    })
+}
+
+fn bench_submit_policy(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let rules_100 = policy_with_rules(100);
+    let mut group = c.benchmark_group("submit_task_policy");
+    group.throughput(Throughput::Elements(1));
+    for (name, policy) in
+        [("no_policy", None), ("permissive", Some("rules: []\n")), ("rules_100", Some(&*rules_100))]
+    {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = service(&dir, policy);
+        let mut seq = 0u64;
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                seq += 1;
+                rt.block_on(async {
+                    let _ = svc.submit_task(request(seq)).await;
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_submit_policy);
+criterion_main!(benches);
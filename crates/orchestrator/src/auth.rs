@@ -0,0 +1,103 @@
+//! Per-RPC authorization scopes.
+//!
+//! A tokens file maps each bearer token to the RPCs it may call:
+//!
+//! ```json
+//! { "reader-token": ["stream_events", "fetch_result"], "admin-token": ["*"] }
+//! ```
+//!
+//! Unknown tokens are `unauthenticated`; known tokens calling an RPC outside their scope are
+//! `permission_denied`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use tonic::Status;
+
+/// An RPC a token may be scoped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scope {
+    StartRun,
+    SubmitTask,
+    StreamEvents,
+    FetchResult,
+    /// Operator RPCs (`ListRuns`); only granted explicitly or via `"*"`.
+    Admin,
+}
+
+impl Scope {
+    /// All scopes (what `"*"` expands to).
+    pub const ALL: [Scope; 5] =
+        [Scope::StartRun, Scope::SubmitTask, Scope::StreamEvents, Scope::FetchResult, Scope::Admin];
+
+    /// RPC name as used in the tokens file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::StartRun => "start_run",
+            Scope::SubmitTask => "submit_task",
+            Scope::StreamEvents => "stream_events",
+            Scope::FetchResult => "fetch_result",
+            Scope::Admin => "admin",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Scope::ALL.into_iter().find(|sc| sc.as_str() == s)
+    }
+}
+
+/// Token → allowed RPCs.
+#[derive(Debug, Clone, Default)]
+pub struct TokenScopes {
+    tokens: BTreeMap<String, BTreeSet<Scope>>,
+}
+
+impl TokenScopes {
+    /// Grant `scopes` to `token` (replacing any previous grant).
+    pub fn with_token(mut self, token: impl Into<String>, scopes: &[Scope]) -> Self {
+        self.tokens.insert(token.into(), scopes.iter().copied().collect());
+        self
+    }
+
+    /// Parse a JSON tokens file body (`{"token": ["rpc", ...]}`; `"*"` grants every RPC).
+    pub fn from_json_str(s: &str) -> Result<Self, String> {
+        let raw: BTreeMap<String, Vec<String>> =
+            serde_json::from_str(s).map_err(|e| format!("tokens file parse error: {}", e))?;
+        let mut tokens = BTreeMap::new();
+        for (token, names) in raw {
+            if token.is_empty() {
+                return Err("tokens file: token must be non-empty".into());
+            }
+            let mut set = BTreeSet::new();
+            for name in names {
+                if name == "*" {
+                    set.extend(Scope::ALL);
+                } else {
+                    let sc = Scope::parse(&name)
+                        .ok_or_else(|| format!("tokens file: unknown scope '{}'", name))?;
+                    set.insert(sc);
+                }
+            }
+            tokens.insert(token, set);
+        }
+        Ok(Self { tokens })
+    }
+
+    /// Load a JSON tokens file from disk.
+    pub fn load_from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, String> {
+        let s = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("tokens file read error: {}", e))?;
+        Self::from_json_str(&s)
+    }
+
+    /// Check that `token` is known and allowed to call `scope`.
+    #[allow(clippy::result_large_err)] // tonic::Status is large; matches service signatures
+    pub fn authorize(&self, token: Option<&str>, scope: Scope) -> Result<(), Status> {
+        let Some(granted) = token.and_then(|t| self.tokens.get(t)) else {
+            return Err(Status::unauthenticated("invalid authorization"));
+        };
+        if granted.contains(&scope) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!("token not authorized for {}", scope.as_str())))
+        }
+    }
+}
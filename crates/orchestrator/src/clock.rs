@@ -82,6 +82,15 @@ pub fn set_process_clock(clock: Arc<dyn Clock>) {
     *guard = clock;
 }
 
+/// In reproduction mode (`orca_core::repro`, e.g. `ORCA_REPRO_SEED`), install a
+/// `VirtualClock` at the seed's start time and return the seed; no-op otherwise. The clock
+/// only moves when advanced explicitly, so every timestamp is a function of the seed.
+pub fn install_repro_clock() -> Option<u64> {
+    let seed = orca_core::repro::init_from_env()?;
+    set_process_clock(Arc::new(VirtualClock::new(orca_core::repro::start_ms(seed))));
+    Some(seed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
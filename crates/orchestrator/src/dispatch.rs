@@ -0,0 +1,82 @@
+//! Routing of accepted tasks to agent workers.
+//!
+//! Once `submit_task` has durably recorded a task (`task_enqueued`) and post-submit policy
+//! allows it, the service hands the envelope to its [`Dispatcher`]. The default
+//! [`WalOnlyDispatcher`] delivers nothing: the WAL is the only record and workers read it
+//! themselves. Dispatchers that do deliver get a `task_dispatched` event after each
+//! successful hand-off; a failed hand-off is logged and leaves the task recorded but
+//! undispatched.
+
+use crate::orca_v1::Envelope;
+use tokio::sync::mpsc;
+
+/// Error returned by a dispatcher; logged by the service, never returned to the client.
+pub type DispatchError = Box<dyn std::error::Error + Send + Sync>;
+
+/// What a dispatcher did with a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchOutcome {
+    /// Handed to a worker; the service records `task_dispatched`.
+    Delivered,
+    /// Left in the WAL only; nothing further is recorded.
+    RecordedOnly,
+}
+
+/// Destination for accepted tasks. Called on the RPC path, so implementations should return
+/// quickly; slow workers belong behind a channel.
+pub trait Dispatcher: Send + Sync {
+    /// Short label recorded in `task_dispatched` and used in logs.
+    fn name(&self) -> &str {
+        "dispatcher"
+    }
+    /// Route `task` of `run_id` to a worker.
+    fn dispatch(&self, run_id: &str, task: &Envelope) -> Result<DispatchOutcome, DispatchError>;
+}
+
+/// Current behavior: tasks are recorded in the WAL and not delivered anywhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalOnlyDispatcher;
+
+impl Dispatcher for WalOnlyDispatcher {
+    fn name(&self) -> &str {
+        "wal_only"
+    }
+    fn dispatch(&self, _run_id: &str, _task: &Envelope) -> Result<DispatchOutcome, DispatchError> {
+        Ok(DispatchOutcome::RecordedOnly)
+    }
+}
+
+/// A task as delivered to the worker side of a [`ChannelDispatcher`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchedTask {
+    pub run_id: String,
+    pub envelope: Envelope,
+}
+
+/// In-process dispatcher for tests and single-binary deployments: tasks are sent, in
+/// acceptance order, on a bounded channel whose receiver plays the worker. A full or closed
+/// channel fails the hand-off instead of blocking the RPC.
+#[derive(Debug, Clone)]
+pub struct ChannelDispatcher {
+    tx: mpsc::Sender<DispatchedTask>,
+}
+
+impl ChannelDispatcher {
+    /// Dispatcher with room for `capacity` undelivered tasks, and the worker-side receiver.
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<DispatchedTask>) {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        (Self { tx }, rx)
+    }
+}
+
+impl Dispatcher for ChannelDispatcher {
+    fn name(&self) -> &str {
+        "channel"
+    }
+    fn dispatch(&self, run_id: &str, task: &Envelope) -> Result<DispatchOutcome, DispatchError> {
+        self.tx
+            .try_send(DispatchedTask { run_id: run_id.to_string(), envelope: task.clone() })
+            .map_err(|e| e.to_string())?;
+        Ok(DispatchOutcome::Delivered)
+    }
+}
@@ -0,0 +1,122 @@
+//! Per-event hooks for embedders that mirror the WAL elsewhere (Kafka, SQS, ...).
+//!
+//! After each successful WAL append the service hands a copy of the record to a
+//! [`HookForwarder`], which queues it on a bounded channel drained by a dedicated thread that
+//! calls [`EventHook::on_event`]. Appends and enqueues are serialized by the forwarder, so the
+//! hook sees records in WAL-append order even when many tasks append concurrently. When the
+//! queue is full, the [`HookOverflow`] policy either drops the record for the hook (it is
+//! already durable in the WAL; counted in [`HookForwarder::dropped`] and the
+//! `orca.event_hook.dropped` metric) or blocks the append path until the hook catches up.
+
+use event_log::EventRecord;
+use serde_json::Value as JsonValue;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tracing::warn;
+
+/// Default queue depth between the append path and the hook thread.
+pub const DEFAULT_HOOK_CAPACITY: usize = 1024;
+
+/// What the append path does when the hook queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HookOverflow {
+    /// Drop the record for the hook and count it (default); appends never wait on the hook.
+    #[default]
+    Drop,
+    /// Wait for queue space: the hook sees every record, and a slow hook slows appends.
+    Block,
+}
+
+/// Receives every record appended to the WAL, in order, on the forwarder thread.
+pub trait EventHook: Send + Sync {
+    fn on_event(&self, record: &EventRecord<JsonValue>);
+}
+
+/// Bounded queue feeding an [`EventHook`] from a background thread. The thread exits once the
+/// forwarder (and every service clone sharing it) is dropped and the queue is drained.
+pub struct HookForwarder {
+    tx: SyncSender<EventRecord<JsonValue>>,
+    overflow: HookOverflow,
+    order: Mutex<()>,
+    dropped: AtomicU64,
+}
+
+impl HookForwarder {
+    /// Start the forwarder thread for `hook` with room for `capacity` queued records,
+    /// dropping records that do not fit ([`HookOverflow::Drop`]).
+    ///
+    /// # Panics
+    /// If the OS refuses to create the thread, as `std::thread::spawn` does.
+    pub fn spawn(hook: Arc<dyn EventHook>, capacity: usize) -> Self {
+        Self::spawn_with_overflow(hook, capacity, HookOverflow::Drop)
+    }
+
+    /// Like [`Self::spawn`], with an explicit policy for a full queue.
+    ///
+    /// # Panics
+    /// If the OS refuses to create the thread, as `std::thread::spawn` does.
+    pub fn spawn_with_overflow(
+        hook: Arc<dyn EventHook>,
+        capacity: usize,
+        overflow: HookOverflow,
+    ) -> Self {
+        let (tx, rx) = sync_channel::<EventRecord<JsonValue>>(capacity.max(1));
+        std::thread::Builder::new()
+            .name("orca-event-hook".into())
+            .spawn(move || {
+                for rec in rx {
+                    hook.on_event(&rec);
+                }
+            })
+            .expect("spawn event hook thread");
+        Self { tx, overflow, order: Mutex::new(()), dropped: AtomicU64::new(0) }
+    }
+
+    /// Hold while appending a record and passing it to [`Self::send`], so records reach the
+    /// queue in the order they were appended.
+    pub fn order_guard(&self) -> MutexGuard<'_, ()> {
+        self.order.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queue `rec` per the overflow policy: with [`HookOverflow::Drop`] a full queue drops
+    /// it, with [`HookOverflow::Block`] this waits for space. Records that cannot be queued
+    /// are counted as dropped.
+    pub fn send(&self, rec: EventRecord<JsonValue>) {
+        let result = match self.overflow {
+            HookOverflow::Drop => self.tx.try_send(rec),
+            HookOverflow::Block => self.tx.send(rec).map_err(|e| TrySendError::Disconnected(e.0)),
+        };
+        match result {
+            Ok(()) => {}
+            Err(TrySendError::Full(rec)) => {
+                self.record_drop();
+                warn!(id = rec.id, "event hook queue full; dropping record for the hook");
+            }
+            Err(TrySendError::Disconnected(rec)) => {
+                self.record_drop();
+                warn!(id = rec.id, "event hook thread gone; dropping record for the hook");
+            }
+        }
+    }
+
+    fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "otel")]
+        telemetry::metrics::init_event_hook_dropped_counter().add(1, &[]);
+    }
+
+    /// Records not delivered to the hook because the queue was full or the thread had exited.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl std::fmt::Debug for HookForwarder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HookForwarder")
+            .field("overflow", &self.overflow)
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
@@ -8,11 +8,12 @@ use event_log::{EventLogError, EventRecord, JsonlEventLog};
 use orca_core::envelope::Envelope;
 use policy::{DecisionKind, Engine as PolicyEngine};
 use serde_json::{json, Value as JsonValue};
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
 #[cfg(feature = "otel")]
 use telemetry::metrics::init_budget_instruments;
 use telemetry::BudgetMetrics;
-use tokio::time::{sleep, timeout, Duration};
+use tokio::time::{sleep, Duration};
 use tonic::{Request, Response, Status};
 use tracing::{info, info_span, instrument, warn, Instrument};
 
@@ -20,11 +21,29 @@ pub mod orca_v1 {
     tonic::include_proto!("orca.v1");
 }
 
+impl orca_v1::Envelope {
+    /// Parse `payload_json` according to `kind`, validating it against the per-kind schema
+    /// (see [`orca_core::payload`]).
+    pub fn payload(&self) -> Result<orca_core::payload::Payload, orca_core::payload::PayloadError> {
+        orca_core::payload::parse(&self.kind, &self.payload_json)
+    }
+}
+
+pub mod auth;
 pub mod clock;
+pub mod dispatch;
+pub mod hook;
 pub mod proxy;
+pub mod results;
+pub mod tee;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
+pub mod updates;
+
+use auth::{Scope, TokenScopes};
 
 // Re-export only stable helpers; client capture types live under orchestrator::proxy
-pub use proxy::redacted_headers_from_http;
+pub use proxy::{redacted_headers_from_http, CaptureSampler};
 
 #[cfg(feature = "capture")]
 pub use proxy::{set_capture_log, ProxyCaptureLayer};
@@ -41,6 +60,195 @@ pub struct RunIndex {
     pub usage_by_run: std::sync::Arc<DashMap<String, (u64, u64)>>,
     pub usage_by_run_agent: std::sync::Arc<DashMap<(String, String), (u64, u64)>>,
     pub run_start_ts_by_run: std::sync::Arc<DashMap<String, u64>>,
+    /// run -> number of `agent_error` tasks (`task_failed` events)
+    pub failed_tasks_by_run: std::sync::Arc<DashMap<String, u64>>,
+}
+
+/// Point-in-time copy of a [`RunIndex`] with deterministically ordered maps, plus the
+/// last WAL event id it covers so a standby can replay only the tail.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SerializableIndex {
+    /// Highest WAL event id already reflected in the maps. Ids come from the process-wide
+    /// monotonic generator, so unlike a record count this cursor survives WAL rotation and
+    /// truncation. Snapshots without it (0) replay the whole WAL, which is idempotent.
+    #[serde(default)]
+    pub last_wal_event_id: u64,
+    pub last_event_id_by_run: BTreeMap<String, u64>,
+    /// run -> (tokens, cost_micros)
+    pub usage_by_run: BTreeMap<String, (u64, u64)>,
+    /// run -> agent -> (tokens, cost_micros)
+    pub usage_by_run_agent: BTreeMap<String, BTreeMap<String, (u64, u64)>>,
+    pub run_start_ts_by_run: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub failed_tasks_by_run: BTreeMap<String, u64>,
+}
+
+impl RunIndex {
+    /// Record `id` as an event of `run`, keeping the per-run maximum: records replayed out of
+    /// id order, or concurrently with live appends, never move `last_event_id_by_run` back.
+    pub fn advance_last_event_id(&self, run: &str, id: u64) {
+        self.last_event_id_by_run
+            .entry(run.to_string())
+            .and_modify(|last| *last = (*last).max(id))
+            .or_insert(id);
+    }
+
+    /// Copy every map into a [`SerializableIndex`] (`last_wal_event_id` left at 0).
+    pub fn snapshot(&self) -> SerializableIndex {
+        let mut by_agent: BTreeMap<String, BTreeMap<String, (u64, u64)>> = BTreeMap::new();
+        for kv in self.usage_by_run_agent.iter() {
+            let ((run, agent), usage) = kv.pair();
+            by_agent.entry(run.clone()).or_default().insert(agent.clone(), *usage);
+        }
+        SerializableIndex {
+            last_wal_event_id: 0,
+            last_event_id_by_run: copy_map(&self.last_event_id_by_run),
+            usage_by_run: copy_map(&self.usage_by_run),
+            usage_by_run_agent: by_agent,
+            run_start_ts_by_run: copy_map(&self.run_start_ts_by_run),
+            failed_tasks_by_run: copy_map(&self.failed_tasks_by_run),
+        }
+    }
+
+    /// Replace every map with the contents of `snap`.
+    pub fn restore(&self, snap: SerializableIndex) {
+        self.last_event_id_by_run.clear();
+        self.usage_by_run.clear();
+        self.usage_by_run_agent.clear();
+        self.run_start_ts_by_run.clear();
+        self.failed_tasks_by_run.clear();
+        for (run, id) in snap.last_event_id_by_run {
+            self.last_event_id_by_run.insert(run, id);
+        }
+        for (run, usage) in snap.usage_by_run {
+            self.usage_by_run.insert(run, usage);
+        }
+        for (run, agents) in snap.usage_by_run_agent {
+            for (agent, usage) in agents {
+                self.usage_by_run_agent.insert((run.clone(), agent), usage);
+            }
+        }
+        for (run, ts) in snap.run_start_ts_by_run {
+            self.run_start_ts_by_run.insert(run, ts);
+        }
+        for (run, n) in snap.failed_tasks_by_run {
+            self.failed_tasks_by_run.insert(run, n);
+        }
+    }
+}
+
+/// Completed runs remembered for [`OrchestratorService::is_run_completed`] and to keep late
+/// events from reopening idle tracking; the oldest are forgotten beyond `cap`.
+struct CompletedRuns {
+    runs: HashSet<String>,
+    failed: HashSet<String>, // subset of `runs` ended by an `agent_error`
+    order: VecDeque<String>,
+    cap: usize,
+}
+
+impl CompletedRuns {
+    fn insert(&mut self, run_id: &str, failed: bool) {
+        if self.runs.insert(run_id.to_string()) {
+            self.order.push_back(run_id.to_string());
+        }
+        if failed {
+            self.failed.insert(run_id.to_string());
+        }
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.order.len() > self.cap {
+            if let Some(old) = self.order.pop_front() {
+                self.runs.remove(&old);
+                self.failed.remove(&old);
+            }
+        }
+    }
+}
+
+/// Default number of completed runs remembered (see
+/// [`OrchestratorService::with_completed_runs_cap`]).
+pub const DEFAULT_COMPLETED_RUNS_CAP: usize = 10_000;
+
+/// Default tokens charged for a task without usage (see
+/// [`OrchestratorService::with_default_token_increment`]).
+pub const DEFAULT_TOKENS_INC: u64 = 1;
+
+/// Appended events buffered per follow-mode subscriber; one that falls further behind is
+/// closed with ABORTED and resumes from its last event id.
+pub const LIVE_EVENTS_CAPACITY: usize = 1024;
+
+/// Background tasks started by [`OrchestratorService::start_background_tasks`]; dropping
+/// this aborts them.
+#[derive(Debug, Default)]
+pub struct BackgroundTasks {
+    handles: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl BackgroundTasks {
+    /// Number of tasks started.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+    /// Whether no task was configured.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+impl Drop for BackgroundTasks {
+    fn drop(&mut self) {
+        for h in &self.handles {
+            h.abort();
+        }
+    }
+}
+
+fn copy_map<V: Copy>(m: &DashMap<String, V>) -> BTreeMap<String, V> {
+    m.iter().map(|kv| (kv.key().clone(), *kv.value())).collect()
+}
+
+/// How `start_run` assigns the run id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunIdMode {
+    /// Use the client-supplied `workflow_id` (default).
+    #[default]
+    ClientSupplied,
+    /// Derive the id from the initial task, `client_id`, and `nonce` (see
+    /// [`content_addressed_run_id`]); identical inputs map to the same id.
+    ContentAddressed,
+}
+
+/// Content-addressed run id: `run_` + hex SHA-256 over canonical JSON of
+/// `{client_id, initial_task, nonce}`. Deterministic and tamper-evident; vary `nonce` for
+/// distinct runs, reuse it for idempotent retries.
+pub fn content_addressed_run_id(
+    client_id: &str,
+    nonce: &str,
+    initial_task: Option<&orca_v1::Envelope>,
+) -> String {
+    let material = json!({"client_id": client_id, "initial_task": initial_task, "nonce": nonce});
+    format!("run_{}", hex::encode(orca_core::hash::canonical_hash(&material)))
+}
+
+/// Envelope kinds accepted on inbound RPCs (see `Envelope.kind` in the proto).
+pub const ENVELOPE_KINDS: &[&str] = &["agent_task", "agent_result", "agent_error"];
+
+/// Cheap, always-on structural check for inbound envelopes: non-empty `id`, `agent` and
+/// `trace_id`, and a `kind` from [`ENVELOPE_KINDS`]. Runs before TTL/version checks and
+/// independently of any schema validation, so malformed envelopes never reach policy or the WAL.
+#[allow(clippy::result_large_err)]
+pub fn validate_envelope_fields(env: &orca_v1::Envelope) -> Result<(), Status> {
+    for (name, value) in [("id", &env.id), ("agent", &env.agent), ("trace_id", &env.trace_id)] {
+        if value.trim().is_empty() {
+            return Err(Status::invalid_argument(format!("envelope {name} is required")));
+        }
+    }
+    if !ENVELOPE_KINDS.contains(&env.kind.as_str()) {
+        return Err(Status::invalid_argument(format!("unsupported envelope kind '{}'", env.kind)));
+    }
+    Ok(())
 }
 
 /// Service state.
@@ -52,29 +260,40 @@ pub struct OrchestratorService {
     policy: Arc<RwLock<PolicyEngine>>,
     budget: BudgetManager,
     budgets_by_run: std::sync::Arc<DashMap<String, BudgetManager>>, // per-run budgets
+    default_tokens_inc: u64, // tokens charged for a task that reports no usage
     metrics: BudgetMetrics,
+    auth: Option<Arc<TokenScopes>>, // per-RPC scopes; falls back to AGENT_AUTH_TOKEN when unset
+    strict_replay: bool,            // fail replay on unknown event kinds instead of warning
+    run_id_mode: RunIdMode,
+    active_runs: std::sync::Arc<DashSet<String>>, // started runs without a run_summary yet
+    max_active_runs: Option<usize>, // cap on active runs; also enables eviction of completed runs
+    grpc_gzip: bool, // accept gzip requests and gzip responses for clients that accept it
+    run_idle_timeout_ms: Option<u64>, // summarize and complete runs with no events for this long
+    clock_skew_tolerance_ms: Option<u64>, // bound on client/server clock skew for ts_ms and TTLs
+    fail_run_on_agent_error: bool, // end a run as failed on its first agent_error
+    reject_duplicate_tasks: bool, // answer resubmitted envelope ids with accepted=false, duplicate=true
+    last_activity_ms_by_run: std::sync::Arc<DashMap<String, u64>>, // open runs -> last event ts
+    completed_runs: Arc<Mutex<CompletedRuns>>, // most recent runs with a run_summary
+    index_snapshots: Option<(std::path::PathBuf, Duration)>, // periodic snapshot path + period
+    wal_checkpoint_every: Option<Duration>, // period of signed WAL chain checkpoints
+    policy_reload: Option<(std::path::PathBuf, Duration)>, // policy file re-read + period
+    wal_tee: Option<Arc<tee::WalTee>>, // best-effort mirror of every appended record
+    event_hook: Option<Arc<hook::HookForwarder>>, // embedder hook fed after each append
+    dispatcher: Arc<dyn dispatch::Dispatcher>, // routes accepted tasks to agent workers
+    result_blobs: Option<Arc<dyn results::BlobSource>>, // backs blob_ref results in FetchResultStream
+    live_events: tokio::sync::broadcast::Sender<EventRecord<JsonValue>>, // feeds follow-mode streams
 }
 
 #[allow(clippy::result_large_err)]
 impl OrchestratorService {
     pub fn new(log: JsonlEventLog) -> Self {
+        // Reproduction mode (ORCA_REPRO_SEED): timestamps come from a seeded virtual clock
+        clock::install_repro_clock();
         let policy = Arc::new(RwLock::new(PolicyEngine::new()));
-        // Optional policy autoload from env
-        if let Ok(path) = std::env::var("ORCA_POLICY_PATH") {
-            let _ = policy.write().unwrap().load_from_yaml_path(&path);
-            if let Ok(ms_str) = std::env::var("ORCA_POLICY_RELOAD_MS") {
-                if let Ok(ms) = ms_str.parse::<u64>() {
-                    if ms > 0 {
-                        let pol = policy.clone();
-                        tokio::spawn(async move {
-                            loop {
-                                sleep(Duration::from_millis(ms)).await;
-                                let _ = pol.write().unwrap().load_from_yaml_path(&path);
-                            }
-                        });
-                    }
-                }
-            }
+        // Optional policy autoload from env; periodic reloads run with the background tasks
+        let policy_path = std::env::var("ORCA_POLICY_PATH").ok();
+        if let Some(path) = &policy_path {
+            let _ = policy.write().unwrap().load_from_yaml_path(path);
         }
         Self {
             log,
@@ -84,34 +303,526 @@ impl OrchestratorService {
                 usage_by_run: std::sync::Arc::new(DashMap::new()),
                 usage_by_run_agent: std::sync::Arc::new(DashMap::new()),
                 run_start_ts_by_run: std::sync::Arc::new(DashMap::new()),
+                failed_tasks_by_run: std::sync::Arc::new(DashMap::new()),
             },
             policy,
             budget: BudgetManager::new(BudgetConfig::default()),
             budgets_by_run: std::sync::Arc::new(DashMap::new()),
+            default_tokens_inc: DEFAULT_TOKENS_INC,
             metrics: BudgetMetrics::new(),
+            // Optional per-RPC token scopes from env; an unreadable file fails closed (no tokens).
+            auth: std::env::var("ORCA_AUTH_TOKENS_PATH").ok().map(|p| {
+                Arc::new(TokenScopes::load_from_path(&p).unwrap_or_else(|e| {
+                    warn!(error = %e, "auth tokens file rejected; denying all tokens");
+                    TokenScopes::default()
+                }))
+            }),
+            strict_replay: std::env::var("ORCA_REPLAY_STRICT").ok().as_deref() == Some("1"),
+            run_id_mode: RunIdMode::default(),
+            active_runs: std::sync::Arc::new(DashSet::new()),
+            max_active_runs: std::env::var("ORCA_MAX_ACTIVE_RUNS")
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .filter(|n| *n > 0),
+            grpc_gzip: std::env::var("ORCA_GRPC_GZIP").ok().as_deref() == Some("1"),
+            run_idle_timeout_ms: std::env::var("ORCA_RUN_IDLE_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|ms| *ms > 0),
+            // ORCA_MAX_FUTURE_SKEW_MS is the older name, still honoured
+            clock_skew_tolerance_ms: std::env::var("ORCA_CLOCK_SKEW_TOLERANCE_MS")
+                .or_else(|_| std::env::var("ORCA_MAX_FUTURE_SKEW_MS"))
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok()),
+            fail_run_on_agent_error: std::env::var("ORCA_FAIL_RUN_ON_AGENT_ERROR").ok().as_deref()
+                == Some("1"),
+            reject_duplicate_tasks: std::env::var("ORCA_REJECT_DUPLICATE_TASKS").ok().as_deref()
+                == Some("1"),
+            last_activity_ms_by_run: std::sync::Arc::new(DashMap::new()),
+            completed_runs: Arc::new(Mutex::new(CompletedRuns {
+                runs: HashSet::new(),
+                failed: HashSet::new(),
+                order: VecDeque::new(),
+                cap: DEFAULT_COMPLETED_RUNS_CAP,
+            })),
+            // Optional periodic run-index snapshots for hot-standby failover
+            index_snapshots: match (
+                std::env::var("ORCA_INDEX_SNAPSHOT_PATH"),
+                std::env::var("ORCA_INDEX_SNAPSHOT_MS").ok().and_then(|s| s.parse::<u64>().ok()),
+            ) {
+                (Ok(path), Some(ms)) if ms > 0 => Some((path.into(), Duration::from_millis(ms))),
+                _ => None,
+            },
+            wal_checkpoint_every: None,
+            policy_reload: match (
+                policy_path,
+                std::env::var("ORCA_POLICY_RELOAD_MS").ok().and_then(|s| s.parse::<u64>().ok()),
+            ) {
+                (Some(path), Some(ms)) if ms > 0 => Some((path.into(), Duration::from_millis(ms))),
+                _ => None,
+            },
+            wal_tee: None,
+            event_hook: None,
+            dispatcher: Arc::new(dispatch::WalOnlyDispatcher),
+            result_blobs: None,
+            live_events: tokio::sync::broadcast::channel(LIVE_EVENTS_CAPACITY).0,
         }
     }
+    /// Start the configured background tasks on the current Tokio runtime: the idle-run
+    /// reaper (with an idle timeout; checks a few times per timeout window), periodic
+    /// index snapshots, periodic signed WAL checkpoints, and periodic policy reloads. The
+    /// tasks run until the returned guard is dropped.
+    ///
+    /// # Panics
+    /// Outside a Tokio runtime.
+    pub fn start_background_tasks(&self) -> BackgroundTasks {
+        let mut tasks = BackgroundTasks::default();
+        if let Some(ms) = self.run_idle_timeout_ms {
+            tasks.handles.push(self.spawn_idle_reaper(Duration::from_millis((ms / 4).max(10))));
+        }
+        if let Some((path, every)) = &self.index_snapshots {
+            tasks.handles.push(self.spawn_index_snapshots(path.clone(), *every));
+        }
+        if let Some(every) = self.wal_checkpoint_every {
+            tasks.handles.push(self.spawn_wal_checkpoints(every));
+        }
+        if let Some((path, every)) = &self.policy_reload {
+            tasks.handles.push(self.spawn_policy_reload(path.clone(), *every));
+        }
+        tasks
+    }
     pub fn with_budget(mut self, cfg: BudgetConfig) -> Self {
         self.budget = BudgetManager::new(cfg);
         self
     }
+    /// Tokens charged for a submitted task whose envelope reports no token usage (default
+    /// [`DEFAULT_TOKENS_INC`]). With 0, such tasks consume no token budget; use it when
+    /// every client reports real usage so tasks are not double-counted.
+    pub fn with_default_token_increment(mut self, tokens: u64) -> Self {
+        self.default_tokens_inc = tokens;
+        self
+    }
+    /// Enforce per-RPC token scopes instead of the single `AGENT_AUTH_TOKEN`.
+    pub fn with_auth_scopes(mut self, scopes: TokenScopes) -> Self {
+        self.auth = Some(Arc::new(scopes));
+        self
+    }
+    /// Make `replay_on_start` fail on event kinds outside `event_log::KNOWN_EVENT_KINDS`
+    /// (default: warn and continue).
+    pub fn with_strict_replay(mut self, strict: bool) -> Self {
+        self.strict_replay = strict;
+        self
+    }
+    /// Select how `start_run` assigns run ids (default: client-supplied `workflow_id`).
+    pub fn with_run_id_mode(mut self, mode: RunIdMode) -> Self {
+        self.run_id_mode = mode;
+        self
+    }
+    /// Cap the number of active runs (started, no `run_summary` yet); `start_run` beyond the
+    /// cap fails with RESOURCE_EXHAUSTED. With a cap set, a run's in-memory budget and index
+    /// entries are evicted once its `run_summary` is written (the summary is the durable
+    /// record), and tasks for runs that are not active are rejected.
+    pub fn with_max_active_runs(mut self, max: usize) -> Self {
+        self.max_active_runs = Some(max);
+        self
+    }
+    /// Run ids known to the index (started or replayed from the WAL), sorted by run id so
+    /// repeated calls and snapshot tests see a stable order. Walks the index shards one at a
+    /// time; no global lock is held.
+    pub fn list_runs(&self) -> Vec<String> {
+        let mut runs: Vec<String> =
+            self.index.run_start_ts_by_run.iter().map(|e| e.key().clone()).collect();
+        runs.sort_unstable();
+        runs
+    }
+    /// Number of runs currently counted against the active-run cap.
+    pub fn active_run_count(&self) -> usize {
+        self.active_runs.len()
+    }
+    /// Enable gzip on the gRPC server built by [`Self::into_server`]: compressed requests are
+    /// accepted, and responses are compressed for clients that advertise gzip. Default off
+    /// (`ORCA_GRPC_GZIP=1` enables it).
+    pub fn with_grpc_gzip(mut self, enabled: bool) -> Self {
+        self.grpc_gzip = enabled;
+        self
+    }
+    /// Complete runs that have seen no new events for `idle` (default off;
+    /// `ORCA_RUN_IDLE_TIMEOUT_MS` enables it). [`Self::start_background_tasks`] runs the
+    /// reaper. Idleness is
+    /// measured on the process [`clock`], so [`Self::reap_idle_runs`] is deterministic under a
+    /// `VirtualClock`.
+    pub fn with_run_idle_timeout(mut self, idle: Duration) -> Self {
+        self.run_idle_timeout_ms = Some(idle.as_millis() as u64).filter(|ms| *ms > 0);
+        self
+    }
+    /// Tolerate client clocks up to `skew` off the process [`clock`]: envelopes whose `ts_ms`
+    /// is more than `skew` ahead are rejected with `INVALID_ARGUMENT` ("timestamp skew"), so a
+    /// clock running ahead cannot dodge TTL expiry, and a TTL only expires once `timeout_ms +
+    /// skew` has passed since `ts_ms`, so a clock running behind does not expire fresh tasks.
+    /// Default off (`ORCA_CLOCK_SKEW_TOLERANCE_MS` sets it).
+    pub fn with_clock_skew_tolerance(mut self, skew: Duration) -> Self {
+        self.clock_skew_tolerance_ms = Some(skew.as_millis() as u64);
+        self
+    }
+    /// End a run on its first `agent_error`: write its `run_summary` with `state: "failed"`
+    /// and complete it (see [`Self::is_run_failed`]). Default off
+    /// (`ORCA_FAIL_RUN_ON_AGENT_ERROR=1` enables it): errors are only counted in `task_failed`
+    /// and the summary's `failed_tasks`.
+    pub fn with_fail_run_on_agent_error(mut self, enabled: bool) -> Self {
+        self.fail_run_on_agent_error = enabled;
+        self
+    }
+    /// Answer a `submit_task` whose envelope id was already seen with `accepted: false,
+    /// duplicate: true`, so clients notice accidental resubmission. Default off
+    /// (`ORCA_REJECT_DUPLICATE_TASKS=1` enables it): duplicates are skipped and reported as
+    /// accepted, for idempotent retries.
+    pub fn with_reject_duplicate_tasks(mut self, enabled: bool) -> Self {
+        self.reject_duplicate_tasks = enabled;
+        self
+    }
+    /// Write run-index snapshots to `path` every `every` once
+    /// [`Self::start_background_tasks`] runs (`ORCA_INDEX_SNAPSHOT_PATH` +
+    /// `ORCA_INDEX_SNAPSHOT_MS` configure the same).
+    pub fn with_index_snapshots(
+        mut self,
+        path: impl Into<std::path::PathBuf>,
+        every: Duration,
+    ) -> Self {
+        self.index_snapshots = Some((path.into(), every)).filter(|(_, d)| !d.is_zero());
+        self
+    }
+    /// Re-load the policy from `path` every `every` once [`Self::start_background_tasks`]
+    /// runs (`ORCA_POLICY_PATH` + `ORCA_POLICY_RELOAD_MS` configure the same). Periods are
+    /// measured on the process clock; a failed load keeps the current policy.
+    pub fn with_policy_reload(
+        mut self,
+        path: impl Into<std::path::PathBuf>,
+        every: Duration,
+    ) -> Self {
+        self.policy_reload = Some((path.into(), every)).filter(|(_, d)| !d.is_zero());
+        self
+    }
+    /// Write a signed chain checkpoint for the WAL every `every` once
+    /// [`Self::start_background_tasks`] runs. The log must have been opened with
+    /// `with_hash_chain` and `with_checkpoint_key`; otherwise each attempt logs a warning.
+    /// Rotation seals each segment with its own final checkpoint regardless of this period.
+    pub fn with_wal_checkpoints(mut self, every: Duration) -> Self {
+        self.wal_checkpoint_every = Some(every).filter(|d| !d.is_zero());
+        self
+    }
+    /// Remember at most `cap` completed runs (default [`DEFAULT_COMPLETED_RUNS_CAP`]); the
+    /// oldest are forgotten first, so a long-lived server or a long WAL replay stays bounded.
+    pub fn with_completed_runs_cap(self, cap: usize) -> Self {
+        {
+            let mut completed = self.completed_runs.lock().unwrap();
+            completed.cap = cap.max(1);
+            completed.trim();
+        }
+        self
+    }
+    /// Whether `run_id` has a `run_summary` (explicit end, budget exhaustion, or idle
+    /// reaping), among the most recently completed runs kept under the cap.
+    pub fn is_run_completed(&self, run_id: &str) -> bool {
+        self.completed_runs.lock().unwrap().runs.contains(run_id)
+    }
+    /// Whether `run_id` was completed as failed by an `agent_error` (see
+    /// [`Self::with_fail_run_on_agent_error`]), under the same cap as completed runs.
+    pub fn is_run_failed(&self, run_id: &str) -> bool {
+        self.completed_runs.lock().unwrap().failed.contains(run_id)
+    }
+    /// Blob store used by `FetchResultStream` to read results whose payload is a `blob_ref`.
+    pub fn with_result_blobs(mut self, blobs: Arc<dyn results::BlobSource>) -> Self {
+        self.result_blobs = Some(blobs);
+        self
+    }
+    /// Mirror every record appended to the WAL to the tee's sinks, after the primary append
+    /// succeeds. Sink failures are logged and never fail or delay the primary write.
+    pub fn with_wal_tee(mut self, tee: tee::WalTee) -> Self {
+        self.wal_tee = Some(Arc::new(tee));
+        self
+    }
+    /// Call `hook` with every record appended to the WAL, in append order, from a background
+    /// thread fed by a queue of `capacity` records. Appends never block on the hook; records
+    /// that do not fit are dropped for the hook (see [`Self::dropped_hook_events`]).
+    pub fn with_event_hook(self, hook: Arc<dyn hook::EventHook>, capacity: usize) -> Self {
+        self.with_event_hook_overflow(hook, capacity, hook::HookOverflow::Drop)
+    }
+    /// Like [`Self::with_event_hook`], choosing what happens when the queue is full:
+    /// [`hook::HookOverflow::Block`] makes appends wait for the hook instead of dropping.
+    pub fn with_event_hook_overflow(
+        mut self,
+        hook: Arc<dyn hook::EventHook>,
+        capacity: usize,
+        overflow: hook::HookOverflow,
+    ) -> Self {
+        self.event_hook =
+            Some(Arc::new(hook::HookForwarder::spawn_with_overflow(hook, capacity, overflow)));
+        self
+    }
+    /// Records the event hook missed because its queue was full (0 without a hook).
+    pub fn dropped_hook_events(&self) -> u64 {
+        self.event_hook.as_ref().map_or(0, |h| h.dropped())
+    }
+    /// Route accepted tasks to workers through `dispatcher` (default
+    /// [`dispatch::WalOnlyDispatcher`]: record only). Each delivered task gets a
+    /// `task_dispatched` event after its `task_enqueued`.
+    pub fn with_dispatcher(mut self, dispatcher: Arc<dyn dispatch::Dispatcher>) -> Self {
+        self.dispatcher = dispatcher;
+        self
+    }
     pub fn into_server(self) -> OrchestratorServer<Self> {
-        OrchestratorServer::new(self)
+        let gzip = self.grpc_gzip;
+        let server = OrchestratorServer::new(self);
+        if gzip {
+            server
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+                .send_compressed(tonic::codec::CompressionEncoding::Gzip)
+        } else {
+            server
+        }
     }
 
     pub fn replay_on_start(&self) -> Result<(), Status> {
-        let recs: Vec<EventRecord<JsonValue>> =
-            self.log.read_range(0, u64::MAX).map_err(internal_io)?;
+        self.replay_records(self.log.iter_range(0, u64::MAX).map_err(internal_io)?, None)
+    }
+
+    /// Restore the run index from a snapshot written by [`Self::write_index_snapshot`],
+    /// then replay only the WAL records with an event id above its `last_wal_event_id`.
+    /// Dedup ids of envelopes seen only before the snapshot are not part of the index and
+    /// are not restored.
+    pub fn replay_from_snapshot<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Status> {
+        let bytes = std::fs::read(path).map_err(|e| internal_io(e.into()))?;
+        let snap: SerializableIndex = serde_json::from_slice(&bytes).map_err(internal_serde)?;
+        let covered = snap.last_wal_event_id;
+        self.index.restore(snap);
+        self.replay_records(self.log.iter_range(0, u64::MAX).map_err(internal_io)?, Some(covered))
+    }
+
+    /// Snapshot the run index together with the highest WAL event id it covers.
+    pub fn snapshot_index(&self) -> Result<SerializableIndex, Status> {
+        // Read first, snapshot after: the index then reflects at least every covered record,
+        // and anything appended meanwhile is re-applied on tail replay, which is idempotent
+        // for the index. Records are streamed, so memory does not grow with the WAL.
+        let mut last_wal_event_id = 0;
+        let mut last_event_id_by_run = BTreeMap::new();
+        for rec in self.log.iter_range::<JsonValue>(0, u64::MAX).map_err(internal_io)? {
+            let rec = rec.map_err(internal_io)?;
+            last_wal_event_id = rec.id.max(last_wal_event_id);
+            // Live handlers do not track last event ids (replay does); derive them from the
+            // covered records so the snapshot matches a replay of the same WAL.
+            if let Some(run) = event_run_id(&rec.payload) {
+                let summary =
+                    rec.payload.get("event").and_then(|v| v.as_str()) == Some("run_summary");
+                if summary && self.max_active_runs.is_some() {
+                    last_event_id_by_run.remove(&run); // evicted, as in replay
+                } else {
+                    let last = last_event_id_by_run.entry(run).or_insert(rec.id);
+                    *last = (*last).max(rec.id);
+                }
+            }
+        }
+        let mut snap = self.index.snapshot();
+        snap.last_wal_event_id = last_wal_event_id;
+        snap.last_event_id_by_run = last_event_id_by_run;
+        Ok(snap)
+    }
+
+    /// Atomically and durably write [`Self::snapshot_index`] as JSON to `path`: the temp
+    /// file is fsynced before the rename and the directory after it, so a crash leaves
+    /// either the old snapshot or the complete new one.
+    pub fn write_index_snapshot<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Status> {
+        use std::io::Write as _;
+        let path = path.as_ref();
+        let snap = self.snapshot_index()?;
+        let bytes = serde_json::to_vec(&snap).map_err(internal_serde)?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let io = |e: std::io::Error| internal_io(e.into());
+        let mut f = std::fs::File::create(&tmp).map_err(io)?;
+        f.write_all(&bytes).map_err(io)?;
+        f.sync_all().map_err(io)?;
+        drop(f);
+        std::fs::rename(&tmp, path).map_err(io)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if let Ok(dir) = std::fs::File::open(parent) {
+                let _ = dir.sync_all();
+            }
+        }
+        Ok(())
+    }
+
+    /// Write an index snapshot to `path` every `every` until the task is aborted.
+    pub fn spawn_index_snapshots(
+        &self,
+        path: std::path::PathBuf,
+        every: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let svc = self.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(every).await;
+                if let Err(e) = svc.write_index_snapshot(&path) {
+                    warn!(error = %e, "index snapshot failed");
+                }
+            }
+        })
+    }
+
+    /// Write a signed WAL checkpoint every `every` until the task is aborted.
+    pub fn spawn_wal_checkpoints(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let log = self.log.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(every).await;
+                if let Err(e) = log.write_checkpoint() {
+                    warn!(error = %e, "WAL checkpoint failed");
+                }
+            }
+        })
+    }
+
+    /// Re-load the policy from `path` each time `every` has passed on the process clock,
+    /// until the task is aborted. The clock is polled at most every 100ms, so a virtual
+    /// clock drives reloads as soon as it is advanced.
+    pub fn spawn_policy_reload(
+        &self,
+        path: std::path::PathBuf,
+        every: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let policy = self.policy.clone();
+        let every_ms = every.as_millis() as u64;
+        let poll = every.clamp(Duration::from_millis(1), Duration::from_millis(100));
+        tokio::spawn(async move {
+            let mut due = crate::clock::process_clock().now_ms().saturating_add(every_ms);
+            loop {
+                sleep(poll).await;
+                let now = crate::clock::process_clock().now_ms();
+                if now < due {
+                    continue;
+                }
+                due = now.saturating_add(every_ms);
+                if let Err(e) = policy.write().unwrap().load_from_yaml_path(&path) {
+                    warn!(error = %e, path = %path.display(), "policy reload failed");
+                }
+            }
+        })
+    }
+
+    /// Summarize and complete every open run whose last event is at least the idle timeout
+    /// old on the process clock. Returns the reaped run ids, sorted. No-op when no idle
+    /// timeout is configured.
+    pub fn reap_idle_runs(&self) -> Result<Vec<String>, Status> {
+        let Some(idle_ms) = self.run_idle_timeout_ms else {
+            return Ok(Vec::new());
+        };
+        let now = crate::clock::process_clock().now_ms();
+        let mut idle: Vec<String> = self
+            .last_activity_ms_by_run
+            .iter()
+            .filter(|kv| now.saturating_sub(*kv.value()) >= idle_ms)
+            .map(|kv| kv.key().clone())
+            .collect();
+        idle.sort();
+        for run_id in &idle {
+            let mgr = self
+                .budgets_by_run
+                .get(run_id)
+                .map(|m| m.value().clone())
+                .unwrap_or_else(|| self.budget.clone());
+            self.append_run_summary(run_id, &mgr, false)?;
+            self.evict_run(run_id);
+            info!(run=%run_id, idle_ms, "idle run completed");
+        }
+        Ok(idle)
+    }
+
+    /// Run [`Self::reap_idle_runs`] every `every` until the task is aborted.
+    pub fn spawn_idle_reaper(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let svc = self.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(every).await;
+                if let Err(e) = svc.reap_idle_runs() {
+                    warn!(error = %e, "idle run reaping failed");
+                }
+            }
+        })
+    }
+
+    /// Record an event for `run_id` at `ts_ms` for idle tracking; completed runs stay closed.
+    fn touch_run(&self, run_id: &str, ts_ms: u64) {
+        if !self.is_run_completed(run_id) {
+            self.last_activity_ms_by_run.insert(run_id.to_string(), ts_ms);
+        }
+    }
+
+    /// Apply WAL records to the index as they stream in, so replay memory does not grow with
+    /// the WAL. Records at or below `covered` (already in a restored snapshot) only advance
+    /// the id generator.
+    fn replay_records(
+        &self,
+        recs: impl IntoIterator<Item = Result<EventRecord<JsonValue>, EventLogError>>,
+        covered: Option<u64>,
+    ) -> Result<(), Status> {
+        // Per run: usage already charged to its budget manager during this replay.
+        let mut charged: HashMap<String, (u64, u64)> = HashMap::new();
         for rec in recs {
+            let rec = rec.map_err(internal_io)?;
+            // Ids may have gaps (compaction) or arrive out of order; continue past the highest.
+            orca_core::ids::advance_monotonic_id_past(rec.id);
+            if covered.is_some_and(|c| rec.id <= c) {
+                continue;
+            }
             let p = rec.payload;
-            if let Some(run) =
-                p.get("run_id").and_then(|v| v.as_str()).map(|s| s.to_string()).or_else(|| {
-                    p.get("workflow_id").and_then(|v| v.as_str()).map(|s| s.to_string())
-                })
-            {
-                self.index.last_event_id_by_run.insert(run.clone(), rec.id);
-                if p.get("event").and_then(|v| v.as_str()) == Some("start_run") {
-                    self.index.run_start_ts_by_run.insert(run, rec.ts_ms);
+            if let Some(kind) = p.get("event").and_then(|v| v.as_str()) {
+                if !event_log::is_known_event_kind(kind) {
+                    if self.strict_replay {
+                        return Err(Status::failed_precondition(format!(
+                            "replay: unknown event kind '{}' at id {}",
+                            kind, rec.id
+                        )));
+                    }
+                    warn!(id = rec.id, kind, "replay: unknown event kind");
+                }
+            }
+            if let Some(run) = event_run_id(&p) {
+                self.index.advance_last_event_id(&run, rec.id);
+                match p.get("event").and_then(|v| v.as_str()) {
+                    Some("start_run") => {
+                        if self.max_active_runs.is_some() {
+                            self.active_runs.insert(run.clone());
+                        }
+                        if let Some(cfg) = p
+                            .get("budget")
+                            .and_then(|b| serde_json::from_value::<BudgetConfig>(b.clone()).ok())
+                        {
+                            self.budgets_by_run.insert(run.clone(), BudgetManager::new(cfg));
+                        }
+                        self.touch_run(&run, rec.ts_ms);
+                        self.index.run_start_ts_by_run.insert(run, rec.ts_ms);
+                    }
+                    Some("usage_update") => {
+                        self.replay_usage(&run, &p, &mut charged);
+                        self.touch_run(&run, rec.ts_ms);
+                    }
+                    Some("budget_state_changed") => {
+                        self.replay_budget_state(&run, &p, &mut charged);
+                        self.touch_run(&run, rec.ts_ms);
+                    }
+                    Some("task_failed") => {
+                        *self.index.failed_tasks_by_run.entry(run.clone()).or_insert(0) += 1;
+                        self.touch_run(&run, rec.ts_ms);
+                    }
+                    Some("run_summary") => {
+                        let failed = p.get("state").and_then(|v| v.as_str()) == Some("failed");
+                        self.mark_run_completed(&run, failed);
+                        self.evict_run(&run);
+                    }
+                    Some("run_cancelled") => {
+                        self.mark_run_completed(&run, false);
+                        self.evict_run(&run);
+                    }
+                    _ => self.touch_run(&run, rec.ts_ms),
                 }
             }
             if let Some(env) = p.get("envelope").and_then(|v| v.get("id")).and_then(|v| v.as_str())
@@ -122,6 +833,60 @@ impl OrchestratorService {
         Ok(())
     }
 
+    /// Apply a `usage_update` (cumulative run totals) during replay: the increase over the
+    /// run's previous totals goes to its agent breakdown, and the increase over what replay
+    /// already `charged` for the run goes to the budget manager that enforced it (the run's
+    /// own, else the global one), as the live path does.
+    fn replay_usage(&self, run: &str, p: &JsonValue, charged: &mut HashMap<String, (u64, u64)>) {
+        let total = |k: &str| p.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+        let (tokens, cost) = (total("tokens"), total("cost_micros"));
+        let (prev_t, prev_c) =
+            self.index.usage_by_run.insert(run.to_string(), (tokens, cost)).unwrap_or((0, 0));
+        if let Some(agent) = p.get("agent").and_then(|v| v.as_str()) {
+            let mut entry = self
+                .index
+                .usage_by_run_agent
+                .entry((run.to_string(), agent.to_string()))
+                .or_insert((0, 0));
+            entry.0 = entry.0.saturating_add(tokens.saturating_sub(prev_t));
+            entry.1 = entry.1.saturating_add(cost.saturating_sub(prev_c));
+        }
+        let seen = charged.entry(run.to_string()).or_insert((prev_t, prev_c));
+        let (dt, dc) = (tokens.saturating_sub(seen.0), cost.saturating_sub(seen.1));
+        *seen = (seen.0.max(tokens), seen.1.max(cost));
+        match self.budgets_by_run.get(run) {
+            Some(mgr) => mgr.add_usage(dt, dc),
+            None => self.budget.add_usage(dt, dc),
+        }
+    }
+
+    /// Bring the enforcing budget manager up to the counters recorded in a
+    /// `budget_state_changed` event. The live path charges a task before its
+    /// `usage_update` (and a task that crosses into `Exceeded` never gets one), so the
+    /// catch-up is credited to the run in `charged` rather than counted again.
+    fn replay_budget_state(
+        &self,
+        run: &str,
+        p: &JsonValue,
+        charged: &mut HashMap<String, (u64, u64)>,
+    ) {
+        let recorded = |k: &str| p.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+        let catch_up = |mgr: &BudgetManager| {
+            let (t, c) = mgr.counters().snapshot();
+            let (dt, dc) =
+                (recorded("tokens").saturating_sub(t), recorded("cost_micros").saturating_sub(c));
+            mgr.add_usage(dt, dc);
+            (dt, dc)
+        };
+        let (dt, dc) = match self.budgets_by_run.get(run) {
+            Some(mgr) => catch_up(mgr.value()),
+            None => catch_up(&self.budget),
+        };
+        let prev = self.index.usage_by_run.get(run).map(|v| *v.value()).unwrap_or((0, 0));
+        let seen = charged.entry(run.to_string()).or_insert(prev);
+        *seen = (seen.0.saturating_add(dt), seen.1.saturating_add(dc));
+    }
+
     async fn retry<F, Fut, T>(&self, mut f: F, attempts: u32, delay_ms: u64) -> Result<T, Status>
     where
         F: FnMut() -> Fut,
@@ -143,11 +908,17 @@ impl OrchestratorService {
     }
 
     fn reject_if_expired_or_version(&self, env: &orca_v1::Envelope) -> Result<(), Status> {
-        if env.timeout_ms > 0 {
-            let now = crate::clock::process_clock().now_ms();
-            if now.saturating_sub(env.ts_ms) > env.timeout_ms {
-                return Err(Status::deadline_exceeded("ttl expired"));
-            }
+        validate_envelope_fields(env)?;
+        let now = crate::clock::process_clock().now_ms();
+        let skew = self.clock_skew_tolerance_ms;
+        if skew.is_some_and(|skew| env.ts_ms > now.saturating_add(skew)) {
+            return Err(Status::invalid_argument("timestamp skew"));
+        }
+        // A ts_ms ahead of now (within tolerance) has age 0; a client running behind gets the
+        // tolerance as grace before its TTL counts as expired.
+        let ttl = env.timeout_ms.saturating_add(skew.unwrap_or(0));
+        if env.timeout_ms > 0 && now.saturating_sub(env.ts_ms) > ttl {
+            return Err(Status::deadline_exceeded("ttl expired"));
         }
         if env.protocol_version != 1 {
             return Err(Status::failed_precondition("unsupported protocol_version"));
@@ -155,7 +926,11 @@ impl OrchestratorService {
         Ok(())
     }
 
-    fn check_auth(md: &tonic::metadata::MetadataMap) -> Result<(), Status> {
+    fn check_auth(&self, md: &tonic::metadata::MetadataMap, scope: Scope) -> Result<(), Status> {
+        if let Some(scopes) = &self.auth {
+            let got = md.get("authorization").and_then(|v| v.to_str().ok());
+            return scopes.authorize(got, scope);
+        }
         if let Ok(Some(required)) =
             std::env::var("AGENT_AUTH_TOKEN").map(|s| if s.is_empty() { None } else { Some(s) })
         {
@@ -168,6 +943,38 @@ impl OrchestratorService {
         }
     }
 
+    /// Append to the primary WAL, then forward the record to the tee and event hook (if any).
+    fn append_event<T: serde::Serialize>(
+        &self,
+        id: event_log::EventId,
+        ts_ms: u64,
+        payload: &T,
+    ) -> Result<event_log::EventId, EventLogError> {
+        // Held across append and enqueue so concurrent appenders reach the hook in WAL order.
+        let _hook_order = self.event_hook.as_ref().map(|h| h.order_guard());
+        let appended = self.log.append(id, ts_ms, payload)?;
+        let following = self.live_events.receiver_count() > 0;
+        if self.wal_tee.is_some() || self.event_hook.is_some() || following {
+            match serde_json::to_value(payload) {
+                Ok(payload) => {
+                    let rec = EventRecord { id, ts_ms, payload };
+                    if let Some(tee) = &self.wal_tee {
+                        tee.forward(&rec);
+                    }
+                    if following {
+                        // No receivers left is fine: followers come and go.
+                        let _ = self.live_events.send(rec.clone());
+                    }
+                    if let Some(hook) = &self.event_hook {
+                        hook.send(rec);
+                    }
+                }
+                Err(e) => warn!(id, error = %e, "wal mirror: payload not representable as JSON"),
+            }
+        }
+        Ok(appended)
+    }
+
     fn redact_event_payload(&self, mut payload: JsonValue) -> JsonValue {
         // If event carries an "envelope" object, apply policy redaction to it
         if let Some(env) = payload.get("envelope").cloned() {
@@ -195,42 +1002,222 @@ impl OrchestratorService {
             .map_err(|e| Status::internal(format!("policy load failed: {}", e)))
     }
 
-    /// Extract attachments array from an Envelope JSON object, if a BlobRef is present.
-    fn extract_attachments_from_env(&self, env: &JsonValue) -> Option<JsonValue> {
-        env.get("payload_json")
-            .and_then(|v| v.as_str())
-            .and_then(|s| self.extract_attachments_from_payload(s))
+    /// Append a supply-chain audit event for a plugin load: `plugin_verified` when the
+    /// report verified, otherwise `plugin_verify_failed` with its stable `error_code`.
+    /// Pass the report from `ManifestVerifier::verify_detailed*` straight through.
+    #[allow(clippy::result_large_err)]
+    pub fn record_plugin_verification(
+        &self,
+        report: &plugin_host::VerificationReport,
+    ) -> Result<(), Status> {
+        let event = if report.is_verified() { "plugin_verified" } else { "plugin_verify_failed" };
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            crate::clock::process_clock().now_ms(),
+            &json!({
+                "event": event, "name": report.name, "digest": report.digest,
+                "signer": report.signer, "error_code": report.error_code(),
+            }),
+        )
+        .map_err(internal_io)?;
+        Ok(())
+    }
+
+    /// Hand an accepted task to the dispatcher and record `task_dispatched` when it was
+    /// delivered. A failed hand-off is logged only: the task is already durable in the WAL.
+    #[allow(clippy::result_large_err)]
+    fn dispatch_task(&self, run_id: &str, env: &orca_v1::Envelope) -> Result<(), Status> {
+        let name = self.dispatcher.name();
+        match self.dispatcher.dispatch(run_id, env) {
+            Ok(dispatch::DispatchOutcome::Delivered) => {
+                self.append_event(
+                    orca_core::ids::next_monotonic_id(),
+                    crate::clock::process_clock().now_ms(),
+                    &json!({
+                        "event": "task_dispatched", "run_id": run_id, "task_id": env.id,
+                        "agent": env.agent, "dispatcher": name,
+                    }),
+                )
+                .map_err(internal_io)?;
+            }
+            Ok(dispatch::DispatchOutcome::RecordedOnly) => {}
+            Err(e) => {
+                warn!(run=%run_id, task=%env.id, dispatcher=name, error=%e, "task dispatch failed")
+            }
+        }
+        Ok(())
+    }
+
+    /// Count an `agent_error` against its run and append `task_failed` with the error's
+    /// `code` and `retryable` flag (the message stays in the redacted `task_enqueued`). With
+    /// [`Self::with_fail_run_on_agent_error`], the run then ends as failed.
+    #[allow(clippy::result_large_err)]
+    fn record_task_failure(&self, run_id: &str, env: &orca_v1::Envelope) -> Result<(), Status> {
+        let failed_tasks = {
+            let mut n = self.index.failed_tasks_by_run.entry(run_id.to_string()).or_insert(0);
+            *n += 1;
+            *n
+        };
+        let (code, retryable) = match env.payload() {
+            Ok(orca_core::payload::Payload::Error(e)) => (e.code, e.retryable),
+            _ => (None, false),
+        };
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            crate::clock::process_clock().now_ms(),
+            &json!({
+                "event": "task_failed", "run_id": run_id, "task_id": env.id, "agent": env.agent,
+                "code": code, "retryable": retryable, "failed_tasks": failed_tasks,
+            }),
+        )
+        .map_err(internal_io)?;
+        if self.fail_run_on_agent_error && !self.is_run_completed(run_id) {
+            let mgr = self
+                .budgets_by_run
+                .get(run_id)
+                .map(|m| m.value().clone())
+                .unwrap_or_else(|| self.budget.clone());
+            self.append_run_summary(run_id, &mgr, true)?;
+            self.evict_run(run_id);
+        }
+        Ok(())
+    }
+
+    /// Append `budget_state_changed` when `mgr` moved from `from` to a different state; the
+    /// sequence of these events is a run's budget trajectory, one record per transition.
+    #[allow(clippy::result_large_err)]
+    fn append_budget_transition(
+        &self,
+        run_id: &str,
+        from: BudgetState,
+        mgr: &BudgetManager,
+    ) -> Result<(), Status> {
+        let to = mgr.status();
+        if to == from {
+            return Ok(());
+        }
+        let (tokens, cost_micros) = mgr.counters().snapshot();
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            crate::clock::process_clock().now_ms(),
+            &json!({
+                "event": "budget_state_changed", "run_id": run_id, "from": from, "to": to,
+                "tokens": tokens, "cost_micros": cost_micros,
+            }),
+        )
+        .map_err(internal_io)?;
+        Ok(())
+    }
+
+    /// Append `budget_soft_exceeded` when this task's usage pushed `mgr` past a soft limit, and
+    /// report whether the run is now past one (the task should be flagged).
+    #[allow(clippy::result_large_err)]
+    fn append_budget_soft_exceeded(
+        &self,
+        run_id: &str,
+        was_soft_exceeded: bool,
+        mgr: &BudgetManager,
+    ) -> Result<bool, Status> {
+        if !mgr.soft_exceeded() {
+            return Ok(false);
+        }
+        if !was_soft_exceeded {
+            let cfg = mgr.config();
+            let (tokens, cost_micros) = mgr.counters().snapshot();
+            self.append_event(
+                orca_core::ids::next_monotonic_id(),
+                crate::clock::process_clock().now_ms(),
+                &json!({
+                    "event": "budget_soft_exceeded", "run_id": run_id,
+                    "tokens": tokens, "cost_micros": cost_micros,
+                    "soft_max_tokens": cfg.soft_max_tokens,
+                    "soft_max_cost_micros": cfg.soft_max_cost_micros,
+                }),
+            )
+            .map_err(internal_io)?;
+            warn!(run=%run_id, "budget past soft limit; flagging tasks");
+        }
+        Ok(true)
+    }
+
+    /// Append a `run_summary` with usage totals, per-agent breakdown, and the final budget
+    /// state/remaining from `mgr` (the run's manager, or the global one), and mark the run
+    /// completed.
+    #[allow(clippy::result_large_err)]
+    fn append_run_summary(
+        &self,
+        run_id: &str,
+        mgr: &BudgetManager,
+        failed: bool,
+    ) -> Result<(), Status> {
+        let (t, c) = self.index.usage_by_run.get(run_id).map(|v| *v.value()).unwrap_or((0, 0));
+        // Build per-agent breakdown
+        let mut breakdown: Vec<JsonValue> = Vec::new();
+        for kv in self.index.usage_by_run_agent.iter() {
+            let ((run, agent), (at, ac)) = kv.pair();
+            if run == run_id {
+                breakdown.push(json!({"agent": agent, "tokens": at, "cost_micros": ac }));
+            }
+        }
+        // DashMap iteration order is unspecified; sort so the summary is byte-stable.
+        breakdown.sort_by(|a, b| a["agent"].as_str().cmp(&b["agent"].as_str()));
+        let (rem_tokens, rem_cost) = mgr.remaining();
+        let failed_tasks =
+            self.index.failed_tasks_by_run.get(run_id).map(|v| *v.value()).unwrap_or(0);
+        let now = crate::clock::process_clock().now_ms();
+        let duration_ms = self
+            .index
+            .run_start_ts_by_run
+            .get(run_id)
+            .map(|v| now.saturating_sub(*v.value()))
+            .unwrap_or(0);
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            now,
+            &json!({
+                "event":"run_summary", "run_id": run_id, "tokens": t, "cost_micros": c,
+                "by_agent": breakdown, "duration_ms": duration_ms,
+                "budget_state": mgr.status(),
+                "remaining": {"tokens": rem_tokens, "cost_micros": rem_cost},
+                "failed_tasks": failed_tasks,
+                "state": if failed { "failed" } else { "completed" },
+            }),
+        )
+        .map_err(internal_io)?;
+        self.mark_run_completed(run_id, failed);
+        Ok(())
+    }
+
+    fn mark_run_completed(&self, run_id: &str, failed: bool) {
+        self.completed_runs.lock().unwrap().insert(run_id, failed);
+        self.last_activity_ms_by_run.remove(run_id);
+    }
+
+    /// Mark `run_id` completed: with an active-run cap configured, drop its per-run budget
+    /// and index entries. No-op without a cap.
+    fn evict_run(&self, run_id: &str) {
+        if self.max_active_runs.is_none() {
+            return;
+        }
+        self.active_runs.remove(run_id);
+        self.budgets_by_run.remove(run_id);
+        self.index.usage_by_run.remove(run_id);
+        self.index.usage_by_run_agent.retain(|(run, _), _| run != run_id);
+        self.index.run_start_ts_by_run.remove(run_id);
+        self.index.last_event_id_by_run.remove(run_id);
+        self.index.failed_tasks_by_run.remove(run_id);
     }
 
     /// Extract attachments array (single element) from a payload_json string when it contains
     /// a `blob_ref` object. Only metadata is recorded (digest, size, mime, compression), never raw bytes.
-    /// Defaults: mime=application/octet-stream, compression="none".
     fn extract_attachments_from_payload(&self, payload_json_str: &str) -> Option<JsonValue> {
-        if let Ok(payload_v) = serde_json::from_str::<serde_json::Value>(payload_json_str) {
-            if let Some(obj) = payload_v.get("blob_ref").and_then(|v| v.as_object()) {
-                let digest = obj.get("digest_sha256").and_then(|v| v.as_str()).unwrap_or("");
-                let size = obj.get("size_bytes").and_then(|v| v.as_u64()).unwrap_or(0);
-                let mime =
-                    obj.get("mime").and_then(|v| v.as_str()).unwrap_or("application/octet-stream");
-                let mut att = serde_json::Map::new();
-                att.insert("digest_sha256".into(), serde_json::Value::String(digest.to_string()));
-                att.insert("size_bytes".into(), serde_json::Value::Number(size.into()));
-                att.insert("mime".into(), serde_json::Value::String(mime.to_string()));
-                att.insert("compression".into(), serde_json::Value::String("none".into()));
-                return Some(serde_json::Value::Array(vec![serde_json::Value::Object(att)]));
-            }
-        }
-        None
+        blob_ref_attachment(payload_json_str).and_then(|att| serde_json::to_value(vec![att]).ok())
     }
 
     /// Append a policy audit record to the WAL.
     ///
-    /// Emits an audit event only when the policy intervenes (deny, modify, or allow_but_flag).
-    /// The `reason` field is sanitized via `redact_pii_reason()` before being recorded to avoid
-    /// leaking PII in durable logs. All attributes are low-cardinality to comply with observability rules.
-    ///
-    /// Event payload fields include: `event`, `phase`, `run_id`, `workflow_id`, `envelope_id`,
-    /// `agent`, `kind`, `trace_id`, `rule_name`, `action`, `reason`, and `outcome`.
+    /// Emits an audit event only when the policy intervenes (deny, modify, or allow_but_flag);
+    /// see [`PolicyAuditPayload`] for the recorded fields and their order.
     fn append_policy_audit(
         &self,
         phase: &str,
@@ -239,52 +1226,11 @@ impl OrchestratorService {
         env: &JsonValue,
         d: &policy::Decision,
     ) {
-        use policy::DecisionKind as DK;
-        let outcome = match d.kind {
-            DK::Deny => "denied",
-            DK::Modify => "modified",
-            DK::Allow => {
-                if matches!(d.action.as_deref(), Some("allow_but_flag")) {
-                    "allowed_flagged"
-                } else {
-                    "allowed"
-                }
-            }
-        };
-        // Only emit for deny/modify/allow_but_flag
-        let should_emit = matches!(d.kind, DK::Deny | DK::Modify)
-            || matches!(d.action.as_deref(), Some("allow_but_flag"));
-        if !should_emit {
+        let Some(evt) = PolicyAuditPayload::from_decision(phase, run_id, workflow_id, env, d)
+        else {
             return;
-        }
-
-        let envelope_id = env.get("id").and_then(|v| v.as_str());
-        let agent = env.get("agent").and_then(|v| v.as_str());
-        let kind = env.get("kind").and_then(|v| v.as_str());
-        let trace_id = env.get("trace_id").and_then(|v| v.as_str());
-
-        let sanitized_reason = d.reason.as_deref().map(redact_pii_reason);
-        // Optionally include attachments metadata if the envelope payload references a blob
-        let attachments_json: Option<serde_json::Value> = self.extract_attachments_from_env(env);
-
-        let mut evt = json!({
-            "event": "policy_audit",
-            "phase": phase,
-            "run_id": run_id,
-            "workflow_id": workflow_id,
-            "envelope_id": envelope_id,
-            "agent": agent,
-            "envelope_kind": kind,
-            "trace_id": trace_id,
-            "rule_name": d.rule_name,
-            "action": d.action,
-            "reason": sanitized_reason,
-            "outcome": outcome,
-        });
-        if let (Some(atts), Some(obj)) = (attachments_json, evt.as_object_mut()) {
-            obj.insert("attachments".into(), atts);
-        }
-        let _ = self.log.append(
+        };
+        let _ = self.append_event(
             orca_core::ids::next_monotonic_id(),
             crate::clock::process_clock().now_ms(),
             &evt,
@@ -292,6 +1238,123 @@ impl OrchestratorService {
     }
 }
 
+/// Attachment metadata for a payload_json string that contains a `blob_ref` object.
+/// Defaults: mime=application/octet-stream, compression="none".
+fn blob_ref_attachment(payload_json_str: &str) -> Option<event_log::v2::Attachment> {
+    let payload_v = serde_json::from_str::<JsonValue>(payload_json_str).ok()?;
+    let obj = payload_v.get("blob_ref").and_then(|v| v.as_object())?;
+    Some(event_log::v2::Attachment {
+        digest_sha256: obj.get("digest_sha256").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        size_bytes: obj.get("size_bytes").and_then(|v| v.as_u64()).unwrap_or(0),
+        mime: obj
+            .get("mime")
+            .and_then(|v| v.as_str())
+            .unwrap_or("application/octet-stream")
+            .to_string(),
+        encoding: None,
+        compression: event_log::v2::Compression::None,
+    })
+}
+
+/// SHA-256 of an envelope's `payload_json` string, or of the whole envelope JSON when it has
+/// none (e.g. a structured `payload`).
+fn payload_digest(env: &JsonValue) -> String {
+    match env.get("payload_json").and_then(|v| v.as_str()) {
+        Some(s) => crate::proxy::sha256_hex(s.as_bytes()),
+        None => crate::proxy::sha256_hex(env.to_string().as_bytes()),
+    }
+}
+
+/// Typed `policy_audit` WAL payload.
+///
+/// Fields serialize in declaration order and absent optionals are omitted rather than written
+/// as `null`, so the same decision on the same envelope always produces byte-identical JSON.
+/// The `reason` is sanitized via `redact_pii_reason()` to avoid leaking PII in durable logs.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PolicyAuditPayload {
+    /// Always `"policy_audit"`.
+    pub event: String,
+    /// Where the decision was taken: `pre_start_run`, `pre_submit_task` or `post_submit_task`.
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope_kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// `denied`, `modified` or `allowed_flagged`.
+    pub outcome: String,
+    /// SHA-256 of the envelope payload before redaction; set only when a `modified` decision
+    /// carries a rewritten envelope, so auditors can prove a change without the content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_redaction_sha256: Option<String>,
+    /// SHA-256 of the redacted envelope payload, alongside `pre_redaction_sha256`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_redaction_sha256: Option<String>,
+    /// Blob metadata when the envelope payload carries a `blob_ref`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<event_log::v2::Attachment>>,
+}
+
+impl PolicyAuditPayload {
+    /// Build the audit payload for decision `d` on envelope JSON `env`, or `None` when the
+    /// decision is a plain allow and nothing should be audited.
+    pub fn from_decision(
+        phase: &str,
+        run_id: Option<&str>,
+        workflow_id: Option<&str>,
+        env: &JsonValue,
+        d: &policy::Decision,
+    ) -> Option<Self> {
+        use policy::DecisionKind as DK;
+        let flagged = matches!(d.action.as_deref(), Some("allow_but_flag"));
+        let outcome = match d.kind {
+            DK::Deny => "denied",
+            DK::Modify => "modified",
+            DK::Allow if flagged => "allowed_flagged",
+            DK::Allow => return None,
+        };
+        let env_str = |k: &str| env.get(k).and_then(|v| v.as_str()).map(str::to_string);
+        let redacted = match d.kind {
+            DK::Modify => d.payload.as_ref(),
+            _ => None,
+        };
+        Some(Self {
+            event: "policy_audit".into(),
+            phase: phase.to_string(),
+            run_id: run_id.map(str::to_string),
+            workflow_id: workflow_id.map(str::to_string),
+            envelope_id: env_str("id"),
+            agent: env_str("agent"),
+            envelope_kind: env_str("kind"),
+            trace_id: env_str("trace_id"),
+            rule_name: d.rule_name.clone(),
+            action: d.action.clone(),
+            reason: d.reason.as_deref().map(redact_pii_reason),
+            outcome: outcome.into(),
+            pre_redaction_sha256: redacted.map(|_| payload_digest(env)),
+            post_redaction_sha256: redacted.map(payload_digest),
+            attachments: env
+                .get("payload_json")
+                .and_then(|v| v.as_str())
+                .and_then(blob_ref_attachment)
+                .map(|att| vec![att]),
+        })
+    }
+}
+
 /// Redact common PII patterns from an audit `reason` string.
 ///
 /// Currently replaces SSN-like substrings of the form `###-##-####` with `[REDACTED]`.
@@ -341,7 +1404,7 @@ impl Orchestrator for OrchestratorService {
         req: Request<StartRunRequest>,
     ) -> Result<Response<StartRunResponse>, Status> {
         let md = req.metadata().clone();
-        Self::check_auth(&md)?;
+        self.check_auth(&md, Scope::StartRun)?;
 
         // External I/O capture (server-side skeleton)
         let capture_on = crate::proxy::capture_enabled();
@@ -363,7 +1426,7 @@ impl Orchestrator for OrchestratorService {
                 "headers": serde_json::Value::Object(crate::proxy::redacted_headers(&md)),
                 "body_digest_sha256": crate::proxy::sha256_hex(&[]),
             });
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t0_ms, &started);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t0_ms, &started);
             let injected =
                 crate::proxy::fail_inject_enabled() || md.get("x-orca-capture-fail").is_some();
             if injected && !crate::proxy::bypass_to_direct() {
@@ -375,6 +1438,14 @@ impl Orchestrator for OrchestratorService {
         if let Some(ref env) = r.initial_task {
             self.reject_if_expired_or_version(env)?;
         }
+        // Derive the run id from the client's inputs before any policy rewrite.
+        let client_workflow_id = match self.run_id_mode {
+            RunIdMode::ClientSupplied => None,
+            RunIdMode::ContentAddressed => {
+                let id = content_addressed_run_id(&r.client_id, &r.nonce, r.initial_task.as_ref());
+                Some(std::mem::replace(&mut r.workflow_id, id))
+            }
+        };
         // Pre-policy: allow/deny/modify (redaction)
         if let Some(ref env) = r.initial_task {
             let _span = info_span!(
@@ -419,28 +1490,37 @@ impl Orchestrator for OrchestratorService {
                 DecisionKind::Allow => {}
             }
         }
+        if let Some(max) = self.max_active_runs {
+            if !self.active_runs.contains(&r.workflow_id) && self.active_runs.len() >= max {
+                return Err(Status::resource_exhausted("too many active runs"));
+            }
+            self.active_runs.insert(r.workflow_id.clone());
+        }
         // Optional per-run budget from request or environment defaults
-        if let Some(b) = r.budget.as_ref() {
+        let run_budget = if let Some(b) = r.budget.as_ref() {
+            let set = |v: u64| (v != 0).then_some(v);
+            Some(BudgetConfig {
+                max_tokens: set(b.max_tokens),
+                max_cost_micros: set(b.max_cost_micros),
+                soft_max_tokens: set(b.soft_max_tokens),
+                soft_max_cost_micros: set(b.soft_max_cost_micros),
+            })
+        } else {
+            let var = |k: &str| std::env::var(k).ok().and_then(|s| s.parse::<u64>().ok());
             let cfg = BudgetConfig {
-                max_tokens: if b.max_tokens == 0 { None } else { Some(b.max_tokens) },
-                max_cost_micros: if b.max_cost_micros == 0 {
-                    None
-                } else {
-                    Some(b.max_cost_micros)
-                },
+                max_tokens: var("ORCA_MAX_TOKENS"),
+                max_cost_micros: var("ORCA_MAX_COST_MICROS"),
+                soft_max_tokens: var("ORCA_SOFT_MAX_TOKENS"),
+                soft_max_cost_micros: var("ORCA_SOFT_MAX_COST_MICROS"),
             };
-            self.budgets_by_run.insert(r.workflow_id.clone(), BudgetManager::new(cfg));
-        } else {
-            let max_tokens =
-                std::env::var("ORCA_MAX_TOKENS").ok().and_then(|s| s.parse::<u64>().ok());
-            let max_cost =
-                std::env::var("ORCA_MAX_COST_MICROS").ok().and_then(|s| s.parse::<u64>().ok());
-            if max_tokens.is_some() || max_cost.is_some() {
-                self.budgets_by_run.insert(
-                    r.workflow_id.clone(),
-                    BudgetManager::new(BudgetConfig { max_tokens, max_cost_micros: max_cost }),
-                );
-            }
+            (cfg.max_tokens.is_some()
+                || cfg.max_cost_micros.is_some()
+                || cfg.soft_max_tokens.is_some()
+                || cfg.soft_max_cost_micros.is_some())
+            .then_some(cfg)
+        };
+        if let Some(cfg) = &run_budget {
+            self.budgets_by_run.insert(r.workflow_id.clone(), BudgetManager::new(cfg.clone()));
         }
         let wf = r.workflow_id.clone();
         self.retry(
@@ -448,12 +1528,21 @@ impl Orchestrator for OrchestratorService {
                 let _span = info_span!("wal.append", event="start_run", workflow=%wf).entered();
                 let now_ts = crate::clock::process_clock().now_ms();
                 self.index.run_start_ts_by_run.insert(wf.clone(), now_ts);
-                let evt = json!({
+                self.touch_run(&wf, now_ts);
+                let mut evt = json!({
                     "event":"start_run", "workflow_id": wf, "envelope": r.initial_task
                 });
+                if let (Some(cw), Some(obj)) = (&client_workflow_id, evt.as_object_mut()) {
+                    obj.insert("client_workflow_id".into(), json!(cw));
+                    obj.insert("client_id".into(), json!(r.client_id));
+                    obj.insert("nonce".into(), json!(r.nonce));
+                }
+                // Recorded so replay can rebuild the run's budget manager
+                if let (Some(cfg), Some(obj)) = (&run_budget, evt.as_object_mut()) {
+                    obj.insert("budget".into(), json!(cfg));
+                }
                 let evt = self.redact_event_payload(evt);
-                self.log
-                    .append(orca_core::ids::next_monotonic_id(), now_ts, &evt)
+                self.append_event(orca_core::ids::next_monotonic_id(), now_ts, &evt)
                     .map_err(internal_io)
             },
             3,
@@ -472,12 +1561,31 @@ impl Orchestrator for OrchestratorService {
                 "status": "ok",
                 "duration_ms": t1.saturating_sub(t0_ms),
             });
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t1, &finished);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t1, &finished);
             let metric = json!({"metric":"proxy.capture.emit.ms","value_ms":0});
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t1, &metric);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t1, &metric);
         }
 
-        Ok(Response::new(StartRunResponse { run_id: r.workflow_id }))
+        let effective = self
+            .budgets_by_run
+            .get(&r.workflow_id)
+            .map(|m| m.config())
+            .unwrap_or_else(|| self.budget.config());
+        let started_ts_ms =
+            self.index.run_start_ts_by_run.get(&r.workflow_id).map(|t| *t).unwrap_or_default();
+        let policy_version =
+            self.policy.read().unwrap().policy_version().unwrap_or_default().to_string();
+        Ok(Response::new(StartRunResponse {
+            run_id: r.workflow_id,
+            budget: Some(orca_v1::Budget {
+                max_tokens: effective.max_tokens.unwrap_or(0),
+                max_cost_micros: effective.max_cost_micros.unwrap_or(0),
+                soft_max_tokens: effective.soft_max_tokens.unwrap_or(0),
+                soft_max_cost_micros: effective.soft_max_cost_micros.unwrap_or(0),
+            }),
+            policy_version,
+            started_ts_ms,
+        }))
     }
 
     #[instrument(skip_all)]
@@ -486,7 +1594,7 @@ impl Orchestrator for OrchestratorService {
         req: Request<SubmitTaskRequest>,
     ) -> Result<Response<SubmitTaskResponse>, Status> {
         let md = req.metadata().clone();
-        Self::check_auth(&md)?;
+        self.check_auth(&md, Scope::SubmitTask)?;
 
         // External I/O capture (server-side skeleton)
         let capture_on = crate::proxy::capture_enabled();
@@ -508,7 +1616,7 @@ impl Orchestrator for OrchestratorService {
                 "headers": serde_json::Value::Object(crate::proxy::redacted_headers(&md)),
                 "body_digest_sha256": crate::proxy::sha256_hex(&[]),
             });
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t0_ms, &started);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t0_ms, &started);
             let injected =
                 crate::proxy::fail_inject_enabled() || md.get("x-orca-capture-fail").is_some();
             if injected && !crate::proxy::bypass_to_direct() {
@@ -522,7 +1630,16 @@ impl Orchestrator for OrchestratorService {
                 r.task.as_ref().ok_or_else(|| Status::invalid_argument("missing envelope"))?;
             self.reject_if_expired_or_version(env)?;
             if self.seen_ids.contains(&env.id) {
-                return Ok(Response::new(SubmitTaskResponse { accepted: true }));
+                return Ok(Response::new(SubmitTaskResponse {
+                    accepted: !self.reject_duplicate_tasks,
+                    modified: false,
+                    modified_by_rule: String::new(),
+                    flagged: false,
+                    duplicate: self.reject_duplicate_tasks,
+                }));
+            }
+            if self.max_active_runs.is_some() && !self.active_runs.contains(&r.run_id) {
+                return Err(Status::failed_precondition("run not active"));
             }
         }
 
@@ -541,7 +1658,11 @@ impl Orchestrator for OrchestratorService {
             .entered();
             serde_json::to_value(env).map_err(internal_serde)?
         };
-        let decision = self.policy.read().unwrap().pre_submit_task(&env_json);
+        // Budget-aware rules see the run's state before this task's usage is counted.
+        let ctx = policy::EvalContext {
+            budget_state: self.budgets_by_run.get(&r.run_id).map(|m| m.status()),
+        };
+        let decision = self.policy.read().unwrap().pre_submit_task_with_context(&env_json, &ctx);
         // Record decision attributes on the current span
         let kind_str = match decision.kind {
             DecisionKind::Allow => "allow",
@@ -553,6 +1674,8 @@ impl Orchestrator for OrchestratorService {
             tracing::Span::current().record("rule_name", tracing::field::display(rn));
         }
         self.append_policy_audit("pre_submit_task", Some(&r.run_id), None, &env_json, &decision);
+        // Reported back to the client so it can tell a rewritten task from a plain accept.
+        let mut modified_by: Option<String> = None;
         match decision.kind {
             DecisionKind::Deny => return Err(Status::permission_denied("policy deny")),
             DecisionKind::Modify => {
@@ -560,13 +1683,14 @@ impl Orchestrator for OrchestratorService {
                     env_json = p;
                 }
                 r.task = Some(serde_json::from_value(env_json).map_err(internal_serde)?);
+                modified_by = Some(decision.rule_name.unwrap_or_default());
             }
             DecisionKind::Allow => {}
         }
 
         // Budget usage/update and thresholds (per-run if configured)
         let env = r.task.as_ref().ok_or_else(|| Status::invalid_argument("missing envelope"))?;
-        let mut tokens_inc: u64 = 1; // default minimal increment
+        let mut tokens_inc: u64 = self.default_tokens_inc;
         let mut cost_inc: u64 = 0;
         if let Some(h) = env.usage.as_ref() {
             if h.tokens > 0 {
@@ -576,8 +1700,15 @@ impl Orchestrator for OrchestratorService {
                 cost_inc = h.cost_micros;
             }
         }
+        // Set once the run is past its soft limit: the task is still accepted, but flagged.
+        let flagged;
         if let Some(mgr) = self.budgets_by_run.get(&r.run_id) {
+            let before = mgr.status();
+            let was_exceeded = before == BudgetState::Exceeded;
+            let was_soft_exceeded = mgr.soft_exceeded();
             mgr.add_usage(tokens_inc, cost_inc);
+            self.append_budget_transition(&r.run_id, before, &mgr)?;
+            flagged = self.append_budget_soft_exceeded(&r.run_id, was_soft_exceeded, &mgr)?;
             self.metrics.add(tokens_inc, cost_inc);
             #[cfg(feature = "otel")]
             {
@@ -590,8 +1721,7 @@ impl Orchestrator for OrchestratorService {
             match status {
                 BudgetState::Exceeded => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -599,12 +1729,17 @@ impl Orchestrator for OrchestratorService {
                             }),
                         )
                         .map_err(internal_io)?;
+                    // The run terminates here; summarize it once, on the transition.
+                    if !was_exceeded {
+                        self.append_run_summary(&r.run_id, &mgr, false)?;
+                        drop(mgr); // release the map guard before evicting this run's entry
+                        self.evict_run(&r.run_id);
+                    }
                     return Err(Status::resource_exhausted("budget exceeded"));
                 }
                 BudgetState::Warning90 => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -616,8 +1751,7 @@ impl Orchestrator for OrchestratorService {
                 }
                 BudgetState::Warning80 => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -630,7 +1764,13 @@ impl Orchestrator for OrchestratorService {
                 BudgetState::Within => {}
             }
         } else {
+            let before = self.budget.status();
+            let was_exceeded = before == BudgetState::Exceeded;
+            let was_soft_exceeded = self.budget.soft_exceeded();
             self.budget.add_usage(tokens_inc, cost_inc);
+            self.append_budget_transition(&r.run_id, before, &self.budget)?;
+            flagged =
+                self.append_budget_soft_exceeded(&r.run_id, was_soft_exceeded, &self.budget)?;
             self.metrics.add(tokens_inc, cost_inc);
             #[cfg(feature = "otel")]
             {
@@ -643,8 +1783,7 @@ impl Orchestrator for OrchestratorService {
             match status {
                 BudgetState::Exceeded => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -652,12 +1791,15 @@ impl Orchestrator for OrchestratorService {
                             }),
                         )
                         .map_err(internal_io)?;
+                    if !was_exceeded {
+                        self.append_run_summary(&r.run_id, &self.budget, false)?;
+                        self.evict_run(&r.run_id);
+                    }
                     return Err(Status::resource_exhausted("budget exceeded"));
                 }
                 BudgetState::Warning90 => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -669,8 +1811,7 @@ impl Orchestrator for OrchestratorService {
                 }
                 BudgetState::Warning80 => {
                     let _ = self
-                        .log
-                        .append(
+                        .append_event(
                             orca_core::ids::next_monotonic_id(),
                             crate::clock::process_clock().now_ms(),
                             &json!({
@@ -684,7 +1825,7 @@ impl Orchestrator for OrchestratorService {
             }
         }
 
-        // Update per-run usage totals and emit usage_update event
+        // Update per-run usage totals and emit usage_update event (totals plus this task's delta)
         {
             let mut entry = self.index.usage_by_run.entry(r.run_id.clone()).or_insert((0, 0));
             let (ref mut t, ref mut c) = *entry;
@@ -697,12 +1838,12 @@ impl Orchestrator for OrchestratorService {
             *at = at.saturating_add(tokens_inc);
             *ac = ac.saturating_add(cost_inc);
             let _ = self
-                .log
-                .append(
+                .append_event(
                     orca_core::ids::next_monotonic_id(),
                     crate::clock::process_clock().now_ms(),
                     &json!({
-                        "event":"usage_update", "run_id": r.run_id, "tokens": *t, "cost_micros": *c,
+                        "event":"usage_update", "run_id": r.run_id, "agent": env.agent, "tokens": *t, "cost_micros": *c,
+                        "delta_tokens": tokens_inc, "delta_cost_micros": cost_inc,
                         "elapsed_ms": self.index.run_start_ts_by_run.get(&r.run_id).map(|v| crate::clock::process_clock().now_ms().saturating_sub(*v.value())).unwrap_or(0)
                     }),
                 )
@@ -731,52 +1872,41 @@ impl Orchestrator for OrchestratorService {
                 }
                 let evt = serde_json::Value::Object(evt_obj);
                 let evt = self.redact_event_payload(evt);
-                self.log
-                    .append(
-                        orca_core::ids::next_monotonic_id(),
-                        crate::clock::process_clock().now_ms(),
-                        &evt,
-                    )
-                    .map_err(internal_io)
+                let now_ts = crate::clock::process_clock().now_ms();
+                self.append_event(orca_core::ids::next_monotonic_id(), now_ts, &evt)
+                    .map_err(internal_io)?;
+                self.touch_run(&run_id, now_ts);
+                Ok(())
             },
             3,
             50,
         )
         .await?;
 
-        if env.timeout_ms > 0 {
-            let dur = Duration::from_millis(env.timeout_ms);
-            let res = timeout(dur, async {}).await;
-            let post = self.policy.read().unwrap().post_submit_task(&json!({"result":"stub"}));
-            // emit audit only if intervention
-            let audit_env = json!({"id": env.id, "agent": env.agent, "kind": env.kind, "trace_id": env.trace_id});
-            self.append_policy_audit("post_submit_task", Some(&r.run_id), None, &audit_env, &post);
-            match res {
-                Ok(_) => info!("task completed"),
-                Err(_) => warn!("task timeout"),
-            }
-            match post.kind {
-                DecisionKind::Deny => return Err(Status::permission_denied("policy deny")),
-                _ => {}
-            }
+        // `timeout_ms` is a TTL enforced at admission (`reject_if_expired_or_version`); the
+        // orchestrator does not execute tasks, so there is nothing further to bound here.
+        let post = self.policy.read().unwrap().post_submit_task(&json!({"result":"stub"}));
+        // emit audit only if intervention
+        let audit_env =
+            json!({"id": env.id, "agent": env.agent, "kind": env.kind, "trace_id": env.trace_id});
+        self.append_policy_audit("post_submit_task", Some(&r.run_id), None, &audit_env, &post);
+        if matches!(post.kind, DecisionKind::Deny) {
+            return Err(Status::permission_denied("policy deny"));
+        }
+        self.dispatch_task(&r.run_id, env)?;
+        if env.kind == "agent_error" {
+            self.record_task_failure(&r.run_id, env)?;
         }
 
         // End-of-run summary heuristic: if this is an agent_result, emit summary
-        if env.kind == "agent_result" {
-            if let Some((t, c)) = self.index.usage_by_run.get(&r.run_id).map(|v| *v.value()) {
-                // Build per-agent breakdown
-                let mut breakdown: Vec<JsonValue> = Vec::new();
-                for kv in self.index.usage_by_run_agent.iter() {
-                    let ((run, agent), (at, ac)) = kv.pair();
-                    if *run == r.run_id {
-                        breakdown.push(json!({"agent": agent, "tokens": at, "cost_micros": ac }));
-                    }
-                }
-                let _ = self.log.append(orca_core::ids::next_monotonic_id(), crate::clock::process_clock().now_ms(), &json!({
-                    "event":"run_summary", "run_id": r.run_id, "tokens": t, "cost_micros": c, "by_agent": breakdown,
-                    "duration_ms": self.index.run_start_ts_by_run.get(&r.run_id).map(|v| crate::clock::process_clock().now_ms().saturating_sub(*v.value())).unwrap_or(0)
-                })).map_err(internal_io)?;
-            }
+        if env.kind == "agent_result" && self.index.usage_by_run.contains_key(&r.run_id) {
+            let mgr = self
+                .budgets_by_run
+                .get(&r.run_id)
+                .map(|m| m.value().clone())
+                .unwrap_or_else(|| self.budget.clone());
+            self.append_run_summary(&r.run_id, &mgr, false)?;
+            self.evict_run(&r.run_id);
         }
         // Emit finished + metric for capture
         if capture_on {
@@ -788,12 +1918,113 @@ impl Orchestrator for OrchestratorService {
                 "status": "ok",
                 "duration_ms": t1.saturating_sub(t0_ms),
             });
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t1, &finished);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t1, &finished);
             let metric = json!({"metric":"proxy.capture.emit.ms","value_ms":0});
-            let _ = self.log.append(orca_core::ids::next_monotonic_id(), t1, &metric);
+            let _ = self.append_event(orca_core::ids::next_monotonic_id(), t1, &metric);
+        }
+
+        Ok(Response::new(SubmitTaskResponse {
+            accepted: true,
+            modified: modified_by.is_some(),
+            modified_by_rule: modified_by.unwrap_or_default(),
+            flagged,
+            duplicate: false,
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn update_task(
+        &self,
+        req: Request<UpdateTaskRequest>,
+    ) -> Result<Response<UpdateTaskResponse>, Status> {
+        self.check_auth(req.metadata(), Scope::SubmitTask)?;
+        let r = req.into_inner();
+        let mut patch: JsonValue = serde_json::from_str(&r.merge_patch_json).map_err(|e| {
+            Status::invalid_argument(format!("merge_patch_json is not JSON: {}", e))
+        })?;
+        if self.is_run_completed(&r.run_id) {
+            return Err(Status::failed_precondition("run completed"));
+        }
+        let recs = self.log.iter_range(0, u64::MAX).map_err(internal_io)?;
+        let mut task = updates::current_task(recs, &r.run_id, &r.envelope_id)?;
+
+        // Policy sees the target envelope carrying the patch as its payload, so content rules
+        // (deny, redaction) apply to exactly what is being added.
+        let mut probe = task.clone();
+        probe.payload_json = patch.to_string();
+        let probe_json = serde_json::to_value(&probe).map_err(internal_serde)?;
+        let ctx = policy::EvalContext {
+            budget_state: self.budgets_by_run.get(&r.run_id).map(|m| m.status()),
+        };
+        let decision = self.policy.read().unwrap().pre_submit_task_with_context(&probe_json, &ctx);
+        self.append_policy_audit("pre_update_task", Some(&r.run_id), None, &probe_json, &decision);
+        let mut modified_by: Option<String> = None;
+        match decision.kind {
+            DecisionKind::Deny => return Err(Status::permission_denied("policy deny")),
+            DecisionKind::Modify => {
+                if let Some(rewritten) = decision
+                    .payload
+                    .as_ref()
+                    .and_then(|p| p.get("payload_json"))
+                    .and_then(|v| v.as_str())
+                {
+                    patch = serde_json::from_str(rewritten).map_err(internal_serde)?;
+                }
+                modified_by = Some(decision.rule_name.unwrap_or_default());
+            }
+            DecisionKind::Allow => {}
         }
 
-        Ok(Response::new(SubmitTaskResponse { accepted: true }))
+        let mut payload: JsonValue =
+            serde_json::from_str(&task.payload_json).unwrap_or(JsonValue::Null);
+        updates::merge_patch(&mut payload, &patch);
+        task.payload_json = payload.to_string();
+        let now_ts = crate::clock::process_clock().now_ms();
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            now_ts,
+            &json!({
+                "event": "task_updated", "run_id": r.run_id, "envelope_id": r.envelope_id,
+                "trace_id": task.trace_id, "patch": patch,
+            }),
+        )
+        .map_err(internal_io)?;
+        self.touch_run(&r.run_id, now_ts);
+        Ok(Response::new(UpdateTaskResponse {
+            accepted: true,
+            modified: modified_by.is_some(),
+            modified_by_rule: modified_by.unwrap_or_default(),
+            payload_json: task.payload_json,
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn cancel_run(
+        &self,
+        req: Request<CancelRunRequest>,
+    ) -> Result<Response<CancelRunResponse>, Status> {
+        self.check_auth(req.metadata(), Scope::StartRun)?;
+        let r = req.into_inner();
+        if r.run_id.is_empty() {
+            return Err(Status::invalid_argument("missing run_id"));
+        }
+        if self.is_run_completed(&r.run_id) {
+            return Ok(Response::new(CancelRunResponse { cancelled: false }));
+        }
+        let reason = r.reason.unwrap_or_default();
+        self.append_event(
+            orca_core::ids::next_monotonic_id(),
+            crate::clock::process_clock().now_ms(),
+            &json!({
+                "event": "run_cancelled", "run_id": r.run_id,
+                "reason": {"code": reason.code, "message": reason.message},
+            }),
+        )
+        .map_err(internal_io)?;
+        self.mark_run_completed(&r.run_id, false);
+        self.evict_run(&r.run_id);
+        info!(run=%r.run_id, code=%reason.code, "run cancelled");
+        Ok(Response::new(CancelRunResponse { cancelled: true }))
     }
 
     type StreamEventsStream =
@@ -803,57 +2034,81 @@ impl Orchestrator for OrchestratorService {
         &self,
         req: Request<StreamEventsRequest>,
     ) -> Result<Response<Self::StreamEventsStream>, Status> {
-        Self::check_auth(req.metadata())?;
+        self.check_auth(req.metadata(), Scope::StreamEvents)?;
         let r = req.into_inner();
         let run_id = r.run_id.clone();
         let start_event_id = r.start_event_id;
         let (tx, rx) = tokio::sync::mpsc::channel(32);
         let log = self.log.clone();
+        // Subscribed before the backlog read so nothing appended in between is missed.
+        let mut live = (r.follow && r.page_size == 0 && r.max_events == 0)
+            .then(|| self.live_events.subscribe());
         tokio::spawn(
             async move {
-                let start_id = r.start_event_id;
-                let recs: Result<Vec<EventRecord<JsonValue>>, _> =
-                    log.read_range(start_id, u64::MAX);
-                let mut sent = 0u32;
-                match recs {
-                    Ok(recs) => {
+                match select_stream_events(&log, &r) {
+                    Ok((recs, exhausted)) => {
+                        let page_end = (r.page_size > 0).then(|| match recs.last() {
+                            Some(last) => StreamPageEnd {
+                                next_since_ts_ms: last.ts_ms,
+                                next_start_event_id: last.id + 1,
+                                exhausted,
+                            },
+                            None => StreamPageEnd {
+                                next_since_ts_ms: r.since_ts_ms,
+                                next_start_event_id: r.start_event_id,
+                                exhausted,
+                            },
+                        });
+                        let mut sent: HashSet<u64> = HashSet::new();
                         for rec in recs {
-                            if r.since_ts_ms > 0 && rec.ts_ms < r.since_ts_ms {
-                                continue;
+                            if live.is_some() {
+                                sent.insert(rec.id);
                             }
-                            if r.max_events > 0 && sent >= r.max_events {
-                                break;
+                            let end = follow_end(&rec.payload);
+                            if tx.send(Ok(stream_item(rec))).await.is_err() {
+                                return;
                             }
-                            let p = rec.payload;
-                            let run_match =
-                                p.get("run_id").and_then(|v| v.as_str()) == Some(r.run_id.as_str());
-                            let wf_match = p.get("workflow_id").and_then(|v| v.as_str())
-                                == Some(r.run_id.as_str());
-                            if !(run_match || wf_match) {
-                                continue;
+                            if let (Some(end), Some(_)) = (end, &live) {
+                                if let Err(status) = end {
+                                    let _ = tx.send(Err(status)).await;
+                                }
+                                return;
                             }
-                            let kind = p
-                                .get("event")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("event")
-                                .to_string();
-                            let env = orca_v1::Envelope {
-                                id: String::new(),
-                                parent_id: String::new(),
-                                trace_id: String::new(),
-                                agent: String::new(),
-                                kind,
-                                payload_json: p.to_string(),
-                                timeout_ms: 0,
-                                protocol_version: 1,
-                                ts_ms: rec.ts_ms,
-                                usage: None,
+                        }
+                        if let Some(end) = page_end {
+                            let item = StreamEventsResponse { event: None, page_end: Some(end) };
+                            let _ = tx.send(Ok(item)).await;
+                        }
+                        let Some(live) = live.as_mut() else { return };
+                        loop {
+                            let recv = tokio::select! {
+                                _ = tx.closed() => return, // subscriber went away
+                                recv = live.recv() => recv,
                             };
-                            if tx.send(Ok(StreamEventsResponse { event: Some(env) })).await.is_err()
+                            let rec = match recv {
+                                Ok(rec) => rec,
+                                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                                    let msg = format!("follower lagged by {} events; resume", n);
+                                    let _ = tx.send(Err(Status::aborted(msg))).await;
+                                    return;
+                                }
+                                Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                            };
+                            if event_run_id(&rec.payload).as_deref() != Some(r.run_id.as_str())
+                                || sent.contains(&rec.id)
                             {
-                                break;
+                                continue;
+                            }
+                            let end = follow_end(&rec.payload);
+                            if tx.send(Ok(stream_item(rec))).await.is_err() {
+                                return;
+                            }
+                            if let Some(end) = end {
+                                if let Err(status) = end {
+                                    let _ = tx.send(Err(status)).await;
+                                }
+                                return;
                             }
-                            sent += 1;
                         }
                     }
                     Err(e) => {
@@ -873,12 +2128,177 @@ impl Orchestrator for OrchestratorService {
         &self,
         req: Request<FetchResultRequest>,
     ) -> Result<Response<FetchResultResponse>, Status> {
-        Self::check_auth(req.metadata())?;
+        self.check_auth(req.metadata(), Scope::FetchResult)?;
         let empty = Envelope::new_result("", "", "", json!({"status":"stub"}));
-        Ok(Response::new(FetchResultResponse { result: Some(convert_envelope(empty)) }))
+        Ok(Response::new(FetchResultResponse { result: Some(empty.into()) }))
+    }
+
+    type FetchResultStreamStream =
+        tokio_stream::wrappers::ReceiverStream<Result<FetchResultChunk, Status>>;
+    #[instrument(skip_all)]
+    async fn fetch_result_stream(
+        &self,
+        req: Request<FetchResultStreamRequest>,
+    ) -> Result<Response<Self::FetchResultStreamStream>, Status> {
+        self.check_auth(req.metadata(), Scope::FetchResult)?;
+        let r = req.into_inner();
+        let recs = self.log.iter_range(0, u64::MAX).map_err(internal_io)?;
+        let env = results::find_result(recs, &r.run_id, &r.parent_id)?.ok_or_else(|| {
+            Status::not_found(format!(
+                "no result for parent '{}' in run '{}'",
+                r.parent_id, r.run_id
+            ))
+        })?;
+        let bytes = results::result_bytes(&env, self.result_blobs.as_deref())?;
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tokio::spawn(
+            async move {
+                for chunk in results::chunks(&bytes, r.chunk_bytes) {
+                    if tx.send(Ok(chunk)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            .instrument(info_span!("agent.core.fetch_result_stream", run=%r.run_id)),
+        );
+        Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(rx)))
+    }
+
+    #[instrument(skip_all)]
+    async fn list_runs(
+        &self,
+        req: Request<ListRunsRequest>,
+    ) -> Result<Response<ListRunsResponse>, Status> {
+        self.check_auth(req.metadata(), Scope::Admin)?;
+        Ok(Response::new(ListRunsResponse { run_ids: OrchestratorService::list_runs(self) }))
+    }
+}
+
+/// A WAL record as a `StreamEvents` item: the event kind and full payload wrapped in an
+/// envelope whose id is the WAL event id (the resume cursor).
+/// A record ordered by its `(ts_ms, id)` stream cursor.
+struct ByCursor(EventRecord<JsonValue>);
+
+impl ByCursor {
+    const fn key(&self) -> (u64, u64) {
+        (self.0.ts_ms, self.0.id)
+    }
+}
+
+impl PartialEq for ByCursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ByCursor {}
+
+impl PartialOrd for ByCursor {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+impl Ord for ByCursor {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The run's records at or after the request's cursor, in send order, and whether nothing
+/// past the limit (`page_size`, else `max_events`) matched.
+///
+/// The WAL is streamed once. With a timestamp the cursor is (since_ts_ms, start_event_id), so
+/// ids alone cannot bound the read. Cursor and paged reads are cut in (ts, id) order rather
+/// than file order, so a WAL whose timestamps step backwards (clock adjustments, merged
+/// segments) still pages exactly once; with a limit only the lowest `limit` records are kept,
+/// in a bounded max-heap, so a page never holds more than a page however long the WAL is.
+fn select_stream_events(
+    log: &JsonlEventLog,
+    r: &StreamEventsRequest,
+) -> Result<(Vec<EventRecord<JsonValue>>, bool), EventLogError> {
+    // Paged mode always cuts in (ts, id) order so its cursor is total.
+    let paged = r.page_size > 0;
+    let sorted = r.since_ts_ms > 0 || paged;
+    let limit = if paged { r.page_size as usize } else { r.max_events as usize };
+    let start_id = if r.since_ts_ms > 0 { 0 } else { r.start_event_id };
+    let mut matched = 0usize;
+    let mut recs = Vec::new();
+    let mut best: BinaryHeap<ByCursor> = BinaryHeap::new();
+    for rec in log.iter_range::<JsonValue>(start_id, u64::MAX)? {
+        let rec = rec?;
+        if !rec.at_or_after(r.since_ts_ms, r.start_event_id) {
+            continue;
+        }
+        let field = |k: &str| rec.payload.get(k).and_then(|v| v.as_str());
+        if field("run_id") != Some(r.run_id.as_str())
+            && field("workflow_id") != Some(r.run_id.as_str())
+        {
+            continue;
+        }
+        matched += 1;
+        if !sorted || limit == 0 {
+            if limit > 0 && recs.len() == limit {
+                break;
+            }
+            recs.push(rec);
+        } else if best.len() < limit {
+            best.push(ByCursor(rec));
+        } else if best.peek().is_some_and(|top| (rec.ts_ms, rec.id) < top.key()) {
+            best.pop();
+            best.push(ByCursor(rec));
+        }
+    }
+    if sorted && limit == 0 {
+        recs.sort_by_key(|rec| (rec.ts_ms, rec.id));
+    } else if sorted {
+        recs = best.into_sorted_vec().into_iter().map(|rec| rec.0).collect();
+    }
+    Ok((recs, limit == 0 || matched <= limit))
+}
+
+fn stream_item(rec: EventRecord<JsonValue>) -> StreamEventsResponse {
+    let kind = rec.payload.get("event").and_then(|v| v.as_str()).unwrap_or("event").to_string();
+    let env = orca_v1::Envelope {
+        id: rec.id.to_string(),
+        parent_id: String::new(),
+        trace_id: String::new(),
+        agent: String::new(),
+        kind,
+        payload_json: rec.payload.to_string(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: rec.ts_ms,
+        usage: None,
+    };
+    StreamEventsResponse { event: Some(env), page_end: None }
+}
+
+/// How a follow stream ends after sending `p`: cleanly after the run's `run_summary`, with
+/// CANCELLED carrying the reason after `run_cancelled`; `None` keeps it open.
+fn follow_end(p: &JsonValue) -> Option<Result<(), Status>> {
+    match p.get("event").and_then(|v| v.as_str()) {
+        Some("run_summary") => Some(Ok(())),
+        Some("run_cancelled") => {
+            let field = |k: &str| p.get("reason").and_then(|r| r.get(k)).and_then(|v| v.as_str());
+            let code = field("code").unwrap_or_default();
+            Some(Err(match field("message").filter(|m| !m.is_empty()) {
+                Some(msg) => Status::cancelled(format!("run cancelled ({}): {}", code, msg)),
+                None => Status::cancelled(format!("run cancelled ({})", code)),
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Run an event belongs to: `run_id`, else `workflow_id` (start_run).
+fn event_run_id(p: &JsonValue) -> Option<String> {
+    p.get("run_id")
+        .and_then(|v| v.as_str())
+        .or_else(|| p.get("workflow_id").and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
 fn internal_io(e: EventLogError) -> Status {
     Status::internal(format!("io error: {}", e))
 }
@@ -886,18 +2306,68 @@ fn internal_serde(e: serde_json::Error) -> Status {
     Status::internal(format!("serde error: {}", e))
 }
 
-fn convert_envelope(e: Envelope) -> orca_v1::Envelope {
-    orca_v1::Envelope {
-        id: e.id,
-        parent_id: e.parent_id.unwrap_or_default(),
-        trace_id: e.trace_id,
-        agent: e.agent,
-        kind: format!("{:?}", e.kind).to_lowercase(),
-        payload_json: serde_json::to_string(&e.payload).unwrap_or_default(),
-        timeout_ms: e.timeout_ms.unwrap_or_default(),
-        protocol_version: e.protocol_version,
-        ts_ms: e.ts_ms,
-        usage: None,
+impl From<Envelope> for orca_v1::Envelope {
+    fn from(e: Envelope) -> Self {
+        orca_v1::Envelope {
+            id: e.id,
+            parent_id: e.parent_id.unwrap_or_default(),
+            trace_id: e.trace_id,
+            agent: e.agent,
+            kind: message_type_str(e.kind).to_string(),
+            payload_json: serde_json::to_string(&e.payload).unwrap_or_default(),
+            timeout_ms: e.timeout_ms.unwrap_or_default(),
+            protocol_version: e.protocol_version,
+            ts_ms: e.ts_ms,
+            usage: None,
+        }
+    }
+}
+
+/// Proto → core: `kind` must be one of [`ENVELOPE_KINDS`], and `payload_json` must parse as
+/// JSON (empty means `null`). An empty `parent_id` and a zero `timeout_ms` become `None`;
+/// `usage` has no core counterpart and is dropped.
+impl TryFrom<orca_v1::Envelope> for Envelope {
+    type Error = Status;
+
+    fn try_from(e: orca_v1::Envelope) -> Result<Self, Status> {
+        let kind = match e.kind.as_str() {
+            "agent_task" => orca_core::envelope::MessageType::AgentTask,
+            "agent_result" => orca_core::envelope::MessageType::AgentResult,
+            "agent_error" => orca_core::envelope::MessageType::AgentError,
+            other => {
+                return Err(Status::invalid_argument(format!(
+                    "unsupported envelope kind '{}'",
+                    other
+                )))
+            }
+        };
+        let payload = if e.payload_json.trim().is_empty() {
+            JsonValue::Null
+        } else {
+            serde_json::from_str(&e.payload_json).map_err(|err| {
+                Status::invalid_argument(format!("envelope payload_json is not JSON: {}", err))
+            })?
+        };
+        Ok(Envelope {
+            id: e.id,
+            parent_id: (!e.parent_id.is_empty()).then_some(e.parent_id),
+            trace_id: e.trace_id,
+            agent: e.agent,
+            kind,
+            payload,
+            timeout_ms: (e.timeout_ms > 0).then_some(e.timeout_ms),
+            protocol_version: e.protocol_version,
+            ts_ms: e.ts_ms,
+        })
+    }
+}
+
+/// Wire name of a message type (the snake_case `kind` in [`ENVELOPE_KINDS`]).
+fn message_type_str(kind: orca_core::envelope::MessageType) -> &'static str {
+    match kind {
+        orca_core::envelope::MessageType::AgentTask => "agent_task",
+        orca_core::envelope::MessageType::AgentResult => "agent_result",
+        orca_core::envelope::MessageType::AgentError => "agent_error",
     }
 }
 
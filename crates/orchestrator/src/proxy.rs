@@ -11,7 +11,8 @@ use http::HeaderMap;
 use sha2::{Digest, Sha256};
 
 use event_log::JsonlEventLog;
-use std::sync::{OnceLock, RwLock};
+use orca_core::ids::{IdGenerator, PrefixedMonotonic};
+use std::sync::{Arc, OnceLock, RwLock};
 
 // Global capture log sink for client-side capture (tests/bench can set/reset).
 static CAPTURE_LOG: OnceLock<RwLock<Option<JsonlEventLog>>> = OnceLock::new();
@@ -39,6 +40,54 @@ pub fn fail_inject_enabled() -> bool {
     std::env::var("ORCA_CAPTURE_FAIL_INJECT").ok().as_deref() == Some("1")
 }
 
+/// Deterministic capture sampler: inclusion is a pure function of the request id and the
+/// configured rate, so replaying the same WAL reproduces the same capture set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureSampler {
+    rate: f64,
+}
+
+impl Default for CaptureSampler {
+    /// Capture everything (rate 1.0).
+    fn default() -> Self {
+        Self { rate: 1.0 }
+    }
+}
+
+impl CaptureSampler {
+    /// Sampler including roughly `rate` of request ids; clamped to `[0.0, 1.0]`
+    /// (NaN samples nothing).
+    pub fn new(rate: f64) -> Self {
+        Self { rate: if rate.is_nan() { 0.0 } else { rate.clamp(0.0, 1.0) } }
+    }
+
+    /// Rate from `ORCA_CAPTURE_SAMPLE_RATE` (default 1.0 when unset or unparsable).
+    pub fn from_env() -> Self {
+        std::env::var("ORCA_CAPTURE_SAMPLE_RATE")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(Self::new)
+            .unwrap_or_default()
+    }
+
+    /// Configured inclusion rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Whether `request_id` is captured: the first 8 bytes of SHA-256(`request_id`),
+    /// read as a fraction of `u64::MAX`, fall below the rate.
+    pub fn should_capture(&self, request_id: &str) -> bool {
+        if self.rate >= 1.0 {
+            return true;
+        }
+        let digest = Sha256::digest(request_id.as_bytes());
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest[..8]);
+        (u64::from_be_bytes(head) as f64) < self.rate * u64::MAX as f64
+    }
+}
+
 /// Redact sensitive headers according to a simple allowlist policy.
 /// Currently redacts: authorization, cookie, x-api-key.
 pub fn redacted_headers(md: &MetadataMap) -> JsonMap<String, JsonValue> {
@@ -92,8 +141,37 @@ use std::{future::Future, pin::Pin};
 use tonic::body::BoxBody;
 use tower::{Layer, Service};
 
-#[derive(Debug, Clone, Default)]
-pub struct ProxyCaptureLayer;
+/// Default capture request-id scheme: `R{n}` from the process-wide monotonic counter.
+/// Not unique across restarts; use [`orca_core::ids::UlidGenerator`] for cross-service ids.
+pub fn default_request_ids() -> Arc<dyn IdGenerator> {
+    Arc::new(PrefixedMonotonic::new("R"))
+}
+
+#[derive(Debug, Clone)]
+pub struct ProxyCaptureLayer {
+    request_ids: Arc<dyn IdGenerator>,
+    sampler: CaptureSampler,
+}
+
+impl Default for ProxyCaptureLayer {
+    fn default() -> Self {
+        Self { request_ids: default_request_ids(), sampler: CaptureSampler::from_env() }
+    }
+}
+
+impl ProxyCaptureLayer {
+    /// Replace the `request_id` scheme used in captured events.
+    pub fn with_request_ids(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.request_ids = ids;
+        self
+    }
+
+    /// Replace the capture sampler (default from `ORCA_CAPTURE_SAMPLE_RATE`).
+    pub fn with_sampler(mut self, sampler: CaptureSampler) -> Self {
+        self.sampler = sampler;
+        self
+    }
+}
 
 impl<S> Layer<S> for ProxyCaptureLayer {
     type Service = ProxyCapturedChannel<S>;
@@ -104,6 +182,8 @@ impl<S> Layer<S> for ProxyCaptureLayer {
             host: "unknown".to_string(),
             port: 0,
             log: capture_log_clone(),
+            request_ids: self.request_ids.clone(),
+            sampler: self.sampler,
         }
     }
 }
@@ -118,6 +198,8 @@ pub struct ProxyCapturedChannel<S> {
     port: u16,
     // Cached capture sink to avoid per-request RwLock reads
     log: Option<JsonlEventLog>,
+    request_ids: Arc<dyn IdGenerator>,
+    sampler: CaptureSampler,
 }
 
 #[cfg(feature = "capture")]
@@ -136,11 +218,15 @@ where
     }
 
     fn call(&mut self, req: Request<BoxBody>) -> Self::Future {
-        // Only emit when runtime capture enabled and a log sink is configured.
-        let log = if capture_enabled() { self.log.clone() } else { None };
-
         let t0 = crate::clock::process_clock().now_ms();
-        let rid = format!("R{}", orca_core::ids::next_monotonic_id());
+        let rid = self.request_ids.next_id();
+        // Only emit when runtime capture is enabled, a log sink is configured, and the
+        // request id is sampled in.
+        let log = if capture_enabled() && self.sampler.should_capture(&rid) {
+            self.log.clone()
+        } else {
+            None
+        };
 
         if let Some(logc) = log.clone() {
             // Extract method and headers; redaction only when sensitive headers present.
@@ -215,7 +301,7 @@ where
 
 /// Convenience helpers for tests/bench to avoid exposing internal types directly.
 pub fn wrap_service<S>(inner: S) -> ProxyCapturedChannel<S> {
-    ProxyCaptureLayer.layer(inner)
+    ProxyCaptureLayer::default().layer(inner)
 }
 
 pub fn test_set_capture_log(log: JsonlEventLog) {
@@ -229,12 +315,33 @@ pub struct CapturedChannelBuilder {
     scheme: String,
     host: String,
     port: u16,
+    request_ids: Arc<dyn IdGenerator>,
+    sampler: CaptureSampler,
 }
 
 impl CapturedChannelBuilder {
     /// Create a builder from a connected tonic Channel.
     pub fn new(inner: tonic::transport::Channel) -> Self {
-        Self { inner, scheme: "grpc".into(), host: "unknown".into(), port: 0 }
+        Self {
+            inner,
+            scheme: "grpc".into(),
+            host: "unknown".into(),
+            port: 0,
+            request_ids: default_request_ids(),
+            sampler: CaptureSampler::from_env(),
+        }
+    }
+
+    /// Replace the `request_id` scheme (default `R{n}`).
+    pub fn request_ids(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.request_ids = ids;
+        self
+    }
+
+    /// Replace the capture sampler (default from `ORCA_CAPTURE_SAMPLE_RATE`).
+    pub fn sampler(mut self, sampler: CaptureSampler) -> Self {
+        self.sampler = sampler;
+        self
     }
 
     /// Optionally set endpoint parts (scheme, host, port) if known.
@@ -252,6 +359,8 @@ impl CapturedChannelBuilder {
             host: self.host,
             port: self.port,
             log: capture_log_clone(),
+            request_ids: self.request_ids,
+            sampler: self.sampler,
         }
     }
 }
@@ -336,6 +445,39 @@ mod tests {
         assert_eq!(rid_s, rid_f, "request_id must correlate started/finished");
     }
 
+    #[test]
+    fn request_id_scheme_is_pluggable_and_defaults_to_r_prefix() {
+        use tower::Layer;
+        let _g = serial_guard();
+        let dir = tempfile::tempdir().unwrap();
+        let log = JsonlEventLog::open(dir.path().join("client_ids.jsonl")).unwrap();
+        run_captured_call_with_headers(&[], &log);
+
+        let layer = super::ProxyCaptureLayer::default()
+            .with_request_ids(std::sync::Arc::new(orca_core::ids::UlidGenerator::new("c-")));
+        let inner = service_fn(|_req: Request<BoxBody>| async move {
+            Ok::<http::Response<tonic::transport::Body>, ()>(http::Response::new(
+                tonic::transport::Body::empty(),
+            ))
+        });
+        let mut svc = layer.layer(inner);
+        let req = Request::builder().uri("/x").body(BoxBody::default()).unwrap();
+        tokio::runtime::Runtime::new().unwrap().block_on(async move {
+            let _ = svc.call(req).await;
+        });
+
+        let rids: Vec<String> = read_log_events(&log)
+            .into_iter()
+            .filter(|r| {
+                r.payload.get("event").and_then(|v| v.as_str()) == Some("external_io_started")
+            })
+            .map(|r| r.payload["request_id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(rids.len(), 2);
+        assert!(rids[0].starts_with('R') && rids[0][1..].parse::<u64>().is_ok(), "{}", rids[0]);
+        assert!(rids[1].starts_with("c-") && rids[1].len() == 2 + 26, "{}", rids[1]);
+    }
+
     #[test]
     fn client_redaction_only_when_sensitive_headers_present() {
         let _g = serial_guard();
@@ -0,0 +1,114 @@
+//! Result lookup and chunking for `FetchResultStream`.
+//!
+//! A result is the most recent `agent_result` envelope enqueued in a run with the requested
+//! `parent_id`. When its payload carries a `blob_ref`, the result bytes are the referenced blob
+//! (read through a [`BlobSource`]); otherwise they are the envelope's `payload_json`. The bytes
+//! are then cut into fixed-size chunks so results of any size fit under gRPC message limits.
+
+use crate::orca_v1::{Envelope, FetchResultChunk};
+use event_log::{EventLogError, EventRecord};
+use serde_json::Value as JsonValue;
+use tonic::Status;
+
+/// Default chunk size when the request leaves `chunk_bytes` at 0.
+pub const DEFAULT_CHUNK_BYTES: usize = 64 * 1024;
+/// Upper bound on a requested chunk size; stays well below tonic's 4 MiB decode limit.
+pub const MAX_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Read access to attachment blobs by hex SHA-256 digest.
+#[allow(clippy::result_large_err)] // tonic::Status is large; matches service signatures
+pub trait BlobSource: Send + Sync {
+    fn fetch(&self, digest_hex: &str) -> Result<Vec<u8>, Status>;
+}
+
+#[allow(clippy::result_large_err)]
+fn blob_get(
+    digest_hex: &str,
+    get: impl FnOnce(&blob_store::Digest) -> Result<Vec<u8>, blob_store::Error>,
+) -> Result<Vec<u8>, Status> {
+    let digest = blob_store::Digest::from_hex(digest_hex)
+        .ok_or_else(|| Status::data_loss(format!("invalid blob digest '{}'", digest_hex)))?;
+    get(&digest).map_err(|e| match e {
+        blob_store::Error::NotFound => Status::not_found(format!("blob {} not found", digest_hex)),
+        e => Status::internal(format!("blob read failed: {}", e)),
+    })
+}
+
+impl<K: blob_store::KeyProvider> BlobSource for blob_store::BlobStore<K> {
+    fn fetch(&self, digest_hex: &str) -> Result<Vec<u8>, Status> {
+        blob_get(digest_hex, |d| self.get(d))
+    }
+}
+
+impl<K: blob_store::KeyProvider> BlobSource for blob_store::ReadOnlyBlobStore<K> {
+    fn fetch(&self, digest_hex: &str) -> Result<Vec<u8>, Status> {
+        blob_get(digest_hex, |d| self.get(d))
+    }
+}
+
+/// Latest `agent_result` envelope enqueued in `run_id` with the given `parent_id`, from the
+/// WAL records `recs` in file order. Records are consumed one at a time and only the latest
+/// match is kept, so memory does not grow with the WAL.
+#[allow(clippy::result_large_err)]
+pub fn find_result(
+    recs: impl IntoIterator<Item = Result<EventRecord<JsonValue>, EventLogError>>,
+    run_id: &str,
+    parent_id: &str,
+) -> Result<Option<Envelope>, Status> {
+    let mut found = None;
+    for rec in recs {
+        let rec = rec.map_err(|e| Status::internal(format!("io error: {}", e)))?;
+        let p = &rec.payload;
+        if p.get("event").and_then(|v| v.as_str()) != Some("task_enqueued")
+            || p.get("run_id").and_then(|v| v.as_str()) != Some(run_id)
+        {
+            continue;
+        }
+        let Some(env) =
+            p.get("envelope").and_then(|e| serde_json::from_value::<Envelope>(e.clone()).ok())
+        else {
+            continue;
+        };
+        if env.kind == "agent_result" && env.parent_id == parent_id {
+            found = Some(env);
+        }
+    }
+    Ok(found)
+}
+
+/// Result bytes for `env`: the referenced blob when the payload has a `blob_ref`, else the
+/// payload JSON itself. A `blob_ref` without a configured blob source is an error.
+#[allow(clippy::result_large_err)]
+pub fn result_bytes(env: &Envelope, blobs: Option<&dyn BlobSource>) -> Result<Vec<u8>, Status> {
+    let digest = serde_json::from_str::<JsonValue>(&env.payload_json)
+        .ok()
+        .and_then(|v| v.get("blob_ref")?.get("digest_sha256")?.as_str().map(str::to_string));
+    match (digest, blobs) {
+        (None, _) => Ok(env.payload_json.clone().into_bytes()),
+        (Some(d), Some(blobs)) => blobs.fetch(&d),
+        (Some(_), None) => Err(Status::failed_precondition(
+            "result is blob-backed but no blob store is configured",
+        )),
+    }
+}
+
+/// Split `bytes` into chunks of `chunk_bytes` (0 → [`DEFAULT_CHUNK_BYTES`], capped at
+/// [`MAX_CHUNK_BYTES`]). An empty result still yields one (empty, final) chunk.
+pub fn chunks(bytes: &[u8], chunk_bytes: u32) -> impl Iterator<Item = FetchResultChunk> + '_ {
+    let size = match chunk_bytes as usize {
+        0 => DEFAULT_CHUNK_BYTES,
+        n => n.min(MAX_CHUNK_BYTES),
+    };
+    let total = bytes.len() as u64;
+    let n = bytes.len().div_ceil(size).max(1);
+    (0..n).map(move |i| {
+        let start = (i * size).min(bytes.len());
+        let end = (start + size).min(bytes.len());
+        FetchResultChunk {
+            data: bytes[start..end].to_vec(),
+            offset: start as u64,
+            total_bytes: total,
+            last: i + 1 == n,
+        }
+    })
+}
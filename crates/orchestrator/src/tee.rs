@@ -0,0 +1,92 @@
+//! Best-effort mirroring of WAL events to secondary sinks.
+//!
+//! After a record is durably appended to the primary WAL, [`WalTee`] hands a copy to each
+//! registered [`WalSink`] (another WAL, a channel feeding analytics, a replication target).
+//! Sinks never affect the primary path: a failing sink is logged and skipped, and channel
+//! sinks use non-blocking sends, dropping the record when the channel is full.
+
+use event_log::{EventRecord, JsonlEventLog};
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Error returned by a sink; logged by the tee, never propagated.
+pub type SinkError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Destination for mirrored WAL records. Implementations should return quickly; slow
+/// consumers belong behind a channel sink.
+pub trait WalSink: Send + Sync {
+    /// Short label used in logs.
+    fn name(&self) -> &str {
+        "sink"
+    }
+    /// Forward one record that is already durable in the primary WAL.
+    fn forward(&self, rec: &EventRecord<JsonValue>) -> Result<(), SinkError>;
+}
+
+impl WalSink for JsonlEventLog {
+    fn name(&self) -> &str {
+        "jsonl"
+    }
+    fn forward(&self, rec: &EventRecord<JsonValue>) -> Result<(), SinkError> {
+        self.append(rec.id, rec.ts_ms, &rec.payload)?;
+        Ok(())
+    }
+}
+
+impl WalSink for std::sync::mpsc::SyncSender<EventRecord<JsonValue>> {
+    fn name(&self) -> &str {
+        "std_channel"
+    }
+    fn forward(&self, rec: &EventRecord<JsonValue>) -> Result<(), SinkError> {
+        self.try_send(rec.clone()).map_err(|e| e.to_string().into())
+    }
+}
+
+impl WalSink for tokio::sync::mpsc::Sender<EventRecord<JsonValue>> {
+    fn name(&self) -> &str {
+        "tokio_channel"
+    }
+    fn forward(&self, rec: &EventRecord<JsonValue>) -> Result<(), SinkError> {
+        self.try_send(rec.clone()).map_err(|e| e.to_string().into())
+    }
+}
+
+/// Fan-out of primary WAL records to secondary sinks, in registration order.
+#[derive(Clone, Default)]
+pub struct WalTee {
+    sinks: Vec<Arc<dyn WalSink>>,
+}
+
+impl WalTee {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register another sink.
+    pub fn with_sink(mut self, sink: Arc<dyn WalSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+    /// Number of registered sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+    /// Forward `rec` to every sink; failures are logged and do not stop later sinks.
+    pub fn forward(&self, rec: &EventRecord<JsonValue>) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.forward(rec) {
+                warn!(sink = sink.name(), id = rec.id, error = %e, "wal tee forward failed");
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for WalTee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.sinks.iter().map(|s| s.name()).collect();
+        f.debug_struct("WalTee").field("sinks", &names).finish()
+    }
+}
@@ -0,0 +1,108 @@
+//! Test helpers for asserting deterministic replay.
+//!
+//! [`assert_deterministic_replay`] runs a request sequence twice, each time against a fresh
+//! service with a [`VirtualClock`] and a reseeded id counter, and asserts both WALs are
+//! byte-identical. Clock and id counter are process-wide, so runs are serialized through a
+//! shared lock; avoid running unrelated orchestrator calls concurrently in the same test
+//! binary.
+
+use crate::clock::{self, VirtualClock};
+use crate::orca_v1::{orchestrator_server::Orchestrator, StartRunRequest, SubmitTaskRequest};
+use crate::OrchestratorService;
+use event_log::JsonlEventLog;
+use std::sync::{Arc, OnceLock};
+use tonic::Request;
+
+/// Virtual clock start for each run.
+pub const START_MS: u64 = 1_000_000;
+/// First event id for each run.
+pub const FIRST_ID: u64 = 1;
+
+/// One step of a replayable request sequence.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// Call `start_run`.
+    StartRun(StartRunRequest),
+    /// Call `submit_task`.
+    SubmitTask(SubmitTaskRequest),
+    /// Advance the virtual clock.
+    AdvanceMs(u64),
+}
+
+static SERIAL: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+
+/// Run `steps` against a fresh service (permissive policy, virtual clock, reseeded ids) and
+/// return the resulting WAL bytes. RPC errors are part of the sequence and are ignored;
+/// whatever they wrote to the WAL is still compared.
+pub async fn run_to_wal(steps: &[Step]) -> Vec<u8> {
+    let _serial = SERIAL.get_or_init(|| tokio::sync::Mutex::new(())).lock().await;
+    let dir = tempfile_dir();
+    let wal = dir.join("replay.jsonl");
+    let policy = dir.join("policy.yaml");
+    std::fs::write(&policy, "rules: []\n").expect("write testkit policy");
+
+    let original = clock::process_clock();
+    let vclk = Arc::new(VirtualClock::new(START_MS));
+    clock::set_process_clock(vclk.clone());
+    orca_core::ids::reseed_monotonic_id(FIRST_ID);
+
+    let svc = OrchestratorService::new(JsonlEventLog::open(&wal).expect("open testkit wal"));
+    svc.load_policy_from_path(&policy).expect("load testkit policy");
+    for step in steps {
+        match step {
+            Step::StartRun(r) => {
+                let _ = svc.start_run(Request::new(r.clone())).await;
+            }
+            Step::SubmitTask(r) => {
+                let _ = svc.submit_task(Request::new(r.clone())).await;
+            }
+            Step::AdvanceMs(ms) => vclk.advance_ms(*ms),
+        }
+    }
+    clock::set_process_clock(original);
+    drop(svc);
+
+    let bytes = std::fs::read(&wal).expect("read testkit wal");
+    let _ = std::fs::remove_dir_all(&dir);
+    bytes
+}
+
+/// Run `steps` twice from identical initial state and panic with a line diff if the WALs
+/// differ.
+pub async fn assert_deterministic_replay(steps: &[Step]) {
+    let a = run_to_wal(steps).await;
+    let b = run_to_wal(steps).await;
+    if a != b {
+        panic!("WAL is not deterministic across replays:\n{}", line_diff(&a, &b));
+    }
+}
+
+/// Line-oriented diff of two WALs (`-` first run, `+` second run).
+pub fn line_diff(a: &[u8], b: &[u8]) -> String {
+    let (a, b) = (String::from_utf8_lossy(a), String::from_utf8_lossy(b));
+    let (la, lb): (Vec<&str>, Vec<&str>) = (a.lines().collect(), b.lines().collect());
+    let mut out = String::new();
+    for i in 0..la.len().max(lb.len()) {
+        let (x, y) = (la.get(i), lb.get(i));
+        if x != y {
+            out.push_str(&format!("line {}:\n", i + 1));
+            if let Some(x) = x {
+                out.push_str(&format!("- {}\n", x));
+            }
+            if let Some(y) = y {
+                out.push_str(&format!("+ {}\n", y));
+            }
+        }
+    }
+    out
+}
+
+fn tempfile_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "orca-testkit-{}-{}",
+        std::process::id(),
+        orca_core::ids::new_trace_id()
+    ));
+    std::fs::create_dir_all(&dir).expect("create testkit dir");
+    dir
+}
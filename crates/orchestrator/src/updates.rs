@@ -0,0 +1,101 @@
+//! Task lookup and JSON merge-patch for `UpdateTask`.
+//!
+//! A task's current state is its `task_enqueued` envelope with every later `task_updated`
+//! patch for it applied in WAL order, so it is rebuilt from the log alone (one streaming pass,
+//! never held in memory) and survives restarts without extra index state. A task is terminal
+//! once an `agent_result` or `agent_error` naming it as `parent_id` has been enqueued; results
+//! and errors themselves are never patchable.
+
+use crate::orca_v1::Envelope;
+use event_log::{EventLogError, EventRecord};
+use serde_json::Value as JsonValue;
+use tonic::Status;
+
+/// Apply an RFC 7396 JSON merge-patch to `target`: object members merge recursively, `null`
+/// removes a member, and any non-object patch replaces the target outright.
+pub fn merge_patch(target: &mut JsonValue, patch: &JsonValue) {
+    let JsonValue::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = JsonValue::Object(serde_json::Map::new());
+    }
+    if let JsonValue::Object(obj) = target {
+        for (k, v) in patch {
+            if v.is_null() {
+                obj.remove(k);
+            } else {
+                merge_patch(obj.entry(k.clone()).or_insert(JsonValue::Null), v);
+            }
+        }
+    }
+}
+
+/// Current state of task `envelope_id` in `run_id`, with earlier `task_updated` patches applied
+/// to its payload, from the WAL records `recs` in file order. Records are consumed one at a
+/// time, and the scan stops as soon as the task turns out to be terminal.
+///
+/// # Errors
+/// NOT_FOUND when the run never enqueued the envelope, FAILED_PRECONDITION when it is a
+/// result/error or has already been answered by one, INTERNAL when reading the WAL fails.
+#[allow(clippy::result_large_err)] // tonic::Status is large; matches service signatures
+pub fn current_task(
+    recs: impl IntoIterator<Item = Result<EventRecord<JsonValue>, EventLogError>>,
+    run_id: &str,
+    envelope_id: &str,
+) -> Result<Envelope, Status> {
+    let mut task: Option<(Envelope, JsonValue)> = None;
+    for rec in recs {
+        let rec = rec.map_err(|e| Status::internal(format!("io error: {}", e)))?;
+        let p = &rec.payload;
+        if p.get("run_id").and_then(|v| v.as_str()) != Some(run_id) {
+            continue;
+        }
+        match p.get("event").and_then(|v| v.as_str()) {
+            Some("task_enqueued") => {
+                let Some(env) = p
+                    .get("envelope")
+                    .and_then(|e| serde_json::from_value::<Envelope>(e.clone()).ok())
+                else {
+                    continue;
+                };
+                if env.id == envelope_id && task.is_none() {
+                    if env.kind != "agent_task" {
+                        return Err(Status::failed_precondition(format!(
+                            "envelope '{}' is a terminal {}",
+                            envelope_id, env.kind
+                        )));
+                    }
+                    let payload = parse_payload(&env.payload_json);
+                    task = Some((env, payload));
+                } else if env.parent_id == envelope_id
+                    && matches!(env.kind.as_str(), "agent_result" | "agent_error")
+                {
+                    return Err(Status::failed_precondition(format!(
+                        "task '{}' already has an {}",
+                        envelope_id, env.kind
+                    )));
+                }
+            }
+            Some("task_updated")
+                if p.get("envelope_id").and_then(|v| v.as_str()) == Some(envelope_id) =>
+            {
+                if let (Some((_, payload)), Some(patch)) = (task.as_mut(), p.get("patch")) {
+                    merge_patch(payload, patch);
+                }
+            }
+            _ => {}
+        }
+    }
+    let (mut env, payload) = task.ok_or_else(|| {
+        Status::not_found(format!("no task '{}' in run '{}'", envelope_id, run_id))
+    })?;
+    env.payload_json = payload.to_string();
+    Ok(env)
+}
+
+/// `payload_json` as JSON; empty (or unparsable, which admission rejects) reads as `null`.
+fn parse_payload(payload_json: &str) -> JsonValue {
+    serde_json::from_str(payload_json).unwrap_or(JsonValue::Null)
+}
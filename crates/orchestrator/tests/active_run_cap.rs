@@ -0,0 +1,66 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::orchestrator_server::Orchestrator;
+use orchestrator::{orca_v1::*, OrchestratorService};
+use tonic::{Code, Request};
+
+fn start(run: &str) -> Request<StartRunRequest> {
+    Request::new(StartRunRequest {
+        workflow_id: run.into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    })
+}
+
+fn task(run: &str, id: &str, kind: &str) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: run.into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: kind.into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+    })
+}
+
+#[tokio::test]
+async fn completed_runs_are_evicted_and_active_runs_retained() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("cap.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log).with_max_active_runs(2);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    svc.start_run(start("r1")).await.unwrap();
+    svc.start_run(start("r2")).await.unwrap();
+    let over = svc.start_run(start("r3")).await.unwrap_err();
+    assert_eq!(over.code(), Code::ResourceExhausted);
+    // Re-starting an active run does not count twice.
+    svc.start_run(start("r2")).await.unwrap();
+
+    svc.submit_task(task("r1", "t1", "agent_task")).await.unwrap();
+    svc.submit_task(task("r2", "t2", "agent_task")).await.unwrap();
+    // agent_result completes r1: run_summary is written and its entries are dropped.
+    svc.submit_task(task("r1", "t3", "agent_result")).await.unwrap();
+    assert_eq!(svc.active_run_count(), 1);
+    assert!(!svc.index.usage_by_run.contains_key("r1"));
+    assert!(!svc.index.run_start_ts_by_run.contains_key("r1"));
+    assert!(svc.index.usage_by_run_agent.iter().all(|kv| kv.key().0 != "r1"));
+    assert!(svc.index.usage_by_run.contains_key("r2"));
+    assert!(svc.index.run_start_ts_by_run.contains_key("r2"));
+
+    // The freed slot admits a new run; the evicted run no longer takes tasks.
+    svc.start_run(start("r3")).await.unwrap();
+    assert_eq!(svc.active_run_count(), 2);
+    let late = svc.submit_task(task("r1", "t4", "agent_task")).await.unwrap_err();
+    assert_eq!(late.code(), Code::FailedPrecondition);
+}
@@ -0,0 +1,96 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use tonic::Request;
+
+fn envelope(id: &str, kind: &str, payload_json: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: String::new(),
+        trace_id: "t".into(),
+        agent: "A".into(),
+        kind: kind.into(),
+        payload_json: payload_json.into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: None,
+    }
+}
+
+async fn run_with_error(svc: &OrchestratorService, dir: &std::path::Path) {
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    for env in [
+        envelope("m1", "agent_task", "{}"),
+        envelope(
+            "m2",
+            "agent_error",
+            r#"{"message":"tool crashed","code":"E_TOOL","retryable":true}"#,
+        ),
+    ] {
+        let req = SubmitTaskRequest { run_id: "r1".into(), task: Some(env) };
+        svc.submit_task(Request::new(req)).await.unwrap();
+    }
+}
+
+fn payloads(path: &std::path::Path, kind: &str) -> Vec<Value> {
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(path).unwrap().read_range(0, u64::MAX).unwrap();
+    recs.into_iter().map(|r| r.payload).filter(|p| p["event"] == kind).collect()
+}
+
+#[tokio::test]
+async fn agent_error_records_task_failed_and_counts_in_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("err.jsonl");
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    run_with_error(&svc, dir.path()).await;
+
+    let failed = payloads(&path, "task_failed");
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0]["run_id"], "r1");
+    assert_eq!(failed[0]["task_id"], "m2");
+    assert_eq!(failed[0]["code"], "E_TOOL");
+    assert_eq!(failed[0]["retryable"], true);
+    assert_eq!(failed[0]["failed_tasks"], 1);
+    assert!(!failed[0].to_string().contains("tool crashed"));
+    // Counting only: the run stays open until it completes normally.
+    assert!(!svc.is_run_completed("r1"));
+    assert!(payloads(&path, "run_summary").is_empty());
+
+    let req =
+        SubmitTaskRequest { run_id: "r1".into(), task: Some(envelope("m3", "agent_result", "{}")) };
+    svc.submit_task(Request::new(req)).await.unwrap();
+    let summary = &payloads(&path, "run_summary")[0];
+    assert_eq!(summary["failed_tasks"], 1);
+    assert_eq!(summary["state"], "completed");
+    assert!(svc.is_run_completed("r1") && !svc.is_run_failed("r1"));
+}
+
+#[tokio::test]
+async fn agent_error_fails_run_when_configured() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fail.jsonl");
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap())
+        .with_fail_run_on_agent_error(true);
+    run_with_error(&svc, dir.path()).await;
+
+    assert_eq!(payloads(&path, "task_failed").len(), 1);
+    let summaries = payloads(&path, "run_summary");
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0]["state"], "failed");
+    assert_eq!(summaries[0]["failed_tasks"], 1);
+    assert!(svc.is_run_completed("r1") && svc.is_run_failed("r1"));
+
+    // The failed state and count survive a restart.
+    let restarted = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    restarted.replay_on_start().unwrap();
+    assert!(restarted.is_run_failed("r1"));
+    assert_eq!(restarted.index.failed_tasks_by_run.get("r1").map(|v| *v.value()), Some(1));
+}
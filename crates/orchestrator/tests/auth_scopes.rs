@@ -0,0 +1,90 @@
+use event_log::JsonlEventLog;
+use orchestrator::auth::{Scope, TokenScopes};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use tonic::{Code, Request};
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    let log = JsonlEventLog::open(dir.path().join("auth.jsonl")).unwrap();
+    let scopes = TokenScopes::from_json_str(
+        r#"{"reader": ["stream_events", "fetch_result"], "admin": ["*"]}"#,
+    )
+    .unwrap();
+    let svc = OrchestratorService::new(log).with_auth_scopes(scopes);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn with_token<T>(msg: T, token: &str) -> Request<T> {
+    let mut req = Request::new(msg);
+    req.metadata_mut().insert("authorization", token.parse().unwrap());
+    req
+}
+
+fn submit(id: &str) -> SubmitTaskRequest {
+    SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "t".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: orca_core::ids::now_ms(),
+            usage: None,
+        }),
+    }
+}
+
+fn stream() -> StreamEventsRequest {
+    StreamEventsRequest { run_id: "r1".into(), ..Default::default() }
+}
+
+#[tokio::test]
+async fn read_only_token_can_stream_but_not_submit() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    assert!(svc.stream_events(with_token(stream(), "reader")).await.is_ok());
+    assert!(svc
+        .fetch_result(with_token(
+            FetchResultRequest { run_id: "r1".into(), parent_id: String::new() },
+            "reader"
+        ))
+        .await
+        .is_ok());
+    let err = svc.submit_task(with_token(submit("m1"), "reader")).await.unwrap_err();
+    assert_eq!(err.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn admin_token_can_stream_and_submit() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    assert!(
+        svc.submit_task(with_token(submit("m1"), "admin")).await.unwrap().into_inner().accepted
+    );
+    assert!(svc.stream_events(with_token(stream(), "admin")).await.is_ok());
+}
+
+#[tokio::test]
+async fn unknown_or_missing_token_is_unauthenticated() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let err = svc.stream_events(with_token(stream(), "nope")).await.unwrap_err();
+    assert_eq!(err.code(), Code::Unauthenticated);
+    let err = svc.stream_events(Request::new(stream())).await.unwrap_err();
+    assert_eq!(err.code(), Code::Unauthenticated);
+}
+
+#[test]
+fn tokens_file_rejects_unknown_scope() {
+    let err = TokenScopes::from_json_str(r#"{"t": ["delete_everything"]}"#).unwrap_err();
+    assert!(err.contains("delete_everything"), "{err}");
+    let scopes = TokenScopes::default().with_token("t", &[Scope::FetchResult]);
+    assert!(scopes.authorize(Some("t"), Scope::FetchResult).is_ok());
+}
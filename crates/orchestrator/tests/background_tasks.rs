@@ -0,0 +1,55 @@
+use event_log::JsonlEventLog;
+use orchestrator::OrchestratorService;
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn construction_needs_no_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("w.jsonl")).unwrap())
+        .with_run_idle_timeout(Duration::from_millis(50))
+        .with_index_snapshots(dir.path().join("snap.json"), Duration::from_millis(10));
+    assert!(!svc.is_run_completed("r"));
+}
+
+#[tokio::test]
+async fn dropping_the_guard_stops_background_tasks() {
+    let dir = tempfile::tempdir().unwrap();
+    let snap = dir.path().join("snap.json");
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("w.jsonl")).unwrap())
+        .with_run_idle_timeout(Duration::from_secs(60))
+        .with_index_snapshots(&snap, Duration::from_millis(10));
+    let tasks = svc.start_background_tasks();
+    assert_eq!(tasks.len(), 2);
+
+    let mut waited = 0;
+    while !snap.exists() {
+        assert!(waited < 5_000, "snapshot task never wrote");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        waited += 10;
+    }
+    drop(tasks);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    std::fs::remove_file(&snap).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!snap.exists(), "aborted snapshot task wrote again");
+}
+
+#[test]
+fn completed_runs_are_bounded_oldest_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("w.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    for (i, run) in ["r1", "r2", "r3"].iter().enumerate() {
+        let id = i as u64 * 2 + 1;
+        log.append(id, id, &json!({"event":"start_run","run_id":run})).unwrap();
+        log.append(id + 1, id + 1, &json!({"event":"run_summary","run_id":run})).unwrap();
+    }
+    let svc = OrchestratorService::new(log).with_completed_runs_cap(2);
+    svc.replay_on_start().unwrap();
+    assert!(!svc.is_run_completed("r1"), "oldest completed run is forgotten");
+    assert!(svc.is_run_completed("r2") && svc.is_run_completed("r3"));
+
+    let svc = svc.with_completed_runs_cap(1);
+    assert!(!svc.is_run_completed("r2") && svc.is_run_completed("r3"));
+}
@@ -18,7 +18,9 @@ async fn warn_and_exceed_budget() {
     let start = StartRunRequest {
         workflow_id: "run1".into(),
         initial_task: None,
-        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0 }),
+        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
     };
     svc.start_run(Request::new(start)).await.unwrap();
 
@@ -75,12 +77,16 @@ async fn isolation_between_runs() {
     let start1 = StartRunRequest {
         workflow_id: "rA".into(),
         initial_task: None,
-        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0 }),
+        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
     };
     let start2 = StartRunRequest {
         workflow_id: "rB".into(),
         initial_task: None,
-        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0 }),
+        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
     };
     svc.start_run(Request::new(start1)).await.unwrap();
     svc.start_run(Request::new(start2)).await.unwrap();
@@ -111,3 +117,215 @@ async fn isolation_between_runs() {
         .await
         .is_ok());
 }
+
+#[tokio::test]
+async fn run_summary_records_exceeded_with_zero_remaining() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("s.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let start = StartRunRequest {
+        workflow_id: "rS".into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 1, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    };
+    svc.start_run(Request::new(start)).await.unwrap();
+    let task = |id: &str| Envelope {
+        id: id.into(),
+        parent_id: "".into(),
+        trace_id: "tr".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        payload_json: "{}".into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: None,
+    };
+    for id in ["t1", "t2", "t3"] {
+        let _ = svc
+            .submit_task(Request::new(SubmitTaskRequest {
+                run_id: "rS".into(),
+                task: Some(task(id)),
+            }))
+            .await;
+    }
+
+    let recs: Vec<event_log::EventRecord<serde_json::Value>> = log.read_range(0, u64::MAX).unwrap();
+    let summaries: Vec<&serde_json::Value> = recs
+        .iter()
+        .map(|r| &r.payload)
+        .filter(|p| p["event"] == "run_summary" && p["run_id"] == "rS")
+        .collect();
+    assert_eq!(summaries.len(), 1, "summary is emitted once, when the run is terminated");
+    assert_eq!(summaries[0]["budget_state"], "Exceeded");
+    assert_eq!(summaries[0]["remaining"]["tokens"], 0);
+    assert!(summaries[0]["remaining"]["cost_micros"].is_null());
+    assert_eq!(summaries[0]["tokens"], 1);
+}
+
+#[tokio::test]
+async fn run_summary_on_result_records_within_and_remaining() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("w.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let start = StartRunRequest {
+        workflow_id: "rW".into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    };
+    svc.start_run(Request::new(start)).await.unwrap();
+    let env = Envelope {
+        id: "res".into(),
+        parent_id: "".into(),
+        trace_id: "tr".into(),
+        agent: "A".into(),
+        kind: "agent_result".into(),
+        payload_json: "{}".into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: None,
+    };
+    svc.submit_task(Request::new(SubmitTaskRequest { run_id: "rW".into(), task: Some(env) }))
+        .await
+        .unwrap();
+
+    let recs: Vec<event_log::EventRecord<serde_json::Value>> = log.read_range(0, u64::MAX).unwrap();
+    let summary =
+        recs.iter().map(|r| &r.payload).find(|p| p["event"] == "run_summary").expect("run_summary");
+    assert_eq!(summary["budget_state"], "Within");
+    assert_eq!(summary["remaining"]["tokens"], 99);
+}
+
+#[tokio::test]
+async fn configured_default_token_increment_applies_to_tasks_without_usage() {
+    for (inc, expected) in [(0u64, 0u64), (7, 21)] {
+        let dir = tempfile::tempdir().unwrap();
+        let log = JsonlEventLog::open(dir.path().join("inc.jsonl")).unwrap();
+        let svc = OrchestratorService::new(log).with_default_token_increment(inc);
+        let policy_path = dir.path().join("policy.yaml");
+        std::fs::write(&policy_path, "rules: []\n").unwrap();
+        svc.load_policy_from_path(&policy_path).unwrap();
+        let start = StartRunRequest {
+            workflow_id: "run1".into(),
+            budget: Some(Budget { max_tokens: 1000, max_cost_micros: 0, ..Default::default() }),
+            ..Default::default()
+        };
+        svc.start_run(Request::new(start)).await.unwrap();
+        for i in 0..3 {
+            let env = Envelope {
+                id: format!("t{i}"),
+                parent_id: "".into(),
+                trace_id: "tr".into(),
+                agent: "A".into(),
+                kind: "agent_task".into(),
+                payload_json: "{}".into(),
+                timeout_ms: 0,
+                protocol_version: 1,
+                ts_ms: 0,
+                usage: None,
+            };
+            let req = SubmitTaskRequest { run_id: "run1".into(), task: Some(env) };
+            svc.submit_task(Request::new(req)).await.unwrap();
+        }
+        let (tokens, cost) = *svc.index.usage_by_run.get("run1").unwrap();
+        assert_eq!((tokens, cost), (expected, 0), "increment {inc}");
+    }
+}
+
+#[tokio::test]
+async fn usage_update_deltas_sum_to_run_totals() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("deltas.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let start = StartRunRequest { workflow_id: "run1".into(), ..Default::default() };
+    svc.start_run(Request::new(start)).await.unwrap();
+    let usage = [Some((5, 50)), None, Some((12, 7)), Some((0, 300))];
+    for (i, u) in usage.into_iter().enumerate() {
+        let env = Envelope {
+            id: format!("t{i}"),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: u.map(|(tokens, cost_micros)| UsageHint { tokens, cost_micros }),
+        };
+        let req = SubmitTaskRequest { run_id: "run1".into(), task: Some(env) };
+        svc.submit_task(Request::new(req)).await.unwrap();
+    }
+
+    let recs: Vec<event_log::EventRecord<serde_json::Value>> = log.read_range(0, u64::MAX).unwrap();
+    let updates: Vec<&serde_json::Value> =
+        recs.iter().map(|r| &r.payload).filter(|p| p["event"] == "usage_update").collect();
+    assert_eq!(updates.len(), 4);
+    let field = |p: &serde_json::Value, k: &str| p[k].as_u64().unwrap();
+    let sum = |k: &str| updates.iter().map(|p| field(p, k)).sum::<u64>();
+    let last = updates.last().unwrap();
+    assert_eq!(sum("delta_tokens"), field(last, "tokens"));
+    assert_eq!(sum("delta_cost_micros"), field(last, "cost_micros"));
+    assert_eq!((field(last, "tokens"), field(last, "cost_micros")), (19, 357));
+    // Tasks reporting no tokens are charged the default increment.
+    assert_eq!(field(updates[1], "delta_tokens"), 1);
+    assert_eq!(field(updates[3], "delta_tokens"), 1);
+}
+
+#[tokio::test]
+async fn run_summary_breakdown_is_sorted_by_agent() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("agents.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let start = StartRunRequest { workflow_id: "run1".into(), ..Default::default() };
+    svc.start_run(Request::new(start)).await.unwrap();
+    let agents = ["zeta", "alpha", "mu", "beta", "omega", "delta"];
+    for (i, agent) in agents.iter().enumerate() {
+        let env = Envelope {
+            id: format!("t{i}"),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: agent.to_string(),
+            kind: if i + 1 == agents.len() { "agent_result" } else { "agent_task" }.into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        };
+        let req = SubmitTaskRequest { run_id: "run1".into(), task: Some(env) };
+        svc.submit_task(Request::new(req)).await.unwrap();
+    }
+
+    let recs: Vec<event_log::EventRecord<serde_json::Value>> = log.read_range(0, u64::MAX).unwrap();
+    let summary =
+        recs.iter().map(|r| &r.payload).find(|p| p["event"] == "run_summary").expect("run_summary");
+    let order: Vec<&str> = summary["by_agent"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["agent"].as_str().unwrap())
+        .collect();
+    let mut expected = agents.to_vec();
+    expected.sort_unstable();
+    assert_eq!(order, expected);
+}
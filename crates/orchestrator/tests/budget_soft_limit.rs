@@ -0,0 +1,89 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use tonic::Request;
+
+fn task(id: &str, tokens: u64) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "run1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: Some(UsageHint { tokens, cost_micros: 0 }),
+        }),
+    })
+}
+
+#[tokio::test]
+async fn soft_limit_flags_tasks_until_hard_cap_blocks() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("soft.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let budget = Budget { max_tokens: 100, soft_max_tokens: 50, ..Default::default() };
+    let started = svc
+        .start_run(Request::new(StartRunRequest {
+            workflow_id: "run1".into(),
+            budget: Some(budget.clone()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(started.budget, Some(budget));
+
+    // Cumulative tokens: 30, 50 (at the soft limit, not past it), 60, 90, then 110 > hard cap.
+    let mut flags = Vec::new();
+    for (i, tokens) in [30, 20, 10, 30].into_iter().enumerate() {
+        let resp = svc.submit_task(task(&format!("t{i}"), tokens)).await.unwrap().into_inner();
+        assert!(resp.accepted);
+        flags.push(resp.flagged);
+    }
+    assert_eq!(flags, [false, false, true, true]);
+    let err = svc.submit_task(task("t4", 20)).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    let soft: Vec<&Value> =
+        recs.iter().map(|r| &r.payload).filter(|p| p["event"] == "budget_soft_exceeded").collect();
+    assert_eq!(soft.len(), 1, "emitted once, on crossing");
+    assert_eq!(soft[0]["run_id"], "run1");
+    assert_eq!(soft[0]["tokens"], 60);
+    assert_eq!(soft[0]["soft_max_tokens"], 50);
+    let kinds: Vec<&str> = recs.iter().filter_map(|r| r.payload["event"].as_str()).collect();
+    let soft_at = kinds.iter().position(|k| *k == "budget_soft_exceeded").unwrap();
+    let hard_at = kinds.iter().position(|k| *k == "budget_exceeded").unwrap();
+    assert!(soft_at < hard_at);
+}
+
+#[tokio::test]
+async fn runs_without_soft_limit_are_never_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("nosoft.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.start_run(Request::new(StartRunRequest {
+        workflow_id: "run1".into(),
+        budget: Some(Budget { max_tokens: 100, ..Default::default() }),
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+
+    let resp = svc.submit_task(task("t0", 95)).await.unwrap().into_inner();
+    assert!(resp.accepted && !resp.flagged);
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    assert!(recs.iter().all(|r| r.payload["event"] != "budget_soft_exceeded"));
+}
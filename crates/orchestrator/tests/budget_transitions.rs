@@ -0,0 +1,69 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use tonic::Request;
+
+fn task(id: &str, tokens: u64) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "run1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: Some(UsageHint { tokens, cost_micros: 0 }),
+        }),
+    })
+}
+
+#[tokio::test]
+async fn climbing_run_emits_exactly_one_event_per_transition() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("bt.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.start_run(Request::new(StartRunRequest {
+        workflow_id: "run1".into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    }))
+    .await
+    .unwrap();
+
+    // Cumulative tokens: 50, 80, 85, 91, 93, 103, 104.
+    for (i, tokens) in [50, 30, 5, 6, 2].into_iter().enumerate() {
+        svc.submit_task(task(&format!("t{i}"), tokens)).await.unwrap();
+    }
+    assert!(svc.submit_task(task("t5", 10)).await.is_err());
+    assert!(svc.submit_task(task("t6", 1)).await.is_err());
+
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    let transitions: Vec<(String, String, u64)> = recs
+        .iter()
+        .filter(|r| r.payload["event"] == "budget_state_changed")
+        .map(|r| {
+            assert_eq!(r.payload["run_id"], "run1");
+            (
+                r.payload["from"].as_str().unwrap().to_string(),
+                r.payload["to"].as_str().unwrap().to_string(),
+                r.payload["tokens"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    let expected = [
+        ("Within", "Warning80", 80),
+        ("Warning80", "Warning90", 91),
+        ("Warning90", "Exceeded", 103),
+    ];
+    assert_eq!(transitions, expected.map(|(f, t, n)| (f.to_string(), t.to_string(), n)).to_vec());
+}
@@ -0,0 +1,32 @@
+use orchestrator::CaptureSampler;
+
+#[test]
+fn same_request_id_gets_same_decision_across_instances() {
+    let a = CaptureSampler::new(0.3);
+    let b = CaptureSampler::new(0.3);
+    for i in 0..1_000 {
+        let rid = format!("R{i}");
+        assert_eq!(a.should_capture(&rid), b.should_capture(&rid), "{rid}");
+    }
+}
+
+#[test]
+fn inclusion_rate_approximates_configured_rate() {
+    for rate in [0.1, 0.5, 0.9] {
+        let s = CaptureSampler::new(rate);
+        let n = 20_000;
+        let hits = (0..n).filter(|i| s.should_capture(&format!("req-{i}"))).count();
+        let got = hits as f64 / n as f64;
+        assert!((got - rate).abs() < 0.02, "rate {rate}: observed {got}");
+    }
+}
+
+#[test]
+fn edge_rates_are_all_or_nothing() {
+    let all = CaptureSampler::new(1.5);
+    let none = CaptureSampler::new(-1.0);
+    assert_eq!(all.rate(), 1.0);
+    assert_eq!(none.rate(), 0.0);
+    assert!((0..100).all(|i| all.should_capture(&i.to_string())));
+    assert!((0..100).all(|i| !none.should_capture(&i.to_string())));
+}
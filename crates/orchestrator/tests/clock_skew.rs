@@ -0,0 +1,55 @@
+use event_log::JsonlEventLog;
+use orchestrator::clock::{set_process_clock, VirtualClock};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::{Code, Request};
+
+fn task(id: &str, timeout_ms: u64, ts_ms: u64) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms,
+            protocol_version: 1,
+            ts_ms,
+            usage: None,
+        }),
+    })
+}
+
+// One test per binary: it installs the process clock.
+#[tokio::test]
+async fn ttl_and_future_checks_honour_the_skew_tolerance_at_its_boundaries() {
+    let now = 1_000_000;
+    set_process_clock(Arc::new(VirtualClock::new(now)));
+    let dir = tempfile::tempdir().unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("skew.jsonl")).unwrap())
+        .with_clock_skew_tolerance(Duration::from_millis(500));
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let submit =
+        |id: &str, timeout_ms: u64, ts_ms: u64| svc.submit_task(task(id, timeout_ms, ts_ms));
+
+    // Future-dated: accepted up to the tolerance ahead, rejected one ms past it.
+    assert!(submit("ahead-at-limit", 100, now + 500).await.is_ok());
+    let err = submit("ahead-past-limit", 100, now + 501).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert_eq!(err.message(), "timestamp skew");
+
+    // A client running behind: age 1_100 with a 1_000 ms TTL is within TTL + tolerance...
+    assert!(submit("behind-in-grace", 1_000, now - 1_100).await.is_ok());
+    assert!(submit("behind-at-limit", 1_000, now - 1_500).await.is_ok());
+    // ...and expires only once the age passes it.
+    let err = submit("behind-past-limit", 1_000, now - 1_501).await.unwrap_err();
+    assert_eq!(err.code(), Code::DeadlineExceeded);
+    // No TTL means no expiry, however old.
+    assert!(submit("no-ttl", 0, 1).await.is_ok());
+}
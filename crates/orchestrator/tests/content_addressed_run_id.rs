@@ -0,0 +1,66 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::{content_addressed_run_id, OrchestratorService, RunIdMode};
+use tonic::Request;
+
+fn service(dir: &tempfile::TempDir, mode: RunIdMode) -> OrchestratorService {
+    let log = JsonlEventLog::open(dir.path().join("ids.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log).with_run_id_mode(mode);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn start(nonce: &str) -> StartRunRequest {
+    StartRunRequest {
+        workflow_id: "client-chosen".into(),
+        initial_task: Some(Envelope {
+            id: "t0".into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{\"goal\":\"x\"}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+        budget: None,
+        client_id: "client-1".into(),
+        nonce: nonce.into(),
+    }
+}
+
+async fn run_id(svc: &OrchestratorService, req: StartRunRequest) -> String {
+    svc.start_run(Request::new(req)).await.unwrap().into_inner().run_id
+}
+
+#[tokio::test]
+async fn nonces_distinguish_runs_and_equal_nonces_collide() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir, RunIdMode::ContentAddressed);
+
+    let a = run_id(&svc, start("n1")).await;
+    let b = run_id(&svc, start("n2")).await;
+    let a_again = run_id(&svc, start("n1")).await;
+
+    assert_ne!(a, b, "different nonces yield different ids");
+    assert_eq!(a, a_again, "identical inputs collide for idempotency");
+    assert!(a.starts_with("run_") && a.len() == 4 + 64, "{a}");
+    let req = start("n1");
+    assert_eq!(a, content_addressed_run_id("client-1", "n1", req.initial_task.as_ref()));
+
+    // A different client with the same nonce gets its own id.
+    let mut other = start("n1");
+    other.client_id = "client-2".into();
+    assert_ne!(run_id(&svc, other).await, a);
+}
+
+#[tokio::test]
+async fn client_supplied_id_remains_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir, RunIdMode::default());
+    assert_eq!(run_id(&svc, start("n1")).await, "client-chosen");
+}
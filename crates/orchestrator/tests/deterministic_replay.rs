@@ -0,0 +1,58 @@
+use orchestrator::orca_v1::*;
+use orchestrator::testkit::{assert_deterministic_replay, line_diff, run_to_wal, Step, START_MS};
+
+fn env(id: &str, agent: &str, kind: &str, payload: &str, tokens: u64) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: "".into(),
+        trace_id: "tr".into(),
+        agent: agent.into(),
+        kind: kind.into(),
+        payload_json: payload.into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: START_MS,
+        usage: (tokens > 0).then_some(UsageHint { tokens, cost_micros: tokens * 10 }),
+    }
+}
+
+fn submit(run: &str, e: Envelope) -> Step {
+    Step::SubmitTask(SubmitTaskRequest { run_id: run.into(), task: Some(e) })
+}
+
+#[tokio::test]
+async fn multi_agent_run_with_budget_and_redaction_replays_byte_identically() {
+    let steps = vec![
+        Step::StartRun(StartRunRequest {
+            workflow_id: "wf".into(),
+            initial_task: Some(env("i0", "planner", "agent_task", "{}", 0)),
+            budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+            client_id: String::new(),
+            nonce: String::new(),
+        }),
+        Step::AdvanceMs(5),
+        submit("wf", env("t1", "planner", "agent_task", r#"{"q":"ssn 123-45-6789"}"#, 10)),
+        Step::AdvanceMs(7),
+        submit("wf", env("t2", "coder", "agent_task", "{}", 20)),
+        submit("wf", env("t2", "coder", "agent_task", "{}", 20)), // duplicate id
+        Step::AdvanceMs(3),
+        submit("wf", env("r1", "coder", "agent_result", "{}", 5)),
+        submit("wf", env("t3", "coder", "agent_task", "{}", 500)), // exceeds budget
+    ];
+    assert_deterministic_replay(&steps).await;
+
+    let wal = String::from_utf8(run_to_wal(&steps).await).unwrap();
+    assert!(wal.contains("\"run_summary\""));
+    assert!(wal.contains("\"budget_exceeded\""));
+    assert!(!wal.contains("123-45-6789"));
+    let first: serde_json::Value = serde_json::from_str(wal.lines().next().unwrap()).unwrap();
+    assert_eq!(first["id"], 1);
+    assert_eq!(first["ts_ms"], START_MS);
+}
+
+#[test]
+fn line_diff_reports_differing_lines() {
+    let d = line_diff(b"a\nb\nc\n", b"a\nx\n");
+    assert_eq!(d, "line 2:\n- b\n+ x\nline 3:\n- c\n");
+    assert!(line_diff(b"same\n", b"same\n").is_empty());
+}
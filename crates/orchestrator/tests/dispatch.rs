@@ -0,0 +1,81 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::dispatch::{ChannelDispatcher, DispatchedTask};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use std::sync::Arc;
+use tonic::Request;
+
+fn envelope(id: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: String::new(),
+        trace_id: "t".into(),
+        agent: "worker-a".into(),
+        kind: "agent_task".into(),
+        payload_json: r#"{"step":1}"#.into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: Some(UsageHint { tokens: 5, cost_micros: 50 }),
+    }
+}
+
+fn service(path: &std::path::Path, dir: &std::path::Path) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(path).unwrap());
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn wal(path: &std::path::Path) -> Vec<EventRecord<Value>> {
+    JsonlEventLog::open(path).unwrap().read_range(0, u64::MAX).unwrap()
+}
+
+fn kinds(recs: &[EventRecord<Value>]) -> Vec<&str> {
+    recs.iter().filter_map(|r| r.payload["event"].as_str()).collect()
+}
+
+#[tokio::test]
+async fn channel_dispatcher_delivers_accepted_task_to_worker() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dispatch.jsonl");
+    let (dispatcher, mut worker) = ChannelDispatcher::new(8);
+    let svc = service(&path, dir.path()).with_dispatcher(Arc::new(dispatcher));
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    let req = SubmitTaskRequest { run_id: "r1".into(), task: Some(envelope("m1")) };
+    svc.submit_task(Request::new(req)).await.unwrap();
+
+    let got = worker.try_recv().unwrap();
+    assert_eq!(got, DispatchedTask { run_id: "r1".into(), envelope: envelope("m1") });
+    assert!(worker.try_recv().is_err());
+
+    let recs = wal(&path);
+    let enq = recs.iter().position(|r| r.payload["event"] == "task_enqueued").unwrap();
+    let disp = recs.iter().position(|r| r.payload["event"] == "task_dispatched").unwrap();
+    assert!(enq < disp, "{:?}", kinds(&recs));
+    let p = &recs[disp].payload;
+    assert_eq!(p["run_id"], "r1");
+    assert_eq!(p["task_id"], "m1");
+    assert_eq!(p["agent"], "worker-a");
+    assert_eq!(p["dispatcher"], "channel");
+}
+
+#[tokio::test]
+async fn wal_only_dispatcher_records_no_dispatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wal_only.jsonl");
+    let svc = service(&path, dir.path());
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    let req = SubmitTaskRequest { run_id: "r1".into(), task: Some(envelope("m1")) };
+    svc.submit_task(Request::new(req)).await.unwrap();
+    let recs = wal(&path);
+    let kinds = kinds(&recs);
+    assert!(kinds.contains(&"task_enqueued"));
+    assert!(!kinds.contains(&"task_dispatched"), "{kinds:?}");
+}
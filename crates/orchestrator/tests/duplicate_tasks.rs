@@ -0,0 +1,70 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use tonic::Request;
+
+fn task(id: &str) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "run1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+    })
+}
+
+fn service(dir: &std::path::Path) -> (OrchestratorService, std::path::PathBuf) {
+    let path = dir.join("dup.jsonl");
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    (svc, path)
+}
+
+fn enqueued(path: &std::path::Path) -> usize {
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(path).unwrap().read_range(0, u64::MAX).unwrap();
+    recs.iter().filter(|r| r.payload["event"] == "task_enqueued").count()
+}
+
+#[tokio::test]
+async fn duplicates_are_silently_accepted_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, path) = service(dir.path());
+    let first = svc.submit_task(task("t1")).await.unwrap().into_inner();
+    assert!(first.accepted && !first.duplicate);
+
+    let again = svc.submit_task(task("t1")).await.unwrap().into_inner();
+    assert!(again.accepted);
+    assert!(!again.duplicate);
+    assert_eq!(enqueued(&path), 1);
+}
+
+#[tokio::test]
+async fn reject_mode_flags_duplicates_without_enqueueing_them() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, path) = service(dir.path());
+    let svc = svc.with_reject_duplicate_tasks(true);
+    let first = svc.submit_task(task("t1")).await.unwrap().into_inner();
+    assert!(first.accepted && !first.duplicate);
+
+    let again = svc.submit_task(task("t1")).await.unwrap().into_inner();
+    assert!(!again.accepted);
+    assert!(again.duplicate);
+    assert_eq!(enqueued(&path), 1);
+
+    // A fresh id is still accepted normally.
+    let other = svc.submit_task(task("t2")).await.unwrap().into_inner();
+    assert!(other.accepted && !other.duplicate);
+    assert_eq!(enqueued(&path), 2);
+}
@@ -0,0 +1,56 @@
+use orca_core::envelope::{Envelope as CoreEnvelope, MessageType};
+use orchestrator::orca_v1::Envelope;
+use serde_json::json;
+use tonic::Code;
+
+#[test]
+fn core_to_proto_round_trips_every_kind() {
+    let task = CoreEnvelope::new_task("planner", json!({"text": "hi", "n": [1, 2]}), Some(500));
+    let result = CoreEnvelope::new_result(
+        task.id.clone(),
+        task.trace_id.clone(),
+        "A",
+        json!({"output": "ok"}),
+    );
+    let error = CoreEnvelope::new_error(
+        task.id.clone(),
+        task.trace_id.clone(),
+        "A",
+        json!({"message": "boom"}),
+    );
+    for core in [task, result, error] {
+        let proto: Envelope = core.clone().into();
+        assert_eq!(proto.kind, serde_json::to_value(core.kind).unwrap());
+        let back = CoreEnvelope::try_from(proto).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&core).unwrap());
+    }
+}
+
+#[test]
+fn empty_proto_fields_map_to_none_and_null() {
+    let proto = Envelope {
+        id: "m1".into(),
+        trace_id: "t".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        protocol_version: 1,
+        ..Default::default()
+    };
+    let core = CoreEnvelope::try_from(proto).unwrap();
+    assert_eq!(core.kind, MessageType::AgentTask);
+    assert_eq!(core.parent_id, None);
+    assert_eq!(core.timeout_ms, None);
+    assert!(core.payload.is_null());
+}
+
+#[test]
+fn unknown_kind_and_bad_payload_are_rejected() {
+    let base = Envelope { id: "m1".into(), kind: "agent_task".into(), ..Default::default() };
+    let err =
+        CoreEnvelope::try_from(Envelope { kind: "agenttask".into(), ..base.clone() }).unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("agenttask"));
+    let err =
+        CoreEnvelope::try_from(Envelope { payload_json: "{nope".into(), ..base }).unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,79 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::{validate_envelope_fields, OrchestratorService};
+use tonic::{Code, Request};
+
+fn full_env(id: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: "".into(),
+        trace_id: "tr".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        payload_json: "{}".into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: None,
+    }
+}
+
+fn submit(env: Envelope) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest { run_id: "r1".into(), task: Some(env) })
+}
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    OrchestratorService::new(JsonlEventLog::open(dir.path().join("f.jsonl")).unwrap())
+}
+
+#[tokio::test]
+async fn empty_id_is_rejected_before_wal() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let err = svc.submit_task(submit(full_env(""))).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("id"));
+    let wal = std::fs::read_to_string(dir.path().join("f.jsonl")).unwrap_or_default();
+    assert!(!wal.contains("task_enqueued"));
+}
+
+#[tokio::test]
+async fn empty_agent_is_rejected_on_submit_and_start() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let env = Envelope { agent: "".into(), ..full_env("t1") };
+    let err = svc.submit_task(submit(env.clone())).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("agent"));
+
+    let start = StartRunRequest {
+        workflow_id: "r2".into(),
+        initial_task: Some(env),
+        budget: None,
+        client_id: String::new(),
+        nonce: String::new(),
+    };
+    let err = svc.start_run(Request::new(start)).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
+
+#[test]
+fn trace_id_and_kind_are_checked() {
+    let no_trace = Envelope { trace_id: " ".into(), ..full_env("t1") };
+    assert_eq!(validate_envelope_fields(&no_trace).unwrap_err().code(), Code::InvalidArgument);
+    let bad_kind = Envelope { kind: "agent_tsak".into(), ..full_env("t1") };
+    let err = validate_envelope_fields(&bad_kind).unwrap_err();
+    assert!(err.message().contains("agent_tsak"));
+}
+
+#[tokio::test]
+async fn fully_populated_envelope_is_accepted() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    validate_envelope_fields(&full_env("t1")).unwrap();
+    let resp = svc.submit_task(submit(full_env("t1"))).await.unwrap().into_inner();
+    assert!(resp.accepted);
+}
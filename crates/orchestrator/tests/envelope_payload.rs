@@ -0,0 +1,45 @@
+use orca_core::payload::{Payload, PayloadError};
+use orchestrator::orca_v1::Envelope;
+
+fn env(kind: &str, payload_json: &str) -> Envelope {
+    Envelope {
+        id: "m1".into(),
+        trace_id: "tr".into(),
+        agent: "A".into(),
+        kind: kind.into(),
+        payload_json: payload_json.into(),
+        protocol_version: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn task_payload_is_typed_by_kind() {
+    let Payload::Task(task) = env("agent_task", r#"{"text":"hi"}"#).payload().unwrap() else {
+        panic!("expected a task payload");
+    };
+    assert_eq!(task.fields["text"], "hi");
+}
+
+#[test]
+fn result_payload_violating_schema_is_rejected() {
+    let err = env("agent_result", r#"{"usage":{"tokens":"lots"}}"#).payload().unwrap_err();
+    match err {
+        PayloadError::Schema { kind, violations } => {
+            assert_eq!(kind, "agent_result");
+            assert!(!violations.is_empty());
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+    let Payload::Result(res) =
+        env("agent_result", r#"{"output":1,"usage":{"tokens":5}}"#).payload().unwrap()
+    else {
+        panic!("expected a result payload");
+    };
+    assert_eq!(res.usage.and_then(|u| u.tokens), Some(5));
+}
+
+#[test]
+fn malformed_json_is_reported() {
+    assert!(matches!(env("agent_task", "{").payload(), Err(PayloadError::Json { .. })));
+}
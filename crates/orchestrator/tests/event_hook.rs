@@ -0,0 +1,151 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::hook::{EventHook, HookOverflow};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tonic::Request;
+
+#[derive(Default)]
+struct Capture(Mutex<Vec<EventRecord<Value>>>);
+
+impl EventHook for Capture {
+    fn on_event(&self, record: &EventRecord<Value>) {
+        self.0.lock().unwrap().push(record.clone());
+    }
+}
+
+fn envelope(id: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: String::new(),
+        trace_id: "t".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        payload_json: "{}".into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: Some(UsageHint { tokens: 5, cost_micros: 50 }),
+    }
+}
+
+/// Hook that takes `delay` per record, so a small queue overflows.
+struct Slow {
+    delay: Duration,
+    seen: Capture,
+}
+
+impl EventHook for Slow {
+    fn on_event(&self, record: &EventRecord<Value>) {
+        std::thread::sleep(self.delay);
+        self.seen.on_event(record);
+    }
+}
+
+/// Run 4 concurrent submitters of 5 tasks each; returns the WAL.
+async fn submit_concurrently(
+    svc: &OrchestratorService,
+    dir: &std::path::Path,
+    path: &std::path::Path,
+) -> Vec<EventRecord<Value>> {
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    let submitters = (0..4).map(|w| async move {
+        for i in 0..5 {
+            let task = Some(envelope(&format!("w{w}-m{i}")));
+            svc.submit_task(Request::new(SubmitTaskRequest { run_id: "r1".into(), task }))
+                .await
+                .unwrap();
+        }
+    });
+    futures_util::future::join_all(submitters).await;
+    JsonlEventLog::open(path).unwrap().read_range(0, u64::MAX).unwrap()
+}
+
+fn wait_for(seen: &Capture, want: usize) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while seen.0.lock().unwrap().len() < want && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[tokio::test]
+async fn slow_hook_with_drop_policy_sees_wal_order_and_counts_drops() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("slow_drop.jsonl");
+    let slow = Arc::new(Slow { delay: Duration::from_millis(20), seen: Capture::default() });
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap())
+        .with_event_hook_overflow(slow.clone(), 1, HookOverflow::Drop);
+    let wal = submit_concurrently(&svc, dir.path(), &path).await;
+
+    let dropped = svc.dropped_hook_events();
+    assert!(dropped > 0, "a 1-slot queue behind a 20ms hook must overflow");
+    wait_for(&slow.seen, wal.len() - dropped as usize);
+    let seen = slow.seen.0.lock().unwrap();
+    assert_eq!(seen.len() as u64 + dropped, wal.len() as u64);
+    // Delivered records are a subsequence of the WAL, in WAL order.
+    let mut wal_ids = wal.iter().map(|r| r.id);
+    for rec in seen.iter() {
+        assert!(wal_ids.any(|id| id == rec.id), "record {} delivered out of WAL order", rec.id);
+    }
+}
+
+#[tokio::test]
+async fn slow_hook_with_block_policy_sees_every_record_in_wal_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("slow_block.jsonl");
+    let slow = Arc::new(Slow { delay: Duration::from_millis(2), seen: Capture::default() });
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap())
+        .with_event_hook_overflow(slow.clone(), 1, HookOverflow::Block);
+    let wal = submit_concurrently(&svc, dir.path(), &path).await;
+
+    wait_for(&slow.seen, wal.len());
+    let seen = slow.seen.0.lock().unwrap();
+    assert_eq!(
+        seen.iter().map(|r| r.id).collect::<Vec<_>>(),
+        wal.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+    assert_eq!(svc.dropped_hook_events(), 0);
+}
+
+#[tokio::test]
+async fn hook_receives_every_appended_event_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hooked.jsonl");
+    let capture = Arc::new(Capture::default());
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap())
+        .with_event_hook(capture.clone(), 1024);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    for i in 0..5 {
+        let req = SubmitTaskRequest { run_id: "r1".into(), task: Some(envelope(&format!("m{i}"))) };
+        svc.submit_task(Request::new(req)).await.unwrap();
+    }
+
+    let wal: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(&path).unwrap().read_range(0, u64::MAX).unwrap();
+    assert!(wal.len() > 5);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while capture.0.lock().unwrap().len() < wal.len() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let seen = capture.0.lock().unwrap();
+    let ids = |recs: &[EventRecord<Value>]| recs.iter().map(|r| r.id).collect::<Vec<_>>();
+    assert_eq!(ids(&seen), ids(&wal));
+    assert_eq!(
+        seen.iter().map(|r| &r.payload).collect::<Vec<_>>(),
+        wal.iter().map(|r| &r.payload).collect::<Vec<_>>()
+    );
+    assert_eq!(svc.dropped_hook_events(), 0);
+}
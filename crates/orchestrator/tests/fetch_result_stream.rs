@@ -0,0 +1,84 @@
+use blob_store::{BlobStore, Config, DevKeyProvider};
+use event_log::JsonlEventLog;
+use futures_util::StreamExt;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::json;
+use std::sync::Arc;
+use tonic::{Code, Request};
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("r.jsonl")).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn result_env(id: &str, parent: &str, payload: serde_json::Value) -> SubmitTaskRequest {
+    SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: parent.into(),
+            trace_id: "t".into(),
+            agent: "A".into(),
+            kind: "agent_result".into(),
+            payload_json: payload.to_string(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: orca_core::ids::now_ms(),
+            usage: None,
+        }),
+    }
+}
+
+async fn fetch(
+    svc: &OrchestratorService,
+    parent: &str,
+    chunk_bytes: u32,
+) -> Result<Vec<FetchResultChunk>, tonic::Status> {
+    let req =
+        FetchResultStreamRequest { run_id: "r1".into(), parent_id: parent.into(), chunk_bytes };
+    let stream = svc.fetch_result_stream(Request::new(req)).await?.into_inner();
+    stream.collect::<Vec<_>>().await.into_iter().collect()
+}
+
+#[tokio::test]
+async fn multi_mib_blob_result_reassembles_from_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let blobs =
+        BlobStore::new(Config::with_root(dir.path().join("blobs")), DevKeyProvider::new([7; 32]))
+            .unwrap();
+    let original: Vec<u8> = (0..3 * 1024 * 1024 + 123).map(|i| (i * 31 % 251) as u8).collect();
+    let digest = blobs.put(&original).unwrap();
+    let svc = service(&dir).with_result_blobs(Arc::new(blobs));
+    let payload =
+        json!({"blob_ref": {"digest_sha256": digest.to_hex(), "size_bytes": original.len()}});
+    svc.submit_task(Request::new(result_env("res1", "task1", payload))).await.unwrap();
+
+    let chunks = fetch(&svc, "task1", 256 * 1024).await.unwrap();
+    assert_eq!(chunks.len(), 13);
+    assert!(chunks.iter().all(|c| c.total_bytes == original.len() as u64));
+    assert!(chunks.last().unwrap().last && chunks.iter().filter(|c| c.last).count() == 1);
+    let mut reassembled = Vec::new();
+    for c in &chunks {
+        assert_eq!(c.offset, reassembled.len() as u64);
+        reassembled.extend_from_slice(&c.data);
+    }
+    assert_eq!(reassembled, original);
+}
+
+#[tokio::test]
+async fn inline_result_streams_payload_json_and_missing_result_is_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let payload = json!({"output": "done"});
+    svc.submit_task(Request::new(result_env("res1", "task1", payload.clone()))).await.unwrap();
+
+    let chunks = fetch(&svc, "task1", 0).await.unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].data, payload.to_string().into_bytes());
+
+    assert_eq!(fetch(&svc, "other", 0).await.unwrap_err().code(), Code::NotFound);
+}
@@ -0,0 +1,109 @@
+use event_log::JsonlEventLog;
+use futures_util::stream::StreamExt;
+use orchestrator::orca_v1::{orchestrator_client::OrchestratorClient, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tonic::codec::CompressionEncoding;
+use tonic::transport::{Channel, Server};
+
+async fn spawn_server(gzip: bool) -> (String, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let log = JsonlEventLog::open(dir.path().join("gz.jsonl")).unwrap();
+    let svc_impl = OrchestratorService::new(log).with_grpc_gzip(gzip);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc_impl.load_policy_from_path(&policy_path).unwrap();
+    let svc = svc_impl.into_server();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let stream = futures_util::stream::unfold(listener, |listener| async move {
+            Some((listener.accept().await.ok()?.0, listener))
+        })
+        .filter_map(|s| async move { Some(Ok::<_, std::io::Error>(s)) });
+        Server::builder().add_service(svc).serve_with_incoming(stream).await.unwrap();
+    });
+    (format!("http://{}", addr), dir)
+}
+
+fn env(id: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: "".into(),
+        trace_id: "tr".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        // Large, repetitive payload: the case compression is for.
+        payload_json: serde_json::json!({"text": "lorem ipsum ".repeat(2048)}).to_string(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: None,
+    }
+}
+
+async fn drive(mut client: OrchestratorClient<Channel>) {
+    client
+        .start_run(StartRunRequest {
+            workflow_id: "wf".into(),
+            initial_task: Some(env("t1")),
+            budget: None,
+            client_id: String::new(),
+            nonce: String::new(),
+        })
+        .await
+        .unwrap();
+    let resp = client
+        .submit_task(SubmitTaskRequest { run_id: "wf".into(), task: Some(env("t2")) })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(resp.accepted);
+}
+
+/// WAL payloads with per-process ids and wall-clock timestamps stripped.
+fn wal_payloads(dir: &tempfile::TempDir) -> Vec<Value> {
+    std::fs::read_to_string(dir.path().join("gz.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|l| {
+            let mut v: Value = serde_json::from_str(l).unwrap();
+            let mut p = v["payload"].take();
+            if let Some(obj) = p.as_object_mut() {
+                obj.retain(|k, _| !k.ends_with("_ms"));
+            }
+            p
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn gzip_request_is_processed_like_plain_request() {
+    let (plain_addr, plain_dir) = spawn_server(true).await;
+    drive(OrchestratorClient::connect(plain_addr).await.unwrap()).await;
+
+    let (gz_addr, gz_dir) = spawn_server(true).await;
+    let gz_client = OrchestratorClient::connect(gz_addr)
+        .await
+        .unwrap()
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip);
+    drive(gz_client).await;
+
+    let plain = wal_payloads(&plain_dir);
+    assert!(!plain.is_empty());
+    assert_eq!(plain, wal_payloads(&gz_dir));
+}
+
+#[tokio::test]
+async fn gzip_request_is_refused_when_disabled() {
+    let (addr, _dir) = spawn_server(false).await;
+    let mut client =
+        OrchestratorClient::connect(addr).await.unwrap().send_compressed(CompressionEncoding::Gzip);
+    let err = client
+        .submit_task(SubmitTaskRequest { run_id: "wf".into(), task: Some(env("t1")) })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::Unimplemented);
+}
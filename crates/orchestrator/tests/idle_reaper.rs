@@ -0,0 +1,91 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::clock::{set_process_clock, VirtualClock};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::Request;
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("idle.jsonl")).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.with_run_idle_timeout(Duration::from_millis(1_000))
+}
+
+fn start(run: &str) -> Request<StartRunRequest> {
+    Request::new(StartRunRequest {
+        workflow_id: run.into(),
+        initial_task: None,
+        budget: None,
+        client_id: String::new(),
+        nonce: String::new(),
+    })
+}
+
+fn task(run: &str, id: &str) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: run.into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: String::new(),
+            trace_id: "t".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+    })
+}
+
+fn summaries(dir: &tempfile::TempDir) -> Vec<EventRecord<serde_json::Value>> {
+    let log = JsonlEventLog::open(dir.path().join("idle.jsonl")).unwrap();
+    let recs: Vec<EventRecord<serde_json::Value>> = log.read_range(0, u64::MAX).unwrap();
+    recs.into_iter().filter(|r| r.payload["event"] == "run_summary").collect()
+}
+
+// One test: the process clock is global to this binary.
+#[tokio::test]
+async fn idle_runs_are_summarized_once_past_the_threshold() {
+    let clock = Arc::new(VirtualClock::new(10_000));
+    set_process_clock(clock.clone());
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+
+    svc.start_run(start("busy")).await.unwrap();
+    svc.start_run(start("quiet")).await.unwrap();
+    clock.advance_ms(600);
+    svc.submit_task(task("busy", "m1")).await.unwrap();
+
+    // quiet: 600ms idle, busy: 0ms
+    assert!(svc.reap_idle_runs().unwrap().is_empty());
+    clock.advance_ms(399);
+    assert!(svc.reap_idle_runs().unwrap().is_empty(), "999ms is below the threshold");
+    clock.advance_ms(1);
+    assert_eq!(svc.reap_idle_runs().unwrap(), vec!["quiet".to_string()]);
+    assert!(svc.is_run_completed("quiet"));
+    assert!(!svc.is_run_completed("busy"));
+
+    let s = summaries(&dir);
+    assert_eq!(s.len(), 1);
+    assert_eq!(s[0].payload["run_id"], "quiet");
+    assert_eq!(s[0].payload["duration_ms"], 1_000);
+    assert_eq!(s[0].ts_ms, 11_000);
+
+    // Reaped runs are not summarized again; busy goes idle 1s after its last task.
+    clock.advance_ms(600);
+    assert_eq!(svc.reap_idle_runs().unwrap(), vec!["busy".to_string()]);
+    assert!(svc.reap_idle_runs().unwrap().is_empty());
+    assert_eq!(summaries(&dir).len(), 2);
+
+    // Replay restores completion, so a restarted service does not reap the runs again.
+    let restarted = service(&dir);
+    restarted.replay_on_start().unwrap();
+    assert!(restarted.is_run_completed("quiet") && restarted.is_run_completed("busy"));
+    clock.advance_ms(10_000);
+    assert!(restarted.reap_idle_runs().unwrap().is_empty());
+}
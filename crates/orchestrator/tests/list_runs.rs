@@ -0,0 +1,70 @@
+use event_log::JsonlEventLog;
+use orchestrator::auth::TokenScopes;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use tonic::{Code, Request};
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("l.jsonl")).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn start(run: &str) -> Request<StartRunRequest> {
+    Request::new(StartRunRequest { workflow_id: run.into(), ..Default::default() })
+}
+
+#[tokio::test]
+async fn lists_started_and_replayed_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    assert!(svc.list_runs().is_empty());
+    svc.start_run(start("r1")).await.unwrap();
+    svc.start_run(start("r2")).await.unwrap();
+    assert_eq!(svc.list_runs(), vec!["r1", "r2"]);
+
+    let restarted = service(&dir);
+    restarted.replay_on_start().unwrap();
+    assert_eq!(restarted.list_runs(), vec!["r1", "r2"]);
+}
+
+#[tokio::test]
+async fn list_runs_rpc_requires_admin_scope() {
+    let dir = tempfile::tempdir().unwrap();
+    let scopes = TokenScopes::from_json_str(
+        r#"{"reader": ["stream_events", "fetch_result"], "ops": ["admin"], "all": ["*"]}"#,
+    )
+    .unwrap();
+    let svc = service(&dir).with_auth_scopes(scopes);
+    let mut req = start("r1");
+    req.metadata_mut().insert("authorization", "all".parse().unwrap());
+    svc.start_run(req).await.unwrap();
+
+    let call = |token: &str| {
+        let mut req = Request::new(ListRunsRequest {});
+        req.metadata_mut().insert("authorization", token.parse().unwrap());
+        Orchestrator::list_runs(&svc, req)
+    };
+    assert_eq!(call("reader").await.unwrap_err().code(), Code::PermissionDenied);
+    assert_eq!(call("ops").await.unwrap().into_inner().run_ids, vec!["r1"]);
+    assert_eq!(call("all").await.unwrap().into_inner().run_ids, vec!["r1"]);
+}
+
+#[tokio::test]
+async fn list_runs_is_sorted_and_stable_across_calls() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let runs = ["run-k", "run-b", "run-z", "run-a", "run-m", "run-c", "run-y", "run-d"];
+    for run in runs {
+        svc.start_run(start(run)).await.unwrap();
+    }
+    let mut expected: Vec<String> = runs.iter().map(|r| r.to_string()).collect();
+    expected.sort();
+    for _ in 0..5 {
+        assert_eq!(svc.list_runs(), expected);
+        let rpc = Orchestrator::list_runs(&svc, Request::new(ListRunsRequest {})).await.unwrap();
+        assert_eq!(rpc.into_inner().run_ids, expected);
+    }
+}
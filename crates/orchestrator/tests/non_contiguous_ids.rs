@@ -0,0 +1,70 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::{json, Value};
+use tokio_stream::StreamExt;
+use tonic::Request;
+
+fn gapped_wal(path: &std::path::Path) {
+    let log = JsonlEventLog::open(path).unwrap();
+    log.append(1, 10, &json!({"event":"start_run","workflow_id":"r1","envelope":null})).unwrap();
+    log.append(2, 20, &json!({"event":"task_enqueued","run_id":"r1","envelope":{"id":"e1"}}))
+        .unwrap();
+    log.append(5, 50, &json!({"event":"usage_update","run_id":"r1","tokens":1})).unwrap();
+    log.append(9, 90, &json!({"event":"task_enqueued","run_id":"r1","envelope":{"id":"e2"}}))
+        .unwrap();
+}
+
+#[tokio::test]
+async fn replay_and_stream_tolerate_id_gaps() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gaps.jsonl");
+    gapped_wal(&path);
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    svc.replay_on_start().unwrap();
+    assert_eq!(svc.index.last_event_id_by_run.get("r1").map(|v| *v), Some(9));
+    assert_eq!(svc.index.run_start_ts_by_run.get("r1").map(|v| *v), Some(10));
+
+    // Allocation continues past the highest id, not the record count.
+    assert!(orca_core::ids::next_monotonic_id() > 9);
+
+    // Resumption token start_event_id=3 falls in a gap and resumes at 5.
+    let req = StreamEventsRequest {
+        run_id: "r1".into(),
+        start_event_id: 3,
+        since_ts_ms: 0,
+        max_events: 0,
+        page_size: 0,
+        follow: false,
+    };
+    let mut stream = svc.stream_events(Request::new(req)).await.unwrap().into_inner();
+    let mut got = Vec::new();
+    while let Some(item) = stream.next().await {
+        got.push(item.unwrap().event.unwrap().id.parse::<u64>().unwrap());
+    }
+    assert_eq!(got, vec![5, 9]);
+}
+
+#[tokio::test]
+async fn appends_after_replay_do_not_collide_with_existing_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gaps2.jsonl");
+    gapped_wal(&path);
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    svc.replay_on_start().unwrap();
+    let report = plugin_host::VerificationReport {
+        name: "p".into(),
+        digest: "d".into(),
+        signer: None,
+        outcome: Ok(()),
+    };
+    svc.record_plugin_verification(&report).unwrap();
+
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(&path).unwrap().read_range(0, u64::MAX).unwrap();
+    let mut ids: Vec<u64> = recs.iter().map(|r| r.id).collect();
+    assert!(*ids.last().unwrap() > 9);
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), recs.len(), "ids must stay unique: {ids:?}");
+}
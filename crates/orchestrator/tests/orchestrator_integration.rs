@@ -48,6 +48,8 @@ async fn happy_path_start_submit() {
             workflow_id: "wf1".into(),
             initial_task: Some(env),
             budget: None,
+            client_id: String::new(),
+            nonce: String::new(),
         })
         .await
         .unwrap()
@@ -0,0 +1,42 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::OrchestratorService;
+use plugin_host::{ManifestVerifier, PluginManifest, VerificationReport};
+use serde_json::Value;
+
+#[test]
+fn plugin_verification_outcomes_are_appended_to_wal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plugins.jsonl");
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    let digest = "ab".repeat(32);
+
+    let ok = VerificationReport {
+        name: "ok-plugin".into(),
+        digest: digest.clone(),
+        signer: Some("ci@example.com".into()),
+        outcome: Ok(()),
+    };
+    svc.record_plugin_verification(&ok).unwrap();
+    let manifest = PluginManifest {
+        name: "bad-plugin".into(),
+        wasm_digest: "00".repeat(32),
+        ..PluginManifest::default()
+    };
+    let bad = ManifestVerifier { require_signed_plugins: false }.verify_detailed(&manifest, b"x");
+    svc.record_plugin_verification(&bad).unwrap();
+
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(&path).unwrap().read_range(0, u64::MAX).unwrap();
+    let events: Vec<&Value> = recs.iter().map(|r| &r.payload).collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event"], "plugin_verified");
+    assert_eq!(events[0]["name"], "ok-plugin");
+    assert_eq!(events[0]["digest"], digest.as_str());
+    assert_eq!(events[0]["signer"], "ci@example.com");
+    assert!(events[0]["error_code"].is_null());
+    assert_eq!(events[1]["event"], "plugin_verify_failed");
+    assert_eq!(events[1]["name"], "bad-plugin");
+    assert_eq!(events[1]["digest"], bad.digest.as_str());
+    assert!(events[1]["signer"].is_null());
+    assert_eq!(events[1]["error_code"], "digest_mismatch");
+}
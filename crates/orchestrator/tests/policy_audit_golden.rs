@@ -0,0 +1,125 @@
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, SubmitTaskRequest};
+use orchestrator::{OrchestratorService, PolicyAuditPayload};
+use policy::{Decision, DecisionKind};
+use serde_json::json;
+
+fn golden(name: &str) -> String {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name);
+    std::fs::read_to_string(path).unwrap().trim_end().to_string()
+}
+
+fn env_json(
+    id: &str,
+    agent: &str,
+    kind: &str,
+    trace_id: &str,
+    payload: serde_json::Value,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "parent_id": "",
+        "trace_id": trace_id,
+        "agent": agent,
+        "kind": kind,
+        "payload_json": payload.to_string(),
+        "timeout_ms": 0,
+        "protocol_version": 1,
+        "ts_ms": 0,
+    })
+}
+
+#[test]
+fn deny_audit_matches_golden() {
+    let env = env_json("m1", "A", "agent_task", "t1", json!({"tool":"shell"}));
+    let d = Decision {
+        kind: DecisionKind::Deny,
+        payload: None,
+        reason: Some("tool use blocked for 123-45-6789".into()),
+        rule_name: Some("Block-Tools".into()),
+        action: Some("deny".into()),
+    };
+    let p = PolicyAuditPayload::from_decision("pre_submit_task", Some("r1"), None, &env, &d)
+        .expect("deny is audited");
+    assert_eq!(serde_json::to_string(&p).unwrap(), golden("policy_audit_deny.json"));
+}
+
+#[test]
+fn modify_audit_with_attachment_matches_golden() {
+    let payload = json!({
+        "text": "[REDACTED]",
+        "blob_ref": {
+            "digest_sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            "size_bytes": 4,
+            "mime": "text/plain",
+        },
+    });
+    let env = env_json("m2", "B", "agent_result", "t2", payload);
+    let d = Decision {
+        kind: DecisionKind::Modify,
+        payload: None,
+        reason: Some("pii redacted".into()),
+        rule_name: Some("Redact-PII".into()),
+        action: Some("modify".into()),
+    };
+    let p = PolicyAuditPayload::from_decision("post_submit_task", Some("r2"), None, &env, &d)
+        .expect("modify is audited");
+    assert_eq!(serde_json::to_string(&p).unwrap(), golden("policy_audit_modify.json"));
+}
+
+#[test]
+fn plain_allow_is_not_audited() {
+    let env = env_json("m3", "C", "agent_task", "t3", json!({}));
+    let d = Decision {
+        kind: DecisionKind::Allow,
+        payload: None,
+        reason: None,
+        rule_name: None,
+        action: None,
+    };
+    assert!(
+        PolicyAuditPayload::from_decision("pre_submit_task", Some("r3"), None, &env, &d).is_none()
+    );
+}
+
+#[tokio::test]
+async fn wal_line_embeds_golden_payload_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal = dir.path().join("audit_golden.jsonl");
+    let svc = OrchestratorService::new(event_log::JsonlEventLog::open(&wal).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(
+        &policy_path,
+        r#"rules:
+  - name: Block-Tools
+    when: ToolInvocation
+    action: deny
+    message: "tool use blocked for 123-45-6789"
+"#,
+    )
+    .unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let env = orchestrator::orca_v1::Envelope {
+        id: "m1".into(),
+        parent_id: "".into(),
+        trace_id: "t1".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        payload_json: json!({"text":"hi"}).to_string(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: orchestrator::clock::process_clock().now_ms(),
+        usage: None,
+    };
+    let res = svc
+        .submit_task(tonic::Request::new(SubmitTaskRequest {
+            run_id: "r1".into(),
+            task: Some(env),
+        }))
+        .await;
+    assert!(res.is_err(), "deny rule should reject the task");
+
+    let raw = std::fs::read_to_string(&wal).unwrap();
+    let line = raw.lines().find(|l| l.contains("\"policy_audit\"")).expect("audit line");
+    assert!(line.contains(&golden("policy_audit_deny.json")), "audit line: {line}");
+}
@@ -57,3 +57,57 @@ async fn audit_reason_redacts_pii_patterns() {
     let reason = p.get("reason").and_then(|v| v.as_str()).unwrap_or("");
     assert!(!reason.contains("123-45-6789"), "audit reason must be redacted");
 }
+
+#[tokio::test]
+async fn pii_redaction_audit_records_differing_payload_digests() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = event_log::JsonlEventLog::open(dir.path().join("audit_digest.jsonl")).unwrap();
+    let log_read = log.clone();
+    let svc = OrchestratorService::new(log);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(
+        &policy_path,
+        r#"rules:
+  - name: Redact-PII-Patterns
+    when: pii_detect
+    action: modify
+    message: redacted
+"#,
+    )
+    .unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let payload_json = json!({"text":"My SSN is 123-45-6789"}).to_string();
+    let env = orchestrator::orca_v1::Envelope {
+        id: "m4".into(),
+        parent_id: "".into(),
+        trace_id: "t4".into(),
+        agent: "A".into(),
+        kind: "agent_task".into(),
+        payload_json: payload_json.clone(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: orchestrator::clock::process_clock().now_ms(),
+        usage: None,
+    };
+    svc.submit_task(tonic::Request::new(SubmitTaskRequest {
+        run_id: "r4".into(),
+        task: Some(env),
+    }))
+    .await
+    .unwrap();
+
+    let recs: Vec<event_log::EventRecord<serde_json::Value>> =
+        log_read.read_range(0, u64::MAX).unwrap();
+    let audit = recs
+        .iter()
+        .map(|r| &r.payload)
+        .find(|p| p["event"] == "policy_audit" && p["outcome"] == "modified")
+        .expect("modify audit event");
+    let pre = audit["pre_redaction_sha256"].as_str().expect("pre digest");
+    let post = audit["post_redaction_sha256"].as_str().expect("post digest");
+    assert_eq!(pre, orchestrator::proxy::sha256_hex(payload_json.as_bytes()));
+    assert_eq!(post.len(), 64);
+    assert_ne!(pre, post);
+    assert!(!audit.to_string().contains("123-45-6789"), "audit must not carry the content");
+}
@@ -0,0 +1,54 @@
+use event_log::JsonlEventLog;
+use orchestrator::clock::{set_process_clock, VirtualClock};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, StartRunRequest};
+use orchestrator::proxy::sha256_hex;
+use orchestrator::OrchestratorService;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::Request;
+
+const V1: &str = "rules: []\n";
+const V2: &str = "rules: []\n# v2\n";
+const V3: &str = "rules: []\n# v3\n";
+
+/// Version of the active policy, as reported by `start_run` for a fresh run.
+async fn active_version(svc: &OrchestratorService, run: &str) -> String {
+    let req = StartRunRequest { workflow_id: run.into(), ..Default::default() };
+    svc.start_run(Request::new(req)).await.unwrap().into_inner().policy_version
+}
+
+// One test: the process clock is global to this binary.
+#[tokio::test]
+async fn policy_reload_follows_the_clock_and_stops_with_the_guard() {
+    let clock = Arc::new(VirtualClock::new(50_000));
+    set_process_clock(clock.clone());
+    let dir = tempfile::tempdir().unwrap();
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, V1).unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("w.jsonl")).unwrap())
+        .with_policy_reload(&policy_path, Duration::from_secs(1));
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let tasks = svc.start_background_tasks();
+    assert_eq!(tasks.len(), 1);
+
+    // The file changes, but no reload is due until the clock moves a full period.
+    std::fs::write(&policy_path, V2).unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(active_version(&svc, "r1").await, sha256_hex(V1.as_bytes()));
+
+    clock.advance_ms(1_000);
+    let mut waited = 0;
+    while active_version(&svc, &format!("wait{waited}")).await != sha256_hex(V2.as_bytes()) {
+        assert!(waited < 5_000, "reload never happened");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        waited += 10;
+    }
+
+    // Shutdown: once the guard is dropped no further reload is observed.
+    drop(tasks);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    std::fs::write(&policy_path, V3).unwrap();
+    clock.advance_ms(10_000);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(active_version(&svc, "after").await, sha256_hex(V2.as_bytes()));
+}
@@ -102,6 +102,8 @@ async fn policy_spans_include_required_attributes() {
             workflow_id: "wf".into(),
             initial_task: Some(env),
             budget: None,
+            client_id: String::new(),
+            nonce: String::new(),
         }))
         .await
         .unwrap();
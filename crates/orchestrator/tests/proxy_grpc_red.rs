@@ -64,6 +64,8 @@ async fn wal_stubs_emitted_for_grpc_capture_red() {
         workflow_id: "wf1".into(),
         initial_task: Some(test_env_envelope("t1")),
         budget: None,
+        client_id: String::new(),
+        nonce: String::new(),
     });
     req.metadata_mut()
         .insert("authorization", MetadataValue::try_from("Bearer secret-token").unwrap());
@@ -136,6 +138,8 @@ async fn redaction_is_applied_red() {
         workflow_id: "wf2".into(),
         initial_task: Some(test_env_envelope("t10")),
         budget: None,
+        client_id: String::new(),
+        nonce: String::new(),
     });
     req.metadata_mut()
         .insert("authorization", MetadataValue::try_from("Bearer secret-token").unwrap());
@@ -0,0 +1,60 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use tonic::Request;
+
+/// Drive one run under `seed` and return the raw WAL bytes.
+async fn run_under_seed(seed: u64, dir: &std::path::Path, name: &str) -> Vec<u8> {
+    orca_core::repro::init(seed);
+    let wal = dir.join(name);
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(&wal).unwrap());
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc.start_run(Request::new(StartRunRequest {
+        workflow_id: "repro".into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    }))
+    .await
+    .unwrap();
+    for i in 0..3 {
+        let env = orca_core::envelope::Envelope::new_task("A", serde_json::json!({"i": i}), None);
+        let task = Envelope {
+            id: env.id,
+            parent_id: String::new(),
+            trace_id: env.trace_id,
+            agent: env.agent,
+            kind: "agent_task".into(),
+            payload_json: env.payload.to_string(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: env.ts_ms,
+            usage: Some(UsageHint { tokens: 10, cost_micros: 0 }),
+        };
+        svc.submit_task(Request::new(SubmitTaskRequest {
+            run_id: "repro".into(),
+            task: Some(task),
+        }))
+        .await
+        .unwrap();
+    }
+    std::fs::read(&wal).unwrap()
+}
+
+// One test: reproduction mode and the process clock are global to this binary.
+#[tokio::test]
+async fn same_seed_reproduces_the_wal_byte_for_byte() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = run_under_seed(7, dir.path(), "a.jsonl").await;
+    let second = run_under_seed(7, dir.path(), "b.jsonl").await;
+    assert!(!first.is_empty());
+    assert_eq!(String::from_utf8_lossy(&first), String::from_utf8_lossy(&second));
+
+    let other = run_under_seed(8, dir.path(), "c.jsonl").await;
+    assert_ne!(first, other, "a different seed changes trace ids and timestamps");
+    let ts = orca_core::repro::start_ms(8);
+    assert!(String::from_utf8_lossy(&other).contains(&format!("\"ts_ms\":{ts}")));
+}
@@ -1,6 +1,34 @@
-use event_log::JsonlEventLog;
+use event_log::{EventRecord, JsonlEventLog, RotatingEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
 use orchestrator::OrchestratorService;
-use serde_json::json;
+use serde_json::{json, Value};
+use tonic::Request;
+
+fn task(id: &str, agent: &str, tokens: u64, cost_micros: u64) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "run1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: agent.into(),
+            kind: "agent_task".into(),
+            payload_json: "{}".into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: Some(UsageHint { tokens, cost_micros }),
+        }),
+    })
+}
+
+fn service(path: &std::path::Path, dir: &std::path::Path) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(path).unwrap());
+    let policy_path = dir.join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
 
 #[tokio::test]
 async fn crash_restart_replay_rebuilds_index() {
@@ -20,3 +48,128 @@ async fn crash_restart_replay_rebuilds_index() {
     // Validate index contains wf1 -> last_event_id=2 and seen_ids includes m1
     assert_eq!(svc.index.last_event_id_by_run.get("wf1").map(|v| *v.value()), Some(2));
 }
+
+#[tokio::test]
+async fn replay_on_start_reads_a_rotated_wal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rotated.jsonl");
+    let log = RotatingEventLog::open(&path, 200).unwrap();
+    log.append(1, 1, &json!({"event":"start_run", "workflow_id":"wf1"})).unwrap();
+    for id in 2..=9 {
+        // Cumulative run totals, as the live path records them.
+        let usage = json!({"event":"usage_update", "run_id":"wf1", "tokens":id * 10});
+        log.append(id, id, &usage).unwrap();
+    }
+    assert!(log.segments().unwrap().len() > 2, "records spread over several segments");
+
+    // Restart on the same rotated set: the service takes the underlying log unchanged.
+    drop(log);
+    let svc = OrchestratorService::new(RotatingEventLog::open(&path, 200).unwrap().into());
+    svc.replay_on_start().unwrap();
+    assert_eq!(svc.index.last_event_id_by_run.get("wf1").map(|v| *v.value()), Some(9));
+    assert_eq!(svc.index.usage_by_run.get("wf1").map(|v| v.value().0), Some(90));
+}
+
+#[tokio::test]
+async fn strict_replay_rejects_unknown_event_kind() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("typo.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    let _ = log.append(1, 1, &json!({"event":"start_run", "workflow_id":"wf1"})).unwrap();
+    let _ = log.append(2, 2, &json!({"event":"usage_updat", "run_id":"wf1", "tokens":1})).unwrap();
+
+    // Lenient (default): warns and keeps indexing.
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    svc.replay_on_start().unwrap();
+    assert_eq!(svc.index.last_event_id_by_run.get("wf1").map(|v| *v.value()), Some(2));
+
+    let strict =
+        OrchestratorService::new(JsonlEventLog::open(&path).unwrap()).with_strict_replay(true);
+    let err = strict.replay_on_start().unwrap_err();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    assert!(err.message().contains("usage_updat"), "{}", err.message());
+    assert!(err.message().contains("id 2"), "{}", err.message());
+}
+
+#[tokio::test]
+async fn replay_restores_usage_and_per_run_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("usage.jsonl");
+    let svc = service(&path, dir.path());
+    svc.start_run(Request::new(StartRunRequest {
+        workflow_id: "run1".into(),
+        initial_task: None,
+        budget: Some(Budget { max_tokens: 100, max_cost_micros: 0, ..Default::default() }),
+        client_id: String::new(),
+        nonce: String::new(),
+    }))
+    .await
+    .unwrap();
+    svc.submit_task(task("t1", "A", 50, 100)).await.unwrap();
+    svc.submit_task(task("t2", "B", 35, 20)).await.unwrap(); // 85 tokens: Warning80
+    drop(svc);
+
+    let svc = service(&path, dir.path());
+    svc.replay_on_start().unwrap();
+    let usage = |k: (&str, &str)| {
+        svc.index.usage_by_run_agent.get(&(k.0.into(), k.1.into())).map(|v| *v.value())
+    };
+    assert_eq!(svc.index.usage_by_run.get("run1").map(|v| *v.value()), Some((85, 120)));
+    assert_eq!(usage(("run1", "A")), Some((50, 100)));
+    assert_eq!(usage(("run1", "B")), Some((35, 20)));
+
+    // The reloaded budget continues from 85 tokens in Warning80 rather than from zero.
+    svc.submit_task(task("t3", "A", 6, 0)).await.unwrap();
+    let err = svc.submit_task(task("t4", "A", 10, 0)).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+    let recs: Vec<EventRecord<Value>> =
+        JsonlEventLog::open(&path).unwrap().read_range(0, u64::MAX).unwrap();
+    let transitions: Vec<(String, String, u64)> = recs
+        .iter()
+        .filter(|r| r.payload["event"] == "budget_state_changed")
+        .map(|r| {
+            (
+                r.payload["from"].as_str().unwrap().to_string(),
+                r.payload["to"].as_str().unwrap().to_string(),
+                r.payload["tokens"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    let expected = [
+        ("Within", "Warning80", 85),
+        ("Warning80", "Warning90", 91),
+        ("Warning90", "Exceeded", 101),
+    ];
+    assert_eq!(transitions, expected.map(|(f, t, n)| (f.to_string(), t.to_string(), n)).to_vec());
+}
+
+#[tokio::test]
+async fn out_of_order_and_concurrent_replays_keep_the_max_event_id_per_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("unordered.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    // Merged segments: ids step backwards within each run.
+    let recs = [(10, "ra"), (3, "rb"), (7, "ra"), (12, "rb"), (4, "ra"), (8, "rb")];
+    for (id, run) in recs {
+        log.append(id, id, &json!({"event":"usage_update","run_id":run,"tokens":id})).unwrap();
+    }
+
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    svc.replay_on_start().unwrap();
+    let last = |run: &str| svc.index.last_event_id_by_run.get(run).map(|v| *v.value());
+    assert_eq!(last("ra"), Some(10));
+    assert_eq!(last("rb"), Some(12));
+
+    // A racy boot: several replays interleaved with a live update that is already ahead.
+    let svc = OrchestratorService::new(JsonlEventLog::open(&path).unwrap());
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            let svc = svc.clone();
+            s.spawn(move || svc.replay_on_start().unwrap());
+        }
+        s.spawn(|| svc.index.advance_last_event_id("ra", 20));
+    });
+    let last = |run: &str| svc.index.last_event_id_by_run.get(run).map(|v| *v.value());
+    assert_eq!(last("ra"), Some(20));
+    assert_eq!(last("rb"), Some(12));
+}
@@ -0,0 +1,101 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tonic::Request;
+
+fn service(dir: &tempfile::TempDir) -> (OrchestratorService, JsonlEventLog) {
+    let log = JsonlEventLog::open(dir.path().join("cancel.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    (svc, log)
+}
+
+fn follow(run_id: &str) -> Request<StreamEventsRequest> {
+    Request::new(StreamEventsRequest { run_id: run_id.into(), follow: true, ..Default::default() })
+}
+
+fn cancel(run_id: &str) -> Request<CancelRunRequest> {
+    Request::new(CancelRunRequest {
+        run_id: run_id.into(),
+        reason: Some(CancelReason { code: "user_requested".into(), message: "stop".into() }),
+    })
+}
+
+#[tokio::test]
+async fn following_subscriber_sees_cancellation_then_stream_ends_cancelled() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, log) = service(&dir);
+    svc.start_run(Request::new(StartRunRequest { workflow_id: "r1".into(), ..Default::default() }))
+        .await
+        .unwrap();
+    let mut stream = svc.stream_events(follow("r1")).await.unwrap().into_inner();
+    let first = stream.next().await.unwrap().unwrap().event.unwrap();
+    assert_eq!(first.kind, "start_run");
+
+    // Appended after the subscriber caught up, so it arrives through the live tail.
+    let resp = svc.cancel_run(cancel("r1")).await.unwrap().into_inner();
+    assert!(resp.cancelled);
+
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap();
+    let evt = next.unwrap().unwrap().event.unwrap();
+    assert_eq!(evt.kind, "run_cancelled");
+    let payload: Value = serde_json::from_str(&evt.payload_json).unwrap();
+    assert_eq!(payload["reason"]["code"], "user_requested");
+    assert_eq!(payload["reason"]["message"], "stop");
+
+    let end = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap();
+    let status = end.unwrap().unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Cancelled);
+    assert!(status.message().contains("user_requested"));
+    assert!(stream.next().await.is_none());
+
+    // Cancelled runs are completed: a second cancel is a no-op and late subscribers get the
+    // recorded cancellation from the backlog.
+    assert!(!svc.cancel_run(cancel("r1")).await.unwrap().into_inner().cancelled);
+    let late: Vec<_> = svc.stream_events(follow("r1")).await.unwrap().into_inner().collect().await;
+    assert_eq!(late.len(), 3);
+    assert_eq!(late[1].as_ref().unwrap().event.as_ref().unwrap().kind, "run_cancelled");
+    assert_eq!(late[2].as_ref().unwrap_err().code(), tonic::Code::Cancelled);
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    assert_eq!(recs.iter().filter(|r| r.payload["event"] == "run_cancelled").count(), 1);
+}
+
+#[tokio::test]
+async fn follower_skips_other_runs_and_non_follow_streams_end_after_backlog() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, _log) = service(&dir);
+    for run in ["a", "b"] {
+        svc.start_run(Request::new(StartRunRequest {
+            workflow_id: run.into(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    }
+    let plain: Vec<_> = svc
+        .stream_events(Request::new(StreamEventsRequest {
+            run_id: "a".into(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .collect()
+        .await;
+    assert_eq!(plain.len(), 1);
+
+    let mut stream = svc.stream_events(follow("a")).await.unwrap().into_inner();
+    assert_eq!(stream.next().await.unwrap().unwrap().event.unwrap().kind, "start_run");
+    svc.cancel_run(cancel("b")).await.unwrap();
+    svc.cancel_run(cancel("a")).await.unwrap();
+    let evt = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap();
+    let payload: Value =
+        serde_json::from_str(&evt.unwrap().unwrap().event.unwrap().payload_json).unwrap();
+    assert_eq!(payload["run_id"], "a", "run b's cancellation is not delivered");
+    assert!(stream.next().await.unwrap().is_err());
+}
@@ -61,6 +61,8 @@ async fn spans_present_for_key_paths() {
             workflow_id: "wf".into(),
             initial_task: Some(env),
             budget: None,
+            client_id: String::new(),
+            nonce: String::new(),
         }))
         .await
         .unwrap();
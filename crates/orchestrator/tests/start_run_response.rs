@@ -0,0 +1,55 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use sha2::{Digest, Sha256};
+use tonic::Request;
+
+const POLICY: &str = "rules: []\n";
+
+fn service(dir: &tempfile::TempDir) -> OrchestratorService {
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("s.jsonl")).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, POLICY).unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    svc
+}
+
+fn start(run: &str, budget: Option<Budget>) -> Request<StartRunRequest> {
+    Request::new(StartRunRequest {
+        workflow_id: run.into(),
+        initial_task: None,
+        budget,
+        client_id: String::new(),
+        nonce: String::new(),
+    })
+}
+
+#[tokio::test]
+async fn response_reflects_env_default_budget_and_policy_hash() {
+    std::env::set_var("ORCA_MAX_TOKENS", "1234");
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let resp = svc.start_run(start("r1", None)).await.unwrap().into_inner();
+    std::env::remove_var("ORCA_MAX_TOKENS");
+
+    assert_eq!(resp.run_id, "r1");
+    assert_eq!(
+        resp.budget,
+        Some(Budget { max_tokens: 1234, max_cost_micros: 0, ..Default::default() })
+    );
+    assert_eq!(resp.policy_version, hex::encode(Sha256::digest(POLICY.as_bytes())));
+    assert!(resp.started_ts_ms > 0);
+
+    let wal = std::fs::read_to_string(dir.path().join("s.jsonl")).unwrap();
+    let rec: serde_json::Value = serde_json::from_str(wal.lines().next().unwrap()).unwrap();
+    assert_eq!(rec["ts_ms"], resp.started_ts_ms);
+}
+
+#[tokio::test]
+async fn request_budget_takes_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = service(&dir);
+    let b = Budget { max_tokens: 10, max_cost_micros: 500, ..Default::default() };
+    let resp = svc.start_run(start("r2", Some(b.clone()))).await.unwrap().into_inner();
+    assert_eq!(resp.budget, Some(b));
+}
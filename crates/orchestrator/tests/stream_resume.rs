@@ -0,0 +1,145 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::json;
+use tokio_stream::StreamExt;
+use tonic::Request;
+
+async fn page(
+    svc: &OrchestratorService,
+    since_ts_ms: u64,
+    start: u64,
+    max: u32,
+) -> Vec<(u64, u64)> {
+    let req = StreamEventsRequest {
+        run_id: "r1".into(),
+        start_event_id: start,
+        since_ts_ms,
+        max_events: max,
+        page_size: 0,
+        follow: false,
+    };
+    let mut stream = svc.stream_events(Request::new(req)).await.unwrap().into_inner();
+    let mut out = Vec::new();
+    while let Some(item) = stream.next().await {
+        let env = item.unwrap().event.unwrap();
+        out.push((env.ts_ms, env.id.parse::<u64>().unwrap()));
+    }
+    out
+}
+
+#[tokio::test]
+async fn resume_at_same_ms_boundary_delivers_exactly_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("resume.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    // A cluster sharing ts=100, then a restart whose ids begin again at 1 within ts=200.
+    let wal: [(u64, u64); 7] =
+        [(5, 100), (6, 100), (7, 100), (8, 100), (1, 200), (2, 200), (3, 201)];
+    for (id, ts) in wal {
+        log.append(id, ts, &json!({"event":"usage_update","run_id":"r1","tokens":id})).unwrap();
+    }
+    let svc = OrchestratorService::new(log);
+
+    let mut seen = Vec::new();
+    let mut cursor = (0u64, 0u64);
+    loop {
+        let got = page(&svc, cursor.0, cursor.1, 2).await;
+        let Some(&(ts, id)) = got.last() else { break };
+        seen.extend(got);
+        cursor = (ts, id + 1);
+    }
+    let expected: Vec<(u64, u64)> = wal.iter().map(|&(id, ts)| (ts, id)).collect();
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn cursor_pages_follow_ts_id_order_when_wal_timestamps_step_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("skew.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    // The clock stepped back after id 11: file order is not (ts, id) order.
+    let wal: [(u64, u64); 5] = [(10, 300), (11, 300), (12, 200), (13, 250), (14, 400)];
+    for (id, ts) in wal {
+        log.append(id, ts, &json!({"event":"usage_update","run_id":"r1","tokens":id})).unwrap();
+    }
+    let svc = OrchestratorService::new(log);
+
+    // Without a limit the cursor stream is sorted by (ts, id).
+    let all = page(&svc, 1, 0, 0).await;
+    assert_eq!(all, vec![(200, 12), (250, 13), (300, 10), (300, 11), (400, 14)]);
+
+    // max_events cuts in that order, so single-event pages neither skip nor repeat.
+    let mut seen = Vec::new();
+    let mut cursor = (1u64, 0u64);
+    loop {
+        let got = page(&svc, cursor.0, cursor.1, 1).await;
+        let Some(&(ts, id)) = got.last() else { break };
+        seen.extend(got);
+        cursor = (ts, id + 1);
+    }
+    assert_eq!(seen, all);
+
+    // Id-only requests keep WAL order.
+    let ids: Vec<u64> = page(&svc, 0, 11, 2).await.into_iter().map(|(_, id)| id).collect();
+    assert_eq!(ids, vec![11, 12]);
+}
+
+#[tokio::test]
+async fn paged_stream_reassembles_history_without_gaps_or_duplicates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("paged.jsonl");
+    let log = JsonlEventLog::open(&path).unwrap();
+    // Shared timestamps, an id restart, and another run's events interleaved.
+    let wal: [(u64, u64, &str); 9] = [
+        (5, 100, "r1"),
+        (6, 100, "r2"),
+        (7, 100, "r1"),
+        (8, 150, "r1"),
+        (1, 200, "r1"),
+        (2, 200, "r2"),
+        (3, 200, "r1"),
+        (4, 210, "r1"),
+        (9, 300, "r1"),
+    ];
+    for (id, ts, run) in wal {
+        log.append(id, ts, &json!({"event":"usage_update","run_id":run,"tokens":id})).unwrap();
+    }
+    let svc = OrchestratorService::new(log);
+
+    let mut seen = Vec::new();
+    let (mut since_ts_ms, mut start_event_id) = (0u64, 0u64);
+    for _ in 0..10 {
+        let req = StreamEventsRequest {
+            run_id: "r1".into(),
+            start_event_id,
+            since_ts_ms,
+            max_events: 0,
+            page_size: 3,
+            follow: false,
+        };
+        let mut stream = svc.stream_events(Request::new(req)).await.unwrap().into_inner();
+        let mut page = Vec::new();
+        let mut end = None;
+        while let Some(item) = stream.next().await {
+            let item = item.unwrap();
+            assert!(end.is_none(), "page_end must be the last message");
+            match (item.event, item.page_end) {
+                (Some(env), None) => page.push((env.ts_ms, env.id.parse::<u64>().unwrap())),
+                (None, Some(e)) => end = Some(e),
+                other => panic!("unexpected message {other:?}"),
+            }
+        }
+        let end = end.expect("paged stream ends with page_end");
+        assert!(page.len() <= 3);
+        seen.extend(page);
+        if end.exhausted {
+            break;
+        }
+        (since_ts_ms, start_event_id) = (end.next_since_ts_ms, end.next_start_event_id);
+    }
+    let mut expected: Vec<(u64, u64)> =
+        wal.iter().filter(|(_, _, run)| *run == "r1").map(|&(id, ts, _)| (ts, id)).collect();
+    expected.sort();
+    assert_eq!(seen, expected);
+}
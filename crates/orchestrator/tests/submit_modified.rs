@@ -0,0 +1,49 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use tonic::Request;
+
+fn task(id: &str, payload_json: &str) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: payload_json.into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+    })
+}
+
+#[tokio::test]
+async fn redacted_submission_reports_modified_with_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("m.jsonl")).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let plain = svc.submit_task(task("t1", "{\"text\":\"hello\"}")).await.unwrap().into_inner();
+    assert!(plain.accepted);
+    assert!(!plain.modified);
+    assert!(plain.modified_by_rule.is_empty());
+
+    let redacted = svc
+        .submit_task(task("t2", "{\"text\":\"My SSN is 123-45-6789\"}"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(redacted.accepted);
+    assert!(redacted.modified);
+    assert_eq!(redacted.modified_by_rule, "builtin_redact_pii");
+
+    // Duplicate submissions are plain accepts: nothing was rewritten this time.
+    let dup = svc.submit_task(task("t2", "{}")).await.unwrap().into_inner();
+    assert!(dup.accepted && !dup.modified);
+}
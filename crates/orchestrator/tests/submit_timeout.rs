@@ -0,0 +1,77 @@
+use event_log::JsonlEventLog;
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tonic::{Code, Request};
+
+struct PostSubmitCounter(Arc<AtomicUsize>);
+
+impl policy::PolicyObserver for PostSubmitCounter {
+    fn on_decision(&self, phase: &str, _: &policy::Decision) {
+        if phase == "post_submit_task" {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+fn task(id: &str, timeout_ms: u64, ts_ms: u64) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: "".into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: "agent_task".into(),
+            payload_json: "{\"text\":\"hi\"}".into(),
+            timeout_ms,
+            protocol_version: 1,
+            ts_ms,
+            usage: None,
+        }),
+    })
+}
+
+#[tokio::test]
+async fn timeout_ms_is_an_admission_ttl_and_post_hook_always_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal = dir.path().join("t.jsonl");
+    let svc = OrchestratorService::new(JsonlEventLog::open(&wal).unwrap());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    let post_hooks = Arc::new(AtomicUsize::new(0));
+    policy::set_observer(Some(Box::new(PostSubmitCounter(post_hooks.clone()))));
+
+    let now = orca_core::ids::now_ms();
+    assert!(svc.submit_task(task("no-ttl", 0, now)).await.unwrap().into_inner().accepted);
+    assert!(svc.submit_task(task("fresh", 60_000, now)).await.unwrap().into_inner().accepted);
+    let expired = svc.submit_task(task("expired", 10, now.saturating_sub(60_000))).await;
+    policy::set_observer(None);
+
+    assert_eq!(expired.unwrap_err().code(), Code::DeadlineExceeded);
+    // The post-submit hook runs for every accepted task, with or without a TTL.
+    assert_eq!(post_hooks.load(Ordering::SeqCst), 2);
+    let log = std::fs::read_to_string(&wal).unwrap();
+    assert!(log.contains("\"fresh\"") && log.contains("\"no-ttl\""));
+    assert!(!log.contains("\"expired\""));
+}
+
+#[tokio::test]
+async fn future_dated_envelopes_beyond_skew_tolerance_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let svc = OrchestratorService::new(JsonlEventLog::open(dir.path().join("s.jsonl")).unwrap())
+        .with_clock_skew_tolerance(std::time::Duration::from_secs(5));
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    let now = orca_core::ids::now_ms();
+    assert!(
+        svc.submit_task(task("small-skew", 10, now + 1_000)).await.unwrap().into_inner().accepted
+    );
+    let err = svc.submit_task(task("far-future", 0, now + 3_600_000)).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert_eq!(err.message(), "timestamp skew");
+}
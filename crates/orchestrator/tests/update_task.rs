@@ -0,0 +1,100 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::OrchestratorService;
+use serde_json::{json, Value};
+use tonic::Request;
+
+fn task(id: &str, parent_id: &str, kind: &str, payload_json: &str) -> Request<SubmitTaskRequest> {
+    Request::new(SubmitTaskRequest {
+        run_id: "r1".into(),
+        task: Some(Envelope {
+            id: id.into(),
+            parent_id: parent_id.into(),
+            trace_id: "tr".into(),
+            agent: "A".into(),
+            kind: kind.into(),
+            payload_json: payload_json.into(),
+            timeout_ms: 0,
+            protocol_version: 1,
+            ts_ms: 0,
+            usage: None,
+        }),
+    })
+}
+
+fn update(envelope_id: &str, patch: &str) -> Request<UpdateTaskRequest> {
+    Request::new(UpdateTaskRequest {
+        run_id: "r1".into(),
+        envelope_id: envelope_id.into(),
+        merge_patch_json: patch.into(),
+    })
+}
+
+fn service(dir: &tempfile::TempDir) -> (OrchestratorService, JsonlEventLog) {
+    let log = JsonlEventLog::open(dir.path().join("u.jsonl")).unwrap();
+    let svc = OrchestratorService::new(log.clone());
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+    (svc, log)
+}
+
+#[tokio::test]
+async fn patch_is_policy_checked_recorded_and_applied() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, log) = service(&dir);
+    svc.submit_task(task("t1", "", "agent_task", r#"{"q":"weather","ctx":{"city":"Oslo"}}"#))
+        .await
+        .unwrap();
+
+    let first =
+        svc.update_task(update("t1", r#"{"ctx":{"units":"metric"}}"#)).await.unwrap().into_inner();
+    assert!(first.accepted && !first.modified);
+    let payload: Value = serde_json::from_str(&first.payload_json).unwrap();
+    assert_eq!(payload, json!({"q":"weather","ctx":{"city":"Oslo","units":"metric"}}));
+
+    // The builtin PII rule redacts the patch before it is applied or recorded.
+    let second = svc
+        .update_task(update("t1", r#"{"note":"SSN 123-45-6789","q":null}"#))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(second.modified);
+    assert_eq!(second.modified_by_rule, "builtin_redact_pii");
+    let payload: Value = serde_json::from_str(&second.payload_json).unwrap();
+    assert_eq!(payload, json!({"ctx":{"city":"Oslo","units":"metric"},"note":"SSN [REDACTED]"}));
+
+    let recs: Vec<EventRecord<Value>> = log.read_range(0, u64::MAX).unwrap();
+    let updates: Vec<&Value> =
+        recs.iter().map(|r| &r.payload).filter(|p| p["event"] == "task_updated").collect();
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0]["run_id"], "r1");
+    assert_eq!(updates[0]["envelope_id"], "t1");
+    assert_eq!(updates[0]["trace_id"], "tr");
+    assert_eq!(updates[0]["patch"], json!({"ctx":{"units":"metric"}}));
+    assert_eq!(updates[1]["patch"], json!({"note":"SSN [REDACTED]","q":null}));
+    assert!(!recs.iter().any(|r| r.payload.to_string().contains("123-45-6789")));
+    assert!(recs.iter().any(|r| {
+        r.payload["event"] == "policy_audit" && r.payload["phase"] == "pre_update_task"
+    }));
+}
+
+#[tokio::test]
+async fn unknown_and_terminal_envelopes_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (svc, _log) = service(&dir);
+    svc.submit_task(task("t1", "", "agent_task", "{}")).await.unwrap();
+    svc.submit_task(task("t2", "", "agent_task", "{}")).await.unwrap();
+    // An agent_error answers t2 without ending the run (an agent_result would summarize it).
+    svc.submit_task(task("err2", "t2", "agent_error", r#"{"code":"tool_failed"}"#)).await.unwrap();
+
+    let missing = svc.update_task(update("nope", "{}")).await.unwrap_err();
+    assert_eq!(missing.code(), tonic::Code::NotFound);
+    let answered = svc.update_task(update("t2", r#"{"a":1}"#)).await.unwrap_err();
+    assert_eq!(answered.code(), tonic::Code::FailedPrecondition);
+    let error = svc.update_task(update("err2", r#"{"a":1}"#)).await.unwrap_err();
+    assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    let bad = svc.update_task(update("t1", "not json")).await.unwrap_err();
+    assert_eq!(bad.code(), tonic::Code::InvalidArgument);
+    assert!(svc.update_task(update("t1", r#"{"a":1}"#)).await.is_ok());
+}
@@ -0,0 +1,90 @@
+use event_log::{EventRecord, JsonlEventLog};
+use orchestrator::orca_v1::{orchestrator_server::Orchestrator, *};
+use orchestrator::tee::{SinkError, WalSink, WalTee};
+use orchestrator::OrchestratorService;
+use serde_json::Value;
+use std::sync::Arc;
+use tonic::Request;
+
+struct FailingSink;
+
+impl WalSink for FailingSink {
+    fn forward(&self, _rec: &EventRecord<Value>) -> Result<(), SinkError> {
+        Err("replica offline".into())
+    }
+}
+
+fn envelope(id: &str, kind: &str) -> Envelope {
+    Envelope {
+        id: id.into(),
+        parent_id: String::new(),
+        trace_id: "t".into(),
+        agent: "A".into(),
+        kind: kind.into(),
+        payload_json: "{}".into(),
+        timeout_ms: 0,
+        protocol_version: 1,
+        ts_ms: 0,
+        usage: Some(UsageHint { tokens: 5, cost_micros: 50 }),
+    }
+}
+
+#[tokio::test]
+async fn tee_receives_every_primary_event_despite_a_failing_sink() {
+    let dir = tempfile::tempdir().unwrap();
+    let primary = JsonlEventLog::open(dir.path().join("primary.jsonl")).unwrap();
+    let replica = JsonlEventLog::open(dir.path().join("replica.jsonl")).unwrap();
+    let (tx, rx) = std::sync::mpsc::sync_channel(1024);
+    let tee = WalTee::new()
+        .with_sink(Arc::new(FailingSink))
+        .with_sink(Arc::new(tx))
+        .with_sink(Arc::new(replica.clone()));
+    let svc = OrchestratorService::new(primary.clone()).with_wal_tee(tee);
+    let policy_path = dir.path().join("policy.yaml");
+    std::fs::write(&policy_path, "rules: []\n").unwrap();
+    svc.load_policy_from_path(&policy_path).unwrap();
+
+    svc.start_run(Request::new(StartRunRequest {
+        workflow_id: "r1".into(),
+        initial_task: None,
+        budget: None,
+        client_id: String::new(),
+        nonce: String::new(),
+    }))
+    .await
+    .unwrap();
+    for (id, kind) in [("m1", "agent_task"), ("m2", "agent_result")] {
+        svc.submit_task(Request::new(SubmitTaskRequest {
+            run_id: "r1".into(),
+            task: Some(envelope(id, kind)),
+        }))
+        .await
+        .unwrap();
+    }
+
+    let written: Vec<EventRecord<Value>> = primary.read_range(0, u64::MAX).unwrap();
+    let kinds: Vec<&str> = written.iter().filter_map(|r| r.payload["event"].as_str()).collect();
+    assert!(kinds.contains(&"usage_update") && kinds.contains(&"run_summary"), "{kinds:?}");
+
+    let teed: Vec<EventRecord<Value>> = rx.try_iter().collect();
+    let key = |r: &EventRecord<Value>| (r.id, r.ts_ms, r.payload.clone());
+    assert_eq!(
+        teed.iter().map(key).collect::<Vec<_>>(),
+        written.iter().map(key).collect::<Vec<_>>()
+    );
+    let mirrored: Vec<EventRecord<Value>> = replica.read_range(0, u64::MAX).unwrap();
+    assert_eq!(
+        mirrored.iter().map(key).collect::<Vec<_>>(),
+        written.iter().map(key).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn full_channel_drops_instead_of_blocking() {
+    let (tx, rx) = std::sync::mpsc::sync_channel(1);
+    let tee = WalTee::new().with_sink(Arc::new(tx));
+    for id in 1..=3 {
+        tee.forward(&EventRecord { id, ts_ms: id, payload: serde_json::json!({"event": "x"}) });
+    }
+    assert_eq!(rx.try_iter().map(|r| r.id).collect::<Vec<_>>(), vec![1]);
+}
@@ -0,0 +1,187 @@
+//! Offline plugin bundles: one file carrying everything needed to verify and run a plugin.
+//!
+//! An [`OrcaPluginBundle`] is a length-prefixed container:
+//!
+//! ```text
+//! b"ORCAPB01"
+//! u32 LE len | manifest JSON  {"name", "version", "wasm_digest", "sbom_sha256"}
+//! u32 LE len | WASM module
+//! u32 LE len | SBOM document
+//! u32 LE len | Sigstore signing bundle JSON (see [`crate::SigstoreOptions`])
+//! ```
+//!
+//! Nothing in the container is trusted on its own. [`OrcaPluginBundle::verify_and_load`] checks
+//! the SBOM against the manifest's `sbom_sha256`, runs [`ManifestVerifier::verify_with_roots`]
+//! (digest pinning plus the signature over the WASM against pinned Fulcio roots), and only then
+//! compiles the module, so tampering with the WASM, SBOM, signature, or pinned digests fails
+//! closed. Name and version are informational, as in [`PluginManifest`].
+
+use crate::{
+    ManifestVerifier, ModuleHandle, PluginManifest, PluginRunner, RunnerError, SigstoreOptions,
+    VerificationError,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use sha2::Digest as _;
+use std::path::Path;
+use thiserror::Error;
+
+/// Leading bytes of every bundle (format version 1).
+pub const BUNDLE_MAGIC: &[u8; 8] = b"ORCAPB01";
+
+/// Errors from reading or loading an [`OrcaPluginBundle`].
+#[derive(Debug, Error)]
+pub enum BundleError {
+    /// The bundle file could not be read.
+    #[error("bundle io: {0}")]
+    Io(#[from] std::io::Error),
+    /// The bytes are not a well-formed bundle.
+    #[error("malformed bundle: {0}")]
+    Malformed(String),
+    /// The SBOM does not hash to the manifest's `sbom_sha256`.
+    #[error("sbom digest mismatch")]
+    SbomMismatch,
+    /// The manifest did not verify against the WASM; nothing was compiled.
+    #[error("bundle unverified: {0}")]
+    Unverified(#[from] VerificationError),
+    /// The verified WASM failed to compile.
+    #[error(transparent)]
+    Load(#[from] RunnerError),
+}
+
+/// A plugin bundle's components, as read from (or to be written to) a bundle file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrcaPluginBundle {
+    /// Plugin name (informational).
+    pub name: String,
+    /// Plugin version (informational).
+    pub version: String,
+    /// Hex SHA-256 the WASM must match.
+    pub wasm_digest: String,
+    /// Hex SHA-256 the SBOM must match.
+    pub sbom_sha256: String,
+    /// WASM module bytes.
+    pub wasm: Vec<u8>,
+    /// SBOM document bytes (format is up to the publisher, e.g. SPDX JSON).
+    pub sbom: Vec<u8>,
+    /// Sigstore signing bundle JSON `{"cert_pem", "signature", "signed_at"}`.
+    pub sigstore_bundle: Vec<u8>,
+}
+
+impl OrcaPluginBundle {
+    /// Read and parse the bundle file at `path`.
+    ///
+    /// # Errors
+    /// [`BundleError::Io`] when the file cannot be read, [`BundleError::Malformed`] as in
+    /// [`Self::parse`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BundleError> {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    /// Split `bytes` into the bundle's components. No verification happens here.
+    ///
+    /// # Errors
+    /// [`BundleError::Malformed`] on a wrong magic, a truncated section, trailing bytes, or a
+    /// manifest section that is not the expected JSON object.
+    pub fn parse(bytes: &[u8]) -> Result<Self, BundleError> {
+        let mut rest = bytes
+            .strip_prefix(BUNDLE_MAGIC.as_slice())
+            .ok_or_else(|| BundleError::Malformed("bad magic".into()))?;
+        let mut section = |what: &str| -> Result<Vec<u8>, BundleError> {
+            if rest.len() < 4 {
+                return Err(BundleError::Malformed(format!("{what}: missing length")));
+            }
+            let (len, tail) = rest.split_at(4);
+            let len = usize::try_from(u32::from_le_bytes([len[0], len[1], len[2], len[3]]))
+                .map_err(|_| BundleError::Malformed(format!("{what}: length overflow")))?;
+            if tail.len() < len {
+                return Err(BundleError::Malformed(format!("{what}: truncated")));
+            }
+            let (body, tail) = tail.split_at(len);
+            rest = tail;
+            Ok(body.to_vec())
+        };
+        let manifest = section("manifest")?;
+        let wasm = section("wasm")?;
+        let sbom = section("sbom")?;
+        let sigstore_bundle = section("sigstore")?;
+        if !rest.is_empty() {
+            return Err(BundleError::Malformed(format!("{} trailing bytes", rest.len())));
+        }
+
+        let manifest: serde_json::Value = serde_json::from_slice(&manifest)
+            .map_err(|e| BundleError::Malformed(format!("manifest: {e}")))?;
+        let field = |k: &str| {
+            manifest
+                .get(k)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| BundleError::Malformed(format!("manifest: missing {k}")))
+        };
+        Ok(Self {
+            name: field("name")?,
+            version: field("version")?,
+            wasm_digest: field("wasm_digest")?,
+            sbom_sha256: field("sbom_sha256")?,
+            wasm,
+            sbom,
+            sigstore_bundle,
+        })
+    }
+
+    /// Serialize into the bundle format; [`Self::parse`] returns an equal bundle.
+    ///
+    /// # Panics
+    /// If a component is 4 GiB or larger.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let manifest = serde_json::json!({
+            "name": self.name,
+            "version": self.version,
+            "wasm_digest": self.wasm_digest,
+            "sbom_sha256": self.sbom_sha256,
+        })
+        .to_string();
+        let mut out = BUNDLE_MAGIC.to_vec();
+        for part in [manifest.as_bytes(), &self.wasm, &self.sbom, &self.sigstore_bundle] {
+            let len = u32::try_from(part.len()).expect("bundle component under 4 GiB");
+            out.extend_from_slice(&len.to_le_bytes());
+            out.extend_from_slice(part);
+        }
+        out
+    }
+
+    /// The [`PluginManifest`] this bundle describes: the Sigstore bundle becomes the
+    /// base64 signature and the SBOM is referenced by digest (`sha256:<hex>`).
+    #[must_use]
+    pub fn manifest(&self) -> PluginManifest {
+        PluginManifest {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            wasm_digest: self.wasm_digest.clone(),
+            signature: Some(STANDARD.encode(&self.sigstore_bundle)),
+            sbom_ref: Some(format!("sha256:{}", self.sbom_sha256)),
+        }
+    }
+
+    /// Verify every component and compile the WASM on `runner`.
+    ///
+    /// # Errors
+    /// [`BundleError::SbomMismatch`] when the SBOM does not match its pinned digest,
+    /// [`BundleError::Unverified`] when `verifier` rejects the manifest against `roots`, and
+    /// [`BundleError::Load`] when compilation fails. Nothing is compiled unless every check
+    /// passed.
+    pub fn verify_and_load(
+        &self,
+        verifier: &ManifestVerifier,
+        roots: &SigstoreOptions,
+        runner: &PluginRunner,
+    ) -> Result<ModuleHandle, BundleError> {
+        let sbom_digest = hex::encode(sha2::Sha256::digest(&self.sbom));
+        if !sbom_digest.eq_ignore_ascii_case(self.sbom_sha256.trim()) {
+            return Err(BundleError::SbomMismatch);
+        }
+        verifier.verify_with_roots(&self.manifest(), &self.wasm, roots)?;
+        Ok(runner.load_module(&self.wasm)?)
+    }
+}
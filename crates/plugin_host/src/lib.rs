@@ -1,11 +1,21 @@
 //! Wasmtime runner + WASI sandbox (REFACTOR): minimal runner with deny-by-default posture.
 //! - Engine with fuel enabled; per-invoke fuel budget to bound CPU (default: 1M units).
 //! - Epoch-based timeout to bound wall time (default: 500 ms per invoke).
-//! - WASI wired with no preopens/network (no ambient authority).
+//! - WASI wired with no preopens/network (no ambient authority); an optional per-runner
+//!   allowlist restricts which preview1 functions a module may import.
 //! - Memory capped via Store limits (fail-closed defaults; default: 128 MiB).
 //!
 //! TODO(observability): add metrics/traces (plugin.invoke.ms, plugin.fuel.consumed, plugin.mem.bytes).
 
+pub mod bundle;
+pub mod registry;
+pub mod scheduler;
+pub use bundle::{BundleError, OrcaPluginBundle, BUNDLE_MAGIC};
+pub use registry::{PluginRegistry, RegisteredPlugin, RegistryError};
+pub use scheduler::{FairScheduler, SchedulerPermit};
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use subtle::ConstantTimeEq;
@@ -63,30 +73,109 @@ pub enum RunnerError {
     /// Invoking an exported function failed.
     #[error("invoke failed: {0}")]
     InvokeFailed(String),
+    /// The runner already has its maximum number of invocations in flight; nothing ran.
+    #[error("at capacity: {0} concurrent invocations")]
+    AtCapacity(usize),
 }
 
 /// Opaque handle for a loaded module (compiled via Wasmtime `Module`).
 #[derive(Debug, Clone)]
 pub struct ModuleHandle {
     module: Arc<Module>,
+    digest: Arc<str>,
 }
 
 impl ModuleHandle {
     #[inline]
-    fn new(module: Module) -> Self {
-        Self { module: Arc::new(module) }
+    fn new(module: Module, wasm: &[u8]) -> Self {
+        use sha2::Digest as _;
+        let digest = hex::encode(sha2::Sha256::digest(wasm));
+        Self { module: Arc::new(module), digest: Arc::from(digest) }
+    }
+
+    /// Lowercase hex SHA-256 of the WASM bytes this module was compiled from.
+    #[must_use]
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+}
+
+/// Resource usage of a single invocation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InvokeStats {
+    /// Fuel units consumed (budget minus remaining).
+    pub fuel_consumed: u64,
+    /// Wall time of instantiate + call, in milliseconds.
+    pub wall_ms: u64,
+    /// Size of exported linear memory at the end of the call (memory only grows, so this
+    /// is the peak).
+    pub peak_memory_bytes: usize,
+}
+
+/// Per-invoke resource limits shared by all clones of a runner.
+///
+/// Each invoke snapshots the values when it starts, so updates apply to subsequent
+/// invocations and never to in-flight ones.
+#[derive(Debug)]
+struct RunnerLimits {
+    memory_limit_bytes: AtomicUsize,
+    fuel_budget: AtomicU64,
+    timeout_ms: AtomicU64,
+}
+
+impl RunnerLimits {
+    fn new(memory_limit_bytes: usize, fuel_budget: u64, timeout_ms: u64) -> Arc<Self> {
+        Arc::new(Self {
+            memory_limit_bytes: AtomicUsize::new(memory_limit_bytes),
+            fuel_budget: AtomicU64::new(fuel_budget),
+            timeout_ms: AtomicU64::new(timeout_ms),
+        })
+    }
+}
+
+/// Runner-wide cap on concurrent invocations, shared by all clones of a runner.
+#[derive(Debug)]
+struct Admission {
+    max: usize,
+    in_flight: AtomicUsize,
+}
+
+impl Admission {
+    /// Take a slot, or `None` when `max` invocations are already in flight.
+    fn try_acquire(self: &Arc<Self>) -> Option<AdmissionPermit> {
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < self.max).then_some(n + 1))
+            .ok()
+            .map(|_| AdmissionPermit(Arc::clone(self)))
+    }
+}
+
+/// Releases its admission slot on drop.
+struct AdmissionPermit(Arc<Admission>);
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::AcqRel);
     }
 }
 
 /// Minimal Wasmtime-backed plugin runner holding a shared `Engine` and default limits.
+///
+/// Clones share the engine and the limits: `set_*` on any clone affects the next invoke
+/// on every clone.
 #[derive(Clone)]
 pub struct PluginRunner {
     engine: Arc<Engine>,
-    memory_limit_bytes: usize,
-    fuel_budget: u64,
-    timeout_ms: u64,
+    limits: Arc<RunnerLimits>,
+    wal: Option<event_log::JsonlEventLog>,
+    scheduler: Option<Arc<FairScheduler>>,
+    wasi_allowlist: Option<Arc<HashSet<String>>>,
+    admission: Option<Arc<Admission>>,
 }
 
+/// Import module name of the WASI preview1 surface.
+pub const WASI_PREVIEW1_MODULE: &str = "wasi_snapshot_preview1";
+
 impl Default for PluginRunner {
     fn default() -> Self {
         let mut cfg = Config::new();
@@ -96,9 +185,11 @@ impl Default for PluginRunner {
         let engine = Engine::new(&cfg).expect("engine config should be valid");
         Self {
             engine: Arc::new(engine),
-            memory_limit_bytes: 128 * 1024 * 1024,
-            fuel_budget: 1_000_000,
-            timeout_ms: 500,
+            limits: RunnerLimits::new(128 * 1024 * 1024, 1_000_000, 500),
+            wal: None,
+            scheduler: None,
+            wasi_allowlist: None,
+            admission: None,
         }
     }
 }
@@ -123,9 +214,11 @@ impl PluginRunner {
         let engine = Engine::new(&cfg).expect("engine config should be valid");
         Self {
             engine: Arc::new(engine),
-            memory_limit_bytes,
-            fuel_budget: 1_000_000,
-            timeout_ms: 500,
+            limits: RunnerLimits::new(memory_limit_bytes, 1_000_000, 500),
+            wal: None,
+            scheduler: None,
+            wasi_allowlist: None,
+            admission: None,
         }
     }
 
@@ -144,7 +237,113 @@ impl PluginRunner {
         cfg.consume_fuel(true);
         cfg.epoch_interruption(true);
         let engine = Engine::new(&cfg).expect("engine config should be valid");
-        Self { engine: Arc::new(engine), memory_limit_bytes, fuel_budget, timeout_ms }
+        Self {
+            engine: Arc::new(engine),
+            limits: RunnerLimits::new(memory_limit_bytes, fuel_budget, timeout_ms),
+            wal: None,
+            scheduler: None,
+            wasi_allowlist: None,
+            admission: None,
+        }
+    }
+
+    /// Append a `plugin_invoke` event (module digest, function, [`InvokeStats`], status) to
+    /// `wal` after every invocation. WAL write failures never fail the invoke.
+    #[must_use]
+    pub fn with_wal(mut self, wal: event_log::JsonlEventLog) -> Self {
+        self.wal = Some(wal);
+        self
+    }
+
+    /// Admit [`Self::invoke_i32_2_for_run`] calls through `scheduler`, bounding concurrent
+    /// invocations and round-robining slots across runs. Share the same scheduler between
+    /// clones (and runners) that should be admitted together.
+    #[must_use]
+    pub fn with_scheduler(mut self, scheduler: Arc<FairScheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Cap invocations in flight at once across this runner and its clones (clamped to at
+    /// least 1; default unlimited). An invoke beyond the cap fails fast with
+    /// [`RunnerError::AtCapacity`] before a store is created, so peak memory and timeout
+    /// threads stay bounded under bursts; use a [`FairScheduler`] to queue instead.
+    #[must_use]
+    pub fn with_max_concurrent_invocations(mut self, max: usize) -> Self {
+        self.admission =
+            Some(Arc::new(Admission { max: max.max(1), in_flight: AtomicUsize::new(0) }));
+        self
+    }
+
+    /// Invocations currently in flight under [`Self::with_max_concurrent_invocations`]
+    /// (always 0 without a cap).
+    #[must_use]
+    pub fn in_flight_invocations(&self) -> usize {
+        self.admission.as_ref().map_or(0, |a| a.in_flight.load(Ordering::Acquire))
+    }
+
+    /// Restrict the WASI preview1 functions modules may import (e.g. `["clock_time_get"]`).
+    ///
+    /// A module importing any other `wasi_snapshot_preview1` function fails instantiation
+    /// with [`RunnerError::InvokeFailed`] naming the denied import. Without an allowlist
+    /// (the default) the full preview1 surface is linked.
+    #[must_use]
+    pub fn with_wasi_allowlist<I, S>(mut self, functions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.wasi_allowlist = Some(Arc::new(functions.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Fail closed on WASI imports outside the configured allowlist.
+    fn check_wasi_imports(&self, module: &ModuleHandle) -> Result<(), RunnerError> {
+        let Some(allow) = &self.wasi_allowlist else {
+            return Ok(());
+        };
+        for import in module.module.imports() {
+            if import.module() == WASI_PREVIEW1_MODULE && !allow.contains(import.name()) {
+                return Err(RunnerError::InvokeFailed(format!(
+                    "WASI import '{}' is not in the allowlist",
+                    import.name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Linear memory cap (bytes) applied to each invoke's store.
+    #[must_use]
+    pub fn memory_limit_bytes(&self) -> usize {
+        self.limits.memory_limit_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Fuel units granted to each invoke (CPU bound).
+    #[must_use]
+    pub fn fuel_budget(&self) -> u64 {
+        self.limits.fuel_budget.load(Ordering::Relaxed)
+    }
+
+    /// Wall-clock timeout (ms) for each invoke, enforced via epoch interruption.
+    #[must_use]
+    pub fn timeout_ms(&self) -> u64 {
+        self.limits.timeout_ms.load(Ordering::Relaxed)
+    }
+
+    /// Update the memory cap for subsequent invocations (in-flight ones keep their limit).
+    pub fn set_memory_limit(&self, bytes: usize) {
+        self.limits.memory_limit_bytes.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Update the fuel budget for subsequent invocations (in-flight ones keep their budget).
+    pub fn set_fuel_budget(&self, fuel: u64) {
+        self.limits.fuel_budget.store(fuel, Ordering::Relaxed);
+    }
+
+    /// Update the timeout for subsequent invocations (in-flight ones keep their deadline).
+    pub fn set_timeout(&self, timeout_ms: u64) {
+        self.limits.timeout_ms.store(timeout_ms, Ordering::Relaxed);
     }
 
     /// Compile WASM bytes into a `Module` and return a handle.
@@ -153,21 +352,116 @@ impl PluginRunner {
     /// Returns [`RunnerError::LoadFailed`] when compilation fails.
     pub fn load_module(&self, wasm: &[u8]) -> Result<ModuleHandle, RunnerError> {
         Module::new(&self.engine, wasm)
-            .map(ModuleHandle::new)
+            .map(|m| ModuleHandle::new(m, wasm))
             .map_err(|e| RunnerError::LoadFailed(e.to_string()))
     }
 
+    /// Fetch WASM bytes from a content-addressed blob store by `digest` and compile them, so
+    /// the module's provenance is the digest itself ([`ModuleHandle::digest`] equals it).
+    ///
+    /// # Errors
+    /// Returns [`RunnerError::LoadFailed`] when the blob is missing or fails to decrypt, when
+    /// the fetched bytes do not hash to `digest`, or when compilation fails.
+    pub fn load_from_blob<K: blob_store::KeyProvider>(
+        &self,
+        store: &blob_store::BlobStore<K>,
+        digest: &blob_store::Digest,
+    ) -> Result<ModuleHandle, RunnerError> {
+        let wasm = store
+            .get(digest)
+            .map_err(|e| RunnerError::LoadFailed(format!("blob {}: {e}", digest.to_hex())))?;
+        // The store verifies content on read; re-check so provenance never rests on it alone.
+        if blob_store::BlobStore::<K>::digest_of(&wasm) != *digest {
+            return Err(RunnerError::LoadFailed(format!(
+                "blob {}: digest mismatch",
+                digest.to_hex()
+            )));
+        }
+        self.load_module(&wasm)
+    }
+
     /// Instantiate the module and invoke a typed export: (i32, i32) -> i32.
     ///
     /// # Errors
     /// Returns [`RunnerError::InvokeFailed`] when instantiation, lookup, or call fails,
-    /// including resource budget violations (fuel exhaustion or timeout via epoch interruption).
+    /// including resource budget violations (fuel exhaustion or timeout via epoch interruption),
+    /// and [`RunnerError::AtCapacity`] when the concurrency cap is reached.
     pub fn invoke_i32_2(
         &self,
         module: &ModuleHandle,
         func: &str,
         a: i32,
         b: i32,
+    ) -> Result<i32, RunnerError> {
+        self.invoke_i32_2_with_stats(module, func, a, b).0
+    }
+
+    /// Like [`Self::invoke_i32_2`], on behalf of `run_id`: with a scheduler configured
+    /// ([`Self::with_scheduler`]) this blocks until the run is granted a slot, which is
+    /// held for the duration of the call.
+    ///
+    /// # Errors
+    /// Same as [`Self::invoke_i32_2`].
+    pub fn invoke_i32_2_for_run(
+        &self,
+        run_id: &str,
+        module: &ModuleHandle,
+        func: &str,
+        a: i32,
+        b: i32,
+    ) -> Result<i32, RunnerError> {
+        let _permit = self.scheduler.as_deref().map(|s| s.acquire(run_id));
+        self.invoke_i32_2(module, func, a, b)
+    }
+
+    /// Like [`Self::invoke_i32_2`], also returning the invocation's resource usage (filled
+    /// as far as the invoke got, including on error). A call rejected with
+    /// [`RunnerError::AtCapacity`] never ran and writes no `plugin_invoke` event.
+    pub fn invoke_i32_2_with_stats(
+        &self,
+        module: &ModuleHandle,
+        func: &str,
+        a: i32,
+        b: i32,
+    ) -> (Result<i32, RunnerError>, InvokeStats) {
+        let _permit = match self.admission.as_ref().map(Admission::try_acquire) {
+            Some(None) => {
+                let max = self.admission.as_ref().map_or(0, |a| a.max);
+                return (Err(RunnerError::AtCapacity(max)), InvokeStats::default());
+            }
+            permit => permit.flatten(),
+        };
+        let started = std::time::Instant::now();
+        let mut stats = InvokeStats::default();
+        let res = self.invoke_inner(module, func, a, b, &mut stats);
+        stats.wall_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        if let Some(wal) = &self.wal {
+            let (outcome, error) = match &res {
+                Ok(_) => ("ok", None),
+                Err(e) => ("error", Some(e.to_string())),
+            };
+            let evt = serde_json::json!({
+                "event": "plugin_invoke",
+                "module_digest": module.digest(),
+                "function": func,
+                "fuel_consumed": stats.fuel_consumed,
+                "wall_ms": stats.wall_ms,
+                "peak_memory_bytes": stats.peak_memory_bytes,
+                "status": outcome,
+                "error": error,
+            });
+            let _ = wal.append(orca_core::ids::next_monotonic_id(), orca_core::ids::now_ms(), &evt);
+        }
+        (res, stats)
+    }
+
+    fn invoke_inner(
+        &self,
+        module: &ModuleHandle,
+        func: &str,
+        a: i32,
+        b: i32,
+        stats: &mut InvokeStats,
     ) -> Result<i32, RunnerError> {
         // Store state carries WASI context and resource limits; limiter returns a mutable
         // reference to the limits enabling Wasmtime to enforce them.
@@ -176,16 +470,21 @@ impl PluginRunner {
             limits: StoreLimits,
         }
 
+        self.check_wasi_imports(module)?;
+
+        // Snapshot limits once so concurrent updates only affect later invocations.
+        let (memory_limit_bytes, fuel_budget, timeout_ms) =
+            (self.memory_limit_bytes(), self.fuel_budget(), self.timeout_ms());
+
         let wasi = WasiCtxBuilder::new().build_p1();
-        let limits = StoreLimitsBuilder::new().memory_size(self.memory_limit_bytes).build();
+        let limits = StoreLimitsBuilder::new().memory_size(memory_limit_bytes).build();
         let mut store: Store<StoreState> = Store::new(&self.engine, StoreState { wasi, limits });
         // Attach the limiter; Wasmtime will consult this to enforce memory/table/instance caps.
         store.limiter(|s| &mut s.limits);
         // Add fuel budget (CPU bound) and set epoch deadline for timeouts.
-        store.set_fuel(self.fuel_budget).map_err(|e| RunnerError::InvokeFailed(e.to_string()))?;
+        store.set_fuel(fuel_budget).map_err(|e| RunnerError::InvokeFailed(e.to_string()))?;
         store.set_epoch_deadline(1);
         let engine_for_timeout = self.engine.clone();
-        let timeout_ms = self.timeout_ms;
         let _timeout_thr = std::thread::spawn(move || {
             std::thread::sleep(Duration::from_millis(timeout_ms));
             engine_for_timeout.increment_epoch();
@@ -234,7 +533,16 @@ impl PluginRunner {
             .get_typed_func::<(i32, i32), i32>(&mut store, func)
             .map_err(|e| RunnerError::InvokeFailed(e.to_string()))?;
 
-        match pollster::block_on(func_typed.call_async(&mut store, (a, b))) {
+        let res = pollster::block_on(func_typed.call_async(&mut store, (a, b)));
+        stats.fuel_consumed = fuel_budget.saturating_sub(store.get_fuel().unwrap_or(0));
+        for export in module.module.exports() {
+            if matches!(export.ty(), wasmtime::ExternType::Memory(_)) {
+                if let Some(mem) = instance.get_memory(&mut store, export.name()) {
+                    stats.peak_memory_bytes += mem.data_size(&store);
+                }
+            }
+        }
+        match res {
             Ok(v) => Ok(v),
             Err(e) => {
                 let fuel = store.get_fuel().ok();
@@ -249,7 +557,7 @@ impl PluginRunner {
 }
 
 /// Plugin manifest describing the WASM module and supply-chain metadata.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PluginManifest {
     /// Human-readable plugin name (informational only).
     pub name: String,
@@ -272,7 +580,7 @@ pub struct PluginManifest {
 /// - `invalid_signature`
 /// - `invalid_digest_format`
 /// - `oversized_signature`
-#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
 pub enum VerificationError {
     /// Signature is required but missing (`require_signed_plugins=true`).
     #[error("manifest missing signature")]
@@ -280,10 +588,11 @@ pub enum VerificationError {
     /// SBOM reference is required but missing (`require_signed_plugins=true`).
     #[error("manifest missing SBOM reference")]
     MissingSbom,
-    /// The `manifest.wasm_digest` is not exactly 64 hex chars after trim+lowercase.
+    /// The `manifest.wasm_digest` (or an accepted digest passed to
+    /// [`ManifestVerifier::verify_accepting`]) is not exactly 64 hex chars after trim+lowercase.
     #[error("invalid digest format")]
     InvalidDigestFormat,
-    /// WASM digest did not match `manifest.wasm_digest`.
+    /// WASM digest matched neither `manifest.wasm_digest` nor any accepted digest.
     #[error("digest mismatch")]
     DigestMismatch,
     /// Signature present but exceeds size cap (16 KiB after trim).
@@ -297,6 +606,108 @@ pub enum VerificationError {
     Other(String),
 }
 
+impl VerificationError {
+    /// Stable `error_code` string for this error (same values as spans/metrics).
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::MissingSignature => "missing_signature",
+            Self::MissingSbom => "missing_sbom",
+            Self::InvalidDigestFormat => "invalid_digest_format",
+            Self::DigestMismatch => "digest_mismatch",
+            Self::OversizedSignature => "oversized_signature",
+            Self::InvalidSignature => "invalid_signature",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+/// Auditable outcome of [`ManifestVerifier::verify_detailed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Plugin name from the manifest.
+    pub name: String,
+    /// Lowercase hex SHA-256 of the WASM bytes actually presented.
+    pub digest: String,
+    /// Signer identity from the verified certificate; `None` unless a signature verified.
+    pub signer: Option<String>,
+    /// Verification result.
+    pub outcome: Result<(), VerificationError>,
+}
+
+impl VerificationReport {
+    /// Whether verification passed.
+    #[must_use]
+    pub const fn is_verified(&self) -> bool {
+        self.outcome.is_ok()
+    }
+
+    /// Stable error code of a failed verification; `None` on success.
+    #[must_use]
+    pub fn error_code(&self) -> Option<&'static str> {
+        self.outcome.as_ref().err().map(VerificationError::error_code)
+    }
+}
+
+/// Offline Sigstore trust material (no TUF, no network).
+///
+/// Holds one or more pinned Fulcio root certificates so operators can rotate roots or
+/// accept staging and production side by side; a signature chaining to any pinned root
+/// verifies.
+///
+/// With roots, `manifest.signature` is a base64 JSON signing bundle
+/// `{"cert_pem", "signature", "signed_at"}`: the PEM signing certificate, a base64 DER
+/// ECDSA P-256/SHA-256 signature over the WASM bytes, and the signing time (Unix seconds)
+/// at which the certificate must be valid for code signing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigstoreOptions {
+    /// PEM-encoded Fulcio root certificates (each entry may hold several certificates).
+    pub fulcio_roots_pem: Vec<Vec<u8>>,
+}
+
+impl SigstoreOptions {
+    /// Options pinning a single Fulcio root (the common case).
+    #[must_use]
+    pub fn with_fulcio_root(pem: impl Into<Vec<u8>>) -> Self {
+        Self { fulcio_roots_pem: vec![pem.into()] }
+    }
+
+    /// Pin an additional Fulcio root (e.g., the next root during rotation).
+    #[must_use]
+    pub fn add_fulcio_root(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.fulcio_roots_pem.push(pem.into());
+        self
+    }
+
+    /// Build a `ManualTrustRoot` holding every pinned Fulcio root, in configuration order.
+    ///
+    /// # Errors
+    /// Returns `VerificationError::Other` when no root is configured or any entry is not a
+    /// PEM `CERTIFICATE` block (fail-closed: a bad root never silently drops out).
+    pub fn trust_root(
+        &self,
+    ) -> Result<sigstore::trust::ManualTrustRoot<'static>, VerificationError> {
+        if self.fulcio_roots_pem.is_empty() {
+            return Err(VerificationError::Other("no fulcio roots configured".into()));
+        }
+        let mut fulcio_certs = Vec::new();
+        for (idx, pem_bytes) in self.fulcio_roots_pem.iter().enumerate() {
+            let blocks = pem::parse_many(pem_bytes)
+                .map_err(|e| VerificationError::Other(format!("fulcio root {idx}: {e}")))?;
+            let certs: Vec<_> = blocks.into_iter().filter(|b| b.tag() == "CERTIFICATE").collect();
+            if certs.is_empty() {
+                return Err(VerificationError::Other(format!(
+                    "fulcio root {idx}: no CERTIFICATE block"
+                )));
+            }
+            fulcio_certs.extend(
+                certs.into_iter().map(|b| pki_types::CertificateDer::from(b.into_contents())),
+            );
+        }
+        Ok(sigstore::trust::ManualTrustRoot { fulcio_certs, ..Default::default() })
+    }
+}
+
 /// Offline verifier (no network). Policy: `require_signed_plugins=true` by default.
 impl From<base64::DecodeError> for VerificationError {
     fn from(_e: base64::DecodeError) -> Self {
@@ -320,6 +731,104 @@ fn normalize_and_validate_digest(s: &str) -> Result<[u8; 32], VerificationError>
     Ok(arr)
 }
 
+/// Check a manifest signature (size cap, base64, then the signing bundle) and return the
+/// signer identity. Without pinned roots no signature verifies.
+fn verify_signature(
+    sig: &str,
+    wasm: &[u8],
+    roots: Option<&SigstoreOptions>,
+) -> Result<String, VerificationError> {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine as _;
+
+    let s = sig.trim();
+    validate_signature_size(s)?;
+    let bundle = STANDARD.decode(s)?;
+    roots.map_or(Err(VerificationError::InvalidSignature), |roots| {
+        verify_bundle(&bundle, wasm, roots)
+    })
+}
+
+/// Extended key usage `id-kp-codeSigning` (1.3.6.1.5.5.7.3.3), as issued by Fulcio.
+const EKU_CODE_SIGNING: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03];
+
+/// Verify a decoded signing bundle over `wasm` and return the signer identity.
+///
+/// The bundle is JSON `{"cert_pem", "signature", "signed_at"}`: the PEM signing certificate,
+/// a base64 DER ECDSA P-256/SHA-256 signature over the WASM bytes, and the signing time in
+/// Unix seconds. The certificate must chain to one of the pinned roots and be valid for code
+/// signing at `signed_at` (Fulcio certificates are short-lived, so the time is the signing
+/// time, never the wall clock).
+fn verify_bundle(
+    bundle: &[u8],
+    wasm: &[u8],
+    roots: &SigstoreOptions,
+) -> Result<String, VerificationError> {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine as _;
+
+    let trust_root = roots.trust_root()?;
+    let anchors = trust_root
+        .fulcio_certs
+        .iter()
+        .map(|c| webpki::anchor_from_trusted_cert(c).map(|a| a.to_owned()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| VerificationError::Other(format!("fulcio root: {e}")))?;
+
+    let bundle: serde_json::Value =
+        serde_json::from_slice(bundle).map_err(|_| VerificationError::InvalidSignature)?;
+    let field = |k: &str| bundle.get(k).ok_or(VerificationError::InvalidSignature);
+    let cert_pem = field("cert_pem")?.as_str().ok_or(VerificationError::InvalidSignature)?;
+    let signature = field("signature")?.as_str().ok_or(VerificationError::InvalidSignature)?;
+    let signed_at = field("signed_at")?.as_u64().ok_or(VerificationError::InvalidSignature)?;
+
+    let leaf = pem::parse(cert_pem).map_err(|_| VerificationError::InvalidSignature)?;
+    if leaf.tag() != "CERTIFICATE" {
+        return Err(VerificationError::InvalidSignature);
+    }
+    let leaf_der = pki_types::CertificateDer::from(leaf.contents());
+    let cert = webpki::EndEntityCert::try_from(&leaf_der)
+        .map_err(|_| VerificationError::InvalidSignature)?;
+    cert.verify_for_usage(
+        &[webpki::ring::ECDSA_P256_SHA256, webpki::ring::ECDSA_P384_SHA384],
+        &anchors,
+        &[],
+        pki_types::UnixTime::since_unix_epoch(Duration::from_secs(signed_at)),
+        webpki::KeyUsage::required(EKU_CODE_SIGNING),
+        None,
+        None,
+    )
+    .map_err(|_| VerificationError::InvalidSignature)?;
+    let sig = STANDARD.decode(signature.trim())?;
+    cert.verify_signature(webpki::ring::ECDSA_P256_SHA256, wasm, &sig)
+        .map_err(|_| VerificationError::InvalidSignature)?;
+    signer_identity(leaf.contents())
+}
+
+/// Signer identity of a certificate: its first email or URI SAN (how Fulcio records the
+/// OIDC identity), else its subject DN.
+fn signer_identity(der: &[u8]) -> Result<String, VerificationError> {
+    use x509_cert::der::Decode as _;
+    use x509_cert::ext::pkix::name::GeneralName;
+    use x509_cert::ext::pkix::SubjectAltName;
+
+    let cert =
+        x509_cert::Certificate::from_der(der).map_err(|_| VerificationError::InvalidSignature)?;
+    let san = cert
+        .tbs_certificate
+        .get::<SubjectAltName>()
+        .map_err(|_| VerificationError::InvalidSignature)?;
+    let from_san = san.and_then(|(_, SubjectAltName(names))| {
+        names.into_iter().find_map(|n| match n {
+            GeneralName::Rfc822Name(v) | GeneralName::UniformResourceIdentifier(v) => {
+                Some(v.to_string())
+            }
+            _ => None,
+        })
+    });
+    Ok(from_san.unwrap_or_else(|| cert.tbs_certificate.subject.to_string()))
+}
+
 fn validate_signature_size(s: &str) -> Result<(), VerificationError> {
     let len = s.trim().len();
     if len > MAX_SIG_LEN {
@@ -356,11 +865,52 @@ impl ManifestVerifier {
     /// Returns:
     /// - `VerificationError::MissingSignature` when a signature is required but not present.
     /// - `VerificationError::MissingSbom` when SBOM reference is required but missing.
-    /// - `VerificationError::DigestMismatch` when the WASM digest does not match the manifest.
+    /// - `VerificationError::InvalidDigestFormat` when any manifest digest is malformed.
+    /// - `VerificationError::DigestMismatch` when the WASM digest matches none of the
+    ///   manifest's digests.
     /// - `VerificationError::InvalidSignature` when signature decoding/verification fails.
     pub fn verify(&self, manifest: &PluginManifest, wasm: &[u8]) -> Result<(), VerificationError> {
-        use base64::engine::general_purpose::STANDARD;
-        use base64::Engine as _;
+        self.verify_accepting(manifest, &[], wasm)
+    }
+
+    /// Like [`Self::verify`], but the WASM may match `manifest.wasm_digest` or any of
+    /// `accepted_digests` (e.g. the old and new builds during a blue/green rollout).
+    ///
+    /// Every digest must be well-formed, even when another one matches.
+    ///
+    /// # Errors
+    /// As [`Self::verify`].
+    pub fn verify_accepting(
+        &self,
+        manifest: &PluginManifest,
+        accepted_digests: &[String],
+        wasm: &[u8],
+    ) -> Result<(), VerificationError> {
+        self.check(manifest, accepted_digests, wasm, None).map(|_| ())
+    }
+
+    /// Like [`Self::verify`], but a present signature is checked as an offline signing
+    /// bundle (see [`SigstoreOptions`]) against the pinned Fulcio roots in `roots`.
+    ///
+    /// # Errors
+    /// As [`Self::verify`]; `VerificationError::Other` when `roots` holds no usable root.
+    pub fn verify_with_roots(
+        &self,
+        manifest: &PluginManifest,
+        wasm: &[u8],
+        roots: &SigstoreOptions,
+    ) -> Result<(), VerificationError> {
+        self.check(manifest, &[], wasm, Some(roots)).map(|_| ())
+    }
+
+    /// Shared verification path; returns the signer identity when a signature verified.
+    fn check(
+        &self,
+        manifest: &PluginManifest,
+        accepted_digests: &[String],
+        wasm: &[u8],
+        roots: Option<&SigstoreOptions>,
+    ) -> Result<Option<String>, VerificationError> {
         use sha2::Digest as _;
 
         // Observability span (no control-path changes).
@@ -394,8 +944,13 @@ impl ManifestVerifier {
             }
         }
 
-        // Validate manifest digest format and decode expected digest bytes.
-        let expected = match normalize_and_validate_digest(&manifest.wasm_digest) {
+        // Validate every manifest digest's format and decode the expected digest bytes.
+        let expected = match std::iter::once(&manifest.wasm_digest)
+            .chain(accepted_digests)
+            .map(String::as_str)
+            .map(normalize_and_validate_digest)
+            .collect::<Result<Vec<_>, _>>()
+        {
             Ok(b) => b,
             Err(e) => {
                 span.record("result", "error");
@@ -409,13 +964,16 @@ impl ManifestVerifier {
             }
         };
 
-        // Digest pinning: sha256(WASM) must equal manifest.wasm_digest (hex, case-insensitive).
+        // Digest pinning: sha256(WASM) must equal manifest.wasm_digest or one of the
+        // accepted digests (hex, case-insensitive). Every candidate is compared in constant
+        // time, without short-circuiting, so timing does not reveal which one matched.
         let mut hasher = sha2::Sha256::new();
         hasher.update(wasm);
         let actual_vec = hasher.finalize();
         let mut actual = [0u8; 32];
         actual.copy_from_slice(&actual_vec);
-        if !bool::from(actual.ct_eq(&expected)) {
+        let matched = expected.iter().fold(subtle::Choice::from(0), |acc, e| acc | actual.ct_eq(e));
+        if !bool::from(matched) {
             span.record("result", "error");
             span.record("error_code", field::display("digest_mismatch"));
             #[cfg(feature = "otel")]
@@ -426,46 +984,79 @@ impl ManifestVerifier {
             return Err(VerificationError::DigestMismatch);
         }
 
-        // Offline signature verification: for now, require base64-encoded material and
-        // return InvalidSignature if decoding or offline verification fails. This remains
-        // offline and deterministic; network is not used.
-        if let Some(sig) = &manifest.signature {
-            let s = sig.trim();
-            if let Err(e) = validate_signature_size(s) {
-                span.record("result", "error");
-                span.record("error_code", field::display("oversized_signature"));
-                #[cfg(feature = "otel")]
-                {
-                    verify_metrics::inc_failure("oversized_signature");
-                    verify_metrics::observe_ms(__start.elapsed().as_secs_f64() * 1000.0);
-                }
-                return Err(e);
-            }
-            if STANDARD.decode(s).is_err() {
-                span.record("result", "error");
-                span.record("error_code", field::display("invalid_signature"));
-                #[cfg(feature = "otel")]
-                {
-                    verify_metrics::inc_failure("invalid_signature");
-                    verify_metrics::observe_ms(__start.elapsed().as_secs_f64() * 1000.0);
+        // Offline signature verification: the signature is a base64 signing bundle checked
+        // against the pinned Fulcio roots. Without roots nothing can verify (fail-closed).
+        // This remains offline and deterministic; network is not used.
+        let signer =
+            match manifest.signature.as_deref().map(|sig| verify_signature(sig, wasm, roots)) {
+                None => None,
+                Some(Ok(id)) => Some(id),
+                Some(Err(e)) => {
+                    span.record("result", "error");
+                    span.record("error_code", field::display(e.error_code()));
+                    #[cfg(feature = "otel")]
+                    {
+                        verify_metrics::inc_failure(e.error_code());
+                        verify_metrics::observe_ms(__start.elapsed().as_secs_f64() * 1000.0);
+                    }
+                    return Err(e);
                 }
-                return Err(VerificationError::InvalidSignature);
-            }
-            // TODO(SEC-04 follow-up): integrate sigstore offline verification against a pinned trust root/bundle.
-            span.record("result", "error");
-            span.record("error_code", field::display("invalid_signature"));
-            #[cfg(feature = "otel")]
-            {
-                verify_metrics::inc_failure("invalid_signature");
-                verify_metrics::observe_ms(__start.elapsed().as_secs_f64() * 1000.0);
-            }
-            return Err(VerificationError::InvalidSignature);
-        }
+            };
 
         span.record("result", "ok");
         #[cfg(feature = "otel")]
         verify_metrics::observe_ms(__start.elapsed().as_secs_f64() * 1000.0);
-        Ok(())
+        Ok(signer)
+    }
+
+    /// Like [`Self::verify`], but returns a [`VerificationReport`] carrying the plugin name,
+    /// actual WASM digest, signer identity, and outcome for durable audit (e.g. WAL events).
+    #[must_use]
+    pub fn verify_detailed(&self, manifest: &PluginManifest, wasm: &[u8]) -> VerificationReport {
+        self.verify_detailed_accepting(manifest, &[], wasm)
+    }
+
+    /// [`Self::verify_detailed`] with the extra digests of [`Self::verify_accepting`].
+    #[must_use]
+    pub fn verify_detailed_accepting(
+        &self,
+        manifest: &PluginManifest,
+        accepted_digests: &[String],
+        wasm: &[u8],
+    ) -> VerificationReport {
+        self.report(manifest, accepted_digests, wasm, None)
+    }
+
+    /// [`Self::verify_detailed`] checking signatures as in [`Self::verify_with_roots`]; the
+    /// report's `signer` is the identity of the verified signing certificate.
+    #[must_use]
+    pub fn verify_detailed_with_roots(
+        &self,
+        manifest: &PluginManifest,
+        wasm: &[u8],
+        roots: &SigstoreOptions,
+    ) -> VerificationReport {
+        self.report(manifest, &[], wasm, Some(roots))
+    }
+
+    fn report(
+        &self,
+        manifest: &PluginManifest,
+        accepted_digests: &[String],
+        wasm: &[u8],
+        roots: Option<&SigstoreOptions>,
+    ) -> VerificationReport {
+        use sha2::Digest as _;
+        let (signer, outcome) = match self.check(manifest, accepted_digests, wasm, roots) {
+            Ok(signer) => (signer, Ok(())),
+            Err(e) => (None, Err(e)),
+        };
+        VerificationReport {
+            name: manifest.name.clone(),
+            digest: hex::encode(sha2::Sha256::digest(wasm)),
+            signer,
+            outcome,
+        }
     }
 }
 
@@ -507,6 +1098,54 @@ mod tests {
         assert_eq!(res, -1, "memory.grow should be denied by limits and return -1");
     }
 
+    #[test]
+    fn updated_limits_apply_to_next_invoke() {
+        // Loops `a` times; cheap for small `a`, fuel-hungry for large `a`.
+        let wat = r#"(module
+            (memory (export "mem") 1)
+            (func (export "count") (param i32 i32) (result i32)
+              (local i32)
+              block
+                loop
+                  local.get 2
+                  local.get 0
+                  i32.ge_s
+                  br_if 1
+                  local.get 2
+                  i32.const 1
+                  i32.add
+                  local.set 2
+                  br 0
+                end
+              end
+              local.get 2)
+            (func (export "grow") (param i32 i32) (result i32)
+              i32.const 1
+              memory.grow))"#;
+        let wasm = wat::parse_str(wat).expect("WAT -> WASM should succeed");
+        let runner = PluginRunner::new();
+        let shared = runner.clone();
+        let handle = runner.load_module(&wasm).expect("load module");
+        assert_eq!(runner.invoke_i32_2(&handle, "count", 10_000, 0).unwrap(), 10_000);
+
+        // Fuel: shrinking the budget on a clone starves the next invoke; restoring fixes it.
+        shared.set_fuel_budget(1_000);
+        assert_eq!(runner.fuel_budget(), 1_000);
+        let err = runner.invoke_i32_2(&handle, "count", 10_000, 0).unwrap_err();
+        assert!(format!("{err}").contains("fuel exhausted"), "{err}");
+        shared.set_fuel_budget(1_000_000);
+        assert_eq!(runner.invoke_i32_2(&handle, "count", 10_000, 0).unwrap(), 10_000);
+
+        // Memory: growing past a lowered cap is denied (memory.grow returns -1).
+        assert_eq!(runner.invoke_i32_2(&handle, "grow", 0, 0).unwrap(), 1);
+        runner.set_memory_limit(64 * 1024);
+        assert_eq!(runner.memory_limit_bytes(), 64 * 1024);
+        assert_eq!(runner.invoke_i32_2(&handle, "grow", 0, 0).unwrap(), -1);
+
+        runner.set_timeout(250);
+        assert_eq!(shared.timeout_ms(), 250);
+    }
+
     #[test]
     fn fuel_exhaustion_returns_error() {
         // Infinite loop to burn fuel; should trap when fuel is exhausted.
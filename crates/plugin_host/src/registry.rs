@@ -0,0 +1,129 @@
+//! Digest-pinned plugin registry keyed by `(name, version)`.
+//!
+//! [`PluginRegistry::register`] verifies a manifest against the WASM bytes, checking any
+//! signature against the registry's pinned Fulcio roots, and compiles the module on the
+//! registry's runner before anything is recorded, so every entry is a verified manifest
+//! paired with a module whose digest matches it. Entries are never
+//! replaced: registering the same `(name, version)` twice fails, and a new build needs a
+//! new version.
+
+use crate::{
+    ManifestVerifier, ModuleHandle, PluginManifest, PluginRunner, RunnerError, SigstoreOptions,
+    VerificationError,
+};
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+use thiserror::Error;
+
+/// Errors from [`PluginRegistry::register`].
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    /// `(name, version)` already has an entry; the registry is left unchanged.
+    #[error("plugin {name}@{version} already registered")]
+    AlreadyRegistered {
+        /// Plugin name from the manifest.
+        name: String,
+        /// Plugin version from the manifest.
+        version: String,
+    },
+    /// The manifest did not verify against the WASM bytes; nothing was compiled.
+    #[error("plugin {name}@{version} unverified: {source}")]
+    Unverified {
+        /// Plugin name from the manifest.
+        name: String,
+        /// Plugin version from the manifest.
+        version: String,
+        /// Why verification failed.
+        source: VerificationError,
+    },
+    /// The verified bytes failed to compile.
+    #[error(transparent)]
+    Load(#[from] RunnerError),
+}
+
+/// A registered plugin: its verified manifest and the module compiled from the pinned bytes.
+#[derive(Debug, Clone)]
+pub struct RegisteredPlugin {
+    /// Manifest that verified at registration.
+    pub manifest: PluginManifest,
+    /// Module compiled on the registry's runner; invoke it through [`PluginRegistry::runner`].
+    pub module: ModuleHandle,
+}
+
+/// Verified plugins by `(name, version)`, compiled on one [`PluginRunner`] and checked
+/// against one set of pinned Fulcio roots.
+pub struct PluginRegistry {
+    runner: PluginRunner,
+    verifier: ManifestVerifier,
+    roots: SigstoreOptions,
+    plugins: RwLock<BTreeMap<(String, String), RegisteredPlugin>>,
+}
+
+impl PluginRegistry {
+    /// Registry that verifies with `verifier` against the Fulcio roots in `roots` and compiles
+    /// modules on `runner` (clones share its engine, so modules stay invocable on the caller's
+    /// runner too).
+    #[must_use]
+    pub const fn new(
+        runner: PluginRunner,
+        verifier: ManifestVerifier,
+        roots: SigstoreOptions,
+    ) -> Self {
+        Self { runner, verifier, roots, plugins: RwLock::new(BTreeMap::new()) }
+    }
+
+    /// Runner the registered modules were compiled on.
+    #[must_use]
+    pub const fn runner(&self) -> &PluginRunner {
+        &self.runner
+    }
+
+    /// Verify `manifest` against `wasm` and the registry's roots, compile it, and record it
+    /// under `(manifest.name, manifest.version)`.
+    ///
+    /// # Errors
+    /// [`RegistryError::AlreadyRegistered`] when the key is taken,
+    /// [`RegistryError::Unverified`] when verification fails, and [`RegistryError::Load`]
+    /// when compilation fails. The registry is unchanged on every error.
+    pub fn register(
+        &self,
+        manifest: PluginManifest,
+        wasm: &[u8],
+    ) -> Result<RegisteredPlugin, RegistryError> {
+        let key = (manifest.name.clone(), manifest.version.clone());
+        let already =
+            |(name, version): (String, String)| RegistryError::AlreadyRegistered { name, version };
+        if self.read().contains_key(&key) {
+            return Err(already(key));
+        }
+        if let Err(source) = self.verifier.verify_with_roots(&manifest, wasm, &self.roots) {
+            return Err(RegistryError::Unverified { name: key.0, version: key.1, source });
+        }
+        let module = self.runner.load_module(wasm)?;
+        let entry = RegisteredPlugin { manifest, module };
+        let mut plugins = self.plugins.write().unwrap_or_else(PoisonError::into_inner);
+        // A concurrent registration of the same key may have won while we compiled.
+        if plugins.contains_key(&key) {
+            return Err(already(key));
+        }
+        plugins.insert(key, entry.clone());
+        drop(plugins);
+        Ok(entry)
+    }
+
+    /// The plugin registered as `name` at `version`.
+    #[must_use]
+    pub fn get(&self, name: &str, version: &str) -> Option<RegisteredPlugin> {
+        self.read().get(&(name.to_string(), version.to_string())).cloned()
+    }
+
+    /// Registered `(name, version)` keys in ascending order.
+    #[must_use]
+    pub fn list(&self) -> Vec<(String, String)> {
+        self.read().keys().cloned().collect()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<(String, String), RegisteredPlugin>> {
+        self.plugins.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
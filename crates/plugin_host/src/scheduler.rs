@@ -0,0 +1,164 @@
+//! Fair admission for plugin invocations across runs.
+//!
+//! [`FairScheduler`] bounds how many invocations execute at once and hands free slots to
+//! waiting runs in round-robin order, so a run that submits a burst cannot starve other
+//! runs of the WASM engine. Within a run, invocations are admitted in arrival order.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+#[derive(Debug, Default)]
+struct State {
+    /// Permits currently held, in total and per run.
+    in_flight: usize,
+    in_flight_by_run: HashMap<String, usize>,
+    /// Waiting tickets per run, oldest first.
+    waiting: HashMap<String, VecDeque<u64>>,
+    /// Runs with waiters, in the order they will next be served.
+    rotation: VecDeque<String>,
+    /// Tickets admitted but not yet picked up by their waiter.
+    granted: Vec<u64>,
+    next_ticket: u64,
+}
+
+/// Round-robin, bounded-concurrency admission queue keyed by run id.
+///
+/// `acquire` blocks until the caller's run is served; the returned permit frees the slot
+/// when dropped. Share one scheduler (e.g. behind an `Arc`) across everything that invokes
+/// plugins on the same engine.
+#[derive(Debug)]
+pub struct FairScheduler {
+    max_concurrent: usize,
+    max_per_run: Option<usize>,
+    state: Mutex<State>,
+    cv: Condvar,
+}
+
+impl FairScheduler {
+    /// Admit at most `max_concurrent` invocations at once (clamped to at least 1).
+    #[must_use]
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            max_per_run: None,
+            state: Mutex::new(State::default()),
+            cv: Condvar::new(),
+        }
+    }
+
+    /// Additionally cap the slots a single run may hold at once (clamped to at least 1).
+    /// Unset by default: a run may use every free slot when no other run is waiting.
+    #[must_use]
+    pub fn with_max_per_run(mut self, max: usize) -> Self {
+        self.max_per_run = Some(max.max(1));
+        self
+    }
+
+    /// Total concurrency bound.
+    #[must_use]
+    pub const fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Per-run concurrency bound, if any.
+    #[must_use]
+    pub const fn max_per_run(&self) -> Option<usize> {
+        self.max_per_run
+    }
+
+    /// Number of invocations currently holding a permit.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight
+    }
+
+    /// Number of invocations waiting for a permit.
+    #[must_use]
+    pub fn waiting(&self) -> usize {
+        let st = self.lock();
+        st.waiting.values().map(VecDeque::len).sum::<usize>() + st.granted.len()
+    }
+
+    /// Block until `run_id` is granted a slot.
+    pub fn acquire(&self, run_id: &str) -> SchedulerPermit<'_> {
+        let mut st = self.lock();
+        let ticket = st.next_ticket;
+        st.next_ticket = st.next_ticket.wrapping_add(1);
+        st.waiting.entry(run_id.to_string()).or_default().push_back(ticket);
+        if !st.rotation.iter().any(|r| r == run_id) {
+            st.rotation.push_back(run_id.to_string());
+        }
+        self.dispatch(&mut st);
+        while !st.granted.contains(&ticket) {
+            st = self.cv.wait(st).unwrap_or_else(PoisonError::into_inner);
+        }
+        st.granted.retain(|t| *t != ticket);
+        drop(st);
+        SchedulerPermit { scheduler: self, run_id: run_id.to_string() }
+    }
+
+    fn release(&self, run_id: &str) {
+        let mut st = self.lock();
+        st.in_flight = st.in_flight.saturating_sub(1);
+        if let Some(n) = st.in_flight_by_run.get_mut(run_id) {
+            *n = n.saturating_sub(1);
+            if *n == 0 {
+                st.in_flight_by_run.remove(run_id);
+            }
+        }
+        self.dispatch(&mut st);
+        drop(st);
+    }
+
+    /// Grant free slots to waiting runs, one per run per turn.
+    fn dispatch(&self, st: &mut State) {
+        let mut skipped = 0;
+        while st.in_flight < self.max_concurrent && skipped < st.rotation.len() {
+            let Some(run) = st.rotation.pop_front() else { break };
+            let held = st.in_flight_by_run.get(&run).copied().unwrap_or(0);
+            if self.max_per_run.is_some_and(|m| held >= m) {
+                st.rotation.push_back(run);
+                skipped += 1;
+                continue;
+            }
+            skipped = 0;
+            let queue = st.waiting.get_mut(&run).expect("rotation entries have waiters");
+            let ticket = queue.pop_front().expect("rotation entries have waiters");
+            if queue.is_empty() {
+                st.waiting.remove(&run);
+            } else {
+                st.rotation.push_back(run.clone());
+            }
+            st.in_flight += 1;
+            *st.in_flight_by_run.entry(run).or_default() += 1;
+            st.granted.push(ticket);
+        }
+        self.cv.notify_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Slot held by one admitted invocation; released on drop.
+#[derive(Debug)]
+#[must_use = "the slot is released as soon as the permit is dropped"]
+pub struct SchedulerPermit<'a> {
+    scheduler: &'a FairScheduler,
+    run_id: String,
+}
+
+impl SchedulerPermit<'_> {
+    /// Run this permit was granted to.
+    #[must_use]
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+}
+
+impl Drop for SchedulerPermit<'_> {
+    fn drop(&mut self) {
+        self.scheduler.release(&self.run_id);
+    }
+}
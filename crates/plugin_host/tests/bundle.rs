@@ -0,0 +1,95 @@
+#![allow(missing_docs)]
+//! Offline plugin bundles: golden valid/tampered files and per-component tampering.
+
+use plugin_host::{
+    BundleError, ManifestVerifier, OrcaPluginBundle, PluginRunner, SigstoreOptions,
+    VerificationError, BUNDLE_MAGIC,
+};
+
+fn golden(dir: &str, name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(dir).join(name)
+}
+
+fn roots() -> SigstoreOptions {
+    let pem = |n| std::fs::read(golden("sigstore", n)).unwrap();
+    SigstoreOptions::with_fulcio_root(pem("fulcio_root_1.pem"))
+        .add_fulcio_root(pem("fulcio_root_2.pem"))
+}
+
+fn load(bundle: &OrcaPluginBundle) -> Result<plugin_host::ModuleHandle, BundleError> {
+    bundle.verify_and_load(&ManifestVerifier::new(), &roots(), &PluginRunner::new())
+}
+
+fn valid() -> OrcaPluginBundle {
+    OrcaPluginBundle::open(golden("bundle", "valid.orcab")).expect("golden bundle parses")
+}
+
+#[test]
+fn valid_golden_bundle_verifies_and_loads() {
+    let bundle = valid();
+    assert_eq!((bundle.name.as_str(), bundle.version.as_str()), ("signed", "0.1.0"));
+    assert_eq!(bundle.wasm, std::fs::read(golden("sigstore", "signed.wasm")).unwrap());
+    let module = load(&bundle).expect("valid bundle loads");
+    assert_eq!(module.digest(), bundle.wasm_digest);
+}
+
+#[test]
+fn bundle_round_trips_through_bytes() {
+    let bundle = valid();
+    let bytes = bundle.to_bytes();
+    assert!(bytes.starts_with(BUNDLE_MAGIC));
+    assert_eq!(OrcaPluginBundle::parse(&bytes).unwrap(), bundle);
+}
+
+#[test]
+fn tampered_golden_bundle_fails_closed() {
+    let bundle = OrcaPluginBundle::open(golden("bundle", "tampered_wasm.orcab")).unwrap();
+    assert!(matches!(
+        load(&bundle),
+        Err(BundleError::Unverified(VerificationError::DigestMismatch))
+    ));
+}
+
+#[test]
+fn tampering_any_component_fails_closed() {
+    let flip = |bytes: &mut Vec<u8>| *bytes.last_mut().unwrap() ^= 0x01;
+
+    let mut wasm = valid();
+    flip(&mut wasm.wasm);
+    assert!(matches!(load(&wasm), Err(BundleError::Unverified(VerificationError::DigestMismatch))));
+
+    let mut sbom = valid();
+    flip(&mut sbom.sbom);
+    assert!(matches!(load(&sbom), Err(BundleError::SbomMismatch)));
+
+    let mut sig = valid();
+    flip(&mut sig.sigstore_bundle);
+    assert!(matches!(
+        load(&sig),
+        Err(BundleError::Unverified(VerificationError::InvalidSignature))
+    ));
+
+    // Re-pinning the manifest to other bytes cannot help: the signature covers the WASM.
+    let mut repinned = valid();
+    repinned.wasm.extend_from_slice(&[0x00, 0x0a, 0x01, 0x00]);
+    repinned.wasm_digest = {
+        use sha2::Digest as _;
+        hex::encode(sha2::Sha256::digest(&repinned.wasm))
+    };
+    assert!(matches!(
+        load(&repinned),
+        Err(BundleError::Unverified(VerificationError::InvalidSignature))
+    ));
+
+    let mut digest = valid();
+    digest.sbom_sha256 = "0".repeat(64);
+    assert!(matches!(load(&digest), Err(BundleError::SbomMismatch)));
+}
+
+#[test]
+fn malformed_containers_are_rejected() {
+    let bytes = valid().to_bytes();
+    for bad in [&b"NOTABNDL"[..], &bytes[..bytes.len() - 1], &[bytes.as_slice(), b"x"].concat()] {
+        assert!(matches!(OrcaPluginBundle::parse(bad), Err(BundleError::Malformed(_))));
+    }
+}
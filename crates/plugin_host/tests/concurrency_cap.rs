@@ -0,0 +1,39 @@
+//! Runner-wide admission control: invocations beyond the cap are rejected, not queued.
+
+use plugin_host::{PluginRunner, RunnerError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const WAT: &str = r#"(module
+  (func (export "spin") (param i32 i32) (result i32) (loop $l (br $l)) (i32.const 0))
+  (func (export "add") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))"#;
+
+#[test]
+fn call_beyond_the_cap_is_rejected_until_a_slot_frees() {
+    let runner = PluginRunner::with_limits_and_budgets(16 * 1024 * 1024, u64::MAX / 2, 300)
+        .with_max_concurrent_invocations(1);
+    let module = runner.load_module(&wat::parse_str(WAT).unwrap()).unwrap();
+
+    let (r, m) = (runner.clone(), module.clone());
+    let spinner = thread::spawn(move || r.invoke_i32_2(&m, "spin", 0, 0));
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while runner.in_flight_invocations() == 0 {
+        assert!(Instant::now() < deadline, "spinner never started");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let err = runner.invoke_i32_2(&module, "add", 1, 2).unwrap_err();
+    assert!(matches!(err, RunnerError::AtCapacity(1)), "{err}");
+
+    assert!(spinner.join().unwrap().is_err(), "spinner ends on its timeout");
+    assert_eq!(runner.in_flight_invocations(), 0);
+    assert_eq!(runner.invoke_i32_2(&module, "add", 1, 2).unwrap(), 3);
+}
+
+#[test]
+fn uncapped_runner_reports_no_in_flight_tracking() {
+    let runner = PluginRunner::new();
+    let module = runner.load_module(&wat::parse_str(WAT).unwrap()).unwrap();
+    assert_eq!(runner.invoke_i32_2(&module, "add", 2, 2).unwrap(), 4);
+    assert_eq!(runner.in_flight_invocations(), 0);
+}
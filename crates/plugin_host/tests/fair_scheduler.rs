@@ -0,0 +1,101 @@
+//! Fair plugin admission: bursts from one run must not starve another.
+
+use plugin_host::FairScheduler;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn wait_until(mut cond: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !cond() {
+        assert!(Instant::now() < deadline, "timed out waiting for scheduler state");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Queue `bursts` (run, count) behind a held slot, release it, and return execution order.
+fn run_bursts(sched: &Arc<FairScheduler>, bursts: &[(&'static str, usize)]) -> Vec<&'static str> {
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let gate = sched.acquire("gate");
+    let mut handles = Vec::new();
+    let mut queued = 0;
+    for &(run, count) in bursts {
+        for _ in 0..count {
+            let (s, o) = (Arc::clone(sched), Arc::clone(&order));
+            handles.push(thread::spawn(move || {
+                let _permit = s.acquire(run);
+                o.lock().unwrap().push(run);
+                thread::sleep(Duration::from_millis(2));
+            }));
+            // Enqueue one at a time so arrival order is deterministic.
+            queued += 1;
+            wait_until(|| sched.waiting() == queued);
+        }
+    }
+    drop(gate);
+    for h in handles {
+        h.join().unwrap();
+    }
+    let order = order.lock().unwrap().clone();
+    order
+}
+
+#[test]
+fn bursts_from_two_runs_are_interleaved() {
+    let sched = Arc::new(FairScheduler::new(1));
+    let order = run_bursts(&sched, &[("a", 4), ("b", 4)]);
+    assert_eq!(order, ["a", "b", "a", "b", "a", "b", "a", "b"]);
+    assert_eq!(sched.in_flight(), 0);
+    assert_eq!(sched.waiting(), 0);
+}
+
+#[test]
+fn late_run_is_served_before_greedy_run_finishes() {
+    let sched = Arc::new(FairScheduler::new(1));
+    let order = run_bursts(&sched, &[("greedy", 6), ("late", 1)]);
+    let late_pos = order.iter().position(|r| *r == "late").unwrap();
+    assert_eq!(late_pos, 1, "late run must get the second slot, got {order:?}");
+}
+
+#[test]
+fn concurrency_bound_is_respected() {
+    let sched = Arc::new(FairScheduler::new(2));
+    let peak = Arc::new(Mutex::new((0usize, 0usize))); // (current, max)
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let (s, p) = (Arc::clone(&sched), Arc::clone(&peak));
+            thread::spawn(move || {
+                let _permit = s.acquire(if i % 2 == 0 { "a" } else { "b" });
+                {
+                    let mut g = p.lock().unwrap();
+                    g.0 += 1;
+                    g.1 = g.1.max(g.0);
+                }
+                thread::sleep(Duration::from_millis(5));
+                p.lock().unwrap().0 -= 1;
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    assert_eq!(peak.lock().unwrap().1, 2);
+}
+
+#[test]
+fn per_run_cap_leaves_room_for_other_runs() {
+    let sched = Arc::new(FairScheduler::new(4).with_max_per_run(1));
+    let first = sched.acquire("a");
+    let s2 = Arc::clone(&sched);
+    let blocked = thread::spawn(move || {
+        let _p = s2.acquire("a");
+    });
+    wait_until(|| sched.waiting() == 1);
+    // Run "a" is at its cap, but "b" is admitted immediately.
+    let other = sched.acquire("b");
+    assert_eq!(sched.in_flight(), 2);
+    drop(other);
+    drop(first);
+    blocked.join().unwrap();
+    assert_eq!(sched.in_flight(), 0);
+}
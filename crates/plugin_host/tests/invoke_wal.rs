@@ -0,0 +1,41 @@
+//! `plugin_invoke` WAL events emitted by the runner when given a WAL sink.
+
+use event_log::{EventRecord, JsonlEventLog};
+use plugin_host::PluginRunner;
+use serde_json::Value;
+
+#[test]
+fn plugin_invoke_event_carries_fuel_function_and_digest() {
+    let wat = r#"(module
+      (memory (export "memory") 2)
+      (func (export "add") (param i32 i32) (result i32)
+        local.get 0
+        local.get 1
+        i32.add))"#;
+    let wasm = wat::parse_str(wat).expect("WAT to wasm should succeed");
+    let dir = tempfile::tempdir().expect("tempdir");
+    let wal = JsonlEventLog::open(dir.path().join("plugins.jsonl")).expect("open wal");
+
+    let runner = PluginRunner::new().with_wal(wal.clone());
+    let module = runner.load_module(&wasm).expect("load module");
+    let (sum, stats) = runner.invoke_i32_2_with_stats(&module, "add", 2, 3);
+    assert_eq!(sum.expect("invoke add"), 5);
+    assert!(stats.fuel_consumed > 0);
+    let _ = runner.invoke_i32_2(&module, "missing", 0, 0).unwrap_err();
+
+    let recs: Vec<EventRecord<Value>> = wal.read_range(0, u64::MAX).expect("read wal");
+    assert_eq!(recs.len(), 2);
+    let ok = &recs[0].payload;
+    assert_eq!(ok["event"], "plugin_invoke");
+    assert_eq!(ok["function"], "add");
+    assert_eq!(ok["status"], "ok");
+    assert!(ok["fuel_consumed"].as_u64().unwrap_or(0) > 0);
+    assert_eq!(ok["peak_memory_bytes"], 2 * 64 * 1024);
+    assert_eq!(ok["module_digest"].as_str(), Some(module.digest()));
+    assert_eq!(module.digest().len(), 64);
+
+    let failed = &recs[1].payload;
+    assert_eq!(failed["function"], "missing");
+    assert_eq!(failed["status"], "error");
+    assert!(failed["error"].as_str().unwrap_or("").contains("invoke failed"));
+}
@@ -0,0 +1,33 @@
+//! Content-addressed plugin loading: WASM stored in the blob store is loaded by digest.
+
+use blob_store::{BlobStore, Config, DevKeyProvider, Digest};
+use plugin_host::{PluginRunner, RunnerError};
+
+const ADD_WAT: &str = r#"(module
+  (func (export "add") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add))"#;
+
+#[test]
+fn load_from_blob_compiles_wasm_stored_by_digest() {
+    let dir = tempfile::tempdir().unwrap();
+    let store =
+        BlobStore::new(Config::with_root(dir.path().into()), DevKeyProvider::new([7; 32])).unwrap();
+    let wasm = wat::parse_str(ADD_WAT).unwrap();
+    let digest = store.put(&wasm).unwrap();
+
+    let runner = PluginRunner::new();
+    let module = runner.load_from_blob(&store, &digest).expect("load by digest");
+    assert_eq!(module.digest(), digest.to_hex());
+    assert_eq!(runner.invoke_i32_2(&module, "add", 2, 3).unwrap(), 5);
+}
+
+#[test]
+fn load_from_blob_rejects_unknown_digest() {
+    let dir = tempfile::tempdir().unwrap();
+    let store =
+        BlobStore::new(Config::with_root(dir.path().into()), DevKeyProvider::new([7; 32])).unwrap();
+    let err = PluginRunner::new().load_from_blob(&store, &Digest([0xab; 32])).unwrap_err();
+    assert!(matches!(err, RunnerError::LoadFailed(ref m) if m.contains(&"ab".repeat(32))), "{err}");
+}
@@ -0,0 +1,57 @@
+//! Blue/green digest pinning: a manifest may accept several WASM builds.
+
+use plugin_host::{ManifestVerifier, PluginManifest, VerificationError};
+use sha2::{Digest, Sha256};
+
+fn digest_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn manifest(primary: String) -> PluginManifest {
+    PluginManifest {
+        name: "demo".into(),
+        version: "2.0.0".into(),
+        wasm_digest: primary,
+        ..PluginManifest::default()
+    }
+}
+
+const fn unsigned_ok() -> ManifestVerifier {
+    ManifestVerifier { require_signed_plugins: false }
+}
+
+#[test]
+fn any_accepted_digest_verifies() {
+    let (new_build, old_build, canary) =
+        (b"new build".as_slice(), b"old build".as_slice(), b"canary");
+    let m = manifest(digest_hex(new_build));
+    let accepted =
+        [digest_hex(old_build).to_ascii_uppercase(), format!("  {}\n", digest_hex(canary))];
+    let v = unsigned_ok();
+    assert_eq!(v.verify_accepting(&m, &accepted, new_build), Ok(()));
+    assert_eq!(v.verify_accepting(&m, &accepted, old_build), Ok(()));
+    assert_eq!(v.verify_accepting(&m, &accepted, canary), Ok(()));
+    assert_eq!(
+        v.verify_accepting(&m, &accepted, b"rogue build"),
+        Err(VerificationError::DigestMismatch)
+    );
+    // Plain verify only pins the manifest's own digest.
+    assert_eq!(v.verify(&m, old_build), Err(VerificationError::DigestMismatch));
+}
+
+#[test]
+fn single_digest_manifest_is_unchanged() {
+    let m = manifest(digest_hex(b"only"));
+    let v = unsigned_ok();
+    assert_eq!(v.verify_accepting(&m, &[], b"only"), Ok(()));
+    assert_eq!(v.verify_accepting(&m, &[], b"other"), Err(VerificationError::DigestMismatch));
+}
+
+#[test]
+fn malformed_accepted_digest_fails_closed() {
+    // Even when the primary matches, a bad entry means the manifest itself is invalid.
+    let m = manifest(digest_hex(b"new build"));
+    let report = unsigned_ok().verify_detailed_accepting(&m, &["deadbeef".into()], b"new build");
+    assert_eq!(report.outcome, Err(VerificationError::InvalidDigestFormat));
+    assert_eq!(report.error_code(), Some("invalid_digest_format"));
+}
@@ -84,3 +84,22 @@ fn missing_sbom_fails_policy_check() {
         "expected MissingSbom, got: {res:?}"
     );
 }
+
+#[test]
+fn verify_detailed_reports_name_actual_digest_and_error_code() {
+    let wasm = wasm_minimal();
+    let manifest = PluginManifest {
+        name: "demo".into(),
+        version: "1.0.0".into(),
+        wasm_digest: "0000000000000000000000000000000000000000000000000000000000000000".into(),
+        signature: Some("c2ln".into()),
+        sbom_ref: Some("sbom.json".into()),
+    };
+    let report = ManifestVerifier::new().verify_detailed(&manifest, &wasm);
+    assert_eq!(report.name, "demo");
+    assert_eq!(report.digest, hex::encode(Sha256::digest(&wasm)));
+    assert_eq!(report.signer, None);
+    assert!(!report.is_verified());
+    assert_eq!(report.outcome, Err(VerificationError::DigestMismatch));
+    assert_eq!(report.error_code(), Some("digest_mismatch"));
+}
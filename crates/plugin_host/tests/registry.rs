@@ -0,0 +1,124 @@
+//! Digest-pinned plugin registry: register, look up, and reject duplicates/unverified.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use plugin_host::{
+    ManifestVerifier, PluginManifest, PluginRegistry, PluginRunner, RegistryError, SigstoreOptions,
+    VerificationError,
+};
+use sha2::{Digest, Sha256};
+
+const ADD_WAT: &str = r#"(module
+  (func (export "add") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add))"#;
+
+fn manifest(version: &str, wasm: &[u8]) -> PluginManifest {
+    PluginManifest {
+        name: "adder".into(),
+        version: version.into(),
+        wasm_digest: hex::encode(Sha256::digest(wasm)),
+        ..PluginManifest::default()
+    }
+}
+
+fn registry() -> PluginRegistry {
+    PluginRegistry::new(
+        PluginRunner::new(),
+        ManifestVerifier { require_signed_plugins: false },
+        SigstoreOptions::default(),
+    )
+}
+
+fn sigstore_fixture(name: &str) -> Vec<u8> {
+    let p =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/sigstore").join(name);
+    std::fs::read(&p).expect("fixture exists and readable")
+}
+
+#[test]
+fn registered_plugin_is_found_by_name_and_version() {
+    let wasm = wat::parse_str(ADD_WAT).unwrap();
+    let reg = registry();
+    reg.register(manifest("1.0.0", &wasm), &wasm).unwrap();
+
+    let found = reg.get("adder", "1.0.0").expect("registered");
+    assert_eq!(found.module.digest(), found.manifest.wasm_digest);
+    assert_eq!(reg.runner().invoke_i32_2(&found.module, "add", 4, 5).unwrap(), 9);
+    assert!(reg.get("adder", "2.0.0").is_none());
+    assert_eq!(reg.list(), [("adder".to_string(), "1.0.0".to_string())]);
+}
+
+#[test]
+fn duplicate_name_and_version_is_rejected() {
+    let wasm = wat::parse_str(ADD_WAT).unwrap();
+    let reg = registry();
+    reg.register(manifest("1.0.0", &wasm), &wasm).unwrap();
+    let err = reg.register(manifest("1.0.0", &wasm), &wasm).unwrap_err();
+    assert!(
+        matches!(err, RegistryError::AlreadyRegistered { ref name, ref version }
+            if name == "adder" && version == "1.0.0"),
+        "{err}"
+    );
+    reg.register(manifest("1.0.1", &wasm), &wasm).unwrap();
+    assert_eq!(reg.list().len(), 2);
+}
+
+#[test]
+fn unverified_plugin_is_rejected_and_not_registered() {
+    let wasm = wat::parse_str(ADD_WAT).unwrap();
+    let reg = registry();
+    let err = reg.register(manifest("1.0.0", b"other build"), &wasm).unwrap_err();
+    assert!(
+        matches!(err, RegistryError::Unverified { source: VerificationError::DigestMismatch, .. }),
+        "{err}"
+    );
+    assert!(reg.get("adder", "1.0.0").is_none());
+
+    let signed_only = PluginRegistry::new(
+        PluginRunner::new(),
+        ManifestVerifier::new(),
+        SigstoreOptions::default(),
+    );
+    let err = signed_only.register(manifest("1.0.0", &wasm), &wasm).unwrap_err();
+    assert!(matches!(err, RegistryError::Unverified { .. }), "{err}");
+    assert!(signed_only.list().is_empty());
+}
+
+#[test]
+fn signed_plugin_registers_against_pinned_roots() {
+    let wasm = sigstore_fixture("signed.wasm");
+    let signed = PluginManifest {
+        name: "signed".into(),
+        version: "1.0.0".into(),
+        wasm_digest: hex::encode(Sha256::digest(&wasm)),
+        signature: Some(STANDARD.encode(sigstore_fixture("valid_bundle.json"))),
+        sbom_ref: Some("sbom://signed".into()),
+    };
+
+    // The leaf chains to the second root only; pinning just the first leaves it untrusted.
+    let first_only = PluginRegistry::new(
+        PluginRunner::new(),
+        ManifestVerifier::default(),
+        SigstoreOptions::with_fulcio_root(sigstore_fixture("fulcio_root_1.pem")),
+    );
+    let err = first_only.register(signed.clone(), &wasm).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            RegistryError::Unverified { source: VerificationError::InvalidSignature, .. }
+        ),
+        "{err}"
+    );
+    assert!(first_only.list().is_empty());
+
+    let reg = PluginRegistry::new(
+        PluginRunner::new(),
+        ManifestVerifier::default(),
+        SigstoreOptions::with_fulcio_root(sigstore_fixture("fulcio_root_1.pem"))
+            .add_fulcio_root(sigstore_fixture("fulcio_root_2.pem")),
+    );
+    reg.register(signed, &wasm).unwrap();
+    assert_eq!(reg.list(), [("signed".to_string(), "1.0.0".to_string())]);
+}
@@ -0,0 +1,40 @@
+//! Per-runner WASI import allowlist: denied preview1 imports fail instantiation.
+
+use plugin_host::PluginRunner;
+
+const WAT: &str = r#"(module
+  (import "wasi_snapshot_preview1" "clock_time_get"
+    (func $clock (param i32 i64 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (func (export "add") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add))"#;
+
+fn wasm() -> Vec<u8> {
+    wat::parse_str(WAT).expect("WAT to wasm should succeed")
+}
+
+#[test]
+fn default_links_full_wasi_surface() {
+    let runner = PluginRunner::new();
+    let module = runner.load_module(&wasm()).expect("load module");
+    assert_eq!(runner.invoke_i32_2(&module, "add", 2, 3).expect("invoke add"), 5);
+}
+
+#[test]
+fn denied_import_fails_instantiation() {
+    let runner = PluginRunner::new().with_wasi_allowlist(["clock_time_get"]);
+    let module = runner.load_module(&wasm()).expect("load module");
+    let err = runner.invoke_i32_2(&module, "add", 2, 3).unwrap_err();
+    assert!(err.to_string().contains("fd_write"), "{err}");
+}
+
+#[test]
+fn allowlisted_imports_instantiate() {
+    let runner = PluginRunner::new().with_wasi_allowlist(["clock_time_get", "fd_write"]);
+    let module = runner.load_module(&wasm()).expect("load module");
+    assert_eq!(runner.invoke_i32_2(&module, "add", 4, 5).expect("invoke add"), 9);
+}
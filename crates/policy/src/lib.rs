@@ -12,19 +12,28 @@
 //! - Flag — represented as `Allow` with `action == "allow_but_flag"` for audit
 //!
 //! Precedence and determinism:
-//! 1) Built-in PII redaction (returns Modify immediately if applied)
+//! 1) Built-in PII redaction (without a loaded policy, returns Modify immediately if applied)
 //! 2) Fail-closed check: if no valid policy is loaded ⇒ Deny
 //! 3) Tool allowlist enforcement
-//! 4) Rule interpreter:
+//! 4) Rule interpreter, over the PII-redacted envelope:
+//!    - A `budget_state <op> <State>` clause in `when` gates the rule on the run's budget
+//!      state from the caller's [`EvalContext`] (never holds without one)
 //!    - Highest priority wins (larger priority is higher)
 //!    - Tie-breaker: most-restrictive-wins (Deny > Modify > Allow)
 //!    - Still tied: first-match-wins (stable file order)
+//!    - A winning Deny or Modify is returned as is; otherwise a PII redaction from step 1
+//!      is returned, so redaction is never lost
 //!
 //! All evaluations are designed to be deterministic for a given policy and input.
+//! [`Engine::explain`] reports every matched rule and the winner for debugging, and
+//! [`Engine::simulate`] decides a batch of envelopes, both without emitting metrics or
+//! observer callbacks.
 //!
 //! Observability and audit:
 //! - Every decision emits a low-cardinality counter `policy.decision.count{phase,kind,action}`.
 //! - The special action `allow_but_flag` also increments an alias with `action="flag"` for ease of querying.
+//! - Evaluation latency (monotonic clock) is summarized per phase in `PolicyMetrics` and
+//!   reported to the observer via `PolicyObserver::on_decision_latency`.
 //! - An optional `PolicyObserver` can be installed to observe decisions in-process.
 //! - A process-global `AuditSink` captures `AuditRecord`s for later inspection in tests.
 
@@ -34,10 +43,14 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Read};
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+pub use budget::BudgetState;
 
 /// Kind of policy decision returned by the policy engine.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -65,6 +78,55 @@ pub struct Decision {
     pub action: Option<String>,
 }
 
+/// A rule that matched during [`Engine::explain`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleMatch {
+    /// Rule name from the policy file.
+    pub name: String,
+    /// Action declared by the rule.
+    pub action: String,
+    /// Rule priority (larger is higher).
+    pub priority: i32,
+    /// True for the single rule selected by precedence.
+    pub fired: bool,
+}
+
+/// Why the engine reached its decision, as reported by [`Engine::explain`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    /// Every policy rule that matched, in file order. Empty when evaluation stopped before
+    /// the rule interpreter (no policy loaded, tool allowlist).
+    pub matched_rules: Vec<RuleMatch>,
+    /// Name of the rule (or builtin stage, e.g. `tool_allowlist`) that produced the decision.
+    pub selected: Option<String>,
+    /// Final decision kind, identical to what `pre_submit_task` returns.
+    pub final_kind: DecisionKind,
+    /// The full decision.
+    pub decision: Decision,
+}
+
+/// Evaluation hook a decision is made for, as passed to [`Engine::simulate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Phase {
+    /// [`Engine::pre_start_run`].
+    PreStartRun,
+    /// [`Engine::pre_submit_task`].
+    PreSubmitTask,
+    /// [`Engine::post_submit_task`].
+    PostSubmitTask,
+}
+
+impl Phase {
+    /// Phase label as used in metrics and audit records (e.g. `pre_submit_task`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Phase::PreStartRun => "pre_start_run",
+            Phase::PreSubmitTask => "pre_submit_task",
+            Phase::PostSubmitTask => "post_submit_task",
+        }
+    }
+}
+
 /// Observer invoked for each policy decision emitted by the engine.
 ///
 /// Install an implementation via [`set_observer()`] to receive callbacks across all
@@ -87,6 +149,10 @@ pub struct Decision {
 pub trait PolicyObserver: Send + Sync {
     /// Called on every decision with the evaluation phase.
     fn on_decision(&self, phase: &str, decision: &Decision);
+
+    /// Called after [`PolicyObserver::on_decision`] with the time the evaluation took.
+    /// Default: ignored.
+    fn on_decision_latency(&self, _phase: &str, _elapsed: Duration) {}
 }
 
 static OBSERVER: OnceLock<RwLock<Option<Arc<dyn PolicyObserver>>>> = OnceLock::new();
@@ -117,6 +183,18 @@ pub fn set_observer(observer: Option<Box<dyn PolicyObserver>>) {
 #[derive(Default)]
 pub struct PolicyMetrics {
     inner: Arc<Mutex<HashMap<String, u64>>>,
+    latency: Arc<Mutex<HashMap<String, DecisionLatency>>>,
+}
+
+/// Summary of decision evaluation latency for one phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecisionLatency {
+    /// Number of timed evaluations.
+    pub count: u64,
+    /// Sum of evaluation times, in nanoseconds.
+    pub total_nanos: u64,
+    /// Slowest single evaluation, in nanoseconds.
+    pub max_nanos: u64,
 }
 
 impl PolicyMetrics {
@@ -125,10 +203,22 @@ impl PolicyMetrics {
         let key = format!("{}:{}:{}", phase, kind, action);
         self.inner.lock().expect("metrics lock poisoned").get(&key).copied().unwrap_or(0)
     }
+    /// Read the evaluation latency summary for a phase (zeroed if none recorded).
+    pub fn decision_latency(&self, phase: &str) -> DecisionLatency {
+        self.latency.lock().expect("metrics lock poisoned").get(phase).copied().unwrap_or_default()
+    }
     fn inc(&self, phase: &str, kind: &str, action: &str) {
         let mut g = self.inner.lock().expect("metrics lock poisoned");
         *g.entry(format!("{}:{}:{}", phase, kind, action)).or_insert(0) += 1;
     }
+    fn observe_latency(&self, phase: &str, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        let mut g = self.latency.lock().expect("metrics lock poisoned");
+        let l = g.entry(phase.to_string()).or_default();
+        l.count += 1;
+        l.total_nanos = l.total_nanos.saturating_add(nanos);
+        l.max_nanos = l.max_nanos.max(nanos);
+    }
 }
 
 static METRICS: OnceLock<PolicyMetrics> = OnceLock::new();
@@ -193,7 +283,7 @@ pub fn install_audit_sink() -> AuditSink {
     sink
 }
 
-fn notify_observers_and_record(phase: &str, d: &Decision) {
+fn notify_observers_and_record(phase: &str, d: &Decision, elapsed: Duration) {
     // Metrics
     let metrics = METRICS.get_or_init(PolicyMetrics::default);
     let kind_str = match d.kind {
@@ -207,11 +297,13 @@ fn notify_observers_and_record(phase: &str, d: &Decision) {
         // Also emit alias for acceptance criteria that expects 'flag'
         metrics.inc(phase, kind_str, "flag");
     }
+    metrics.observe_latency(phase, elapsed);
     // Observer
     if let Some(lock) = OBSERVER.get() {
         if let Ok(r) = lock.read() {
             if let Some(obs) = r.as_ref() {
                 obs.on_decision(phase, d);
+                obs.on_decision_latency(phase, elapsed);
             }
         }
     }
@@ -228,15 +320,95 @@ fn notify_observers_and_record(phase: &str, d: &Decision) {
     }
 }
 
-/// Deterministic policy engine implementing fail-closed governance semantics.
+/// Pluggable PII detector applied to `payload_json` before any rule evaluation.
+///
+/// The default is [`RegexPiiDetector::ssn`]; install richer detectors (NER-based,
+/// locale-specific) via [`Engine::with_pii_detector`]. Implementations must be
+/// deterministic for a given input to preserve replay guarantees.
+///
+/// Example
+/// ```
+/// struct Ticket;
+/// impl policy::PiiDetector for Ticket {
+///     fn detect_and_redact(&self, text: &str) -> Option<String> {
+///         text.contains("TKT-").then(|| text.replace("TKT-", "[REDACTED]-"))
+///     }
+/// }
+/// let eng = policy::Engine::new().with_pii_detector(Box::new(Ticket));
+/// # let _ = eng;
+/// ```
+pub trait PiiDetector: Send + Sync {
+    /// Return the redacted text if any PII was found, or `None` to leave `text` unchanged.
+    fn detect_and_redact(&self, text: &str) -> Option<String>;
+}
+
+/// Regex-based [`PiiDetector`] replacing every match with `[REDACTED]`.
 #[derive(Debug, Clone)]
+pub struct RegexPiiDetector {
+    pattern: Regex,
+}
+
+impl RegexPiiDetector {
+    /// Detector for an arbitrary pattern.
+    #[must_use]
+    pub fn new(pattern: Regex) -> Self {
+        Self { pattern }
+    }
+
+    /// Built-in default: US SSNs in `ddd-dd-dddd` form.
+    #[must_use]
+    pub fn ssn() -> Self {
+        Self::new(Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap())
+    }
+}
+
+impl PiiDetector for RegexPiiDetector {
+    fn detect_and_redact(&self, text: &str) -> Option<String> {
+        match self.pattern.replace_all(text, "[REDACTED]") {
+            std::borrow::Cow::Borrowed(_) => None,
+            std::borrow::Cow::Owned(s) => Some(s),
+        }
+    }
+}
+
+/// Default maximum number of rules accepted by [`Engine::load_from_yaml_path`].
+pub const DEFAULT_MAX_RULES: usize = 10_000;
+/// Default maximum number of `tool_allowlist` entries accepted at load time.
+pub const DEFAULT_MAX_TOOL_ALLOWLIST: usize = 10_000;
+
+/// Size caps enforced when loading a policy file.
+///
+/// Evaluation cost is linear in the number of rules, so oversized policies are rejected
+/// at load time (fail-closed: the previously loaded policy, if any, stays active).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadLimits {
+    /// Maximum number of entries in `rules`.
+    pub max_rules: usize,
+    /// Maximum number of entries in `tool_allowlist`.
+    pub max_tool_allowlist: usize,
+}
+
+impl Default for LoadLimits {
+    fn default() -> Self {
+        Self { max_rules: DEFAULT_MAX_RULES, max_tool_allowlist: DEFAULT_MAX_TOOL_ALLOWLIST }
+    }
+}
+
+/// Deterministic policy engine implementing fail-closed governance semantics.
+#[derive(Clone)]
 pub struct Engine {
-    pii: Regex,
+    pii: Arc<dyn PiiDetector>,
     rules: Vec<Rule>,
+    budget_conds: Vec<Option<BudgetCond>>, // parsed `budget_state` clause per rule
+    cost_conds: Vec<Option<CostCond>>,     // parsed `cost_per_token` clause per rule
     tool_allowlist: Option<HashSet<String>>, // deny-by-default when present and tool not allowed
+    tool_name_keys: Vec<String>,           // payload paths naming the tool, checked in order
     /// True once a valid policy file has been loaded successfully. While `false`,
     /// evaluations are fail-closed (`DecisionKind::Deny`) after builtin PII redaction.
     policy_loaded: bool,
+    /// Hex SHA-256 of the loaded policy file bytes.
+    policy_version: Option<String>,
+    limits: LoadLimits,
 }
 
 /// In-memory representation of a policy file loaded from YAML.
@@ -248,14 +420,161 @@ pub struct PolicyFile {
     /// tools not listed will be denied by default.
     #[serde(default)]
     pub tool_allowlist: Option<Vec<String>>,
+    /// Payload keys (dot-separated paths, e.g. `action.name`) that name the tool an
+    /// envelope invokes, checked in order. Defaults to `["tool", "tool_name"]`.
+    #[serde(default)]
+    pub tool_name_keys: Option<Vec<String>>,
+}
+
+/// Facts about the evaluation beyond the envelope, supplied by the caller (the orchestrator
+/// passes the run's current budget state).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalContext {
+    /// Current budget state of the run; `budget_state` conditions never hold without it.
+    pub budget_state: Option<BudgetState>,
+}
+
+/// Parsed `budget_state <op> <State>` clause of a rule's `when`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BudgetCond {
+    op: CmpOp,
+    state: BudgetState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Ge,
+    Gt,
+    Le,
+    Lt,
+    Eq,
+    Ne,
+}
+
+impl BudgetCond {
+    /// Parse the clause out of `when`; `Ok(None)` when `when` does not mention `budget_state`.
+    fn parse(when: &str) -> Result<Option<Self>, String> {
+        static CLAUSE: OnceLock<Regex> = OnceLock::new();
+        if !when.contains("budget_state") {
+            return Ok(None);
+        }
+        let re = CLAUSE.get_or_init(|| {
+            Regex::new(r"budget_state\s*(>=|<=|==|!=|>|<)\s*([A-Za-z0-9_]+)").unwrap()
+        });
+        let caps = re.captures(when).ok_or_else(|| {
+            "budget_state condition must be 'budget_state <op> <state>' with op one of \
+             >=|>|<=|<|==|!="
+                .to_string()
+        })?;
+        let op = CmpOp::parse(&caps[1]);
+        let state = match &caps[2] {
+            "Within" => BudgetState::Within,
+            "Warning80" => BudgetState::Warning80,
+            "Warning90" => BudgetState::Warning90,
+            "Exceeded" => BudgetState::Exceeded,
+            other => {
+                return Err(format!(
+                    "budget state '{}' is invalid; valid: Within|Warning80|Warning90|Exceeded",
+                    other
+                ))
+            }
+        };
+        Ok(Some(Self { op, state }))
+    }
+
+    fn holds(self, current: BudgetState) -> bool {
+        self.op.holds(current.cmp(&self.state))
+    }
+}
+
+impl CmpOp {
+    /// Map an operator matched by a clause regex; anything unrecognized is `!=`.
+    fn parse(op: &str) -> Self {
+        match op {
+            ">=" => Self::Ge,
+            ">" => Self::Gt,
+            "<=" => Self::Le,
+            "<" => Self::Lt,
+            "==" => Self::Eq,
+            _ => Self::Ne,
+        }
+    }
+
+    /// Whether `lhs <op> rhs` holds, given `lhs.cmp(rhs)`.
+    fn holds(self, ord: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+        match self {
+            Self::Ge => ord != Less,
+            Self::Gt => ord == Greater,
+            Self::Le => ord != Greater,
+            Self::Lt => ord == Less,
+            Self::Eq => ord == Equal,
+            Self::Ne => ord != Equal,
+        }
+    }
+}
+
+/// Parsed `cost_per_token <op> <micros>` clause of a rule's `when`: compares the envelope's
+/// `usage.cost_micros / usage.tokens` against a threshold in micros per token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CostCond {
+    op: CmpOp,
+    micros_per_token: f64,
 }
 
+impl CostCond {
+    /// Parse the clause out of `when`; `Ok(None)` when `when` does not mention
+    /// `cost_per_token`.
+    fn parse(when: &str) -> Result<Option<Self>, String> {
+        static CLAUSE: OnceLock<Regex> = OnceLock::new();
+        if !when.contains("cost_per_token") {
+            return Ok(None);
+        }
+        let re = CLAUSE.get_or_init(|| {
+            Regex::new(r"cost_per_token\s*(>=|<=|==|!=|>|<)\s*([0-9]+(?:\.[0-9]+)?)\b").unwrap()
+        });
+        let caps = re.captures(when).ok_or_else(|| {
+            "cost_per_token condition must be 'cost_per_token <op> <micros>' with op one of \
+             >=|>|<=|<|==|!= and a non-negative number"
+                .to_string()
+        })?;
+        let micros_per_token = caps[2]
+            .parse::<f64>()
+            .map_err(|e| format!("cost_per_token threshold '{}' is invalid: {}", &caps[2], e))?;
+        Ok(Some(Self { op: CmpOp::parse(&caps[1]), micros_per_token }))
+    }
+
+    /// Whether the clause holds for `envelope`; never without usage or with zero tokens.
+    fn holds(self, envelope: &Value) -> bool {
+        cost_per_token(envelope)
+            .and_then(|c| c.partial_cmp(&self.micros_per_token))
+            .is_some_and(|ord| self.op.holds(ord))
+    }
+}
+
+/// `usage.cost_micros / usage.tokens` of an envelope, or `None` when it carries no usage or
+/// zero tokens.
+fn cost_per_token(envelope: &Value) -> Option<f64> {
+    let usage = envelope.get("usage")?;
+    let tokens = usage.get("tokens").and_then(Value::as_u64).filter(|t| *t > 0)?;
+    let cost = usage.get("cost_micros").and_then(Value::as_u64).unwrap_or(0);
+    Some(cost as f64 / tokens as f64)
+}
+
+/// Payload keys naming a tool when a policy file does not set `tool_name_keys`.
+pub const DEFAULT_TOOL_NAME_KEYS: &[&str] = &["tool", "tool_name"];
+
 /// A single policy rule compiled from YAML.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Rule {
     /// Human-readable name of the rule (unique within a file is recommended).
     pub name: String,
-    /// Condition string; matching is implementation-defined for the current baseline.
+    /// Condition string; matching is implementation-defined for the current baseline. A
+    /// `budget_state <op> <State>` clause (e.g. `ToolInvocation && budget_state >= Warning90`)
+    /// additionally requires the run's budget state to compare true. A
+    /// `cost_per_token <op> <micros>` clause (e.g. `cost_per_token > 200`) requires the
+    /// envelope's `usage.cost_micros / usage.tokens` to compare true, and on its own is enough
+    /// to trigger a `deny` or `allow_but_flag` rule.
     pub when: String,
     /// Action to take: one of `deny`, `modify`, or `allow_but_flag`.
     pub action: String,
@@ -271,6 +590,26 @@ pub struct Rule {
     /// Higher number = higher priority. Defaults to 0 for backward compatibility.
     #[serde(default)]
     pub priority: i32,
+    /// Modify transform: dot-separated envelope paths to remove. Paths rooted at `payload`
+    /// address the parsed `payload_json` (e.g. `payload.internal_notes`).
+    #[serde(default)]
+    pub drop_fields: Vec<String>,
+    /// Modify transform: dot-separated envelope paths whose values become `"[REDACTED]"`.
+    #[serde(default)]
+    pub mask_fields: Vec<String>,
+}
+
+impl std::fmt::Debug for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Engine")
+            .field("rules", &self.rules)
+            .field("tool_allowlist", &self.tool_allowlist)
+            .field("tool_name_keys", &self.tool_name_keys)
+            .field("policy_loaded", &self.policy_loaded)
+            .field("policy_version", &self.policy_version)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for Engine {
@@ -284,25 +623,80 @@ impl Engine {
     /// fail-closed (Deny) after builtin PII redaction until a valid policy is loaded.
     #[must_use]
     pub fn new() -> Self {
-        let pii = Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap();
-        Self { pii, rules: Vec::new(), tool_allowlist: None, policy_loaded: false }
+        Self {
+            pii: Arc::new(RegexPiiDetector::ssn()),
+            rules: Vec::new(),
+            budget_conds: Vec::new(),
+            cost_conds: Vec::new(),
+            tool_allowlist: None,
+            tool_name_keys: default_tool_name_keys(),
+            policy_loaded: false,
+            policy_version: None,
+            limits: LoadLimits::default(),
+        }
+    }
+
+    /// Replace the built-in SSN detector used for PII redaction.
+    #[must_use]
+    pub fn with_pii_detector(mut self, detector: Box<dyn PiiDetector>) -> Self {
+        self.pii = Arc::from(detector);
+        self
+    }
+
+    /// Override the size caps applied by subsequent [`Engine::load_from_yaml_path`] calls.
+    #[must_use]
+    pub fn with_load_limits(mut self, limits: LoadLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Hex SHA-256 of the currently loaded policy file; `None` until a policy loads.
+    /// Identifies exactly which policy produced a decision for auditing.
+    #[must_use]
+    pub fn policy_version(&self) -> Option<&str> {
+        self.policy_version.as_deref()
+    }
+
+    /// Size caps currently applied at load time.
+    #[must_use]
+    pub fn load_limits(&self) -> LoadLimits {
+        self.limits
     }
 
     /// Load a policy from a YAML file at `path`.
     ///
-    /// Validates schema, tool allowlist, and transforms; on success marks the engine
-    /// as policy-loaded. Returns an error string describing the first validation
-    /// failure encountered.
+    /// Validates schema, size caps ([`LoadLimits`]), tool allowlist, and transforms; on
+    /// success marks the engine as policy-loaded. Returns an error string describing the
+    /// first validation failure encountered.
     pub fn load_from_yaml_path<P: AsRef<std::path::Path>>(
         &mut self,
         path: P,
     ) -> Result<(), String> {
-        let f = File::open(&path)
+        let mut bytes = Vec::new();
+        File::open(&path)
+            .and_then(|f| BufReader::new(f).read_to_end(&mut bytes))
             .map_err(|e| format!("Failed to open policy file {:?}: {}", path.as_ref(), e))?;
-        let rdr = BufReader::new(f);
-        let pf: PolicyFile = serde_yaml::from_reader(rdr)
+        let pf: PolicyFile = serde_yaml::from_slice(&bytes)
             .map_err(|e| format!("Malformed YAML in policy file {:?}: {}", path.as_ref(), e))?;
 
+        // Enforce size caps before any per-entry work
+        if pf.rules.len() > self.limits.max_rules {
+            return Err(format!(
+                "rules count {} exceeds max {}",
+                pf.rules.len(),
+                self.limits.max_rules
+            ));
+        }
+        if let Some(v) = &pf.tool_allowlist {
+            if v.len() > self.limits.max_tool_allowlist {
+                return Err(format!(
+                    "tool_allowlist count {} exceeds max {}",
+                    v.len(),
+                    self.limits.max_tool_allowlist
+                ));
+            }
+        }
+
         // Validate tool_allowlist: non-empty strings, no duplicates (case-insensitive)
         let tool_allowlist = if let Some(v) = pf.tool_allowlist {
             let mut set = HashSet::new();
@@ -320,77 +714,174 @@ impl Engine {
             None
         };
 
-        // Validate rules
+        // Validate tool_name_keys: non-empty paths without empty segments, no duplicates
+        let tool_name_keys = match pf.tool_name_keys {
+            Some(keys) => {
+                if keys.is_empty() {
+                    return Err("tool_name_keys must not be empty".into());
+                }
+                let mut seen = HashSet::new();
+                let mut out = Vec::with_capacity(keys.len());
+                for (i, k) in keys.into_iter().enumerate() {
+                    let k = k.trim().to_string();
+                    if k.is_empty() || k.split('.').any(|seg| seg.trim().is_empty()) {
+                        return Err(format!("tool_name_keys[{}] '{}' is invalid", i, k));
+                    }
+                    if !seen.insert(k.clone()) {
+                        return Err(format!("tool_name_keys contains duplicate entry: '{}'", k));
+                    }
+                    out.push(k);
+                }
+                out
+            }
+            None => default_tool_name_keys(),
+        };
+
+        // Validate rules; every error names the rule (`rules[i] '<name>': ...`) so large
+        // files are easy to debug.
+        let mut budget_conds = Vec::with_capacity(pf.rules.len());
+        let mut cost_conds = Vec::with_capacity(pf.rules.len());
         for (i, r) in pf.rules.iter().enumerate() {
+            let rule_err = |msg: String| format!("rules[{}] '{}': {}", i, r.name, msg);
             if r.name.trim().is_empty() {
-                return Err(format!("rules[{}].name must be non-empty", i));
+                return Err(rule_err("name must be non-empty".into()));
             }
             if r.when.trim().is_empty() {
-                return Err(format!("rules[{}].when must be non-empty", i));
+                return Err(rule_err("when must be non-empty".into()));
             }
+            budget_conds.push(BudgetCond::parse(&r.when).map_err(rule_err)?);
+            cost_conds.push(CostCond::parse(&r.when).map_err(rule_err)?);
             match r.action.as_str() {
                 "deny" | "modify" | "allow_but_flag" => {}
                 other => {
-                    return Err(format!(
-                        "rules[{}].action '{}' is invalid; valid: deny|modify|allow_but_flag",
-                        i, other
-                    ))
+                    return Err(rule_err(format!(
+                        "action '{}' is invalid; valid: deny|modify|allow_but_flag",
+                        other
+                    )))
+                }
+            }
+            if !(r.drop_fields.is_empty() && r.mask_fields.is_empty()) {
+                if r.action != "modify" {
+                    return Err(rule_err("drop_fields/mask_fields require action 'modify'".into()));
+                }
+                for path in r.drop_fields.iter().chain(&r.mask_fields) {
+                    if path.split('.').any(|seg| seg.trim().is_empty()) {
+                        return Err(rule_err(format!("field path '{}' is invalid", path)));
+                    }
                 }
             }
             if let Some(t) = &r.transform {
                 let t = t.trim();
                 if let Some(rest) = t.strip_prefix("regex:") {
-                    // Validate regex patterns if declared as transform: "regex:<pattern>"
-                    Regex::new(rest)
-                        .map_err(|e| format!("rules[{}].transform regex invalid: {}", i, e))?;
+                    // Validate redaction patterns declared as transform: "regex:<pattern>"
+                    Regex::new(rest).map_err(|e| {
+                        rule_err(format!("transform regex '{}' invalid: {}", rest, e))
+                    })?;
                 }
             }
         }
 
         self.rules = pf.rules;
+        self.budget_conds = budget_conds;
+        self.cost_conds = cost_conds;
         self.tool_allowlist = tool_allowlist;
+        self.tool_name_keys = tool_name_keys;
         self.policy_loaded = true;
+        self.policy_version = Some(hex::encode(Sha256::digest(&bytes)));
         Ok(())
     }
 
     /// Evaluate a policy prior to starting a run, returning a deterministic decision.
     pub fn pre_start_run(&self, envelope: &Value) -> Decision {
-        let d = self.apply_rules_then_redact(envelope, Some("pre_start_run"));
-        notify_observers_and_record("pre_start_run", &d);
+        let started = Instant::now();
+        let d = self.evaluate(envelope, &EvalContext::default(), None);
+        notify_observers_and_record("pre_start_run", &d, started.elapsed());
         d
     }
 
     /// Evaluate a policy prior to submitting a task, returning a deterministic decision.
     pub fn pre_submit_task(&self, envelope: &Value) -> Decision {
-        let d = self.apply_rules_then_redact(envelope, Some("pre_submit_task"));
-        notify_observers_and_record("pre_submit_task", &d);
+        self.pre_submit_task_with_context(envelope, &EvalContext::default())
+    }
+
+    /// [`Self::pre_submit_task`] with caller-supplied context, so `budget_state` conditions
+    /// can match.
+    pub fn pre_submit_task_with_context(&self, envelope: &Value, ctx: &EvalContext) -> Decision {
+        let started = Instant::now();
+        let d = self.evaluate(envelope, ctx, None);
+        notify_observers_and_record("pre_submit_task", &d, started.elapsed());
         d
     }
 
+    /// Explain the `pre_submit_task` decision for `envelope`: every matched rule and the one
+    /// that won under precedence. Runs the same pipeline, but notifies no observers and
+    /// records no metrics or audit entries.
+    pub fn explain(&self, envelope: &Value) -> Explanation {
+        let mut matched_rules = Vec::new();
+        let decision = self.evaluate(envelope, &EvalContext::default(), Some(&mut matched_rules));
+        Explanation {
+            matched_rules,
+            selected: decision.rule_name.clone(),
+            final_kind: decision.kind,
+            decision,
+        }
+    }
+
     /// Evaluate a policy after submitting a task; current baseline always allows.
-    pub fn post_submit_task(&self, _result: &Value) -> Decision {
-        let d = Decision {
+    pub fn post_submit_task(&self, result: &Value) -> Decision {
+        let started = Instant::now();
+        let d = self.post_decision(result);
+        notify_observers_and_record("post_submit_task", &d, started.elapsed());
+        d
+    }
+
+    /// Decide `phase` for each envelope in `envelopes`, in order, exactly as the matching
+    /// hook would (without caller context), but with no side effects: no metrics, observer
+    /// callbacks, or audit entries. Intended for trying a policy against recorded traffic.
+    pub fn simulate(&self, envelopes: &[Value], phase: Phase) -> Vec<Decision> {
+        envelopes
+            .iter()
+            .map(|env| match phase {
+                Phase::PreStartRun | Phase::PreSubmitTask => {
+                    self.evaluate(env, &EvalContext::default(), None)
+                }
+                Phase::PostSubmitTask => self.post_decision(env),
+            })
+            .collect()
+    }
+
+    fn post_decision(&self, _result: &Value) -> Decision {
+        Decision {
             kind: DecisionKind::Allow,
             payload: None,
             reason: None,
             rule_name: None,
             action: None,
-        };
-        notify_observers_and_record("post_submit_task", &d);
-        d
+        }
     }
 
     /// Apply the evaluation pipeline in deterministic order:
-    /// 1) Built-in PII redaction (returns `Modify` immediately if applied)
+    /// 1) Built-in PII redaction (returned immediately only when no policy is loaded)
     /// 2) Fail-closed deny if no valid policy is loaded
     /// 3) Tool allowlist enforcement
-    /// 4) Rule interpreter with precedence (priority -> most-restrictive -> first-match)
-    fn apply_rules_then_redact(&self, envelope: &Value, _phase: Option<&str>) -> Decision {
-        // 1) Built-in PII redaction first (fail-closed if needed in callers)
-        //    If PII is detected, return immediately with a Modify decision.
-        let d = self.scan_and_redact(envelope, Some("builtin_redact_pii"));
-        if matches!(d.kind, DecisionKind::Modify) {
-            return d;
+    /// 4) Rule interpreter with precedence (priority -> most-restrictive -> first-match),
+    ///    with field transforms applied on top of the PII-redacted envelope; a winner that
+    ///    is not Deny or Modify yields to the step 1 redaction
+    ///
+    /// Rules whose `budget_state` clause does not hold under `ctx` are skipped in steps 3
+    /// and 4. When `trace` is set, every rule matched in step 4 is pushed to it.
+    fn evaluate(
+        &self,
+        envelope: &Value,
+        ctx: &EvalContext,
+        trace: Option<&mut Vec<RuleMatch>>,
+    ) -> Decision {
+        // 1) Built-in PII redaction first. Without a policy it is the whole decision;
+        //    otherwise rules run over the redacted envelope so their edits keep it.
+        let pii = self.scan_and_redact(envelope, Some("builtin_redact_pii"));
+        let has_pii = matches!(pii.kind, DecisionKind::Modify);
+        if has_pii && !self.policy_loaded {
+            return pii;
         }
         // Fail-closed: deny when no valid policy is loaded
         if !self.policy_loaded {
@@ -404,7 +895,7 @@ impl Engine {
         }
 
         // 2) Tool allowlist enforcement (deny by default when a tool is present and not allowed)
-        if let Some(dec) = self.check_tool_allowlist(envelope) {
+        if let Some(dec) = self.check_tool_allowlist(envelope, ctx) {
             return dec;
         }
         // 3) Rule interpreter with priority and precedence
@@ -412,9 +903,48 @@ impl Engine {
         //    - Select highest priority (larger = higher)
         //    - Tie-break by most-restrictive-wins: Deny > Modify > Allow
         //    - If still tied, first-match-wins to preserve file order determinism
+        let redacted = pii.payload.as_ref().unwrap_or(envelope);
         let mut matches: Vec<(i32, usize, Decision)> = Vec::new();
         for (idx, r) in self.rules.iter().enumerate() {
+            if !self.budget_gate_holds(idx, ctx) || !self.cost_gate_holds(idx, envelope) {
+                continue;
+            }
+            // `ToolInvocation && cost_per_token ...` and `ToolInvocation && budget_state ...`
+            // gate tool calls only.
+            if (self.has_cost_cond(idx) || self.has_budget_cond(idx))
+                && r.when.contains("ToolInvocation")
+                && self.tool_name(envelope).is_none()
+            {
+                continue;
+            }
             match (r.action.as_str(), r.when.as_str()) {
+                ("modify", cond)
+                    if !(r.drop_fields.is_empty() && r.mask_fields.is_empty())
+                        && when_matches(cond, has_pii) =>
+                {
+                    // Field-level access control: applies only when a targeted path exists
+                    // (or, for a `pii_detect` rule, when PII was redacted)
+                    let modified = apply_field_transforms(redacted, &r.drop_fields, &r.mask_fields)
+                        .or_else(|| {
+                            cond.contains("pii_detect").then(|| pii.payload.clone()).flatten()
+                        });
+                    if let Some(modified) = modified {
+                        matches.push((
+                            r.priority,
+                            idx,
+                            Decision {
+                                kind: DecisionKind::Modify,
+                                payload: Some(modified),
+                                reason: r
+                                    .message
+                                    .clone()
+                                    .or_else(|| Some("fields dropped/masked".into())),
+                                rule_name: Some(r.name.clone()),
+                                action: Some(r.action.clone()),
+                            },
+                        ));
+                    }
+                }
                 ("deny", cond) if cond.contains("ToolInvocation") => {
                     matches.push((
                         r.priority,
@@ -441,6 +971,22 @@ impl Engine {
                         },
                     ));
                 }
+                (action @ ("deny" | "allow_but_flag"), _) if self.has_cost_cond(idx) => {
+                    // A `cost_per_token` clause triggers on its own (it already held above).
+                    let kind =
+                        if action == "deny" { DecisionKind::Deny } else { DecisionKind::Allow };
+                    matches.push((
+                        r.priority,
+                        idx,
+                        Decision {
+                            kind,
+                            payload: None,
+                            reason: r.message.clone(),
+                            rule_name: Some(r.name.clone()),
+                            action: Some(r.action.clone()),
+                        },
+                    ));
+                }
                 ("modify", cond) if cond.contains("pii_detect") => {
                     // apply redaction and attribute decision to this rule
                     let mut d2 = self.scan_and_redact(envelope, Some(r.name.as_str()));
@@ -453,18 +999,32 @@ impl Engine {
                 _ => {}
             }
         }
+        let record = |trace: Option<&mut Vec<RuleMatch>>, fired_idx: Option<usize>| {
+            if let Some(trace) = trace {
+                trace.extend(matches.iter().map(|(p, idx, _)| RuleMatch {
+                    name: self.rules[*idx].name.clone(),
+                    action: self.rules[*idx].action.clone(),
+                    priority: *p,
+                    fired: Some(*idx) == fired_idx,
+                }));
+            }
+        };
         if matches.is_empty() {
-            return Decision {
-                kind: DecisionKind::Allow,
-                payload: None,
-                reason: None,
-                rule_name: None,
-                action: None,
+            return if has_pii {
+                pii
+            } else {
+                Decision {
+                    kind: DecisionKind::Allow,
+                    payload: None,
+                    reason: None,
+                    rule_name: None,
+                    action: None,
+                }
             };
         }
         let max_pri = matches.iter().map(|(p, _, _)| *p).max().unwrap_or(0);
-        let mut best: Option<(i32, usize, Decision)> = None;
-        for (p, idx, d) in matches.into_iter().filter(|(p, _, _)| *p == max_pri) {
+        let mut best: Option<(i32, usize, &Decision)> = None;
+        for (p, idx, d) in matches.iter().filter(|(p, _, _)| *p == max_pri) {
             let severity = match d.kind {
                 DecisionKind::Deny => 3,
                 DecisionKind::Modify => 2,
@@ -482,10 +1042,14 @@ impl Engine {
                 }
             };
             if better {
-                best = Some((p, idx, d));
+                best = Some((*p, *idx, d));
             }
         }
-        best.map(|(_, _, d)| d).unwrap_or(Decision {
+        record(trace, best.map(|(_, idx, _)| idx));
+        if has_pii && best.is_some_and(|(_, _, d)| d.kind == DecisionKind::Allow) {
+            return pii; // a flag never skips redaction
+        }
+        best.map(|(_, _, d)| d.clone()).unwrap_or(Decision {
             kind: DecisionKind::Allow,
             payload: None,
             reason: None,
@@ -494,19 +1058,26 @@ impl Engine {
         })
     }
 
+    /// Redact PII in the envelope payload. A string `payload_json` is scanned as text (the
+    /// fast path); a structured `payload_json` or `payload` is walked and each string value
+    /// redacted in place, so nested fields are caught and the JSON shape is preserved.
     fn scan_and_redact(&self, envelope: &Value, rule_name: Option<&str>) -> Decision {
         let mut modified = envelope.clone();
         let mut changed = false;
         if let Some(payload) =
             modified.get_mut("payload_json").and_then(|v| v.as_str()).map(|s| s.to_string())
         {
-            let redacted = self.pii.replace_all(&payload, "[REDACTED]").into_owned();
-            if redacted != payload {
+            if let Some(redacted) = self.pii.detect_and_redact(&payload) {
                 changed = true;
                 if let Some(v) = modified.get_mut("payload_json") {
                     *v = json!(redacted);
                 }
             }
+        } else if let Some(v) = modified.get_mut("payload_json") {
+            changed |= self.redact_structured(v);
+        }
+        if let Some(v) = modified.get_mut("payload") {
+            changed |= self.redact_structured(v);
         }
         if changed {
             Decision {
@@ -527,15 +1098,64 @@ impl Engine {
         }
     }
 
-    fn check_tool_allowlist(&self, envelope: &Value) -> Option<Decision> {
-        // Parse payload_json if present and look for tool name under common keys
+    /// Recursively redact every string value under `v`; returns whether anything changed.
+    fn redact_structured(&self, v: &mut Value) -> bool {
+        match v {
+            Value::String(s) => match self.pii.detect_and_redact(s) {
+                Some(redacted) => {
+                    *s = redacted;
+                    true
+                }
+                None => false,
+            },
+            Value::Array(items) => {
+                items.iter_mut().fold(false, |acc, item| self.redact_structured(item) | acc)
+            }
+            Value::Object(map) => {
+                map.values_mut().fold(false, |acc, item| self.redact_structured(item) | acc)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether rule `idx` has no `budget_state` clause, or its clause holds under `ctx`.
+    fn budget_gate_holds(&self, idx: usize, ctx: &EvalContext) -> bool {
+        match self.budget_conds.get(idx).copied().flatten() {
+            None => true,
+            Some(cond) => ctx.budget_state.is_some_and(|s| cond.holds(s)),
+        }
+    }
+
+    /// Whether rule `idx` has no `cost_per_token` clause, or its clause holds for `envelope`.
+    fn cost_gate_holds(&self, idx: usize, envelope: &Value) -> bool {
+        match self.cost_conds.get(idx).copied().flatten() {
+            None => true,
+            Some(cond) => cond.holds(envelope),
+        }
+    }
+
+    fn has_budget_cond(&self, idx: usize) -> bool {
+        self.budget_conds.get(idx).is_some_and(Option::is_some)
+    }
+
+    fn has_cost_cond(&self, idx: usize) -> bool {
+        self.cost_conds.get(idx).is_some_and(Option::is_some)
+    }
+
+    /// Lowercased tool name in the envelope's `payload_json`, under the configured keys.
+    fn tool_name(&self, envelope: &Value) -> Option<String> {
         let payload_str = envelope.get("payload_json").and_then(|v| v.as_str())?;
         let payload_val: Value = serde_json::from_str(payload_str).unwrap_or(Value::Null);
-        let tool_name = payload_val
-            .get("tool")
-            .and_then(|v| v.as_str())
-            .or_else(|| payload_val.get("tool_name").and_then(|v| v.as_str()));
-        if let Some(tn) = tool_name.map(|s| s.to_lowercase()) {
+        self.tool_name_keys.iter().find_map(|key| {
+            key.split('.')
+                .try_fold(&payload_val, |v, seg| v.get(seg))
+                .and_then(|v| v.as_str())
+                .map(str::to_lowercase)
+        })
+    }
+
+    fn check_tool_allowlist(&self, envelope: &Value, ctx: &EvalContext) -> Option<Decision> {
+        if let Some(tn) = self.tool_name(envelope) {
             if let Some(allow) = &self.tool_allowlist {
                 if !allow.contains(&tn) {
                     return Some(Decision {
@@ -548,11 +1168,12 @@ impl Engine {
                 }
             } else {
                 // No explicit allowlist: if a rule exists to deny ToolInvocation, deny on any tool presence
-                if self
-                    .rules
-                    .iter()
-                    .any(|r| r.action == "deny" && r.when.contains("ToolInvocation"))
-                {
+                if self.rules.iter().enumerate().any(|(idx, r)| {
+                    r.action == "deny"
+                        && r.when.contains("ToolInvocation")
+                        && self.budget_gate_holds(idx, ctx)
+                        && self.cost_gate_holds(idx, envelope)
+                }) {
                     return Some(Decision {
                         kind: DecisionKind::Deny,
                         payload: None,
@@ -566,3 +1187,91 @@ impl Engine {
         None
     }
 }
+
+/// Whether a field-transform rule's `when` holds, using the keywords the other actions
+/// match on: `always` (or `*`), `ToolInvocation`, `LLMPrompt`, and `pii_detect` (only when
+/// the envelope carried PII). Anything else never matches.
+fn when_matches(when: &str, has_pii: bool) -> bool {
+    let when = when.trim();
+    when == "always"
+        || when == "*"
+        || when.contains("ToolInvocation")
+        || when.contains("LLMPrompt")
+        || (when.contains("pii_detect") && has_pii)
+}
+
+fn default_tool_name_keys() -> Vec<String> {
+    DEFAULT_TOOL_NAME_KEYS.iter().map(|k| (*k).to_string()).collect()
+}
+
+/// Apply `drop`/`mask` field paths to a copy of `envelope`; `None` when nothing matched.
+///
+/// Paths rooted at `payload` are resolved inside the parsed `payload_json` string, which is
+/// re-serialized afterwards; all other paths address envelope fields directly.
+fn apply_field_transforms(envelope: &Value, drop: &[String], mask: &[String]) -> Option<Value> {
+    let mut env = envelope.clone();
+    let mut payload: Option<Value> = envelope
+        .get("payload_json")
+        .and_then(|v| v.as_str())
+        .and_then(|s| serde_json::from_str(s).ok());
+    let mut changed = false;
+    let mut payload_changed = false;
+    let ops = drop.iter().map(|p| (p, true)).chain(mask.iter().map(|p| (p, false)));
+    for (path, is_drop) in ops {
+        let segs: Vec<&str> = path.split('.').collect();
+        let hit = match segs.split_first() {
+            Some((&"payload", rest)) if !rest.is_empty() => {
+                let hit = payload.as_mut().is_some_and(|p| edit_path(p, rest, is_drop));
+                payload_changed |= hit;
+                hit
+            }
+            _ => edit_path(&mut env, &segs, is_drop),
+        };
+        changed |= hit;
+    }
+    if payload_changed {
+        if let (Some(p), Some(slot)) = (payload, env.get_mut("payload_json")) {
+            *slot = json!(p.to_string());
+        }
+    }
+    changed.then_some(env)
+}
+
+/// Drop or mask the value at `segs` under `root`; returns whether the path existed.
+fn edit_path(root: &mut Value, segs: &[&str], is_drop: bool) -> bool {
+    let Some((last, parents)) = segs.split_last() else {
+        return false;
+    };
+    let mut cur = root;
+    for seg in parents {
+        let next = match cur {
+            Value::Object(m) => m.get_mut(*seg),
+            Value::Array(a) => seg.parse::<usize>().ok().and_then(|i| a.get_mut(i)),
+            _ => None,
+        };
+        match next {
+            Some(v) => cur = v,
+            None => return false,
+        }
+    }
+    match cur {
+        Value::Object(m) if is_drop => m.remove(*last).is_some(),
+        Value::Object(m) => match m.get_mut(*last) {
+            Some(v) => {
+                *v = json!("[REDACTED]");
+                true
+            }
+            None => false,
+        },
+        Value::Array(a) if !is_drop => {
+            match last.parse::<usize>().ok().and_then(|i| a.get_mut(i)) {
+                Some(v) => {
+                    *v = json!("[REDACTED]");
+                    true
+                }
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,84 @@
+use policy::{BudgetState, DecisionKind, Engine, EvalContext};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+fn write_temp_yaml(name: &str, content: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!("policy_test_{}_{}_{}.yaml", name, std::process::id(), rand_suffix()));
+    fs::write(&p, content).expect("write temp yaml");
+    p
+}
+
+fn rand_suffix() -> u128 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+}
+
+const BUDGET_RULES: &str = r#"
+rules:
+  - name: Deny-Tools-Under-Budget-Pressure
+    when: "ToolInvocation && budget_state >= Warning90"
+    action: deny
+    message: "run is near its budget"
+"#;
+
+fn at(state: Option<BudgetState>) -> EvalContext {
+    EvalContext { budget_state: state }
+}
+
+#[test]
+fn tool_invocations_are_denied_only_at_warning90_or_above() {
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml("budget_deny", BUDGET_RULES)).unwrap();
+    let tool = json!({"payload_json": "{\"tool\":\"web_search\"}"});
+
+    for state in [BudgetState::Warning90, BudgetState::Exceeded] {
+        let d = eng.pre_submit_task_with_context(&tool, &at(Some(state)));
+        assert_eq!(d.kind, DecisionKind::Deny, "{state:?}");
+    }
+    // Only tool calls are gated: a plain prompt is still allowed at Warning90.
+    let prompt = json!({"payload_json": "hi"});
+    let d = eng.pre_submit_task_with_context(&prompt, &at(Some(BudgetState::Warning90)));
+    assert_eq!(d.kind, DecisionKind::Allow);
+    assert_eq!(d.rule_name, None);
+    for state in [Some(BudgetState::Within), Some(BudgetState::Warning80), None] {
+        let d = eng.pre_submit_task_with_context(&tool, &at(state));
+        assert_eq!(d.kind, DecisionKind::Allow, "{state:?}");
+    }
+    // Without context (plain pre_submit_task) the budget clause never holds.
+    assert_eq!(eng.pre_submit_task(&tool).kind, DecisionKind::Allow);
+}
+
+#[test]
+fn budget_state_operators_compare_by_severity() {
+    let yaml = r#"
+rules:
+  - name: Flag-Below-Warning80
+    when: "LLMPrompt && budget_state < Warning80"
+    action: allow_but_flag
+"#;
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml("budget_lt", yaml)).unwrap();
+    let env = json!({"payload_json": "hi"});
+    let d = eng.pre_submit_task_with_context(&env, &at(Some(BudgetState::Within)));
+    assert_eq!(d.rule_name.as_deref(), Some("Flag-Below-Warning80"));
+    let d = eng.pre_submit_task_with_context(&env, &at(Some(BudgetState::Warning80)));
+    assert_eq!(d.rule_name, None);
+}
+
+#[test]
+fn malformed_budget_conditions_fail_load_with_the_rule_prefix() {
+    for (when, needle) in [
+        ("ToolInvocation && budget_state >= Warning95", "budget state 'Warning95' is invalid"),
+        ("ToolInvocation && budget_state is high", "budget_state condition must be"),
+    ] {
+        let yaml = format!(
+            "rules:\n  - name: ok\n    when: LLMPrompt\n    action: allow_but_flag\n  - name: Budget-Gate\n    when: \"{when}\"\n    action: deny\n"
+        );
+        let mut eng = Engine::new();
+        let err = eng.load_from_yaml_path(write_temp_yaml("budget_bad", &yaml)).unwrap_err();
+        assert!(err.starts_with("rules[1] 'Budget-Gate': "), "missing prefix: {err}");
+        assert!(err.contains(needle), "unexpected error: {err}");
+    }
+}
@@ -0,0 +1,85 @@
+use policy::{DecisionKind, Engine};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+fn write_temp_yaml(name: &str, content: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!("policy_test_{}_{}_{}.yaml", name, std::process::id(), rand_suffix()));
+    fs::write(&p, content).expect("write temp yaml");
+    p
+}
+
+fn rand_suffix() -> u128 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+}
+
+fn task(tokens: u64, cost_micros: u64) -> serde_json::Value {
+    json!({
+        "payload_json": "{\"prompt\":\"summarize\"}",
+        "usage": {"tokens": tokens, "cost_micros": cost_micros},
+    })
+}
+
+const COST_RULES: &str = r#"
+rules:
+  - name: Flag-Expensive-Calls
+    when: "cost_per_token > 200"
+    action: allow_but_flag
+    message: "cost per token above 200 micros"
+"#;
+
+#[test]
+fn high_cost_per_token_task_is_flagged() {
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml("cost_flag", COST_RULES)).unwrap();
+    let d = eng.pre_submit_task(&task(100, 50_000));
+    assert_eq!(d.kind, DecisionKind::Allow);
+    assert_eq!(d.action.as_deref(), Some("allow_but_flag"));
+    assert_eq!(d.rule_name.as_deref(), Some("Flag-Expensive-Calls"));
+}
+
+#[test]
+fn normal_cost_task_passes_unflagged() {
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml("cost_pass", COST_RULES)).unwrap();
+    for env in [task(100, 20_000), task(100, 10), task(0, 50_000), json!({"payload_json": "{}"})] {
+        let d = eng.pre_submit_task(&env);
+        assert_eq!(d.kind, DecisionKind::Allow, "{env}");
+        assert_eq!(d.rule_name, None, "{env}");
+    }
+}
+
+#[test]
+fn cost_per_token_can_deny_and_combine_with_other_conditions() {
+    let yaml = r#"
+rules:
+  - name: Deny-Very-Expensive
+    when: "cost_per_token >= 1000.5"
+    action: deny
+  - name: Deny-Pricey-Tools
+    when: "ToolInvocation && cost_per_token > 100"
+    action: deny
+"#;
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml("cost_deny", yaml)).unwrap();
+    let d = eng.pre_submit_task(&task(2, 2_001));
+    assert_eq!(d.kind, DecisionKind::Deny);
+    assert_eq!(d.rule_name.as_deref(), Some("Deny-Very-Expensive"));
+    assert_eq!(eng.pre_submit_task(&task(2, 2_000)).kind, DecisionKind::Allow);
+
+    let tool = |cost| json!({"payload_json": "{\"tool\":\"web_search\"}", "usage": {"tokens": 10, "cost_micros": cost}});
+    assert_eq!(eng.pre_submit_task(&tool(2_000)).kind, DecisionKind::Deny);
+    assert_eq!(eng.pre_submit_task(&tool(500)).kind, DecisionKind::Allow);
+}
+
+#[test]
+fn malformed_cost_conditions_fail_load_with_the_rule_prefix() {
+    let yaml =
+        "rules:\n  - name: Cost-Gate\n    when: \"cost_per_token > lots\"\n    action: deny\n";
+    let mut eng = Engine::new();
+    let err = eng.load_from_yaml_path(write_temp_yaml("cost_bad", yaml)).unwrap_err();
+    assert!(err.starts_with("rules[0] 'Cost-Gate': "), "missing prefix: {err}");
+    assert!(err.contains("cost_per_token condition must be"), "unexpected error: {err}");
+}
@@ -0,0 +1,53 @@
+use policy::{Decision, Engine, PolicyObserver};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+struct Timings(Arc<Mutex<Vec<(String, Duration)>>>);
+
+impl PolicyObserver for Timings {
+    fn on_decision(&self, _: &str, _: &Decision) {}
+    fn on_decision_latency(&self, phase: &str, elapsed: Duration) {
+        self.0.lock().unwrap().push((phase.to_string(), elapsed));
+    }
+}
+
+#[test]
+fn evaluation_with_several_rules_records_nonzero_latency() {
+    let yaml = r#"
+rules:
+  - name: Flag Prompts
+    when: LLMPrompt
+    action: allow_but_flag
+    priority: 1
+  - name: Deny Tools
+    when: ToolInvocation
+    action: deny
+    priority: 5
+  - name: Modify Prompts
+    when: LLMPrompt
+    action: modify
+    priority: 3
+    drop_fields: ["payload.debug"]
+"#;
+    let path = std::env::temp_dir().join(format!("policy_latency_{}.yaml", std::process::id()));
+    std::fs::write(&path, yaml).unwrap();
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(&path).unwrap();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    policy::set_observer(Some(Box::new(Timings(seen.clone()))));
+    let before = policy::policy_metrics().decision_latency("pre_submit_task");
+    let _ = eng.pre_submit_task(&json!({"payload_json": "{\"prompt\":\"hi\",\"debug\":1}"}));
+    let _ = eng.pre_submit_task(&json!({"payload_json": "{\"tool\":\"curl\"}"}));
+    policy::set_observer(None);
+
+    let after = policy::policy_metrics().decision_latency("pre_submit_task");
+    assert_eq!(after.count - before.count, 2);
+    assert!(after.total_nanos > before.total_nanos);
+    assert!(after.max_nanos > 0);
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert!(seen.iter().all(|(phase, d)| phase == "pre_submit_task" && !d.is_zero()));
+}
@@ -0,0 +1,101 @@
+use policy::{policy_metrics, DecisionKind, Engine, RuleMatch};
+use serde_json::json;
+use std::sync::Mutex;
+
+// Policy metrics are process-wide; serialize tests that compare counters.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn engine(yaml: &str) -> Engine {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static N: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "policy_explain_{}_{}.yaml",
+        std::process::id(),
+        N.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&path, yaml).unwrap();
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    eng
+}
+
+const RULES: &str = r#"
+rules:
+  - name: Flag Prompts
+    when: LLMPrompt
+    action: allow_but_flag
+    priority: 10
+  - name: Deny Tools
+    when: ToolInvocation
+    action: deny
+    priority: 10
+  - name: Low Deny
+    when: ToolInvocation
+    action: deny
+    priority: 1
+"#;
+
+#[test]
+fn reports_all_matches_and_the_winner() {
+    let _g = SERIAL.lock().unwrap();
+    let eng = engine(RULES);
+    let ex = eng.explain(&json!({"payload_json": "ok"}));
+    let m = |name: &str, action: &str, priority, fired| RuleMatch {
+        name: name.into(),
+        action: action.into(),
+        priority,
+        fired,
+    };
+    assert_eq!(
+        ex.matched_rules,
+        vec![
+            m("Flag Prompts", "allow_but_flag", 10, false),
+            m("Deny Tools", "deny", 10, true),
+            m("Low Deny", "deny", 1, false),
+        ]
+    );
+    assert_eq!(ex.selected.as_deref(), Some("Deny Tools"));
+    assert_eq!(ex.final_kind, DecisionKind::Deny);
+}
+
+#[test]
+fn matches_pre_submit_task_exactly() {
+    let _g = SERIAL.lock().unwrap();
+    let eng = engine(RULES);
+    let empty = engine("rules: []\n");
+    let unloaded = Engine::new();
+    let envs = [
+        json!({"payload_json": "ok"}),
+        json!({"payload_json": "SSN 123-45-6789"}),
+        json!({"payload_json": "{\"tool\":\"shell\"}"}),
+    ];
+    for e in [&eng, &empty, &unloaded] {
+        for env in &envs {
+            let ex = e.explain(env);
+            let d = e.pre_submit_task(env);
+            assert_eq!(ex.final_kind, d.kind);
+            assert_eq!(ex.selected, d.rule_name);
+            assert_eq!(ex.decision.payload, d.payload);
+            assert_eq!(ex.decision.action, d.action);
+            assert!(ex.matched_rules.iter().filter(|r| r.fired).count() <= 1);
+        }
+    }
+    // Short-circuit stages explain themselves through `selected` without rule matches.
+    let ex = unloaded.explain(&envs[0]);
+    assert_eq!(ex.selected.as_deref(), Some("fail_closed_default"));
+    assert!(ex.matched_rules.is_empty());
+}
+
+#[test]
+fn explain_records_no_metrics() {
+    let _g = SERIAL.lock().unwrap();
+    let eng = engine(RULES);
+    let before = policy_metrics().decision_counter("pre_submit_task", "deny", "deny");
+    let latency_before = policy_metrics().decision_latency("pre_submit_task").count;
+    for _ in 0..3 {
+        eng.explain(&json!({"payload_json": "ok"}));
+    }
+    assert_eq!(policy_metrics().decision_counter("pre_submit_task", "deny", "deny"), before);
+    assert_eq!(policy_metrics().decision_latency("pre_submit_task").count, latency_before);
+}
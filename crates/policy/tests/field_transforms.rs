@@ -0,0 +1,158 @@
+use policy::{DecisionKind, Engine};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+fn write_temp_yaml(name: &str, content: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!("policy_test_{}_{}_{}.yaml", name, std::process::id(), rand_suffix()));
+    fs::write(&p, content).expect("write temp yaml");
+    p
+}
+
+fn rand_suffix() -> u128 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+}
+
+fn engine(name: &str, yaml: &str) -> Engine {
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(write_temp_yaml(name, yaml)).unwrap();
+    eng
+}
+
+const FIELD_RULES: &str = r#"
+rules:
+  - name: Strip-Internal-Fields
+    when: always
+    action: modify
+    message: "internal fields removed"
+    drop_fields: ["payload.meta.internal_notes"]
+    mask_fields: ["payload.customer.email", "trace_id"]
+"#;
+
+#[test]
+fn drops_nested_field_and_masks_others_attributed_to_rule() {
+    let eng = engine("fields1", FIELD_RULES);
+    let payload = json!({
+        "meta": {"internal_notes": "do not share", "ticket": 7},
+        "customer": {"email": "a@example.com", "name": "Ada"}
+    });
+    let env = json!({"id": "m1", "trace_id": "tr-1", "payload_json": payload.to_string()});
+
+    let d = eng.pre_submit_task(&env);
+    assert_eq!(d.kind, DecisionKind::Modify);
+    assert_eq!(d.rule_name.as_deref(), Some("Strip-Internal-Fields"));
+    assert_eq!(d.action.as_deref(), Some("modify"));
+    assert_eq!(d.reason.as_deref(), Some("internal fields removed"));
+
+    let out = d.payload.unwrap();
+    assert_eq!(out["id"], "m1");
+    assert_eq!(out["trace_id"], "[REDACTED]");
+    let p: Value = serde_json::from_str(out["payload_json"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        p,
+        json!({
+            "meta": {"ticket": 7},
+            "customer": {"email": "[REDACTED]", "name": "Ada"}
+        })
+    );
+}
+
+#[test]
+fn field_rule_does_not_match_when_paths_absent() {
+    let eng = engine("fields2", FIELD_RULES);
+    let env = json!({"id": "m1", "payload_json": "{\"other\":1}"});
+    let d = eng.pre_submit_task(&env);
+    assert_eq!(d.kind, DecisionKind::Allow);
+    assert!(d.payload.is_none());
+}
+
+#[test]
+fn field_transforms_require_modify_and_valid_paths() {
+    let mut eng = Engine::new();
+    let deny = r#"
+rules:
+  - name: Bad-Action
+    when: x
+    action: deny
+    drop_fields: ["payload.a"]
+"#;
+    let err = eng.load_from_yaml_path(write_temp_yaml("fields3", deny)).unwrap_err();
+    assert!(err.contains("'Bad-Action'") && err.contains("require action 'modify'"), "{err}");
+
+    let bad_path = r#"
+rules:
+  - name: Bad-Path
+    when: x
+    action: modify
+    mask_fields: ["payload..a"]
+"#;
+    let err = eng.load_from_yaml_path(write_temp_yaml("fields4", bad_path)).unwrap_err();
+    assert!(err.contains("'payload..a'"), "{err}");
+}
+
+#[test]
+fn field_rule_only_fires_when_its_condition_holds() {
+    let yaml = FIELD_RULES.replace("when: always", "when: \"agent is external\"");
+    let eng = engine("fields5", &yaml);
+    let env = json!({"id": "m1", "payload_json": "{\"meta\":{\"internal_notes\":\"x\"}}"});
+    let d = eng.pre_submit_task(&env);
+    assert_eq!(d.kind, DecisionKind::Allow);
+    assert!(d.payload.is_none());
+}
+
+#[test]
+fn field_transforms_and_pii_redaction_both_apply() {
+    let eng = engine(
+        "fields6",
+        r#"
+rules:
+  - name: Drop-Notes
+    when: always
+    action: modify
+    drop_fields: ["payload.internal_notes"]
+"#,
+    );
+    let payload = json!({"internal_notes": "escalate", "text": "ssn 123-45-6789"});
+    let env = json!({"id": "m1", "payload_json": payload.to_string()});
+
+    let d = eng.pre_submit_task(&env);
+    assert_eq!(d.kind, DecisionKind::Modify);
+    assert_eq!(d.rule_name.as_deref(), Some("Drop-Notes"));
+    let out = d.payload.unwrap();
+    let p: Value = serde_json::from_str(out["payload_json"].as_str().unwrap()).unwrap();
+    assert_eq!(p, json!({"text": "ssn [REDACTED]"}));
+}
+
+#[test]
+fn pii_detect_rule_with_drop_fields_keeps_both_effects() {
+    let eng = engine(
+        "fields7",
+        r#"
+rules:
+  - name: Redact-And-Drop
+    when: pii_detect
+    action: modify
+    drop_fields: ["payload.internal_notes"]
+"#,
+    );
+    let with_field = json!({
+        "id": "m1",
+        "payload_json": json!({"internal_notes": "n", "text": "123-45-6789"}).to_string()
+    });
+    let d = eng.pre_submit_task(&with_field);
+    assert_eq!(d.rule_name.as_deref(), Some("Redact-And-Drop"));
+    let p: Value =
+        serde_json::from_str(d.payload.unwrap()["payload_json"].as_str().unwrap()).unwrap();
+    assert_eq!(p, json!({"text": "[REDACTED]"}));
+
+    // PII without the field still redacts under the rule; no PII means no match.
+    let pii_only = json!({"id": "m2", "payload_json": "{\"text\":\"123-45-6789\"}"});
+    let d = eng.pre_submit_task(&pii_only);
+    assert_eq!(d.kind, DecisionKind::Modify);
+    assert_eq!(d.rule_name.as_deref(), Some("Redact-And-Drop"));
+    assert!(d.payload.unwrap()["payload_json"].as_str().unwrap().contains("[REDACTED]"));
+    let clean = json!({"id": "m3", "payload_json": "{\"internal_notes\":\"n\"}"});
+    assert_eq!(eng.pre_submit_task(&clean).kind, DecisionKind::Allow);
+}
@@ -31,3 +31,47 @@ fn load_yaml_rules() {
         policy::DecisionKind::Allow | policy::DecisionKind::Modify | policy::DecisionKind::Deny
     ));
 }
+
+struct EmployeeIdDetector;
+
+impl policy::PiiDetector for EmployeeIdDetector {
+    fn detect_and_redact(&self, text: &str) -> Option<String> {
+        let re = regex::Regex::new(r"\bEMP-\d{6}\b").unwrap();
+        let out = re.replace_all(text, "[REDACTED]");
+        (out != text).then(|| out.into_owned())
+    }
+}
+
+#[test]
+fn custom_detector_redacts_domain_identifier_default_misses() {
+    let env = json!({"payload_json": "Employee EMP-004217 requested access"});
+
+    let default = Engine::new().pre_submit_task(&env);
+    assert_ne!(default.kind, policy::DecisionKind::Modify, "default detector ignores EMP ids");
+
+    let eng = Engine::new().with_pii_detector(Box::new(EmployeeIdDetector));
+    let d = eng.pre_submit_task(&env);
+    assert_eq!(d.kind, policy::DecisionKind::Modify);
+    assert_eq!(d.rule_name.as_deref(), Some("builtin_redact_pii"));
+    let s = d.payload.unwrap()["payload_json"].as_str().unwrap().to_string();
+    assert_eq!(s, "Employee [REDACTED] requested access");
+}
+
+#[test]
+fn ssn_nested_in_structured_payload_is_redacted_in_place() {
+    let env = json!({
+        "agent": "A",
+        "payload": {"contact": {"name": "Ada", "ssn": "123-45-6789"}, "tags": ["ok", "SSN 987-65-4321"]}
+    });
+    let d = Engine::new().pre_submit_task(&env);
+    assert_eq!(d.kind, policy::DecisionKind::Modify);
+    let out = d.payload.unwrap();
+    assert_eq!(out["payload"]["contact"]["ssn"], "[REDACTED]");
+    assert_eq!(out["payload"]["contact"]["name"], "Ada");
+    assert_eq!(out["payload"]["tags"], json!(["ok", "SSN [REDACTED]"]));
+    assert_eq!(out["agent"], "A");
+
+    let structured = json!({"payload_json": {"contact": {"ssn": "123-45-6789"}}});
+    let d = Engine::new().pre_submit_task(&structured);
+    assert_eq!(d.payload.unwrap()["payload_json"]["contact"]["ssn"], "[REDACTED]");
+}
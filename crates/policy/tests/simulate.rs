@@ -0,0 +1,62 @@
+use policy::{policy_metrics, DecisionKind, Engine, Phase};
+use serde_json::json;
+
+fn engine(yaml: &str) -> Engine {
+    let path = std::env::temp_dir().join(format!(
+        "policy_simulate_{}_{}.yaml",
+        std::process::id(),
+        rand_suffix()
+    ));
+    std::fs::write(&path, yaml).unwrap();
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    eng
+}
+
+fn rand_suffix() -> u128 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+}
+
+fn env(payload: serde_json::Value) -> serde_json::Value {
+    json!({"payload_json": payload.to_string()})
+}
+
+#[test]
+fn simulating_a_deny_policy_reports_decisions_without_metrics() {
+    let eng = engine(
+        r#"
+tool_allowlist: [search]
+rules:
+  - name: Flag Prompts
+    when: LLMPrompt
+    action: allow_but_flag
+"#,
+    );
+    let sample = [
+        env(json!({"tool": "shell"})),
+        env(json!({"tool": "search"})),
+        env(json!({"tool": "curl"})),
+        env(json!({"prompt": "SSN 123-45-6789"})),
+        env(json!({"prompt": "hello"})),
+    ];
+    let deny_before = policy_metrics().decision_counter("pre_submit_task", "deny", "deny");
+    let latency_before = policy_metrics().decision_latency("pre_submit_task").count;
+
+    let decisions = eng.simulate(&sample, Phase::PreSubmitTask);
+
+    let count = |k: DecisionKind| decisions.iter().filter(|d| d.kind == k).count();
+    assert_eq!(decisions.len(), sample.len());
+    assert_eq!(count(DecisionKind::Deny), 2);
+    assert_eq!(count(DecisionKind::Modify), 1);
+    assert_eq!(count(DecisionKind::Allow), 2);
+    assert_eq!(decisions[0].rule_name.as_deref(), Some("tool_allowlist"));
+    assert_eq!(decisions[4].action.as_deref(), Some("allow_but_flag"));
+    assert!(eng
+        .simulate(&sample, Phase::PostSubmitTask)
+        .iter()
+        .all(|d| d.kind == DecisionKind::Allow));
+
+    assert_eq!(policy_metrics().decision_counter("pre_submit_task", "deny", "deny"), deny_before);
+    assert_eq!(policy_metrics().decision_latency("pre_submit_task").count, latency_before);
+}
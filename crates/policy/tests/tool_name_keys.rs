@@ -0,0 +1,55 @@
+use policy::{DecisionKind, Engine};
+use serde_json::json;
+use std::path::PathBuf;
+
+fn engine(name: &str, yaml: &str) -> Result<Engine, String> {
+    let p: PathBuf =
+        std::env::temp_dir().join(format!("policy_toolkeys_{}_{}.yaml", name, std::process::id()));
+    std::fs::write(&p, yaml).expect("write temp yaml");
+    let mut eng = Engine::new();
+    eng.load_from_yaml_path(&p).map(|()| eng)
+}
+
+fn env(payload: serde_json::Value) -> serde_json::Value {
+    json!({"payload_json": payload.to_string()})
+}
+
+#[test]
+fn tool_under_custom_keys_is_denied_by_allowlist() {
+    let eng = engine(
+        "custom",
+        r#"
+tool_allowlist: [echo]
+tool_name_keys: [function, action.name]
+rules: []
+"#,
+    )
+    .unwrap();
+    let d = eng.pre_submit_task(&env(json!({"function": "curl"})));
+    assert_eq!(d.kind, DecisionKind::Deny);
+    assert_eq!(d.rule_name.as_deref(), Some("tool_allowlist"));
+    let d = eng.pre_submit_task(&env(json!({"action": {"name": "rm"}})));
+    assert_eq!(d.kind, DecisionKind::Deny);
+    assert_eq!(d.reason.as_deref(), Some("tool 'rm' not allowed"));
+    let d = eng.pre_submit_task(&env(json!({"action": {"name": "ECHO"}})));
+    assert_eq!(d.kind, DecisionKind::Allow);
+}
+
+#[test]
+fn default_keys_apply_when_unset() {
+    let eng = engine("default", "tool_allowlist: [echo]\nrules: []\n").unwrap();
+    assert_eq!(eng.pre_submit_task(&env(json!({"tool": "curl"}))).kind, DecisionKind::Deny);
+    assert_eq!(eng.pre_submit_task(&env(json!({"tool_name": "curl"}))).kind, DecisionKind::Deny);
+    // Custom keys are not consulted by default.
+    assert_eq!(eng.pre_submit_task(&env(json!({"function": "curl"}))).kind, DecisionKind::Allow);
+}
+
+#[test]
+fn invalid_tool_name_keys_are_rejected_at_load() {
+    let empty = engine("empty", "tool_name_keys: []\nrules: []\n").unwrap_err();
+    assert!(empty.contains("tool_name_keys"), "{empty}");
+    let bad = engine("bad", "tool_name_keys: [tool, 'action..name']\nrules: []\n").unwrap_err();
+    assert!(bad.contains("tool_name_keys[1]"), "{bad}");
+    let dup = engine("dup", "tool_name_keys: [tool, tool]\nrules: []\n").unwrap_err();
+    assert!(dup.contains("duplicate"), "{dup}");
+}
@@ -1,4 +1,4 @@
-use policy::Engine;
+use policy::{Engine, LoadLimits};
 use std::fs;
 use std::path::PathBuf;
 
@@ -78,6 +78,40 @@ rules:
     assert!(res.is_err(), "expected invalid regex to error");
 }
 
+#[test]
+fn invalid_regex_error_names_rule_and_pattern() {
+    let yaml = r#"
+rules:
+  - name: Fine
+    when: LLMPrompt
+    action: allow_but_flag
+  - name: Redact-Account-Numbers
+    when: pii_detect
+    action: modify
+    transform: "regex:acct-[0-9"
+"#;
+    let p = write_temp_yaml("bad_regex_named", yaml);
+    let mut eng = Engine::new();
+    let err = eng.load_from_yaml_path(&p).unwrap_err();
+    assert!(err.contains("rules[1]"), "missing index: {err}");
+    assert!(err.contains("Redact-Account-Numbers"), "missing rule name: {err}");
+    assert!(err.contains("acct-[0-9"), "missing pattern: {err}");
+}
+
+#[test]
+fn invalid_action_error_names_rule() {
+    let yaml = r#"
+rules:
+  - name: Approve-Everything
+    when: ToolInvocation
+    action: approve
+"#;
+    let p = write_temp_yaml("bad_action_named", yaml);
+    let mut eng = Engine::new();
+    let err = eng.load_from_yaml_path(&p).unwrap_err();
+    assert!(err.contains("Approve-Everything"), "missing rule name: {err}");
+}
+
 #[test]
 fn missing_required_fields_errors() {
     // Missing 'action' and 'when' should trigger a serde error -> mapped to our Err
@@ -90,3 +124,64 @@ rules:
     let res = eng.load_from_yaml_path(&p);
     assert!(res.is_err(), "expected missing fields to error");
 }
+
+fn rules_yaml(n: usize) -> String {
+    let mut s = String::from("rules:\n");
+    for i in 0..n {
+        s.push_str(&format!("  - name: R{i}\n    when: LLMPrompt\n    action: allow_but_flag\n"));
+    }
+    s
+}
+
+#[test]
+fn rule_count_at_limit_loads_and_above_limit_errors() {
+    let limits = LoadLimits { max_rules: 3, ..LoadLimits::default() };
+
+    let p = write_temp_yaml("rules_at_cap", &rules_yaml(3));
+    let mut eng = Engine::new().with_load_limits(limits);
+    assert!(eng.load_from_yaml_path(&p).is_ok(), "exactly max_rules should load");
+
+    let p = write_temp_yaml("rules_over_cap", &rules_yaml(4));
+    let mut eng = Engine::new().with_load_limits(limits);
+    let err = eng.load_from_yaml_path(&p).unwrap_err();
+    assert!(err.contains("rules count 4 exceeds max 3"), "unexpected error: {err}");
+}
+
+#[test]
+fn allowlist_size_at_limit_loads_and_above_limit_errors() {
+    let limits = LoadLimits { max_tool_allowlist: 2, ..LoadLimits::default() };
+
+    let p = write_temp_yaml("allow_at_cap", "tool_allowlist: [a, b]\nrules: []\n");
+    let mut eng = Engine::new().with_load_limits(limits);
+    assert!(eng.load_from_yaml_path(&p).is_ok(), "exactly max_tool_allowlist should load");
+
+    let p = write_temp_yaml("allow_over_cap", "tool_allowlist: [a, b, c]\nrules: []\n");
+    let mut eng = Engine::new().with_load_limits(limits);
+    let err = eng.load_from_yaml_path(&p).unwrap_err();
+    assert!(err.contains("tool_allowlist count 3 exceeds max 2"), "unexpected error: {err}");
+}
+
+#[test]
+fn default_limits_are_generous() {
+    let limits = Engine::new().load_limits();
+    assert_eq!(limits, LoadLimits::default());
+    assert!(limits.max_rules >= 1_000);
+    assert!(limits.max_tool_allowlist >= 1_000);
+}
+
+#[test]
+fn empty_rule_name_error_uses_the_rule_prefix() {
+    let yaml = r#"
+rules:
+  - name: Fine
+    when: LLMPrompt
+    action: allow_but_flag
+  - name: "  "
+    when: LLMPrompt
+    action: allow_but_flag
+"#;
+    let p = write_temp_yaml("empty_name", yaml);
+    let mut eng = Engine::new();
+    let err = eng.load_from_yaml_path(&p).unwrap_err();
+    assert!(err.starts_with("rules[1] '  ': name must be non-empty"), "{err}");
+}
@@ -2,9 +2,10 @@
 
 #![deny(unsafe_code)]
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use event_log::{EventRecord, JsonlEventLog};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
@@ -16,6 +17,16 @@ struct Cli {
     cmd: Command,
 }
 
+/// Rendering for `inspect` output.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum OutputFormat {
+    /// Indented, human-readable JSON
+    #[default]
+    Pretty,
+    /// Single-line JSON, suitable for piping into other tools
+    Json,
+}
+
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Show high-level stats for a WAL file
@@ -24,6 +35,12 @@ enum Command {
         wal: PathBuf,
         #[arg(short = 'r', long)]
         run_id: Option<String>,
+        /// Fail on event kinds outside the known allowlist (default: warn on stderr)
+        #[arg(long, default_value_t = false)]
+        strict: bool,
+        /// Output format; `json` prints one compact line
+        #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
+        output: OutputFormat,
     },
     /// Replay events to stdout with filters
     Replay {
@@ -43,6 +60,9 @@ enum Command {
         dry_run: bool,
         #[arg(short, long, default_value_t = false)]
         interactive: bool,
+        /// Fail on event kinds outside the known allowlist (default: warn on stderr)
+        #[arg(long, default_value_t = false)]
+        strict: bool,
     },
     /// Convert events into a simple trace JSON for downstream tools
     ToTrace {
@@ -57,23 +77,159 @@ enum Command {
         #[arg(long)]
         out: Option<PathBuf>,
     },
+    /// Re-emit a run's events as JSONL with `ts_ms` relative to its `start_run` (t=0), so
+    /// two runs with the same timing are byte-comparable
+    Normalize {
+        #[arg(short, long)]
+        wal: PathBuf,
+        #[arg(short = 'r', long)]
+        run_id: String,
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Evaluate a policy file against the task envelopes recorded in a WAL and report the
+    /// decision distribution (no side effects)
+    PolicySimulate {
+        #[arg(short, long)]
+        policy: PathBuf,
+        #[arg(short, long)]
+        wal: PathBuf,
+        #[arg(short = 'r', long)]
+        run_id: Option<String>,
+    },
+    /// Verify a hash-chained WAL against its signed checkpoints: fails on any inserted,
+    /// deleted, or modified record and on a forged or truncated checkpoint trail
+    VerifyChain {
+        #[arg(short, long)]
+        wal: PathBuf,
+        /// File holding the 32-byte checkpoint signing key as hex
+        #[arg(long)]
+        checkpoint_key_file: PathBuf,
+        /// File holding the 32-byte chain link key as hex (keyed chains only)
+        #[arg(long)]
+        chain_key_file: Option<PathBuf>,
+    },
+    /// Convert a v1 JSONL WAL into typed v2 records (best-effort; unconvertible records are
+    /// reported on stderr and left out)
+    MigrateV2 {
+        #[arg(long = "in")]
+        input: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Export a run as an OpenTelemetry trace over OTLP/HTTP: a root span for the run and
+    /// one child span per event
+    #[cfg(feature = "otel")]
+    ExportOtlp {
+        #[arg(short, long)]
+        wal: PathBuf,
+        #[arg(short = 'r', long)]
+        run_id: String,
+        /// Collector base URL, e.g. http://localhost:4318
+        #[arg(long)]
+        endpoint: String,
+        #[arg(long, default_value = "orca-replay")]
+        service_name: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     match cli.cmd {
-        Command::Inspect { wal, run_id } => cmd_inspect(&wal, run_id.as_deref())?,
-        Command::Replay { wal, run_id, from, to, since_ts_ms, max, dry_run, interactive } => {
-            cmd_replay(&wal, run_id.as_deref(), from, to, since_ts_ms, max, dry_run, interactive)?
+        Command::Inspect { wal, run_id, strict, output } => {
+            cmd_inspect(&wal, run_id.as_deref(), strict, output)?
         }
+        Command::Replay {
+            wal,
+            run_id,
+            from,
+            to,
+            since_ts_ms,
+            max,
+            dry_run,
+            interactive,
+            strict,
+        } => cmd_replay(
+            &wal,
+            run_id.as_deref(),
+            from,
+            to,
+            since_ts_ms,
+            max,
+            dry_run,
+            interactive,
+            strict,
+        )?,
         Command::ToTrace { wal, run_id, from, to, out } => {
             cmd_to_trace(&wal, &run_id, from, to, out.as_deref())?
         }
+        Command::Normalize { wal, run_id, out } => {
+            let trace = cmd_normalize(&wal, &run_id)?;
+            match out {
+                Some(path) => {
+                    File::create(&path)?.write_all(trace.as_bytes())?;
+                    println!("wrote normalized trace to {:?}", path);
+                }
+                None => print!("{}", trace),
+            }
+        }
+        Command::PolicySimulate { policy, wal, run_id } => {
+            let report = cmd_policy_simulate(&policy, &wal, run_id.as_deref())?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Command::VerifyChain { wal, checkpoint_key_file, chain_key_file } => {
+            let report = cmd_verify_chain(&wal, &checkpoint_key_file, chain_key_file.as_ref())?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Command::MigrateV2 { input, out } => {
+            let report = cmd_migrate_v2(&input, &out)?;
+            for s in &report.skipped {
+                eprintln!("skipped: {}", s);
+            }
+            println!(
+                "migrated {} records to {:?} ({} skipped)",
+                report.converted,
+                out,
+                report.skipped.len()
+            );
+        }
+        #[cfg(feature = "otel")]
+        Command::ExportOtlp { wal, run_id, endpoint, service_name } => {
+            let trace = otlp_trace(&run_id, &load_events(&wal, Some(&run_id), 0, u64::MAX, 0, 0)?);
+            let n = tokio::task::spawn_blocking(move || {
+                telemetry::replay_export::export_replay_trace(&endpoint, &service_name, &trace)
+            })
+            .await??;
+            println!("exported {} spans for run {}", n, run_id);
+        }
+    }
+    Ok(())
+}
+
+/// WAL size above which an unbounded `--to` with no `--max` gets a warning on stderr.
+const LARGE_WAL_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Reject an inverted `--from`/`--to` range (`to` is exclusive, so `from == to` is empty).
+fn validate_range(from: u64, to: u64) -> Result<(), Box<dyn std::error::Error>> {
+    if from > to {
+        return Err(format!("invalid range: --from {} is greater than --to {}", from, to).into());
     }
     Ok(())
 }
 
+/// Warning for a range that would load all of a large WAL into memory, if any.
+fn unbounded_range_warning(wal: &PathBuf, to: u64, max: u64) -> Option<String> {
+    let len = std::fs::metadata(wal).ok()?.len();
+    (to == u64::MAX && max == 0 && len > LARGE_WAL_BYTES).then(|| {
+        format!(
+            "warning: loading all events from {:?} ({} MiB); bound the range with --to or --max",
+            wal,
+            len / (1024 * 1024)
+        )
+    })
+}
+
 fn load_events(
     wal: &PathBuf,
     run_id: Option<&str>,
@@ -82,36 +238,90 @@ fn load_events(
     since_ts_ms: u64,
     max: u64,
 ) -> Result<Vec<EventRecord<Value>>, Box<dyn std::error::Error>> {
-    let log = JsonlEventLog::open(wal)?;
-    let mut recs: Vec<EventRecord<Value>> = log.read_range(from, to)?;
-    if let Some(rid) = run_id {
-        recs.retain(|rec| {
+    validate_range(from, to)?;
+    let log = JsonlEventLog::open_read_only(wal)?;
+    // With a timestamp, `from` is the id half of a (since_ts_ms, from) resume cursor.
+    let start = if since_ts_ms > 0 { 0 } else { from };
+    // Streamed and filtered record by record: only matches are held, never the whole WAL.
+    let mut recs: Vec<EventRecord<Value>> = Vec::new();
+    for rec in log.iter_range::<Value>(start, to)? {
+        let rec = rec?;
+        if let Some(rid) = run_id {
             let p = &rec.payload;
             let run = p
                 .get("run_id")
                 .and_then(|v| v.as_str())
                 .or_else(|| p.get("workflow_id").and_then(|v| v.as_str()));
-            run == Some(rid)
-        });
+            if run != Some(rid) {
+                continue;
+            }
+        }
+        if !rec.at_or_after(since_ts_ms, from) {
+            continue;
+        }
+        recs.push(rec);
+        if max > 0 && recs.len() as u64 >= max {
+            break;
+        }
     }
-    if since_ts_ms > 0 {
-        recs.retain(|rec| rec.ts_ms >= since_ts_ms);
+    Ok(recs)
+}
+
+/// Check `payload["event"]` kinds against `event_log::KNOWN_EVENT_KINDS`.
+/// Returns one warning per unknown kind (lenient), or an error on the first one (strict).
+fn check_event_kinds(
+    recs: &[EventRecord<Value>],
+    strict: bool,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut warnings = Vec::new();
+    for rec in recs {
+        if let Some(kind) = rec.payload.get("event").and_then(|v| v.as_str()) {
+            if !event_log::is_known_event_kind(kind) {
+                let msg = format!("unknown event kind '{}' at id {}", kind, rec.id);
+                if strict {
+                    return Err(msg.into());
+                }
+                warnings.push(msg);
+            }
+        }
     }
-    if max > 0 && recs.len() as u64 > max {
-        recs.truncate(max as usize);
+    Ok(warnings)
+}
+
+fn load_checked(
+    recs: Vec<EventRecord<Value>>,
+    strict: bool,
+) -> Result<Vec<EventRecord<Value>>, Box<dyn std::error::Error>> {
+    for w in check_event_kinds(&recs, strict)? {
+        eprintln!("warning: {}", w);
     }
     Ok(recs)
 }
 
-fn cmd_inspect(wal: &PathBuf, run_id: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    let recs = load_events(wal, run_id, 0, u64::MAX, 0, 0)?;
+fn cmd_inspect(
+    wal: &PathBuf,
+    run_id: Option<&str>,
+    strict: bool,
+    output: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let recs = load_checked(load_events(wal, run_id, 0, u64::MAX, 0, 0)?, strict)?;
+    println!("{}", render_inspect(&recs, output)?);
+    Ok(())
+}
+
+/// Summarize records for `inspect`. `by_event` is a `BTreeMap`, so kinds are always emitted
+/// in sorted order and the rendered output is byte-stable for a given WAL.
+fn render_inspect(
+    recs: &[EventRecord<Value>],
+    output: OutputFormat,
+) -> Result<String, Box<dyn std::error::Error>> {
     let total = recs.len();
     let first_id = recs.first().map(|r| r.id).unwrap_or(0);
     let last_id = recs.last().map(|r| r.id).unwrap_or(0);
     let first_ts = recs.first().map(|r| r.ts_ms).unwrap_or(0);
     let last_ts = recs.last().map(|r| r.ts_ms).unwrap_or(0);
     let mut by_event = std::collections::BTreeMap::<String, usize>::new();
-    for rec in &recs {
+    for rec in recs {
         let kind = rec.payload.get("event").and_then(|v| v.as_str()).unwrap_or("event").to_string();
         *by_event.entry(kind).or_default() += 1;
     }
@@ -123,8 +333,10 @@ fn cmd_inspect(wal: &PathBuf, run_id: Option<&str>) -> Result<(), Box<dyn std::e
         "last_ts_ms": last_ts,
         "by_event": by_event,
     });
-    println!("{}", serde_json::to_string_pretty(&out)?);
-    Ok(())
+    Ok(match output {
+        OutputFormat::Pretty => serde_json::to_string_pretty(&out)?,
+        OutputFormat::Json => serde_json::to_string(&out)?,
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -137,8 +349,12 @@ fn cmd_replay(
     max: u64,
     dry_run: bool,
     interactive: bool,
+    strict: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let recs = load_events(wal, run_id, from, to, since_ts_ms, max)?;
+    if let Some(w) = unbounded_range_warning(wal, to, max) {
+        eprintln!("{}", w);
+    }
+    let recs = load_checked(load_events(wal, run_id, from, to, since_ts_ms, max)?, strict)?;
     if dry_run {
         println!("events={}", recs.len());
         return Ok(());
@@ -165,6 +381,9 @@ fn cmd_to_trace(
     to: u64,
     out: Option<&std::path::Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(w) = unbounded_range_warning(wal, to, 0) {
+        eprintln!("{}", w);
+    }
     let recs = load_events(wal, Some(run_id), from, to, 0, 0)?;
     let mut items = Vec::with_capacity(recs.len());
     for rec in recs {
@@ -187,6 +406,152 @@ fn cmd_to_trace(
     Ok(())
 }
 
+/// Shift every record's `ts_ms` so the run's `start_run` is at 0, preserving spacing. Ids
+/// and payloads are untouched; records stamped before the start clamp to 0.
+fn normalize_events(
+    run_id: &str,
+    recs: Vec<EventRecord<Value>>,
+) -> Result<Vec<EventRecord<Value>>, Box<dyn std::error::Error>> {
+    let base = recs
+        .iter()
+        .find(|r| r.payload.get("event").and_then(|v| v.as_str()) == Some("start_run"))
+        .map(|r| r.ts_ms)
+        .ok_or_else(|| format!("run {} has no start_run event", run_id))?;
+    Ok(recs.into_iter().map(|r| EventRecord { ts_ms: r.ts_ms.saturating_sub(base), ..r }).collect())
+}
+
+/// Normalized JSONL trace of `run_id` (one record per line, WAL order).
+fn cmd_normalize(wal: &PathBuf, run_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let recs = normalize_events(run_id, load_events(wal, Some(run_id), 0, u64::MAX, 0, 0)?)?;
+    let mut out = String::new();
+    for rec in &recs {
+        out.push_str(&serde_json::to_string(rec)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Decide every `task_enqueued` envelope in the WAL under `policy` (as `pre_submit_task`
+/// would) and count the outcomes per decision kind and per deciding rule.
+fn cmd_policy_simulate(
+    policy: &PathBuf,
+    wal: &PathBuf,
+    run_id: Option<&str>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut engine = policy::Engine::new();
+    engine.load_from_yaml_path(policy)?;
+    let envelopes: Vec<Value> = load_events(wal, run_id, 0, u64::MAX, 0, 0)?
+        .into_iter()
+        .filter(|rec| rec.payload.get("event").and_then(|v| v.as_str()) == Some("task_enqueued"))
+        .filter_map(|mut rec| rec.payload.get_mut("envelope").map(Value::take))
+        .collect();
+    let decisions = engine.simulate(&envelopes, policy::Phase::PreSubmitTask);
+    let mut by_kind: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut by_rule: BTreeMap<String, u64> = BTreeMap::new();
+    for d in &decisions {
+        let kind = match d.kind {
+            policy::DecisionKind::Allow => "allow",
+            policy::DecisionKind::Deny => "deny",
+            policy::DecisionKind::Modify => "modify",
+        };
+        *by_kind.entry(kind).or_default() += 1;
+        if let Some(rule) = &d.rule_name {
+            *by_rule.entry(rule.clone()).or_default() += 1;
+        }
+    }
+    Ok(json!({
+        "policy_version": engine.policy_version(),
+        "envelopes": envelopes.len(),
+        "decisions": by_kind,
+        "by_rule": by_rule,
+    }))
+}
+
+/// Read a 32-byte key stored as hex (surrounding whitespace ignored).
+fn read_key_file(path: &PathBuf) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let bytes = hex::decode(text.trim())?;
+    <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("key file {:?} must hold 32 bytes of hex", path).into())
+}
+
+/// Check every checkpoint signature and recompute the chain through each signed head.
+fn cmd_verify_chain(
+    wal: &PathBuf,
+    checkpoint_key_file: &PathBuf,
+    chain_key_file: Option<&PathBuf>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let checkpoint_key = read_key_file(checkpoint_key_file)?;
+    let chain_key = chain_key_file.map(read_key_file).transpose()?;
+    let v = event_log::verify_checkpoints(wal, chain_key.as_ref(), &checkpoint_key)?;
+    Ok(json!({ "records": v.records, "checkpoints": v.checkpoints }))
+}
+
+/// Outcome of `migrate-v2`.
+#[derive(Debug, Default)]
+struct MigrateReport {
+    converted: usize,
+    /// One `id <n>: <reason>` entry per record with no v2 mapping.
+    skipped: Vec<String>,
+}
+
+fn cmd_migrate_v2(
+    input: &PathBuf,
+    out: &std::path::Path,
+) -> Result<MigrateReport, Box<dyn std::error::Error>> {
+    let recs = load_events(input, None, 0, u64::MAX, 0, 0)?;
+    let mut w = std::io::BufWriter::new(File::create(out)?);
+    let mut report = MigrateReport::default();
+    for rec in &recs {
+        match event_log::v2::MigratedV2::try_from(rec).and_then(|m| m.to_jsonl_line()) {
+            Ok(line) => {
+                writeln!(w, "{}", line)?;
+                report.converted += 1;
+            }
+            Err(e) => report.skipped.push(format!("id {}: {}", rec.id, e)),
+        }
+    }
+    w.flush()?;
+    Ok(report)
+}
+
+/// Rebuild a run as spans: each event spans from its own timestamp to the next event's
+/// (the last one is instantaneous), named by `payload["event"]`, with the top-level payload
+/// fields as `payload.<key>` attributes (non-strings rendered as JSON).
+#[cfg(feature = "otel")]
+fn otlp_trace(run_id: &str, recs: &[EventRecord<Value>]) -> telemetry::replay_export::ReplayTrace {
+    use telemetry::replay_export::{ReplaySpan, ReplayTrace};
+    let events = recs
+        .iter()
+        .enumerate()
+        .map(|(i, rec)| {
+            let mut attributes = vec![
+                ("orca.run_id".to_string(), run_id.to_string()),
+                ("orca.record_id".to_string(), rec.id.to_string()),
+            ];
+            if let Some(obj) = rec.payload.as_object() {
+                for (k, v) in obj.iter().filter(|(k, _)| k.as_str() != "event") {
+                    let v = v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string());
+                    attributes.push((format!("payload.{}", k), v));
+                }
+            }
+            ReplaySpan {
+                name: rec.payload.get("event").and_then(|v| v.as_str()).unwrap_or("event").into(),
+                start_ms: rec.ts_ms,
+                end_ms: recs.get(i + 1).map_or(rec.ts_ms, |next| next.ts_ms.max(rec.ts_ms)),
+                attributes,
+            }
+        })
+        .collect();
+    let root = ReplaySpan {
+        name: format!("run {}", run_id),
+        start_ms: recs.first().map_or(0, |r| r.ts_ms),
+        end_ms: recs.iter().map(|r| r.ts_ms).max().unwrap_or(0),
+        attributes: vec![("orca.run_id".to_string(), run_id.to_string())],
+    };
+    ReplayTrace { root, events }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,6 +595,60 @@ mod tests {
         assert_eq!(recs[0].id, 2);
     }
 
+    #[test]
+    fn inverted_range_is_rejected_and_valid_range_loads() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        let err = load_events(&wal, None, 10, 5, 0, 0).unwrap_err();
+        assert_eq!(err.to_string(), "invalid range: --from 10 is greater than --to 5");
+        let recs = load_events(&wal, None, 2, 4, 0, 0).unwrap();
+        assert_eq!(recs.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(load_events(&wal, None, 3, 3, 0, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unbounded_range_warns_only_for_large_wal() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        assert_eq!(unbounded_range_warning(&wal, u64::MAX, 0), None);
+        std::fs::File::options()
+            .append(true)
+            .open(&wal)
+            .unwrap()
+            .set_len(LARGE_WAL_BYTES + 1)
+            .unwrap();
+        assert!(unbounded_range_warning(&wal, u64::MAX, 0).unwrap().contains("--to or --max"));
+        assert_eq!(unbounded_range_warning(&wal, 100, 0), None);
+        assert_eq!(unbounded_range_warning(&wal, u64::MAX, 10), None);
+    }
+
+    #[test]
+    fn policy_simulate_reports_decision_distribution() {
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("sim.jsonl");
+        let log = JsonlEventLog::open(&wal).unwrap();
+        let payloads = [
+            r#"{"tool":"shell"}"#,
+            r#"{"tool":"search"}"#,
+            r#"{"prompt":"SSN 123-45-6789"}"#,
+            r#"{"tool":"curl"}"#,
+        ];
+        log.append(1, 1, &json!({"event":"start_run","workflow_id":"R1"})).unwrap();
+        for (i, p) in payloads.iter().enumerate() {
+            let evt = json!({"event":"task_enqueued","run_id":"R1","envelope":{"id":format!("e{i}"),"payload_json":p}});
+            log.append(i as u64 + 2, i as u64 + 2, &evt).unwrap();
+        }
+        let policy = dir.path().join("policy.yaml");
+        std::fs::write(&policy, "tool_allowlist: [search]\nrules: []\n").unwrap();
+
+        let report = cmd_policy_simulate(&policy, &wal, None).unwrap();
+        assert_eq!(report["envelopes"], 4);
+        assert_eq!(report["decisions"], json!({"allow": 1, "deny": 2, "modify": 1}));
+        assert_eq!(report["by_rule"], json!({"builtin_redact_pii": 1, "tool_allowlist": 2}));
+        let none = cmd_policy_simulate(&policy, &wal, Some("R2")).unwrap();
+        assert_eq!(none["envelopes"], 0);
+    }
+
     #[test]
     fn since_ts_and_max() {
         let dir = tempdir().unwrap();
@@ -241,6 +660,161 @@ mod tests {
         assert_eq!(limited.len(), 2);
     }
 
+    #[test]
+    fn unknown_event_kind_warns_lenient_and_fails_strict() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        let log = JsonlEventLog::open(&wal).unwrap();
+        log.append(5, 5, &json!({"event":"usage_updat","run_id":"R1"})).unwrap();
+        let recs = load_events(&wal, None, 0, u64::MAX, 0, 0).unwrap();
+
+        let warnings = check_event_kinds(&recs, false).unwrap();
+        assert_eq!(warnings, vec!["unknown event kind 'usage_updat' at id 5".to_string()]);
+        let err = check_event_kinds(&recs, true).unwrap_err();
+        assert!(err.to_string().contains("usage_updat"));
+        assert!(cmd_replay(&wal, None, 0, u64::MAX, 0, 0, true, false, true).is_err());
+        assert!(cmd_replay(&wal, None, 0, u64::MAX, 0, 0, true, false, false).is_ok());
+    }
+
+    #[test]
+    fn inspect_by_event_sorted_and_byte_stable() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        let log = JsonlEventLog::open(&wal).unwrap();
+        log.append(5, 5, &json!({"event":"agent_result","run_id":"R1"})).unwrap();
+        log.append(6, 6, &json!({"event":"zz_custom","run_id":"R1"})).unwrap();
+        let recs = load_events(&wal, None, 0, u64::MAX, 0, 0).unwrap();
+
+        let s = render_inspect(&recs, OutputFormat::Json).unwrap();
+        let v: Value = serde_json::from_str(&s).unwrap();
+        let kinds: Vec<&String> = v["by_event"].as_object().unwrap().keys().collect();
+        let mut sorted = kinds.clone();
+        sorted.sort();
+        assert_eq!(kinds, sorted);
+        assert_eq!(v["by_event"]["task_enqueued"], 2);
+
+        for format in [OutputFormat::Json, OutputFormat::Pretty] {
+            let again = load_events(&wal, None, 0, u64::MAX, 0, 0).unwrap();
+            assert_eq!(
+                render_inspect(&recs, format).unwrap(),
+                render_inspect(&again, format).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn inspect_json_output_is_single_line() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        let recs = load_events(&wal, None, 0, u64::MAX, 0, 0).unwrap();
+        let compact = render_inspect(&recs, OutputFormat::Json).unwrap();
+        assert!(!compact.contains('\n'));
+        let pretty = render_inspect(&recs, OutputFormat::Pretty).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&compact).unwrap(),
+            serde_json::from_str::<Value>(&pretty).unwrap()
+        );
+        let cli = Cli::try_parse_from(["orca-replay", "inspect", "-w", "x", "--output", "json"]);
+        assert!(matches!(cli.unwrap().cmd, Command::Inspect { output: OutputFormat::Json, .. }));
+    }
+
+    #[test]
+    fn migrate_v2_types_known_events_and_reports_the_rest() {
+        use event_log::v2::{
+            EventTypeV2, ExternalIOFinishedPayload, RecordV2, StartRunPayload, TaskEnqueuedPayload,
+            UsageUpdatePayload,
+        };
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("v1.jsonl");
+        let log = JsonlEventLog::open(&wal).unwrap();
+        let digest = "ab".repeat(32);
+        log.append(10, 100, &json!({"event":"start_run","workflow_id":"R1","envelope":null}))
+            .unwrap();
+        log.append(
+            11,
+            101,
+            &json!({
+                "event":"task_enqueued","run_id":"R1",
+                "envelope":{"id":"e1","agent":"A","trace_id":"T1"},
+                "attachments":[{"digest_sha256":digest,"size_bytes":4,"mime":"text/plain","compression":"none"}],
+            }),
+        )
+        .unwrap();
+        log.append(
+            12,
+            102,
+            &json!({"event":"usage_update","run_id":"R1","tokens":7,"cost_micros":70}),
+        )
+        .unwrap();
+        log.append(13, 103, &json!({"event":"policy_audit","run_id":"R1","outcome":"denied"}))
+            .unwrap();
+        log.append(
+            14,
+            104,
+            &json!({"event":"external_io_finished","request_id":"R9","status":"ok","duration_ms":3}),
+        )
+        .unwrap();
+
+        let out = dir.path().join("v2.jsonl");
+        let report = cmd_migrate_v2(&wal, &out).unwrap();
+        assert_eq!(report.converted, 4);
+        assert_eq!(
+            report.skipped,
+            vec!["id 13: invalid: no v2 mapping for event kind 'policy_audit'".to_string()]
+        );
+
+        let text = std::fs::read_to_string(&out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        let start: RecordV2<StartRunPayload> = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!((start.id, start.ts_ms, start.version), (10, 100, 2));
+        assert_eq!(start.event_type, EventTypeV2::StartRun);
+        assert_eq!((start.run_id.as_str(), start.payload.workflow_id.as_str()), ("R1", "R1"));
+        let task: RecordV2<TaskEnqueuedPayload> = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!((task.id, task.ts_ms, task.trace_id.as_str()), (11, 101, "T1"));
+        assert_eq!((task.payload.envelope_id.as_str(), task.payload.agent.as_str()), ("e1", "A"));
+        assert_eq!(task.attachments.unwrap()[0].digest_sha256, digest);
+        let usage: RecordV2<UsageUpdatePayload> = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!((usage.id, usage.ts_ms), (12, 102));
+        assert_eq!((usage.payload.tokens, usage.payload.cost_micros), (7, 70));
+        let io: RecordV2<ExternalIOFinishedPayload> = serde_json::from_str(lines[3]).unwrap();
+        assert_eq!((io.id, io.ts_ms, io.event_type), (14, 104, EventTypeV2::ExternalIoFinished));
+        assert_eq!((io.payload.request_id.as_str(), io.payload.duration_ms), ("R9", 3));
+
+        let cli = Cli::try_parse_from(["orca-replay", "migrate-v2", "--in", "a", "--out", "b"]);
+        assert!(matches!(cli.unwrap().cmd, Command::MigrateV2 { .. }));
+    }
+
+    #[cfg(feature = "otel")]
+    #[test]
+    fn otlp_trace_spans_events_between_consecutive_timestamps() {
+        let dir = tempdir().unwrap();
+        let wal = write_sample_wal(dir.path());
+        let recs = load_events(&wal, Some("R1"), 0, u64::MAX, 0, 0).unwrap();
+        let trace = otlp_trace("R1", &recs);
+        assert_eq!(trace.root.name, "run R1");
+        assert_eq!((trace.root.start_ms, trace.root.end_ms), (recs[0].ts_ms, recs[2].ts_ms));
+        let names: Vec<&str> = trace.events.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["start_run", "task_enqueued", "usage_update"]);
+        assert_eq!(trace.events[0].end_ms, trace.events[1].start_ms);
+        assert_eq!(trace.events[2].start_ms, trace.events[2].end_ms);
+        let usage = &trace.events[2].attributes;
+        assert!(usage.contains(&("payload.tokens".to_string(), "10".to_string())));
+        assert!(usage.contains(&("orca.record_id".to_string(), "3".to_string())));
+
+        let cli = Cli::try_parse_from([
+            "orca-replay",
+            "export-otlp",
+            "-w",
+            "x",
+            "-r",
+            "R1",
+            "--endpoint",
+            "http://localhost:4318",
+        ]);
+        assert!(matches!(cli.unwrap().cmd, Command::ExportOtlp { .. }));
+    }
+
     #[test]
     fn to_trace_deterministic_output() {
         let dir = tempdir().unwrap();
@@ -253,4 +827,66 @@ mod tests {
         let s2 = std::fs::read_to_string(out2).unwrap();
         assert_eq!(s1, s2);
     }
+
+    fn write_run(dir: &std::path::Path, name: &str, start: u64, gaps: [u64; 3]) -> PathBuf {
+        let wal = dir.join(name);
+        let log = JsonlEventLog::open(&wal).unwrap();
+        let mut ts = start;
+        let _ = log.append(1, ts, &json!({"event":"start_run","workflow_id":"R1"})).unwrap();
+        let events = [
+            json!({"event":"task_enqueued","run_id":"R1","envelope":{"id":"e1"}}),
+            json!({"event":"task_enqueued","run_id":"R2","envelope":{"id":"x"}}),
+            json!({"event":"usage_update","run_id":"R1","tokens":10,"cost_micros":1000}),
+        ];
+        for (i, (payload, gap)) in events.iter().zip(gaps).enumerate() {
+            ts += gap;
+            let _ = log.append(i as u64 + 2, ts, payload).unwrap();
+        }
+        wal
+    }
+
+    #[test]
+    fn normalize_makes_runs_with_matching_timing_byte_identical() {
+        let dir = tempdir().unwrap();
+        let a = write_run(dir.path(), "a.jsonl", 1_700_000_000_000, [5, 1, 20]);
+        let b = write_run(dir.path(), "b.jsonl", 1_800_000_123_456, [5, 1, 20]);
+        assert_ne!(std::fs::read(&a).unwrap(), std::fs::read(&b).unwrap());
+
+        let (na, nb) = (cmd_normalize(&a, "R1").unwrap(), cmd_normalize(&b, "R1").unwrap());
+        assert_eq!(na, nb);
+        let recs: Vec<EventRecord<Value>> =
+            na.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(
+            recs.iter().map(|r| (r.id, r.ts_ms)).collect::<Vec<_>>(),
+            [(1, 0), (2, 5), (4, 26)]
+        );
+        assert_eq!(recs[2].payload["tokens"], 10);
+
+        // Different spacing still shows up after normalization.
+        let c = write_run(dir.path(), "c.jsonl", 1_700_000_000_000, [5, 1, 21]);
+        assert_ne!(cmd_normalize(&c, "R1").unwrap(), na);
+        // A run without start_run cannot be anchored.
+        assert!(cmd_normalize(&a, "R2").unwrap_err().to_string().contains("no start_run"));
+    }
+
+    #[test]
+    fn verify_chain_checks_checkpoints() {
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("log.jsonl");
+        let key = [7u8; 32];
+        let log = JsonlEventLog::open(&wal).unwrap().with_hash_chain().unwrap();
+        let log = log.with_checkpoint_key(key);
+        for id in 1..=3u64 {
+            let _ = log.append(id, id, &json!({"event":"start_run","workflow_id":"R1"})).unwrap();
+        }
+        log.write_checkpoint().unwrap();
+        let key_file = dir.path().join("checkpoint.key");
+        std::fs::write(&key_file, format!("{}\n", hex::encode(key))).unwrap();
+        let report = cmd_verify_chain(&wal, &key_file, None).unwrap();
+        assert_eq!(report, json!({"records": 3, "checkpoints": 1}));
+
+        std::fs::write(&key_file, hex::encode([8u8; 32])).unwrap();
+        let err = cmd_verify_chain(&wal, &key_file, None).unwrap_err();
+        assert!(err.to_string().contains("checkpoint signature invalid"), "{}", err);
+    }
 }
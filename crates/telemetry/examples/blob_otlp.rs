@@ -3,13 +3,11 @@ use std::time::Duration;
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    telemetry::init_json_logging();
+    let _telemetry =
+        telemetry::init(telemetry::TelemetryConfig::from_env().with_otel(cfg!(feature = "otel")))?;
 
     #[cfg(feature = "otel")]
-    {
-        telemetry::init_otlp_from_env()?;
-        blob_store::set_observer(telemetry::blob_observer::global());
-    }
+    blob_store::set_observer(telemetry::blob_observer::global());
 
     // Temp directory for demo
     let dir = std::env::temp_dir().join("orca_blob_otlp_demo");
@@ -17,7 +15,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     std::fs::create_dir_all(&dir)?;
 
     // Create a blob store
-    let cfg = blob_store::Config { root: PathBuf::from(&dir), zstd_level: 3 };
+    let cfg =
+        blob_store::Config { root: PathBuf::from(&dir), zstd_level: 3, zstd_dictionary: None };
     let store: blob_store::BlobStore<blob_store::DevKeyProvider> =
         blob_store::BlobStore::new(cfg, blob_store::DevKeyProvider::new([0xAA; 32]))?;
 
@@ -28,7 +27,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(got, data);
 
     // Create an incomplete artifact and cleanup to exercise cleanup metric
-    let shard = store.path_for(&dg.to_hex());
+    let shard = store.path_for_digest(&dg);
     let tmp = shard.with_extension("incomplete");
     std::fs::create_dir_all(tmp.parent().unwrap())?;
     std::fs::write(&tmp, b"junk")?;
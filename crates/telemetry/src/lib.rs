@@ -13,6 +13,12 @@ pub mod blob_observer;
 #[cfg(feature = "otel")]
 pub mod policy_observer;
 
+#[cfg(feature = "otel")]
+pub mod replay_export;
+
+#[cfg(feature = "otel")]
+pub mod span_redaction;
+
 #[derive(Debug, Error)]
 pub enum TelemetryError {
     #[error("otel setup failed: {0}")]
@@ -28,6 +34,104 @@ pub fn init_json_logging() {
     tracing::subscriber::set_global_default(subscriber).ok();
 }
 
+/// Settings for [`init`].
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    /// Install the global JSON logging subscriber.
+    pub json_logging: bool,
+    /// Log filter directives; falls back to `RUST_LOG`, then `info`.
+    pub log_filter: Option<String>,
+    /// Export traces and metrics over OTLP (requires the `otel` feature and a Tokio runtime).
+    pub otel: bool,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self::logging_only()
+    }
+}
+
+impl TelemetryConfig {
+    /// JSON logging only; no exporters, no collector needed.
+    pub fn logging_only() -> Self {
+        Self { json_logging: true, log_filter: None, otel: false }
+    }
+
+    /// JSON logging, plus OTLP export when `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+    pub fn from_env() -> Self {
+        Self {
+            otel: std::env::var_os("OTEL_EXPORTER_OTLP_ENDPOINT").is_some(),
+            ..Self::logging_only()
+        }
+    }
+
+    /// Override the log filter directives (e.g. `"info,orchestrator=debug"`).
+    pub fn with_log_filter(mut self, directives: impl Into<String>) -> Self {
+        self.log_filter = Some(directives.into());
+        self
+    }
+
+    /// Enable or disable OTLP export.
+    pub fn with_otel(mut self, enabled: bool) -> Self {
+        self.otel = enabled;
+        self
+    }
+}
+
+/// Returned by [`init`]; flushes and shuts down the OTLP tracer provider on drop.
+#[derive(Debug)]
+#[must_use = "dropping the guard shuts down trace export"]
+pub struct TelemetryGuard {
+    logging_installed: bool,
+    otel: bool,
+}
+
+impl TelemetryGuard {
+    /// Whether this call installed the global subscriber (false if one was already set).
+    pub fn logging_installed(&self) -> bool {
+        self.logging_installed
+    }
+
+    /// Whether OTLP export was initialized.
+    pub fn otel_enabled(&self) -> bool {
+        self.otel
+    }
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if self.otel {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Configure logging, trace export, and metrics in one call.
+///
+/// An already-installed global subscriber is left in place (see
+/// [`TelemetryGuard::logging_installed`]). Requesting OTLP export without the `otel`
+/// feature is an error rather than a silent no-op.
+pub fn init(cfg: TelemetryConfig) -> Result<TelemetryGuard, TelemetryError> {
+    let mut logging_installed = false;
+    if cfg.json_logging {
+        let fmt_layer = fmt::layer().json().with_current_span(true).with_span_list(true);
+        let filter = match &cfg.log_filter {
+            Some(d) => EnvFilter::new(d),
+            None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        };
+        let subscriber = Registry::default().with(filter).with(fmt_layer);
+        logging_installed = tracing::subscriber::set_global_default(subscriber).is_ok();
+    }
+    if cfg.otel {
+        #[cfg(feature = "otel")]
+        init_otlp_from_env()?;
+        #[cfg(not(feature = "otel"))]
+        return Err(TelemetryError::Otel("built without the `otel` feature".into()));
+    }
+    Ok(TelemetryGuard { logging_installed, otel: cfg.otel })
+}
+
 /// Initialize OpenTelemetry tracer (optional; behind `otel` feature). No tracing subscriber hookup.
 #[cfg(feature = "otel")]
 pub fn init_otel(service_name: &str) -> Result<(), TelemetryError> {
@@ -52,12 +156,15 @@ static OTLP_INIT: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new
 /// Env:
 /// - OTEL_EXPORTER_OTLP_ENDPOINT (e.g., http://localhost:4318)
 /// - OTEL_SERVICE_NAME (fallback ORCA_SERVICE_NAME; default "orchestrator")
+/// - ORCA_OTEL_HASH_ATTRS / ORCA_OTEL_DROP_ATTRS: span attributes redacted before export
+///   (see [`span_redaction::SpanAttributeFilter::from_env`])
 #[cfg(feature = "otel")]
 pub fn init_otlp_from_env() -> Result<(), TelemetryError> {
     use opentelemetry::{global, KeyValue};
     use opentelemetry_otlp::WithExportConfig;
     use opentelemetry_sdk::trace as sdktrace;
     use opentelemetry_sdk::{runtime, Resource};
+    use span_redaction::{RedactingExporter, SpanAttributeFilter};
 
     if OTLP_INIT.get().is_some() {
         return Ok(());
@@ -71,13 +178,20 @@ pub fn init_otlp_from_env() -> Result<(), TelemetryError> {
 
     let resource = Resource::new(vec![KeyValue::new("service.name", service_name)]);
 
-    // Traces
-    let _tracer_provider = opentelemetry_otlp::new_pipeline()
-        .tracing()
-        .with_exporter(opentelemetry_otlp::new_exporter().http().with_endpoint(endpoint.clone()))
-        .with_trace_config(sdktrace::config().with_resource(resource.clone()))
-        .install_batch(runtime::Tokio)
+    // Traces, with sensitive/high-cardinality attributes redacted on the way out
+    let span_exporter = opentelemetry_otlp::new_exporter()
+        .http()
+        .with_endpoint(endpoint.clone())
+        .build_span_exporter()
         .map_err(|e| TelemetryError::Otel(e.to_string()))?;
+    let tracer_provider = sdktrace::TracerProvider::builder()
+        .with_batch_exporter(
+            RedactingExporter::new(span_exporter, SpanAttributeFilter::from_env()),
+            runtime::Tokio,
+        )
+        .with_config(sdktrace::config().with_resource(resource.clone()))
+        .build();
+    global::set_tracer_provider(tracer_provider);
 
     // Metrics
     let meter_provider = opentelemetry_otlp::new_pipeline()
@@ -184,6 +298,16 @@ pub mod metrics {
         };
         BudgetInstruments { tokens, cost_micros: cost }
     }
+
+    /// Counter of WAL records an embedder event hook did not receive
+    /// (`orca.event_hook.dropped`).
+    pub fn init_event_hook_dropped_counter() -> Counter<u64> {
+        ensure_metrics_provider();
+        global::meter("orca.event_hook")
+            .u64_counter("orca.event_hook.dropped")
+            .with_description("WAL records dropped for the event hook (queue full or thread gone)")
+            .init()
+    }
 }
 
 /// Returns whether telemetry is initialized (stubbed).
@@ -2,11 +2,12 @@
 
 use once_cell::sync::OnceCell;
 use opentelemetry::global;
-use opentelemetry::metrics::{Counter, Meter};
+use opentelemetry::metrics::{Counter, Histogram, Meter};
 use opentelemetry::KeyValue;
 
 struct Instruments {
     counter: Counter<u64>,
+    latency_ms: Histogram<f64>,
 }
 
 static INSTR: OnceCell<Instruments> = OnceCell::new();
@@ -19,7 +20,11 @@ fn ensure_instruments() -> &'static Instruments {
             .u64_counter("policy.decision.count")
             .with_description("Policy decision counter")
             .init();
-        Instruments { counter }
+        let latency_ms = meter
+            .f64_histogram("policy.decision.latency.ms")
+            .with_description("Policy evaluation latency in milliseconds")
+            .init();
+        Instruments { counter, latency_ms }
     })
 }
 
@@ -28,6 +33,7 @@ fn ensure_instruments() -> &'static Instruments {
 /// Emits a counter named `policy.decision.count` with low-cardinality attributes
 /// `{phase, kind, action}` on every decision. When `action == "allow_but_flag"`,
 /// also emits a convenience alias with `action == "flag"` to simplify dashboarding.
+/// Evaluation latency is recorded to the histogram `policy.decision.latency.ms{phase}`.
 ///
 /// Notes
 /// - Uses the global meter provider; if no exporter is installed, this is a no-op.
@@ -61,6 +67,11 @@ impl policy::PolicyObserver for OtelPolicyObserver {
             inst.counter.add(1, &attrs2);
         }
     }
+
+    fn on_decision_latency(&self, phase: &str, elapsed: std::time::Duration) {
+        let attrs = [KeyValue::new("phase", phase.to_string())];
+        ensure_instruments().latency_ms.record(elapsed.as_secs_f64() * 1000.0, &attrs);
+    }
 }
 
 /// Create an observer instance and ensure instruments are initialized.
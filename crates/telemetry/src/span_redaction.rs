@@ -0,0 +1,134 @@
+//! Span attribute redaction applied at export time.
+//!
+//! Orchestrator spans carry fields such as `run` and `agent` that are useful in local logs
+//! but leak identifiers into trace backends and explode their cardinality. A
+//! [`RedactingExporter`] wraps any OTel [`SpanExporter`] and rewrites each span's attributes
+//! with a [`SpanAttributeFilter`] just before handing the batch on: designated keys are
+//! dropped or replaced by a short SHA-256 digest (still joinable across spans, never
+//! reversible). The `tracing` fmt/JSON logs are a separate pipeline and keep every field.
+
+use opentelemetry::{KeyValue, Value};
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Attribute keys hashed by [`SpanAttributeFilter::from_env`] when `ORCA_OTEL_HASH_ATTRS`
+/// is unset.
+pub const DEFAULT_HASHED_ATTRIBUTES: &[&str] =
+    &["run", "run_id", "orca.run_id", "workflow", "agent"];
+
+/// What happens to a designated attribute before export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeAction {
+    /// Remove the attribute.
+    Drop,
+    /// Replace the value with `sha256:<first 16 hex digits>` of its string form.
+    Hash,
+}
+
+/// Per-key redaction rules for exported span attributes; keys without a rule pass through.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanAttributeFilter {
+    rules: BTreeMap<String, AttributeAction>,
+}
+
+impl SpanAttributeFilter {
+    /// A filter with no rules (exports attributes unchanged).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop `key` from exported spans.
+    pub fn drop_attribute(mut self, key: impl Into<String>) -> Self {
+        self.rules.insert(key.into(), AttributeAction::Drop);
+        self
+    }
+
+    /// Hash the value of `key` in exported spans.
+    pub fn hash_attribute(mut self, key: impl Into<String>) -> Self {
+        self.rules.insert(key.into(), AttributeAction::Hash);
+        self
+    }
+
+    /// Rules from comma-separated key lists: `ORCA_OTEL_HASH_ATTRS` (default
+    /// [`DEFAULT_HASHED_ATTRIBUTES`]; set it empty to hash nothing) and `ORCA_OTEL_DROP_ATTRS`.
+    /// A key listed in both is dropped.
+    pub fn from_env() -> Self {
+        let hashed = std::env::var("ORCA_OTEL_HASH_ATTRS")
+            .unwrap_or_else(|_| DEFAULT_HASHED_ATTRIBUTES.join(","));
+        let dropped = std::env::var("ORCA_OTEL_DROP_ATTRS").unwrap_or_default();
+        let keys = |list: &str| -> Vec<String> {
+            list.split(',').map(str::trim).filter(|k| !k.is_empty()).map(String::from).collect()
+        };
+        let mut filter = Self::new();
+        for key in keys(&hashed) {
+            filter = filter.hash_attribute(key);
+        }
+        for key in keys(&dropped) {
+            filter = filter.drop_attribute(key);
+        }
+        filter
+    }
+
+    /// Rule for `key`, if any.
+    pub fn action(&self, key: &str) -> Option<AttributeAction> {
+        self.rules.get(key).copied()
+    }
+
+    /// Apply the rules to `attributes` in place, keeping the order of the survivors.
+    pub fn apply(&self, attributes: &mut Vec<KeyValue>) {
+        if self.rules.is_empty() {
+            return;
+        }
+        attributes.retain(|kv| self.action(kv.key.as_str()) != Some(AttributeAction::Drop));
+        for kv in attributes.iter_mut() {
+            if self.action(kv.key.as_str()) == Some(AttributeAction::Hash) {
+                kv.value = Value::from(hash_value(&kv.value.as_str()));
+            }
+        }
+    }
+}
+
+/// `sha256:` plus the first 16 hex digits of SHA-256 over `value`.
+fn hash_value(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// [`SpanExporter`] that redacts span attributes with a [`SpanAttributeFilter`] before
+/// delegating to `inner`.
+#[derive(Debug)]
+pub struct RedactingExporter<E> {
+    inner: E,
+    filter: SpanAttributeFilter,
+}
+
+impl<E: SpanExporter> RedactingExporter<E> {
+    /// Wrap `inner`, redacting with `filter`.
+    pub fn new(inner: E, filter: SpanAttributeFilter) -> Self {
+        Self { inner, filter }
+    }
+}
+
+impl<E: SpanExporter> SpanExporter for RedactingExporter<E> {
+    fn export(
+        &mut self,
+        mut batch: Vec<SpanData>,
+    ) -> Pin<Box<dyn Future<Output = ExportResult> + Send + 'static>> {
+        for span in &mut batch {
+            self.filter.apply(&mut span.attributes);
+        }
+        self.inner.export(batch)
+    }
+
+    fn shutdown(&mut self) {
+        self.inner.shutdown();
+    }
+
+    fn force_flush(&mut self) -> Pin<Box<dyn Future<Output = ExportResult> + Send + 'static>> {
+        self.inner.force_flush()
+    }
+}
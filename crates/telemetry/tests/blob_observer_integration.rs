@@ -20,7 +20,8 @@ async fn registers_observer_and_counts_metrics() -> Result<(), Box<dyn std::erro
 
     // Create a store and exercise put/get/cleanup
     let dir = temp_dir_path();
-    let cfg = blob_store::Config { root: PathBuf::from(&dir), zstd_level: 3 };
+    let cfg =
+        blob_store::Config { root: PathBuf::from(&dir), zstd_level: 3, zstd_dictionary: None };
     let store: BlobStore<DevKeyProvider> = BlobStore::new(cfg, DevKeyProvider::new([9u8; 32]))?;
 
     let data = b"abc".to_vec();
@@ -29,7 +30,7 @@ async fn registers_observer_and_counts_metrics() -> Result<(), Box<dyn std::erro
     assert_eq!(got, data);
 
     // Create an incomplete artifact to trigger cleanup
-    let shard = store.path_for(&dg.to_hex());
+    let shard = store.path_for_digest(&dg);
     let tmp = shard.with_extension("incomplete");
     fs::create_dir_all(tmp.parent().unwrap())?;
     fs::write(&tmp, b"junk")?;
@@ -34,7 +34,7 @@ proptest! {
         let before = snapshot_counters();
 
         let dir = unique_dir();
-        let cfg = blob_store::Config { root: dir.clone(), zstd_level: 3 };
+        let cfg = blob_store::Config { root: dir.clone(), zstd_level: 3, zstd_dictionary: None };
         let store: BlobStore<DevKeyProvider> = BlobStore::new(cfg, DevKeyProvider::new([7u8; 32])).unwrap();
 
         let data = vec![7u8; sz];
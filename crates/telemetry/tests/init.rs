@@ -0,0 +1,23 @@
+use telemetry::{init, TelemetryConfig};
+
+#[test]
+fn logging_only_init_sets_global_subscriber_without_collector() {
+    assert!(!tracing::dispatcher::has_been_set());
+    let guard = init(TelemetryConfig::logging_only().with_log_filter("warn")).unwrap();
+    assert!(guard.logging_installed());
+    assert!(!guard.otel_enabled());
+    assert!(tracing::dispatcher::has_been_set());
+
+    // A second init keeps the existing subscriber and still needs no collector.
+    let again = init(TelemetryConfig::logging_only().with_otel(false)).unwrap();
+    assert!(!again.logging_installed());
+    drop(again);
+    drop(guard);
+}
+
+#[cfg(not(feature = "otel"))]
+#[test]
+fn otel_requested_without_feature_is_an_error() {
+    let cfg = TelemetryConfig { json_logging: false, ..TelemetryConfig::logging_only() };
+    assert!(init(cfg.with_otel(true)).is_err());
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "otel")]
+
+use opentelemetry::trace::{SpanId, Status};
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+use telemetry::replay_export::{export_replay_trace_to, ReplaySpan, ReplayTrace};
+
+#[derive(Debug, Clone, Default)]
+struct Capture(Arc<Mutex<Vec<SpanData>>>);
+
+impl SpanExporter for Capture {
+    fn export(
+        &mut self,
+        batch: Vec<SpanData>,
+    ) -> Pin<Box<dyn Future<Output = ExportResult> + Send + 'static>> {
+        self.0.lock().unwrap().extend(batch);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+fn span(name: &str, start_ms: u64, end_ms: u64) -> ReplaySpan {
+    ReplaySpan {
+        name: name.into(),
+        start_ms,
+        end_ms,
+        attributes: vec![("orca.run_id".into(), "R1".into())],
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn events_become_children_of_the_run_span_with_wal_timestamps() {
+    let trace = ReplayTrace {
+        root: span("orca.run", 1_000, 1_030),
+        events: vec![span("start_run", 1_000, 1_010), span("task_enqueued", 1_010, 1_030)],
+    };
+    let capture = Capture::default();
+    let exporter = capture.clone();
+    let n = tokio::task::spawn_blocking(move || export_replay_trace_to(exporter, "test", &trace))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(n, 3);
+
+    let spans = capture.0.lock().unwrap().clone();
+    assert_eq!(spans.len(), 3);
+    let root = spans.iter().find(|s| s.name == "orca.run").unwrap();
+    assert_eq!(root.parent_span_id, SpanId::INVALID);
+    assert_eq!(root.status, Status::Unset);
+    let task = spans.iter().find(|s| s.name == "task_enqueued").unwrap();
+    assert_eq!(task.parent_span_id, root.span_context.span_id());
+    assert_eq!(task.span_context.trace_id(), root.span_context.trace_id());
+    assert_eq!(task.start_time, UNIX_EPOCH + Duration::from_millis(1_010));
+    assert_eq!(task.end_time, UNIX_EPOCH + Duration::from_millis(1_030));
+    assert!(task.attributes.iter().any(|kv| kv.key.as_str() == "orca.run_id"));
+}
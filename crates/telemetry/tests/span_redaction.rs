@@ -0,0 +1,106 @@
+#![cfg(feature = "otel")]
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry_sdk::trace::TracerProvider;
+use std::future::Future;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use telemetry::span_redaction::{RedactingExporter, SpanAttributeFilter};
+use tracing_subscriber::layer::SubscriberExt;
+
+#[derive(Debug, Clone, Default)]
+struct Capture(Arc<Mutex<Vec<SpanData>>>);
+
+impl SpanExporter for Capture {
+    fn export(
+        &mut self,
+        batch: Vec<SpanData>,
+    ) -> Pin<Box<dyn Future<Output = ExportResult> + Send + 'static>> {
+        self.0.lock().unwrap().extend(batch);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[derive(Clone, Default)]
+struct LogBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for LogBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn attr<'a>(span: &'a SpanData, key: &str) -> Option<&'a opentelemetry::Value> {
+    span.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| &kv.value)
+}
+
+#[test]
+fn sensitive_fields_are_redacted_in_export_but_kept_in_local_logs() {
+    // One subscriber feeds each span to both the local JSON logs and the OTel exporter.
+    let capture = Capture::default();
+    let filter = SpanAttributeFilter::new().hash_attribute("run").drop_attribute("agent");
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(RedactingExporter::new(capture.clone(), filter))
+        .build();
+    let logs = LogBuf::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_writer(move || writer.clone()),
+        )
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    tracing::subscriber::with_default(subscriber, || {
+        for _ in 0..2 {
+            let _span = tracing::info_span!(
+                "agent.budget.check",
+                run = %"run-7f3a",
+                agent = "billing-bot",
+                tokens = 12i64
+            )
+            .entered();
+            tracing::info!("checked");
+        }
+    });
+    for res in provider.force_flush() {
+        res.unwrap();
+    }
+
+    // Local JSON logs see the raw fields.
+    let local = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(local.contains("run-7f3a") && local.contains("billing-bot"), "{}", local);
+
+    // The same spans, as exported, carry hashed or dropped attributes.
+    let spans = capture.0.lock().unwrap().clone();
+    assert_eq!(spans.len(), 2);
+    assert!(spans.iter().all(|s| s.name == "agent.budget.check"));
+    let run = attr(&spans[0], "run").unwrap().as_str().into_owned();
+    assert!(run.starts_with("sha256:") && run.len() == "sha256:".len() + 16, "{}", run);
+    assert_eq!(attr(&spans[1], "run").unwrap().as_str(), run, "hashing is deterministic");
+    assert!(attr(&spans[0], "agent").is_none());
+    assert_eq!(attr(&spans[0], "tokens").unwrap().as_str(), "12");
+    let exported = format!("{:?}", spans);
+    assert!(!exported.contains("run-7f3a") && !exported.contains("billing-bot"));
+}
+
+#[test]
+fn env_filter_defaults_to_hashing_run_and_agent_fields() {
+    std::env::remove_var("ORCA_OTEL_HASH_ATTRS");
+    std::env::set_var("ORCA_OTEL_DROP_ATTRS", " workflow , session ");
+    let filter = SpanAttributeFilter::from_env();
+    std::env::remove_var("ORCA_OTEL_DROP_ATTRS");
+    use telemetry::span_redaction::AttributeAction::{Drop, Hash};
+    assert_eq!(filter.action("run"), Some(Hash));
+    assert_eq!(filter.action("agent"), Some(Hash));
+    assert_eq!(filter.action("workflow"), Some(Drop), "drop wins over the default hash");
+    assert_eq!(filter.action("session"), Some(Drop));
+    assert_eq!(filter.action("tokens"), None);
+}